@@ -23,12 +23,13 @@ use crate::framing::FrameMode;
 use crate::state_machine::{ConnectionState, HandshakeStateMachine};
 use crate::transport::FramedTransport;
 use crate::types::Creation;
-use bytes::{BufMut, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use erltf::decoder::AtomCache;
 use erltf::types::{Atom, ExternalPid, ExternalReference};
 use erltf::{OwnedTerm, decoder};
+use std::io::IoSlice;
 use std::time::Duration;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::net::tcp::OwnedReadHalf;
 use tracing::{debug, trace};
@@ -435,14 +436,21 @@ impl Connection {
     pub fn decode_complete_fragment(
         complete_data: &[u8],
         atom_cache: &mut AtomCache,
+    ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
+        Self::decode_shared_fragment(&Bytes::copy_from_slice(complete_data), atom_cache)
+    }
+
+    fn decode_shared_fragment(
+        complete_data: &Bytes,
+        atom_cache: &mut AtomCache,
     ) -> Result<(ControlMessage, Option<OwnedTerm>)> {
         let (control_term, message) = if complete_data.len() >= 2
             && complete_data[0] == VERSION_TAG
             && complete_data[1] == DIST_HEADER
         {
-            decoder::decode_with_atom_cache(complete_data, atom_cache)?
+            decoder::decode_shared_with_atom_cache(complete_data, atom_cache)?
         } else {
-            (decoder::decode(complete_data)?, None)
+            (decoder::decode_shared(complete_data)?, None)
         };
 
         let control = ControlMessage::from_term(&control_term)?;
@@ -457,7 +465,8 @@ impl Connection {
         }
 
         loop {
-            let data = self.read_message().await?;
+            // Large binaries in decoded terms share this buffer instead of being copied
+            let data = Bytes::from(self.read_message().await?);
 
             if data.is_empty() {
                 trace!("Received tick (heartbeat), continuing...");
@@ -496,7 +505,10 @@ impl Connection {
                     remaining[payload_start..].to_vec(),
                 ) {
                     trace!("Fragment sequence complete, processing");
-                    return Self::decode_complete_fragment(&complete_data, &mut self.atom_cache);
+                    return Self::decode_shared_fragment(
+                        &Bytes::from(complete_data),
+                        &mut self.atom_cache,
+                    );
                 } else {
                     continue;
                 }
@@ -514,7 +526,10 @@ impl Connection {
                     remaining.to_vec(),
                 ) {
                     trace!("Fragment sequence complete, processing");
-                    return Self::decode_complete_fragment(&complete_data, &mut self.atom_cache);
+                    return Self::decode_shared_fragment(
+                        &Bytes::from(complete_data),
+                        &mut self.atom_cache,
+                    );
                 } else {
                     continue;
                 }
@@ -522,13 +537,13 @@ impl Connection {
 
             let (control_term, message) = if !data.is_empty() && data[0] == PASS_THROUGH {
                 trace!("Pass-through message detected");
-                let (control, remaining) = decoder::decode_with_trailing(&data[1..])?;
+                let (control, remaining) = decoder::decode_shared_with_trailing(&data.slice(1..))?;
                 trace!(
                     "Decoded control term from pass-through message, {} bytes remaining",
                     remaining.len()
                 );
                 let message = if !remaining.is_empty() {
-                    let (msg, _) = decoder::decode_with_trailing(remaining)?;
+                    let (msg, _) = decoder::decode_shared_with_trailing(&remaining)?;
                     trace!("Decoded message term from pass-through message");
                     Some(msg)
                 } else {
@@ -537,10 +552,10 @@ impl Connection {
                 (control, message)
             } else if data.len() >= 2 && data[0] == VERSION_TAG && data[1] == DIST_HEADER {
                 let (control, payload_opt) =
-                    decoder::decode_with_atom_cache(&data, &mut self.atom_cache)?;
+                    decoder::decode_shared_with_atom_cache(&data, &mut self.atom_cache)?;
                 (control, payload_opt)
             } else {
-                (decoder::decode(&data)?, None)
+                (decoder::decode_shared(&data)?, None)
             };
 
             let control = ControlMessage::from_term(&control_term)?;
@@ -570,24 +585,33 @@ impl Connection {
             let control_encoded = erltf::encode(&control_term)?;
 
             if let Some(msg) = message {
-                let msg_encoded = erltf::encode(&msg)?;
-                let total_len = 1 + control_encoded.len() + msg_encoded.len();
+                // Large binaries in the message are written from where they are,
+                // not copied into one buffer
+                let msg_chunks = erltf::encode_vectored(&msg)?;
+                let msg_len: usize = msg_chunks.iter().map(Bytes::len).sum();
+                let total_len = 1 + control_encoded.len() + msg_len;
                 trace!(
                     "Sending pass-through message: control_len={}, msg_len={}, total_len={}",
                     control_encoded.len(),
-                    msg_encoded.len(),
+                    msg_len,
                     total_len
                 );
 
+                let mut header = BytesMut::with_capacity(5 + control_encoded.len());
+                header.put_u32(total_len as u32);
+                header.put_u8(PASS_THROUGH);
+                header.put_slice(&control_encoded);
+
+                let mut chunks = Vec::with_capacity(msg_chunks.len() + 1);
+                chunks.push(header.freeze());
+                chunks.extend(msg_chunks);
+
                 let stream = self
                     .transport
                     .write_half_mut()
                     .ok_or_else(|| Error::InvalidStateMessage("no active stream".to_string()))?;
 
-                stream.write_u32(total_len as u32).await?;
-                stream.write_u8(PASS_THROUGH).await?;
-                stream.write_all(&control_encoded).await?;
-                stream.write_all(&msg_encoded).await?;
+                write_all_vectored(stream, &mut chunks).await?;
                 stream.flush().await?;
             } else {
                 let total_len = 1 + control_encoded.len();
@@ -712,13 +736,14 @@ impl Connection {
                 )));
             }
 
-            let control_and_payload = &buf[1..];
+            let control_and_payload = Bytes::from(buf).slice(1..);
             trace!(
                 "Decoding control and payload from {} bytes",
                 control_and_payload.len()
             );
 
-            let (control_term, remaining) = decoder::decode_with_trailing(control_and_payload)?;
+            let (control_term, remaining) =
+                decoder::decode_shared_with_trailing(&control_and_payload)?;
             trace!("Decoded control term: {:?}", control_term);
             trace!("Remaining bytes after control: {}", remaining.len());
 
@@ -727,7 +752,7 @@ impl Connection {
 
             let payload = if !remaining.is_empty() {
                 trace!("Decoding payload from {} bytes", remaining.len());
-                let (payload_term, _) = decoder::decode_with_trailing(remaining)?;
+                let (payload_term, _) = decoder::decode_shared_with_trailing(&remaining)?;
                 trace!("Decoded payload: {:?}", payload_term);
                 Some(payload_term)
             } else {
@@ -739,3 +764,37 @@ impl Connection {
         }
     }
 }
+
+/// Writes every chunk, as few `writev` calls at a time as the stream accepts.
+async fn write_all_vectored<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    chunks: &mut [Bytes],
+) -> std::io::Result<()> {
+    let mut first = 0;
+    loop {
+        while first < chunks.len() && chunks[first].is_empty() {
+            first += 1;
+        }
+        if first == chunks.len() {
+            return Ok(());
+        }
+
+        let slices: Vec<IoSlice<'_>> = chunks[first..]
+            .iter()
+            .map(|chunk| IoSlice::new(chunk))
+            .collect();
+        let mut written = writer.write_vectored(&slices).await?;
+        if written == 0 {
+            return Err(std::io::ErrorKind::WriteZero.into());
+        }
+
+        while written > 0 {
+            let len = chunks[first].len().min(written);
+            chunks[first].advance(len);
+            written -= len;
+            if chunks[first].is_empty() {
+                first += 1;
+            }
+        }
+    }
+}
@@ -16,7 +16,7 @@ use anyhow::{Context, Result};
 use clap::{value_parser, Arg, ArgMatches, Command};
 use edp_examples::common;
 use edp_node::Node;
-use erltf::OwnedTerm;
+use erltf::{OwnedTerm, Selector};
 use tabled::{Table, Tabled};
 use tracing_subscriber::EnvFilter;
 
@@ -115,43 +115,36 @@ async fn list_listeners(node: &mut Node, target_node: &str, quiet: bool) -> Resu
         OwnedTerm::List(listeners) => {
             let mut rows = Vec::new();
 
-            for listener in listeners {
-                if let OwnedTerm::Tuple(tuple_items) = listener {
-                    if tuple_items.len() >= 7 {
-                        if let (
-                            OwnedTerm::Atom(tag),
-                            OwnedTerm::Atom(_node),
-                            OwnedTerm::Atom(protocol),
-                            _host,
-                            OwnedTerm::Tuple(ip_tuple),
-                            OwnedTerm::Integer(port),
-                            _opts,
-                        ) = (
-                            &tuple_items[0],
-                            &tuple_items[1],
-                            &tuple_items[2],
-                            &tuple_items[3],
-                            &tuple_items[4],
-                            &tuple_items[5],
-                            &tuple_items[6],
-                        ) {
-                            if tag.as_ref() != "listener" {
-                                continue;
-                            }
-
-                            let ip = format_ip_address(ip_tuple);
-
-                            if quiet {
-                                println!("{}:{}:{}", protocol, ip, port);
-                            } else {
-                                rows.push(ListenerRow {
-                                    interface: ip,
-                                    port: *port,
-                                    protocol: protocol.to_string(),
-                                });
-                            }
-                        }
-                    }
+            let tag = Selector::parse("[0]")?;
+            let protocol = Selector::parse("[2]")?;
+            let ip_address = Selector::parse("[4]")?;
+            let port = Selector::parse("[5]")?;
+
+            for listener in &listeners {
+                if !tag
+                    .first(listener)
+                    .is_some_and(|tag| tag.is_atom_with_name("listener"))
+                {
+                    continue;
+                }
+                let (Some(protocol), Some(ip_tuple), Some(port)) = (
+                    protocol.first(listener).and_then(OwnedTerm::atom_name),
+                    ip_address.first(listener).and_then(OwnedTerm::as_tuple),
+                    port.first(listener).and_then(OwnedTerm::as_integer),
+                ) else {
+                    continue;
+                };
+
+                let ip = format_ip_address(ip_tuple);
+
+                if quiet {
+                    println!("{}:{}:{}", protocol, ip, port);
+                } else {
+                    rows.push(ListenerRow {
+                        interface: ip,
+                        port,
+                        protocol: protocol.to_string(),
+                    });
                 }
             }
 
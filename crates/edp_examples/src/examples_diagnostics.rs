@@ -16,8 +16,7 @@ use anyhow::{Context, Result};
 use clap::{value_parser, Arg, ArgMatches, Command};
 use edp_examples::common;
 use edp_node::Node;
-use erltf::OwnedTerm;
-use std::collections::HashMap;
+use erltf::{OwnedTerm, Selector};
 use tabled::{Table, Tabled};
 use tracing_subscriber::EnvFilter;
 
@@ -92,24 +91,11 @@ fn unwrap_rpc_response(response: OwnedTerm) -> Result<OwnedTerm> {
     }
 }
 
-fn parse_proplist(props: &[OwnedTerm]) -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    for term in props {
-        if let OwnedTerm::Tuple(tuple) = term {
-            if tuple.len() == 2 {
-                if let OwnedTerm::Atom(key) = &tuple[0] {
-                    map.insert(key.to_string(), term_to_string(&tuple[1]));
-                }
-            }
-        }
-    }
-    map
-}
-
 fn term_to_string(term: &OwnedTerm) -> String {
     match term {
         OwnedTerm::Atom(s) => s.to_string(),
         OwnedTerm::Binary(b) => String::from_utf8_lossy(b).to_string(),
+        OwnedTerm::SharedBinary(b) => String::from_utf8_lossy(b).to_string(),
         OwnedTerm::Integer(n) => n.to_string(),
         OwnedTerm::Float(f) => f.to_string(),
         OwnedTerm::List(items) => {
@@ -121,23 +107,26 @@ fn term_to_string(term: &OwnedTerm) -> String {
             }
         }
         OwnedTerm::Tuple(items) if items.len() == 4 => {
-            if let (OwnedTerm::Atom(tag), OwnedTerm::Binary(_vhost), OwnedTerm::Atom(kind), name) =
+            if let (OwnedTerm::Atom(tag), vhost, OwnedTerm::Atom(kind), name) =
                 (&items[0], &items[1], &items[2], &items[3])
             {
-                if tag.as_ref() == "resource" && kind.as_ref() == "queue" {
+                if tag.as_ref() == "resource"
+                    && vhost.as_binary().is_some()
+                    && kind.as_ref() == "queue"
+                {
                     return term_to_string(name);
                 }
             }
-            format!("{:?}", term)
+            term.to_pretty_string()
         }
         OwnedTerm::Tuple(items) if items.len() == 2 => {
             if let (OwnedTerm::Atom(_), value) = (&items[0], &items[1]) {
                 term_to_string(value)
             } else {
-                format!("{:?}", term)
+                term.to_pretty_string()
             }
         }
-        _ => format!("{:?}", term),
+        _ => term.to_pretty_string(),
     }
 }
 
@@ -225,43 +214,55 @@ async fn list_queues(
                 return Ok(());
             }
 
+            let header: Vec<String> = if columns.is_empty() {
+                ["name", "messages", "consumers"]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect()
+            } else {
+                columns.clone()
+            };
+            let selectors = header
+                .iter()
+                .map(|col| {
+                    Selector::parse(col).with_context(|| format!("Invalid column name: {}", col))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let rows = queues
+                .iter()
+                .filter(|queue| matches!(queue, OwnedTerm::List(_)))
+                .map(|queue| {
+                    selectors
+                        .iter()
+                        .map(|selector| {
+                            selector
+                                .first(queue)
+                                .map(term_to_string)
+                                .unwrap_or_default()
+                        })
+                        .collect::<Vec<String>>()
+                });
+
             if use_default_columns && !quiet {
-                let mut rows = Vec::new();
-                for queue in queues {
-                    if let OwnedTerm::List(props) = queue {
-                        let props_map = parse_proplist(&props);
-                        rows.push(QueueRow {
-                            name: props_map.get("name").cloned().unwrap_or_default(),
-                            messages: props_map.get("messages").cloned().unwrap_or_default(),
-                            consumers: props_map.get("consumers").cloned().unwrap_or_default(),
-                        });
-                    }
-                }
+                let rows: Vec<QueueRow> = rows
+                    .map(|values| {
+                        let mut values = values.into_iter();
+                        QueueRow {
+                            name: values.next().unwrap_or_default(),
+                            messages: values.next().unwrap_or_default(),
+                            consumers: values.next().unwrap_or_default(),
+                        }
+                    })
+                    .collect();
                 let table = Table::new(rows);
                 println!("\n{}", table);
             } else {
-                let header: Vec<String> = if columns.is_empty() {
-                    ["name", "messages", "consumers"]
-                        .iter()
-                        .map(|s| s.to_string())
-                        .collect()
-                } else {
-                    columns.clone()
-                };
-
                 if !quiet {
                     println!("\n{}", header.join("\t"));
                 }
 
-                for queue in queues {
-                    if let OwnedTerm::List(props) = queue {
-                        let props_map = parse_proplist(&props);
-                        let values: Vec<String> = header
-                            .iter()
-                            .map(|col| props_map.get(col).cloned().unwrap_or_default())
-                            .collect();
-                        println!("{}", values.join("\t"));
-                    }
+                for values in rows {
+                    println!("{}", values.join("\t"));
                 }
             }
         }
@@ -287,15 +288,17 @@ async fn log_location(node: &mut Node, target_node: &str, quiet: bool) -> Result
 
     let response = unwrap_rpc_response(response)?;
 
-    match response {
-        OwnedTerm::Binary(path) => {
-            let path_str = String::from_utf8_lossy(&path);
-            if quiet {
-                println!("{}", path_str);
-            } else {
-                println!("\nLog file location: {}", path_str);
-            }
+    if let Some(path) = response.as_binary() {
+        let path_str = String::from_utf8_lossy(path);
+        if quiet {
+            println!("{}", path_str);
+        } else {
+            println!("\nLog file location: {}", path_str);
         }
+        return Ok(());
+    }
+
+    match response {
         OwnedTerm::Atom(path) => {
             if quiet {
                 println!("{}", path);
@@ -341,3 +341,71 @@ async fn test_rpc_concurrent_calls() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_phash2_matches_the_runtime() -> Result<()> {
+    use erltf::hash::{phash2, phash2_range};
+    use erltf::types::BigInt;
+    use erltf::{erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+    let mut ctx = TestContext::new("phash2").await?;
+
+    let terms = vec![
+        erl_int!(0),
+        erl_int!(42),
+        erl_int!(-1),
+        erl_int!(-134217728),
+        erl_int!(134217728),
+        erl_int!(i64::MAX),
+        erl_int!(i64::MIN),
+        OwnedTerm::BigInt(BigInt::from(u128::MAX)),
+        OwnedTerm::from(-(1i128 << 100)),
+        OwnedTerm::Float(0.0),
+        OwnedTerm::Float(-0.0),
+        OwnedTerm::Float(1.5),
+        OwnedTerm::Float(-1.0e300),
+        erl_atom!("ok"),
+        erl_atom!("caf\u{e9}"),
+        erl_atom!("\u{1f600}"),
+        OwnedTerm::Binary(vec![]),
+        OwnedTerm::Binary(b"hello world, a binary longer than one block".to_vec()),
+        OwnedTerm::BitBinary {
+            bytes: vec![0xab, 0xc0],
+            bits: 3,
+        },
+        OwnedTerm::charlist("abcdefg"),
+        erl_list![erl_int!(1), erl_int!(256), erl_int!(2)],
+        OwnedTerm::Nil,
+        erl_tuple![],
+        erl_tuple![
+            erl_atom!("a"),
+            erl_tuple![erl_int!(1), erl_tuple![OwnedTerm::Nil]]
+        ],
+        OwnedTerm::improper_list(vec![erl_int!(1), erl_atom!("b")], erl_int!(3)),
+        erl_map! {},
+        erl_map! {
+            erl_atom!("a") => erl_int!(1),
+            OwnedTerm::Binary(b"k".to_vec()) => erl_list![erl_atom!("v")],
+            erl_tuple![erl_int!(1)] => erl_map! { erl_int!(2) => erl_int!(3) }
+        },
+    ];
+
+    for term in terms {
+        let response = ctx.rpc_call("erlang", "phash2", vec![term.clone()]).await?;
+        let expected = TestContext::unwrap_rex_response(response)?;
+        assert_eq!(expected, erl_int!(phash2(&term) as i64), "{:?}", term);
+
+        let response = ctx
+            .rpc_call("erlang", "phash2", vec![term.clone(), erl_int!(1000)])
+            .await?;
+        let expected = TestContext::unwrap_rex_response(response)?;
+        assert_eq!(
+            expected,
+            erl_int!(phash2_range(&term, 1000) as i64),
+            "{:?}",
+            term
+        );
+    }
+
+    Ok(())
+}
@@ -16,8 +16,8 @@ use crate::errors::Result;
 use crate::mailbox::Message;
 use crate::process::Process;
 use crate::registry::ProcessRegistry;
-use erltf::OwnedTerm;
 use erltf::types::Atom;
+use erltf::{OwnedTerm, erl_match};
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
@@ -76,10 +76,6 @@ struct HandlerEntry {
 
 pub struct GenEventManager {
     handlers: HashMap<String, HandlerEntry>,
-    notify_tag: Atom,
-    sync_notify_tag: Atom,
-    call_tag: Atom,
-    which_handlers_tag: Atom,
     registry: Arc<ProcessRegistry>,
 }
 
@@ -87,10 +83,6 @@ impl GenEventManager {
     pub fn new(registry: Arc<ProcessRegistry>) -> Self {
         Self {
             handlers: HashMap::new(),
-            notify_tag: Atom::new("$gen_notify"),
-            sync_notify_tag: Atom::new("$gen_sync_notify"),
-            call_tag: Atom::new("$gen_call"),
-            which_handlers_tag: Atom::new("$gen_which_handlers"),
             registry,
         }
     }
@@ -222,79 +214,67 @@ impl Process for GenEventManager {
     async fn handle_message(&mut self, msg: Message) -> Result<()> {
         match msg {
             Message::Regular { from, body } => {
-                if let OwnedTerm::Tuple(elements) = &body
-                    && elements.len() >= 2
-                    && let OwnedTerm::Atom(tag) = &elements[0]
-                {
-                    if tag == &self.notify_tag && elements.len() == 2 {
-                        let event = elements[1].clone();
-                        return self.notify(event).await;
-                    } else if tag == &self.sync_notify_tag && elements.len() == 2 {
-                        let event = elements[1].clone();
-                        self.notify(event).await?;
-                        if let Some(from_pid) = from
-                            && let Some(handle) = self.registry.get(&from_pid).await
-                        {
-                            handle
-                                .send(Message::Regular {
-                                    from: None,
-                                    body: OwnedTerm::Atom(Atom::new("ok")),
-                                })
-                                .await?;
-                        }
-                        return Ok(());
-                    } else if tag == &self.call_tag && elements.len() == 4 {
-                        if let OwnedTerm::Tuple(from_tuple) = &elements[1]
-                            && from_tuple.len() == 2
-                            && let OwnedTerm::Pid(from_pid) = &from_tuple[0]
-                            && let OwnedTerm::Reference(reference) = &from_tuple[1]
-                        {
-                            let handler_id = elements[2].clone();
-                            let request = elements[3].clone();
-
-                            let reply = self
-                                .call_handler(handler_id, request)
-                                .await
-                                .unwrap_or_else(|_| OwnedTerm::Atom(Atom::new("error")));
-
-                            let reply_msg = OwnedTerm::Tuple(vec![
-                                OwnedTerm::Reference(reference.clone()),
-                                reply,
-                            ]);
-
-                            if let Some(handle) = self.registry.get(from_pid).await {
-                                handle
-                                    .send(Message::Regular {
-                                        from: None,
-                                        body: reply_msg,
-                                    })
-                                    .await?;
-                            }
-                            return Ok(());
-                        }
-                    } else if tag == &self.which_handlers_tag
-                        && elements.len() == 2
-                        && let OwnedTerm::Tuple(from_tuple) = &elements[1]
-                        && from_tuple.len() == 2
-                        && let OwnedTerm::Pid(from_pid) = &from_tuple[0]
-                        && let OwnedTerm::Reference(reference) = &from_tuple[1]
+                if let Some(event) = erl_match!(body, {:"$gen_notify", Event}) {
+                    return self.notify(event.clone()).await;
+                }
+                if let Some(event) = erl_match!(body, {:"$gen_sync_notify", Event}) {
+                    self.notify(event.clone()).await?;
+                    if let Some(from_pid) = from
+                        && let Some(handle) = self.registry.get(&from_pid).await
                     {
-                        let handlers = self.which_handlers();
-                        let reply_msg = OwnedTerm::Tuple(vec![
-                            OwnedTerm::Reference(reference.clone()),
-                            OwnedTerm::List(handlers),
-                        ]);
-
-                        if let Some(handle) = self.registry.get(from_pid).await {
-                            handle
-                                .send(Message::Regular {
-                                    from: None,
-                                    body: reply_msg,
-                                })
-                                .await?;
-                        }
-                        return Ok(());
+                        handle
+                            .send(Message::Regular {
+                                from: None,
+                                body: OwnedTerm::Atom(Atom::new("ok")),
+                            })
+                            .await?;
+                    }
+                    return Ok(());
+                }
+                if let Some((from, reference, handler_id, request)) = erl_match!(
+                    body,
+                    {:"$gen_call", {From, Reference}, HandlerId, Request}
+                ) && let OwnedTerm::Pid(from_pid) = from
+                    && let OwnedTerm::Reference(reference) = reference
+                {
+                    let reply = self
+                        .call_handler(handler_id.clone(), request.clone())
+                        .await
+                        .unwrap_or_else(|_| OwnedTerm::Atom(Atom::new("error")));
+
+                    let reply_msg =
+                        OwnedTerm::Tuple(vec![OwnedTerm::Reference(reference.clone()), reply]);
+
+                    if let Some(handle) = self.registry.get(from_pid).await {
+                        handle
+                            .send(Message::Regular {
+                                from: None,
+                                body: reply_msg,
+                            })
+                            .await?;
+                    }
+                    return Ok(());
+                }
+                if let Some((from, reference)) =
+                    erl_match!(body, {:"$gen_which_handlers", {From, Reference}})
+                    && let OwnedTerm::Pid(from_pid) = from
+                    && let OwnedTerm::Reference(reference) = reference
+                {
+                    let handlers = self.which_handlers();
+                    let reply_msg = OwnedTerm::Tuple(vec![
+                        OwnedTerm::Reference(reference.clone()),
+                        OwnedTerm::List(handlers),
+                    ]);
+
+                    if let Some(handle) = self.registry.get(from_pid).await {
+                        handle
+                            .send(Message::Regular {
+                                from: None,
+                                body: reply_msg,
+                            })
+                            .await?;
                     }
+                    return Ok(());
                 }
 
                 for entry in self.handlers.values_mut() {
@@ -16,8 +16,8 @@ use crate::errors::Result;
 use crate::mailbox::Message;
 use crate::process::Process;
 use crate::registry::ProcessRegistry;
-use erltf::OwnedTerm;
 use erltf::types::{Atom, ExternalPid, ExternalReference};
+use erltf::{OwnedTerm, erl_match};
 use std::future::Future;
 use std::sync::Arc;
 
@@ -46,19 +46,12 @@ pub trait GenServer: Send + 'static {
 
 pub struct GenServerProcess<T: GenServer> {
     server: T,
-    call_tag: Atom,
-    cast_tag: Atom,
     registry: Arc<ProcessRegistry>,
 }
 
 impl<T: GenServer> GenServerProcess<T> {
     pub fn new(server: T, registry: Arc<ProcessRegistry>) -> Self {
-        Self {
-            server,
-            call_tag: Atom::new("$gen_call"),
-            cast_tag: Atom::new("$gen_cast"),
-            registry,
-        }
+        Self { server, registry }
     }
 
     async fn handle_gen_call(
@@ -97,24 +90,17 @@ impl<T: GenServer> Process for GenServerProcess<T> {
     async fn handle_message(&mut self, msg: Message) -> Result<()> {
         match msg {
             Message::Regular { from: _, body } => {
-                if let OwnedTerm::Tuple(elements) = &body
-                    && elements.len() >= 2
-                    && let OwnedTerm::Atom(tag) = &elements[0]
+                if let Some((from, reference, request)) =
+                    erl_match!(body, {:"$gen_call", {From, Reference}, Request})
+                    && let OwnedTerm::Pid(from_pid) = from
+                    && let OwnedTerm::Reference(reference) = reference
                 {
-                    if tag == &self.call_tag && elements.len() == 3 {
-                        if let OwnedTerm::Tuple(from_tuple) = &elements[1]
-                            && from_tuple.len() == 2
-                            && let OwnedTerm::Pid(from_pid) = &from_tuple[0]
-                            && let OwnedTerm::Reference(reference) = &from_tuple[1]
-                        {
-                            let request = elements[2].clone();
-                            return self
-                                .handle_gen_call(from_pid.clone(), reference.clone(), request)
-                                .await;
-                        }
-                    } else if tag == &self.cast_tag && elements.len() == 2 {
-                        return self.handle_gen_cast(elements[1].clone()).await;
-                    }
+                    return self
+                        .handle_gen_call(from_pid.clone(), reference.clone(), request.clone())
+                        .await;
+                }
+                if let Some(request) = erl_match!(body, {:"$gen_cast", Request}) {
+                    return self.handle_gen_cast(request.clone()).await;
                 }
 
                 self.server.handle_info(body).await
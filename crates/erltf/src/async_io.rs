@@ -0,0 +1,116 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding to and decoding from tokio's [`AsyncWrite`] and [`AsyncRead`].
+//!
+//! [`decode_from_async_reader`] reads exactly the bytes of one term, so terms
+//! written back to back without any framing can be read one at a time and the
+//! reader is left at the start of the next one.
+//!
+//! None of these functions are cancel safe. Dropping a future returned by
+//! [`decode_from_async_reader`] after it has read part of a term loses those
+//! bytes and leaves the reader in the middle of the term; dropping a writing
+//! future may leave a partially written term behind. In `tokio::select!` loops,
+//! keep the future alive across iterations (e.g. pinned outside the loop)
+//! rather than re-creating it, or feed a [`StreamDecoder`] from a cancel-safe read.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use erltf::{decode_from_async_reader, encode_to_async_writer, erl_atom, erl_int};
+//!
+//! let mut stream = Vec::new();
+//! encode_to_async_writer(&erl_atom!("hello"), &mut stream).await.unwrap();
+//! encode_to_async_writer(&erl_int!(1000), &mut stream).await.unwrap();
+//!
+//! let mut reader = stream.as_slice();
+//! assert_eq!(decode_from_async_reader(&mut reader).await.unwrap(), Some(erl_atom!("hello")));
+//! assert_eq!(decode_from_async_reader(&mut reader).await.unwrap(), Some(erl_int!(1000)));
+//! assert_eq!(decode_from_async_reader(&mut reader).await.unwrap(), None);
+//! # }
+//! ```
+
+use crate::decoder::DecodeOptions;
+use crate::encoder::{EncodeOptions, encode_vectored_with_options};
+use crate::errors::{DecodeError, EncodeError};
+use crate::streaming::{StreamDecoder, StreamItem};
+use crate::term::OwnedTerm;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Encodes `term` and writes it to `writer`. Large shared binaries are written
+/// from their own buffers, see [`crate::encode_vectored`].
+pub async fn encode_to_async_writer<W: AsyncWrite + Unpin>(
+    term: &OwnedTerm,
+    writer: &mut W,
+) -> Result<(), EncodeError> {
+    encode_to_async_writer_with_options(term, writer, &EncodeOptions::default()).await
+}
+
+pub async fn encode_to_async_writer_with_options<W: AsyncWrite + Unpin>(
+    term: &OwnedTerm,
+    writer: &mut W,
+    options: &EncodeOptions,
+) -> Result<(), EncodeError> {
+    for chunk in encode_vectored_with_options(term, options)? {
+        writer.write_all(&chunk).await?;
+    }
+    Ok(())
+}
+
+/// Reads one version-prefixed term from `reader`.
+///
+/// Returns `Ok(None)` if the reader is at end of input before the first byte,
+/// and [`DecodeError::UnexpectedEof`] if the input ends in the middle of a term.
+///
+/// The decoder never asks for more bytes than the term is known to need, so
+/// nothing past its end is consumed. Compressed terms have no length prefix and
+/// are read a byte at a time; wrap unbuffered readers in a `BufReader`.
+///
+/// Not cancel safe, see the [module documentation](self).
+pub async fn decode_from_async_reader<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<OwnedTerm>, DecodeError> {
+    decode_from_async_reader_with_options(reader, &DecodeOptions::default()).await
+}
+
+/// Like [`decode_from_async_reader`], applying the limits in `options`. Size
+/// limits are checked as the term is read, before it is buffered in full.
+pub async fn decode_from_async_reader_with_options<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    options: &DecodeOptions,
+) -> Result<Option<OwnedTerm>, DecodeError> {
+    let mut decoder = StreamDecoder::with_options(options.clone());
+    loop {
+        let needed = match decoder.next_term()? {
+            StreamItem::Term(term) => return Ok(Some(term)),
+            StreamItem::NeedMore(needed) => needed,
+        };
+
+        let buffer = decoder.buffer_mut();
+        let start = buffer.len();
+        buffer.resize(start + needed, 0);
+        let mut filled = start;
+        while filled < buffer.len() {
+            let read = reader.read(&mut buffer[filled..]).await?;
+            if read == 0 {
+                return if filled == 0 {
+                    Ok(None)
+                } else {
+                    Err(DecodeError::UnexpectedEof)
+                };
+            }
+            filled += read;
+        }
+    }
+}
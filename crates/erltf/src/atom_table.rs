@@ -0,0 +1,183 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An optional process-wide atom table.
+//!
+//! By default every [`Atom`] other than a few common ones (`ok`, `error`, `true`, ...)
+//! owns its name. Once a table is [installed](install), [`Atom::new`] and the decoder
+//! look names up in it, so equal atoms share one allocation and compare by pointer.
+//!
+//! Like the BEAM's atom table, it has a maximum size. When it is full, the
+//! [`OverflowPolicy`] decides whether new names are rejected or become uninterned atoms.
+//! Unlike the BEAM's, atoms nothing refers to anymore can be [purged](AtomTable::purge_unused).
+//!
+//! ```
+//! use erltf::atom_table::{self, AtomTable, OverflowPolicy};
+//! use erltf::{Atom, decode, encode, erl_atom};
+//!
+//! atom_table::install(AtomTable::new(100_000, OverflowPolicy::Uninterned)).unwrap();
+//!
+//! let decoded = decode(&encode(&erl_atom!("node_down")).unwrap()).unwrap();
+//! let atom = decoded.as_atom().unwrap();
+//! assert!(atom.ptr_eq(&Atom::new("node_down")));
+//! ```
+
+use crate::errors::AtomTableFull;
+use crate::types::Atom;
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+static GLOBAL: OnceLock<AtomTable> = OnceLock::new();
+
+/// What [`AtomTable::intern`] does with a new name once the table is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Fail with [`AtomTableFull`]. The decoder reports `DecodeError::AtomTableFull`.
+    Error,
+    /// Return an atom that owns its name, as if no table were installed.
+    #[default]
+    Uninterned,
+}
+
+/// A concurrent set of atom names with a maximum size.
+#[derive(Debug)]
+pub struct AtomTable {
+    names: DashMap<Arc<str>, ()>,
+    len: AtomicUsize,
+    max_atoms: AtomicUsize,
+    reject_overflow: AtomicBool,
+}
+
+impl AtomTable {
+    pub fn new(max_atoms: usize, overflow: OverflowPolicy) -> Self {
+        Self {
+            names: DashMap::new(),
+            len: AtomicUsize::new(0),
+            max_atoms: AtomicUsize::new(max_atoms),
+            reject_overflow: AtomicBool::new(overflow == OverflowPolicy::Error),
+        }
+    }
+
+    /// Returns the atom for `name`, adding it to the table if it is not there yet.
+    ///
+    /// The predefined atoms are never added; they are shared regardless.
+    pub fn intern(&self, name: &str) -> Result<Atom, AtomTableFull> {
+        if let Some(atom) = Atom::predefined(name) {
+            return Ok(atom);
+        }
+        if let Some(entry) = self.names.get(name) {
+            return Ok(Atom::from_shared(entry.key().clone()));
+        }
+
+        match self.names.entry(Arc::from(name)) {
+            Entry::Occupied(entry) => Ok(Atom::from_shared(entry.key().clone())),
+            Entry::Vacant(entry) => {
+                let max = self.max_atoms();
+                if self.len.fetch_add(1, Ordering::Relaxed) >= max {
+                    self.len.fetch_sub(1, Ordering::Relaxed);
+                    return match self.overflow_policy() {
+                        OverflowPolicy::Error => Err(AtomTableFull { max }),
+                        OverflowPolicy::Uninterned => Ok(Atom::from_shared(entry.into_key())),
+                    };
+                }
+                let name = entry.key().clone();
+                entry.insert(());
+                Ok(Atom::from_shared(name))
+            }
+        }
+    }
+
+    /// Returns the atom for `name` if it is predefined or already in the table.
+    pub fn get(&self, name: &str) -> Option<Atom> {
+        Atom::predefined(name).or_else(|| {
+            self.names
+                .get(name)
+                .map(|entry| Atom::from_shared(entry.key().clone()))
+        })
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains_key(name)
+    }
+
+    /// The number of interned atoms, not counting the predefined ones.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn max_atoms(&self) -> usize {
+        self.max_atoms.load(Ordering::Relaxed)
+    }
+
+    /// Changes the maximum size. Lowering it below [`AtomTable::len`] removes
+    /// nothing, but no new names are added until the table shrinks.
+    pub fn set_max_atoms(&self, max_atoms: usize) {
+        self.max_atoms.store(max_atoms, Ordering::Relaxed);
+    }
+
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        if self.reject_overflow.load(Ordering::Relaxed) {
+            OverflowPolicy::Error
+        } else {
+            OverflowPolicy::Uninterned
+        }
+    }
+
+    pub fn set_overflow_policy(&self, overflow: OverflowPolicy) {
+        self.reject_overflow
+            .store(overflow == OverflowPolicy::Error, Ordering::Relaxed);
+    }
+
+    /// Removes the names no [`Atom`] refers to anymore, returning how many were removed.
+    pub fn purge_unused(&self) -> usize {
+        let mut removed = 0;
+        self.names.retain(|name, _| {
+            let used = Arc::strong_count(name) > 1;
+            if !used {
+                removed += 1;
+            }
+            used
+        });
+        self.len.fetch_sub(removed, Ordering::Relaxed);
+        removed
+    }
+}
+
+impl Default for AtomTable {
+    /// A table of up to 1,048,576 atoms, the BEAM's default limit, that
+    /// falls back to uninterned atoms when full.
+    fn default() -> Self {
+        Self::new(1 << 20, OverflowPolicy::Uninterned)
+    }
+}
+
+/// Installs `table` as the process-wide atom table.
+///
+/// A table can only be installed once; later calls return the rejected table.
+/// Atoms created before installation are not interned.
+pub fn install(table: AtomTable) -> Result<(), AtomTable> {
+    GLOBAL.set(table)
+}
+
+/// The process-wide atom table, if one is installed.
+#[inline]
+pub fn global() -> Option<&'static AtomTable> {
+    GLOBAL.get()
+}
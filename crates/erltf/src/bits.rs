@@ -0,0 +1,649 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Building and matching bitstrings segment by segment, like Erlang's bit syntax.
+//!
+//! [`BitBuilder`] constructs `<<X:4, Y:12/little, Rest/binary>>`-style values and
+//! produces an [`OwnedTerm::Binary`] or, when the length is not a whole number of
+//! bytes, an [`OwnedTerm::BitBinary`]. [`BitReader`] takes them apart again.
+//!
+//! ```
+//! use erltf::bits::{BitBuilder, BitReader, Endianness, Signedness};
+//! use erltf::{OwnedTerm, erl_int};
+//!
+//! // <<3:4, -2:12/little-signed, 1.5/float, "ok"/binary>>
+//! let term = BitBuilder::new()
+//!     .integer(3, 4, Endianness::Big)
+//!     .integer(-2, 12, Endianness::Little)
+//!     .float(1.5, 64, Endianness::Big)
+//!     .binary(b"ok")
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut reader = BitReader::from_term(&term).unwrap();
+//! assert_eq!(reader.read_u64(4, Endianness::Big).unwrap(), 3);
+//! assert_eq!(
+//!     reader.read_integer(12, Endianness::Little, Signedness::Signed).unwrap(),
+//!     erl_int!(-2)
+//! );
+//! assert_eq!(reader.read_float(64, Endianness::Big).unwrap(), 1.5);
+//! assert_eq!(reader.rest_binary().unwrap(), b"ok");
+//! ```
+
+use crate::errors::BitSyntaxError;
+use crate::term::OwnedTerm;
+use crate::types::{BigInt, Sign};
+
+/// Byte order of a segment, like the `big`, `little` and `native` type specifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+    Native,
+}
+
+impl Endianness {
+    fn is_little(self) -> bool {
+        match self {
+            Endianness::Big => false,
+            Endianness::Little => true,
+            Endianness::Native => cfg!(target_endian = "little"),
+        }
+    }
+}
+
+/// Whether an integer segment is matched as `unsigned` or `signed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Signedness {
+    #[default]
+    Unsigned,
+    Signed,
+}
+
+/// Builds a bitstring from segments.
+///
+/// Segments are appended in order; the first invalid one is reported by [`BitBuilder::build`].
+#[derive(Debug, Clone, Default)]
+pub struct BitBuilder {
+    bytes: Vec<u8>,
+    bit_len: usize,
+    error: Option<BitSyntaxError>,
+}
+
+impl BitBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the `size` low bits of `value`, like `Value:Size/Endianness`.
+    /// Sizes beyond 128 bits are sign-extended.
+    pub fn integer<V: Into<i128>>(mut self, value: V, size: usize, endianness: Endianness) -> Self {
+        let value: i128 = value.into();
+        let mut le = value.to_le_bytes().to_vec();
+        le.resize(size.div_ceil(8), if value < 0 { 0xff } else { 0 });
+        self.put_integer(&le, size, endianness);
+        self
+    }
+
+    /// Appends the `size` low bits of a bignum in two's complement.
+    pub fn big_integer(mut self, value: &BigInt, size: usize, endianness: Endianness) -> Self {
+        let mut le = value.digits.clone();
+        le.resize(size.div_ceil(8).max(le.len()), 0);
+        if value.sign.is_negative() {
+            negate(&mut le);
+        }
+        le.truncate(size.div_ceil(8));
+        self.put_integer(&le, size, endianness);
+        self
+    }
+
+    /// Appends a float segment of 16, 32 or 64 bits, like `Value:Size/float`.
+    ///
+    /// Values that are not finite or do not fit the size are rejected, as in Erlang.
+    pub fn float(mut self, value: f64, size: usize, endianness: Endianness) -> Self {
+        let bits = match size {
+            64 if value.is_finite() => Some(value.to_bits()),
+            32 if (value as f32).is_finite() => Some((value as f32).to_bits() as u64),
+            16 => f64_to_f16_bits(value).map(u64::from),
+            64 | 32 => None,
+            _ => {
+                self.fail(BitSyntaxError::InvalidFloatSize(size));
+                return self;
+            }
+        };
+        match bits {
+            Some(bits) => self.integer(bits, size, endianness),
+            None => {
+                self.fail(BitSyntaxError::FloatOutOfRange(value));
+                self
+            }
+        }
+    }
+
+    /// Appends a character as a `utf8` segment.
+    pub fn utf8(mut self, c: char) -> Self {
+        let mut buf = [0u8; 4];
+        for &byte in c.encode_utf8(&mut buf).as_bytes() {
+            self.put_bits(byte, 8);
+        }
+        self
+    }
+
+    /// Appends a character as a `utf16` segment, one or two code units long.
+    pub fn utf16(mut self, c: char, endianness: Endianness) -> Self {
+        let mut buf = [0u16; 2];
+        for &unit in c.encode_utf16(&mut buf).iter() {
+            self = self.integer(unit, 16, endianness);
+        }
+        self
+    }
+
+    /// Appends a character as a `utf32` segment.
+    pub fn utf32(self, c: char, endianness: Endianness) -> Self {
+        self.integer(c as u32, 32, endianness)
+    }
+
+    /// Appends whole bytes, like `Bin/binary`.
+    pub fn binary(mut self, bytes: &[u8]) -> Self {
+        if self.bit_len.is_multiple_of(8) {
+            self.bytes.extend_from_slice(bytes);
+            self.bit_len += bytes.len() * 8;
+        } else {
+            for &byte in bytes {
+                self.put_bits(byte, 8);
+            }
+        }
+        self
+    }
+
+    /// Appends the first `bit_len` bits of `bytes`, like `Bits/bitstring`.
+    pub fn bitstring(mut self, bytes: &[u8], bit_len: usize) -> Self {
+        if bit_len > bytes.len() * 8 {
+            self.fail(BitSyntaxError::UnexpectedEnd {
+                needed: bit_len,
+                remaining: bytes.len() * 8,
+            });
+            return self;
+        }
+        self = self.binary(&bytes[..bit_len / 8]);
+        let tail = bit_len % 8;
+        if tail > 0 {
+            self.put_bits(bytes[bit_len / 8] >> (8 - tail), tail);
+        }
+        self
+    }
+
+    /// Appends an [`OwnedTerm::Binary`], [`OwnedTerm::SharedBinary`] or
+    /// [`OwnedTerm::BitBinary`].
+    pub fn term(mut self, term: &OwnedTerm) -> Self {
+        match bitstring_of(term) {
+            Some((bytes, bit_len)) => self.bitstring(bytes, bit_len),
+            None => {
+                self.fail(BitSyntaxError::NotABitstring);
+                self
+            }
+        }
+    }
+
+    /// The number of bits appended so far.
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// Returns an [`OwnedTerm::Binary`] if the length is a multiple of 8 bits,
+    /// and an [`OwnedTerm::BitBinary`] otherwise.
+    pub fn build(self) -> Result<OwnedTerm, BitSyntaxError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let bits = (self.bit_len % 8) as u8;
+        if bits == 0 {
+            Ok(OwnedTerm::Binary(self.bytes))
+        } else {
+            Ok(OwnedTerm::BitBinary {
+                bytes: self.bytes,
+                bits,
+            })
+        }
+    }
+
+    fn fail(&mut self, error: BitSyntaxError) {
+        self.error.get_or_insert(error);
+    }
+
+    /// Writes `size` bits of a two's complement value given as little-endian bytes.
+    fn put_integer(&mut self, le: &[u8], size: usize, endianness: Endianness) {
+        let whole = size / 8;
+        let partial = size % 8;
+        if endianness.is_little() {
+            // Whole bytes least significant first, then the most significant bits
+            for &byte in &le[..whole] {
+                self.put_bits(byte, 8);
+            }
+            if partial > 0 {
+                self.put_bits(le[whole], partial);
+            }
+        } else {
+            if partial > 0 {
+                self.put_bits(le[whole], partial);
+            }
+            for &byte in le[..whole].iter().rev() {
+                self.put_bits(byte, 8);
+            }
+        }
+    }
+
+    /// Appends the `count` low bits of `value`, most significant first.
+    fn put_bits(&mut self, value: u8, count: usize) {
+        let value = (value as u16) & ((1u16 << count) - 1);
+        let offset = self.bit_len % 8;
+        if offset == 0 {
+            self.bytes.push(((value << (8 - count)) & 0xff) as u8);
+        } else {
+            let free = 8 - offset;
+            let last = self.bytes.len() - 1;
+            if count <= free {
+                self.bytes[last] |= (value << (free - count)) as u8;
+            } else {
+                self.bytes[last] |= (value >> (count - free)) as u8;
+                self.bytes
+                    .push(((value << (8 - (count - free))) & 0xff) as u8);
+            }
+        }
+        self.bit_len += count;
+    }
+}
+
+/// Reads segments from a bitstring, front to back.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_len: usize,
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Reads all bits of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_bit_len(bytes, bytes.len() * 8)
+    }
+
+    /// Reads the first `bit_len` bits of `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than `bit_len` bits.
+    pub fn with_bit_len(bytes: &'a [u8], bit_len: usize) -> Self {
+        assert!(bit_len <= bytes.len() * 8, "bit length exceeds input");
+        Self {
+            bytes,
+            bit_len,
+            position: 0,
+        }
+    }
+
+    /// Reads an [`OwnedTerm::Binary`], [`OwnedTerm::SharedBinary`] or [`OwnedTerm::BitBinary`].
+    pub fn from_term(term: &'a OwnedTerm) -> Result<Self, BitSyntaxError> {
+        let (bytes, bit_len) = bitstring_of(term).ok_or(BitSyntaxError::NotABitstring)?;
+        Ok(Self::with_bit_len(bytes, bit_len))
+    }
+
+    /// The number of bits not read yet.
+    pub fn remaining_bits(&self) -> usize {
+        self.bit_len - self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining_bits() == 0
+    }
+
+    /// Reads an integer segment of any size as an [`OwnedTerm::Integer`], or an
+    /// [`OwnedTerm::BigInt`] if it does not fit, like `X:Size/Endianness-Signedness`.
+    pub fn read_integer(
+        &mut self,
+        size: usize,
+        endianness: Endianness,
+        signedness: Signedness,
+    ) -> Result<OwnedTerm, BitSyntaxError> {
+        let mut le = self.take_integer(size, endianness)?;
+        let negative = signedness == Signedness::Signed && sign_extend(&mut le, size);
+        if negative {
+            negate(&mut le);
+        }
+        let big = BigInt::new(Sign::from(negative), le);
+        Ok(match i128::try_from(&big) {
+            Ok(value) => OwnedTerm::from(value),
+            Err(_) => {
+                let len = big
+                    .digits
+                    .iter()
+                    .rposition(|&d| d != 0)
+                    .map_or(0, |i| i + 1);
+                OwnedTerm::BigInt(BigInt::new(big.sign, big.digits[..len].to_vec()))
+            }
+        })
+    }
+
+    /// Reads an unsigned integer segment of at most 64 bits.
+    pub fn read_u64(&mut self, size: usize, endianness: Endianness) -> Result<u64, BitSyntaxError> {
+        check_integer_size(size)?;
+        let le = self.take_integer(size, endianness)?;
+        Ok(le_to_u64(&le))
+    }
+
+    /// Reads a signed integer segment of at most 64 bits.
+    pub fn read_i64(&mut self, size: usize, endianness: Endianness) -> Result<i64, BitSyntaxError> {
+        check_integer_size(size)?;
+        let mut le = self.take_integer(size, endianness)?;
+        let negative = sign_extend(&mut le, size);
+        le.resize(8, if negative { 0xff } else { 0 });
+        Ok(le_to_u64(&le) as i64)
+    }
+
+    /// Reads a float segment of 16, 32 or 64 bits. NaN and infinities do not
+    /// match, as in Erlang.
+    pub fn read_float(
+        &mut self,
+        size: usize,
+        endianness: Endianness,
+    ) -> Result<f64, BitSyntaxError> {
+        if !matches!(size, 16 | 32 | 64) {
+            return Err(BitSyntaxError::InvalidFloatSize(size));
+        }
+        self.check_remaining(size)?;
+        let mut probe = self.clone();
+        let bits = le_to_u64(&probe.take_integer(size, endianness)?);
+        let value = match size {
+            16 => f16_bits_to_f64(bits as u16),
+            32 => f32::from_bits(bits as u32) as f64,
+            _ => f64::from_bits(bits),
+        };
+        if !value.is_finite() {
+            return Err(BitSyntaxError::FloatOutOfRange(value));
+        }
+        *self = probe;
+        Ok(value)
+    }
+
+    /// Reads a `utf8` segment of one to four bytes.
+    pub fn read_utf8(&mut self) -> Result<char, BitSyntaxError> {
+        let mut probe = self.clone();
+        let first = probe.read_u64(8, Endianness::Big)? as u8;
+        let len = match first {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(BitSyntaxError::InvalidUnicode("utf8")),
+        };
+        let mut buf = [first, 0, 0, 0];
+        for byte in buf.iter_mut().take(len).skip(1) {
+            *byte = probe.read_u64(8, Endianness::Big)? as u8;
+        }
+        let c = std::str::from_utf8(&buf[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .ok_or(BitSyntaxError::InvalidUnicode("utf8"))?;
+        *self = probe;
+        Ok(c)
+    }
+
+    /// Reads a `utf16` segment of one or two code units.
+    pub fn read_utf16(&mut self, endianness: Endianness) -> Result<char, BitSyntaxError> {
+        let mut probe = self.clone();
+        let first = probe.read_u64(16, endianness)? as u16;
+        let mut units = vec![first];
+        if (0xd800..0xdc00).contains(&first) {
+            units.push(probe.read_u64(16, endianness)? as u16);
+        }
+        let c = char::decode_utf16(units)
+            .next()
+            .and_then(Result::ok)
+            .ok_or(BitSyntaxError::InvalidUnicode("utf16"))?;
+        *self = probe;
+        Ok(c)
+    }
+
+    /// Reads a `utf32` segment.
+    pub fn read_utf32(&mut self, endianness: Endianness) -> Result<char, BitSyntaxError> {
+        let mut probe = self.clone();
+        let c = char::from_u32(probe.read_u64(32, endianness)? as u32)
+            .ok_or(BitSyntaxError::InvalidUnicode("utf32"))?;
+        *self = probe;
+        Ok(c)
+    }
+
+    /// Reads `len` whole bytes, like `Bin:Len/binary`.
+    pub fn read_binary(&mut self, len: usize) -> Result<Vec<u8>, BitSyntaxError> {
+        self.check_remaining(len * 8)?;
+        let start = self.position;
+        self.position += len * 8;
+        Ok(copy_bits(self.bytes, start, len * 8))
+    }
+
+    /// Reads `bit_len` bits as a bitstring, like `Bits:Len/bitstring`.
+    pub fn read_bitstring(&mut self, bit_len: usize) -> Result<OwnedTerm, BitSyntaxError> {
+        self.check_remaining(bit_len)?;
+        let start = self.position;
+        self.position += bit_len;
+        let bytes = copy_bits(self.bytes, start, bit_len);
+        let bits = (bit_len % 8) as u8;
+        Ok(if bits == 0 {
+            OwnedTerm::Binary(bytes)
+        } else {
+            OwnedTerm::BitBinary { bytes, bits }
+        })
+    }
+
+    /// Reads the rest as whole bytes, like a trailing `Rest/binary`. Fails
+    /// if the remaining length is not a multiple of 8 bits.
+    pub fn rest_binary(&mut self) -> Result<Vec<u8>, BitSyntaxError> {
+        let remaining = self.remaining_bits();
+        if !remaining.is_multiple_of(8) {
+            return Err(BitSyntaxError::NotByteAligned { bits: remaining });
+        }
+        self.read_binary(remaining / 8)
+    }
+
+    /// Reads the rest as a bitstring, like a trailing `Rest/bitstring`.
+    pub fn rest(&mut self) -> OwnedTerm {
+        let remaining = self.remaining_bits();
+        match self.read_bitstring(remaining) {
+            Ok(term) => term,
+            Err(_) => unreachable!("the remaining bits are always available"),
+        }
+    }
+
+    fn check_remaining(&self, needed: usize) -> Result<(), BitSyntaxError> {
+        let remaining = self.remaining_bits();
+        if needed > remaining {
+            Err(BitSyntaxError::UnexpectedEnd { needed, remaining })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reads `size` bits of an integer as little-endian bytes of its unsigned value.
+    fn take_integer(
+        &mut self,
+        size: usize,
+        endianness: Endianness,
+    ) -> Result<Vec<u8>, BitSyntaxError> {
+        self.check_remaining(size)?;
+        let whole = size / 8;
+        let partial = size % 8;
+        let mut le = vec![0u8; size.div_ceil(8)];
+        if endianness.is_little() {
+            for byte in le.iter_mut().take(whole) {
+                *byte = self.take_bits(8);
+            }
+            if partial > 0 {
+                le[whole] = self.take_bits(partial);
+            }
+        } else {
+            if partial > 0 {
+                le[whole] = self.take_bits(partial);
+            }
+            for byte in le[..whole].iter_mut().rev() {
+                *byte = self.take_bits(8);
+            }
+        }
+        Ok(le)
+    }
+
+    /// Reads `count` bits, at most 8, as the low bits of a byte.
+    fn take_bits(&mut self, count: usize) -> u8 {
+        let index = self.position / 8;
+        let offset = self.position % 8;
+        let mut window = (self.bytes[index] as u16) << 8;
+        if offset + count > 8 {
+            window |= self.bytes[index + 1] as u16;
+        }
+        self.position += count;
+        ((window >> (16 - offset - count)) & ((1u16 << count) - 1)) as u8
+    }
+}
+
+/// The bytes and the length in bits of a bitstring term.
+fn bitstring_of(term: &OwnedTerm) -> Option<(&[u8], usize)> {
+    match term {
+        OwnedTerm::Binary(bytes) => Some((bytes, bytes.len() * 8)),
+        OwnedTerm::SharedBinary(bytes) => Some((bytes, bytes.len() * 8)),
+        OwnedTerm::BitBinary { bytes, bits } if *bits < 8 && !bytes.is_empty() => {
+            Some((bytes, (bytes.len() - 1) * 8 + *bits as usize))
+        }
+        OwnedTerm::BitBinary { bytes, .. } => Some((bytes, bytes.len() * 8)),
+        _ => None,
+    }
+}
+
+/// Copies `bit_len` bits starting at bit `start`, leaving the unused bits of the last byte zero.
+fn copy_bits(bytes: &[u8], start: usize, bit_len: usize) -> Vec<u8> {
+    if start.is_multiple_of(8) {
+        let mut out = bytes[start / 8..(start + bit_len).div_ceil(8)].to_vec();
+        if let Some(last) = out.last_mut()
+            && !bit_len.is_multiple_of(8)
+        {
+            *last &= 0xffu8 << (8 - bit_len % 8);
+        }
+        return out;
+    }
+    let mut reader = BitReader::with_bit_len(bytes, start + bit_len);
+    reader.position = start;
+    let mut out = Vec::with_capacity(bit_len.div_ceil(8));
+    while reader.remaining_bits() >= 8 {
+        out.push(reader.take_bits(8));
+    }
+    let tail = reader.remaining_bits();
+    if tail > 0 {
+        out.push(reader.take_bits(tail) << (8 - tail));
+    }
+    out
+}
+
+fn check_integer_size(size: usize) -> Result<(), BitSyntaxError> {
+    if size > 64 {
+        Err(BitSyntaxError::SizeTooLarge { size, max: 64 })
+    } else {
+        Ok(())
+    }
+}
+
+fn le_to_u64(le: &[u8]) -> u64 {
+    le.iter()
+        .take(8)
+        .rev()
+        .fold(0u64, |acc, &byte| (acc << 8) | byte as u64)
+}
+
+/// Fills the bits above `size` with the sign bit, returning whether it is set.
+fn sign_extend(le: &mut [u8], size: usize) -> bool {
+    if size == 0 {
+        return false;
+    }
+    let top = (size - 1) / 8;
+    let bit = (size - 1) % 8;
+    let negative = le[top] & (1 << bit) != 0;
+    if negative {
+        le[top] |= !((2u16 << bit) - 1) as u8;
+    }
+    negative
+}
+
+/// Negates a two's complement number in place.
+fn negate(le: &mut [u8]) {
+    let mut carry = true;
+    for byte in le.iter_mut() {
+        let (value, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = value;
+        carry = overflow;
+    }
+}
+
+/// Rounds `value >> shift` to the nearest integer, ties to even.
+fn round_shift(value: u64, shift: u32) -> u64 {
+    let quotient = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if remainder > half || (remainder == half && quotient & 1 == 1) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Converts to IEEE 754 half precision, or `None` if the value does not fit.
+fn f64_to_f16_bits(value: f64) -> Option<u16> {
+    if !value.is_finite() {
+        return None;
+    }
+    let bits = value.to_bits();
+    let sign = ((bits >> 48) & 0x8000) as u16;
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    let mantissa = bits & 0x000f_ffff_ffff_ffff;
+    if exponent == 0 {
+        // Zero, or too small for half precision
+        return Some(sign);
+    }
+
+    let half_exponent = exponent - 1023 + 15;
+    let half = if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return Some(sign);
+        }
+        // A subnormal, in units of 2^-24
+        round_shift(mantissa | (1 << 52), (43 - half_exponent) as u32)
+    } else {
+        // A carry out of the mantissa correctly bumps the exponent
+        ((half_exponent as u64) << 10) + round_shift(mantissa, 42)
+    };
+    if half >= 0x7c00 {
+        None
+    } else {
+        Some(sign | half as u16)
+    }
+}
+
+fn f16_bits_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    match exponent {
+        0 => sign * mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => sign * f64::INFINITY,
+        31 => f64::NAN,
+        _ => sign * (1024.0 + mantissa) * 2f64.powi(exponent - 25),
+    }
+}
@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::term::OwnedTerm;
+use crate::term::{KeyValueAccess, KeyValueTerm, OwnedTerm};
 use crate::types::{
-    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun, Sign,
+    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun, Mfa,
+    compare_bigint, compare_bigint_float, compare_bigint_int, compare_bitstrings,
+    compare_float_bigint, compare_float_int, compare_int_bigint, compare_int_float,
 };
 use std::borrow::Cow;
 use std::cmp::Ordering;
@@ -319,6 +321,161 @@ impl<'a> BorrowedTerm<'a> {
             _ => None,
         }
     }
+
+    #[inline]
+    #[must_use]
+    pub fn tuple_get(&self, index: usize) -> Option<&BorrowedTerm<'a>> {
+        match self {
+            BorrowedTerm::Tuple(t) => t.get(index),
+            _ => None,
+        }
+    }
+
+    pub fn map_get_atom_key(&self, key: &str) -> Option<&BorrowedTerm<'a>> {
+        match self {
+            BorrowedTerm::Map(map) => map
+                .iter()
+                .find_map(|(k, v)| k.is_atom_with_name(key).then_some(v)),
+            _ => None,
+        }
+    }
+
+    pub fn proplist_get_atom_key(&self, key: &str) -> Option<&BorrowedTerm<'a>> {
+        match self {
+            BorrowedTerm::List(elements) => elements.iter().find_map(|element| match element {
+                BorrowedTerm::Tuple(pair) if pair.len() == 2 && pair[0].is_atom_with_name(key) => {
+                    Some(&pair[1])
+                }
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn is_proplist(&self) -> bool {
+        match self {
+            BorrowedTerm::List(elements) => elements.iter().all(|element| match element {
+                BorrowedTerm::Tuple(pair) if pair.len() == 2 => matches!(
+                    &pair[0],
+                    BorrowedTerm::Atom(_) | BorrowedTerm::Binary(_) | BorrowedTerm::String(_)
+                ),
+                BorrowedTerm::Atom(_) => true,
+                _ => false,
+            }),
+            BorrowedTerm::Nil => true,
+            _ => false,
+        }
+    }
+
+    /// Like [`OwnedTerm::as_erlang_string`]: a charlist of bytes, a string or a binary.
+    pub fn as_erlang_string(&self) -> Option<String> {
+        match self {
+            BorrowedTerm::List(elements) => {
+                let bytes: Option<Vec<u8>> = elements
+                    .iter()
+                    .map(|t| match t {
+                        BorrowedTerm::Integer(i) => u8::try_from(*i).ok(),
+                        _ => None,
+                    })
+                    .collect();
+                bytes.map(|b| String::from_utf8_lossy(&b).to_string())
+            }
+            BorrowedTerm::String(s) => Some(s.to_string()),
+            BorrowedTerm::Binary(b) => Some(String::from_utf8_lossy(b).to_string()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_pid(&self) -> bool {
+        matches!(self, BorrowedTerm::Pid(_))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_pid(&self) -> Option<&ExternalPid> {
+        match self {
+            BorrowedTerm::Pid(pid) => Some(pid),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_port(&self) -> Option<&ExternalPort> {
+        match self {
+            BorrowedTerm::Port(port) => Some(port),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_reference(&self) -> Option<&ExternalReference> {
+        match self {
+            BorrowedTerm::Reference(reference) => Some(reference),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn try_as_mfa(&self) -> Option<Mfa> {
+        match self.as_tuple()? {
+            [module, function, BorrowedTerm::Integer(arity)] => Some(Mfa::new(
+                module.as_atom()?,
+                function.as_atom()?,
+                u8::try_from(*arity).ok()?,
+            )),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn format_as_mfa(&self) -> Option<String> {
+        self.try_as_mfa().map(|mfa| mfa.to_string())
+    }
+}
+
+impl KeyValueTerm for BorrowedTerm<'_> {
+    type Atom = str;
+
+    fn as_integer(&self) -> Option<i64> {
+        BorrowedTerm::as_integer(self)
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        BorrowedTerm::as_bool(self)
+    }
+
+    fn as_atom(&self) -> Option<&str> {
+        BorrowedTerm::as_atom(self)
+    }
+
+    fn as_erlang_string(&self) -> Option<String> {
+        BorrowedTerm::as_erlang_string(self)
+    }
+
+    fn as_pid(&self) -> Option<&ExternalPid> {
+        BorrowedTerm::as_pid(self)
+    }
+
+    fn format_as_mfa(&self) -> Option<String> {
+        BorrowedTerm::format_as_mfa(self)
+    }
+}
+
+impl<'a> KeyValueAccess for BorrowedTerm<'a> {
+    type Value = BorrowedTerm<'a>;
+
+    fn kv_get(&self, key: &str) -> Option<&BorrowedTerm<'a>> {
+        match self {
+            BorrowedTerm::Map(_) => self.map_get_atom_key(key),
+            BorrowedTerm::List(_) => self.proplist_get_atom_key(key),
+            _ => None,
+        }
+    }
 }
 
 pub enum BorrowedTermIter<'a> {
@@ -363,6 +520,7 @@ impl<'a> From<&'a OwnedTerm> for BorrowedTerm<'a> {
             OwnedTerm::Port(p) => BorrowedTerm::Port(p.clone()),
             OwnedTerm::Reference(r) => BorrowedTerm::Reference(r.clone()),
             OwnedTerm::Binary(b) => BorrowedTerm::Binary(Cow::Borrowed(b.as_slice())),
+            OwnedTerm::SharedBinary(b) => BorrowedTerm::Binary(Cow::Borrowed(b)),
             OwnedTerm::BitBinary { bytes, bits } => BorrowedTerm::BitBinary {
                 bytes: Cow::Borrowed(bytes.as_slice()),
                 bits: *bits,
@@ -391,6 +549,46 @@ impl<'a> From<&'a OwnedTerm> for BorrowedTerm<'a> {
     }
 }
 
+/// Moves an owned term into a `BorrowedTerm` that owns all of its data.
+impl From<OwnedTerm> for BorrowedTerm<'static> {
+    fn from(term: OwnedTerm) -> Self {
+        match term {
+            OwnedTerm::Atom(a) => BorrowedTerm::Atom(Cow::Owned(a.as_str().to_string())),
+            OwnedTerm::Integer(i) => BorrowedTerm::Integer(i),
+            OwnedTerm::Float(f) => BorrowedTerm::Float(f),
+            OwnedTerm::Pid(p) => BorrowedTerm::Pid(p),
+            OwnedTerm::Port(p) => BorrowedTerm::Port(p),
+            OwnedTerm::Reference(r) => BorrowedTerm::Reference(r),
+            OwnedTerm::Binary(b) => BorrowedTerm::Binary(Cow::Owned(b)),
+            OwnedTerm::SharedBinary(b) => BorrowedTerm::Binary(Cow::Owned(b.to_vec())),
+            OwnedTerm::BitBinary { bytes, bits } => BorrowedTerm::BitBinary {
+                bytes: Cow::Owned(bytes),
+                bits,
+            },
+            OwnedTerm::String(s) => BorrowedTerm::String(Cow::Owned(s)),
+            OwnedTerm::List(elements) => {
+                BorrowedTerm::List(elements.into_iter().map(BorrowedTerm::from).collect())
+            }
+            OwnedTerm::ImproperList { elements, tail } => BorrowedTerm::ImproperList {
+                elements: elements.into_iter().map(BorrowedTerm::from).collect(),
+                tail: Box::new(BorrowedTerm::from(*tail)),
+            },
+            OwnedTerm::Map(m) => BorrowedTerm::Map(
+                m.into_iter()
+                    .map(|(k, v)| (BorrowedTerm::from(k), BorrowedTerm::from(v)))
+                    .collect(),
+            ),
+            OwnedTerm::Tuple(elements) => {
+                BorrowedTerm::Tuple(elements.into_iter().map(BorrowedTerm::from).collect())
+            }
+            OwnedTerm::BigInt(b) => BorrowedTerm::BigInt(b),
+            OwnedTerm::ExternalFun(f) => BorrowedTerm::ExternalFun(f),
+            OwnedTerm::InternalFun(f) => BorrowedTerm::InternalFun(f),
+            OwnedTerm::Nil => BorrowedTerm::Nil,
+        }
+    }
+}
+
 impl<'a> Eq for BorrowedTerm<'a> {}
 
 impl<'a> Ord for BorrowedTerm<'a> {
@@ -496,48 +694,10 @@ impl<'a> Ord for BorrowedTerm<'a> {
                         Ordering::Equal
                     })
                 }
-                (BorrowedTerm::Nil, BorrowedTerm::Nil) => Ordering::Equal,
-                (BorrowedTerm::List(a), BorrowedTerm::List(b)) => {
-                    for (x, y) in a.iter().zip(b.iter()) {
-                        match x.cmp(y) {
-                            Ordering::Equal => continue,
-                            other => return other,
-                        }
-                    }
-                    a.len().cmp(&b.len())
-                }
-                (BorrowedTerm::List(a), BorrowedTerm::Nil) => {
-                    if a.is_empty() {
-                        Ordering::Equal
-                    } else {
-                        Ordering::Greater
-                    }
-                }
-                (BorrowedTerm::Nil, BorrowedTerm::List(b)) => {
-                    if b.is_empty() {
-                        Ordering::Equal
-                    } else {
-                        Ordering::Less
-                    }
-                }
                 (
-                    BorrowedTerm::ImproperList {
-                        elements: a,
-                        tail: ta,
-                    },
-                    BorrowedTerm::ImproperList {
-                        elements: b,
-                        tail: tb,
-                    },
-                ) => {
-                    for (x, y) in a.iter().zip(b.iter()) {
-                        match x.cmp(y) {
-                            Ordering::Equal => continue,
-                            other => return other,
-                        }
-                    }
-                    a.len().cmp(&b.len()).then_with(|| ta.cmp(tb))
-                }
+                    BorrowedTerm::Nil | BorrowedTerm::List(_) | BorrowedTerm::ImproperList { .. },
+                    BorrowedTerm::Nil | BorrowedTerm::List(_) | BorrowedTerm::ImproperList { .. },
+                ) => compare_lists(self, other),
                 (BorrowedTerm::Binary(a), BorrowedTerm::Binary(b)) => a.cmp(b),
                 (BorrowedTerm::String(a), BorrowedTerm::String(b)) => a.cmp(b),
                 (BorrowedTerm::Binary(a), BorrowedTerm::String(b)) => a.as_ref().cmp(b.as_bytes()),
@@ -552,6 +712,13 @@ impl<'a> Ord for BorrowedTerm<'a> {
                         bits: bbits,
                     },
                 ) => a.cmp(b).then_with(|| abits.cmp(bbits)),
+                (BorrowedTerm::BitBinary { bytes, bits }, binary) => {
+                    compare_bitstrings(bytes, *bits, binary_bytes(binary), 8)
+                        .then(Ordering::Greater)
+                }
+                (binary, BorrowedTerm::BitBinary { bytes, bits }) => {
+                    compare_bitstrings(binary_bytes(binary), 8, bytes, *bits).then(Ordering::Less)
+                }
                 _ => Ordering::Equal,
             },
             other => other,
@@ -591,109 +758,80 @@ impl<'a> Index<&BorrowedTerm<'a>> for BorrowedTerm<'a> {
     }
 }
 
-fn compare_int_bigint(i: i64, big: &BigInt) -> Ordering {
-    if big.digits.is_empty() {
-        return i.cmp(&0);
+fn list_parts<'t, 'a>(
+    term: &'t BorrowedTerm<'a>,
+) -> Option<(&'t [BorrowedTerm<'a>], Option<&'t BorrowedTerm<'a>>)> {
+    match term {
+        BorrowedTerm::Nil => Some((&[], None)),
+        BorrowedTerm::List(elements) => Some((elements, None)),
+        BorrowedTerm::ImproperList { elements, tail } => Some((elements, Some(tail))),
+        _ => None,
     }
-
-    if big.sign.is_negative() {
-        if i >= 0 {
-            return Ordering::Greater;
-        }
-        if big.digits.len() > 8 {
-            return Ordering::Greater;
-        }
-        let abs_i = i.wrapping_neg() as u64;
-        let big_val = bigint_to_u64(big);
-        abs_i.cmp(&big_val).reverse()
-    } else {
-        if i < 0 {
-            return Ordering::Less;
-        }
-        if big.digits.len() > 8 {
-            return Ordering::Less;
-        }
-        let abs_i = i as u64;
-        let big_val = bigint_to_u64(big);
-        abs_i.cmp(&big_val)
-    }
-}
-
-fn compare_bigint_int(big: &BigInt, i: i64) -> Ordering {
-    compare_int_bigint(i, big).reverse()
 }
 
-fn compare_bigint(a: &BigInt, b: &BigInt) -> Ordering {
-    match (a.sign, b.sign) {
-        (Sign::Positive, Sign::Negative) => Ordering::Greater,
-        (Sign::Negative, Sign::Positive) => Ordering::Less,
-        (Sign::Positive, Sign::Positive) => a
-            .digits
-            .len()
-            .cmp(&b.digits.len())
-            .then_with(|| a.digits.cmp(&b.digits)),
-        (Sign::Negative, Sign::Negative) => a
-            .digits
-            .len()
-            .cmp(&b.digits.len())
-            .then_with(|| a.digits.cmp(&b.digits))
-            .reverse(),
+/// Compares lists cell by cell, like Erlang, so that proper and improper lists
+/// sharing a prefix are ordered by what follows it.
+fn compare_lists<'a>(a: &BorrowedTerm<'a>, b: &BorrowedTerm<'a>) -> Ordering {
+    match (list_parts(a), list_parts(b)) {
+        (Some((a, a_tail)), Some((b, b_tail))) => compare_list_cells(a, a_tail, b, b_tail),
+        _ => Ordering::Equal,
     }
 }
 
-fn bigint_to_u64(big: &BigInt) -> u64 {
-    let mut result = 0u64;
-    for (i, &byte) in big.digits.iter().enumerate().take(8) {
-        result |= (byte as u64) << (i * 8);
+fn compare_list_cells<'a>(
+    a: &[BorrowedTerm<'a>],
+    a_tail: Option<&BorrowedTerm<'a>>,
+    b: &[BorrowedTerm<'a>],
+    b_tail: Option<&BorrowedTerm<'a>>,
+) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        match x.cmp(y) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
     }
-    result
-}
-
-fn compare_int_float(i: i64, f: f64) -> Ordering {
-    if f.is_nan() {
-        return Ordering::Less;
+    let common = a.len().min(b.len());
+    if a.len() > common {
+        compare_tail_with_cells(b_tail, &a[common..], a_tail).reverse()
+    } else if b.len() > common {
+        compare_tail_with_cells(a_tail, &b[common..], b_tail)
+    } else {
+        match (a_tail, b_tail) {
+            (None, None) => Ordering::Equal,
+            (Some(a), None) => a.cmp(&BorrowedTerm::Nil),
+            (None, Some(b)) => BorrowedTerm::Nil.cmp(b),
+            (Some(a), Some(b)) => a.cmp(b),
+        }
     }
-    let i_as_f = i as f64;
-    i_as_f.partial_cmp(&f).unwrap_or(Ordering::Equal)
 }
 
-fn compare_float_int(f: f64, i: i64) -> Ordering {
-    compare_int_float(i, f).reverse()
-}
-
-fn compare_bigint_float(big: &BigInt, f: f64) -> Ordering {
-    if f.is_nan() {
-        return Ordering::Less;
+/// Compares the tail of one list with the remaining, non-empty cells of another.
+fn compare_tail_with_cells<'a>(
+    tail: Option<&BorrowedTerm<'a>>,
+    cells: &[BorrowedTerm<'a>],
+    cells_tail: Option<&BorrowedTerm<'a>>,
+) -> Ordering {
+    match tail {
+        None => Ordering::Less,
+        Some(tail) => match list_parts(tail) {
+            Some((elements, tail)) => compare_list_cells(elements, tail, cells, cells_tail),
+            None if matches!(
+                tail,
+                BorrowedTerm::Binary(_) | BorrowedTerm::BitBinary { .. } | BorrowedTerm::String(_)
+            ) =>
+            {
+                Ordering::Greater
+            }
+            None => Ordering::Less,
+        },
     }
-    let big_as_f = bigint_to_f64(big);
-    big_as_f.partial_cmp(&f).unwrap_or(Ordering::Equal)
 }
 
-fn compare_float_bigint(f: f64, big: &BigInt) -> Ordering {
-    compare_bigint_float(big, f).reverse()
-}
-
-fn bigint_to_f64(big: &BigInt) -> f64 {
-    let mut result = 0f64;
-    let mut scale = 1.0f64;
-
-    for &byte in big.digits.iter() {
-        let contribution = (byte as f64) * scale;
-        if contribution.is_infinite() || scale.is_infinite() {
-            return if big.sign.is_negative() {
-                f64::NEG_INFINITY
-            } else {
-                f64::INFINITY
-            };
-        }
-        result += contribution;
-        scale *= 256.0;
-    }
-
-    if big.sign.is_negative() {
-        -result
-    } else {
-        result
+fn binary_bytes<'t>(term: &'t BorrowedTerm<'_>) -> &'t [u8] {
+    match term {
+        BorrowedTerm::Binary(bytes) | BorrowedTerm::BitBinary { bytes, .. } => bytes,
+        BorrowedTerm::String(s) => s.as_bytes(),
+        _ => &[],
     }
 }
 
@@ -0,0 +1,568 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between Rust values and terms.
+//!
+//! [`IntoTerm`] and [`FromTerm`] are implemented for primitives, strings,
+//! collections and the term types, and can be derived for structs and enums
+//! with the `derive` feature:
+//!
+//! * structs with named fields become maps with atom keys, or proplists with `#[erltf(proplist)]`
+//! * `#[erltf(record = "user")]` makes a struct a tagged tuple, `{user, Name, Age}`
+//! * tuple structs become tuples, and newtypes their only field
+//! * unit enum variants become atoms, other variants tagged tuples such as `{circle, Radius}`
+//!
+//! Names are converted to snake case atoms unless set with `#[erltf(rename = "...")]`.
+//! String fields are binaries by default, `#[erltf(charlist)]` and `#[erltf(atom)]` change that.
+
+use crate::errors::TermConversionError;
+use crate::term::OwnedTerm;
+use crate::types::{Atom, BigInt, ExternalPid, ExternalPort, ExternalReference};
+use bytes::Bytes;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// A value that converts to a term.
+pub trait IntoTerm {
+    fn into_term(self) -> OwnedTerm;
+}
+
+/// A value that can be built from a term.
+pub trait FromTerm: Sized {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError>;
+
+    /// The value to use when a map key or proplist entry is absent,
+    /// `None` if that is an error.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+/// How a string is represented as a term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StringEncoding {
+    /// A UTF-8 binary, `<<"text">>`
+    #[default]
+    Binary,
+    /// A list of code points, `"text"` in Erlang
+    Charlist,
+    Atom,
+}
+
+impl StringEncoding {
+    pub fn encode(self, s: &str) -> OwnedTerm {
+        match self {
+            StringEncoding::Binary => OwnedTerm::Binary(s.as_bytes().to_vec()),
+            StringEncoding::Charlist => OwnedTerm::charlist(s),
+            StringEncoding::Atom => OwnedTerm::Atom(Atom::new(s)),
+        }
+    }
+
+    pub fn decode(self, term: OwnedTerm) -> Result<String, TermConversionError> {
+        let decoded = match (self, &term) {
+            (StringEncoding::Binary, OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_)) => {
+                return String::try_from(term);
+            }
+            (StringEncoding::Charlist, OwnedTerm::List(_) | OwnedTerm::Nil) => {
+                term.as_charlist_string()
+            }
+            (StringEncoding::Atom, OwnedTerm::Atom(atom)) => Some(atom.name.to_string()),
+            _ => {
+                return Err(TermConversionError::WrongType {
+                    expected: self.type_name(),
+                    actual: term.type_name(),
+                });
+            }
+        };
+        decoded.ok_or(TermConversionError::OutOfRange)
+    }
+
+    fn type_name(self) -> &'static str {
+        match self {
+            StringEncoding::Binary => "Binary",
+            StringEncoding::Charlist => "List",
+            StringEncoding::Atom => "Atom",
+        }
+    }
+}
+
+fn wrong_type(expected: &'static str, term: &OwnedTerm) -> TermConversionError {
+    TermConversionError::WrongType {
+        expected,
+        actual: term.type_name(),
+    }
+}
+
+impl IntoTerm for OwnedTerm {
+    fn into_term(self) -> OwnedTerm {
+        self
+    }
+}
+
+impl FromTerm for OwnedTerm {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        Ok(term)
+    }
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl IntoTerm for $t {
+            fn into_term(self) -> OwnedTerm {
+                OwnedTerm::from(self)
+            }
+        }
+
+        impl FromTerm for $t {
+            fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+                let value = i128::try_from(term)?;
+                <$t>::try_from(value).map_err(|_| TermConversionError::OutOfRange)
+            }
+        }
+    )*};
+}
+
+impl_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl IntoTerm for u128 {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::from(self)
+    }
+}
+
+impl FromTerm for u128 {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        u128::try_from(term)
+    }
+}
+
+impl IntoTerm for f64 {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::Float(self)
+    }
+}
+
+impl FromTerm for f64 {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        f64::try_from(term)
+    }
+}
+
+impl IntoTerm for f32 {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::Float(self as f64)
+    }
+}
+
+impl FromTerm for f32 {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        f64::try_from(term).map(|f| f as f32)
+    }
+}
+
+impl IntoTerm for bool {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::boolean(self)
+    }
+}
+
+impl FromTerm for bool {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        term.as_bool().ok_or_else(|| wrong_type("Boolean", &term))
+    }
+}
+
+/// Strings are converted to binaries.
+impl IntoTerm for String {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::Binary(self.into_bytes())
+    }
+}
+
+impl IntoTerm for &str {
+    fn into_term(self) -> OwnedTerm {
+        StringEncoding::Binary.encode(self)
+    }
+}
+
+/// Accepts binaries and charlists.
+impl FromTerm for String {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        match term {
+            OwnedTerm::List(_) | OwnedTerm::Nil => StringEncoding::Charlist.decode(term),
+            _ => String::try_from(term),
+        }
+    }
+}
+
+impl IntoTerm for Atom {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::Atom(self)
+    }
+}
+
+impl FromTerm for Atom {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        match term {
+            OwnedTerm::Atom(atom) => Ok(atom),
+            _ => Err(wrong_type("Atom", &term)),
+        }
+    }
+}
+
+impl IntoTerm for Bytes {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::SharedBinary(self)
+    }
+}
+
+impl FromTerm for Bytes {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        match term {
+            OwnedTerm::SharedBinary(bytes) => Ok(bytes),
+            OwnedTerm::Binary(bytes) => Ok(Bytes::from(bytes)),
+            _ => Err(wrong_type("Binary", &term)),
+        }
+    }
+}
+
+macro_rules! impl_variant {
+    ($($t:ty => $variant:ident),*) => {$(
+        impl IntoTerm for $t {
+            fn into_term(self) -> OwnedTerm {
+                OwnedTerm::$variant(self)
+            }
+        }
+
+        impl FromTerm for $t {
+            fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+                match term {
+                    OwnedTerm::$variant(value) => Ok(value),
+                    _ => Err(wrong_type(stringify!($variant), &term)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_variant!(
+    BigInt => BigInt,
+    ExternalPid => Pid,
+    ExternalPort => Port,
+    ExternalReference => Reference
+);
+
+/// `None` is the atom `undefined`.
+impl<T: IntoTerm> IntoTerm for Option<T> {
+    fn into_term(self) -> OwnedTerm {
+        match self {
+            Some(value) => value.into_term(),
+            None => OwnedTerm::atom("undefined"),
+        }
+    }
+}
+
+impl<T: FromTerm> FromTerm for Option<T> {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        if term.is_undefined() {
+            return Ok(None);
+        }
+        T::from_term(term).map(Some)
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: IntoTerm> IntoTerm for Box<T> {
+    fn into_term(self) -> OwnedTerm {
+        (*self).into_term()
+    }
+}
+
+impl<T: FromTerm> FromTerm for Box<T> {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        T::from_term(term).map(Box::new)
+    }
+}
+
+impl<T: IntoTerm> IntoTerm for Vec<T> {
+    fn into_term(self) -> OwnedTerm {
+        OwnedTerm::List(self.into_iter().map(IntoTerm::into_term).collect())
+    }
+}
+
+impl<T: FromTerm> FromTerm for Vec<T> {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        match term {
+            OwnedTerm::List(elements) => elements.into_iter().map(T::from_term).collect(),
+            OwnedTerm::Nil => Ok(Vec::new()),
+            _ => Err(wrong_type("List", &term)),
+        }
+    }
+}
+
+impl<K: IntoTerm, V: IntoTerm> IntoTerm for BTreeMap<K, V> {
+    fn into_term(self) -> OwnedTerm {
+        self.into_iter()
+            .map(|(k, v)| (k.into_term(), v.into_term()))
+            .collect()
+    }
+}
+
+impl<K: FromTerm + Ord, V: FromTerm> FromTerm for BTreeMap<K, V> {
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        match term {
+            OwnedTerm::Map(map) => map
+                .into_iter()
+                .map(|(k, v)| Ok((K::from_term(k)?, V::from_term(v)?)))
+                .collect(),
+            _ => Err(wrong_type("Map", &term)),
+        }
+    }
+}
+
+impl<K: IntoTerm, V: IntoTerm, S> IntoTerm for HashMap<K, V, S> {
+    fn into_term(self) -> OwnedTerm {
+        self.into_iter()
+            .map(|(k, v)| (k.into_term(), v.into_term()))
+            .collect()
+    }
+}
+
+impl<K, V, S> FromTerm for HashMap<K, V, S>
+where
+    K: FromTerm + Eq + Hash,
+    V: FromTerm,
+    S: BuildHasher + Default,
+{
+    fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+        match term {
+            OwnedTerm::Map(map) => map
+                .into_iter()
+                .map(|(k, v)| Ok((K::from_term(k)?, V::from_term(v)?)))
+                .collect(),
+            _ => Err(wrong_type("Map", &term)),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($len:expr => $($name:ident),+) => {
+        impl<$($name: IntoTerm),+> IntoTerm for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_term(self) -> OwnedTerm {
+                let ($($name,)+) = self;
+                OwnedTerm::Tuple(vec![$($name.into_term()),+])
+            }
+        }
+
+        impl<$($name: FromTerm),+> FromTerm for ($($name,)+) {
+            fn from_term(term: OwnedTerm) -> Result<Self, TermConversionError> {
+                let mut elements = __private::tuple_elements(term, $len)?;
+                Ok(($(__private::next::<$name>(&mut elements)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(1 => A);
+impl_tuple!(2 => A, B);
+impl_tuple!(3 => A, B, C);
+impl_tuple!(4 => A, B, C, D);
+impl_tuple!(5 => A, B, C, D, E);
+impl_tuple!(6 => A, B, C, D, E, F);
+
+/// Helpers for the code `#[derive(IntoTerm, FromTerm)]` generates.
+#[doc(hidden)]
+pub mod __private {
+    use super::{FromTerm, StringEncoding};
+    use crate::errors::TermConversionError;
+    use crate::term::OwnedTerm;
+    use std::collections::BTreeMap;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::vec::IntoIter;
+
+    pub use std::collections::BTreeMap as Map;
+
+    /// A field type with a `#[erltf(binary | charlist | atom)]` encoding:
+    /// a string, or an `Option` of one that is `undefined` when `None`.
+    pub trait EncodedString: Sized {
+        fn encode(&self, encoding: StringEncoding) -> OwnedTerm;
+
+        fn decode(term: OwnedTerm, encoding: StringEncoding) -> Result<Self, TermConversionError>;
+
+        /// See [`FromTerm::from_missing`].
+        fn from_missing() -> Option<Self> {
+            None
+        }
+    }
+
+    macro_rules! impl_encoded_string {
+        ($($ty:ty),*) => {
+            $(
+                impl EncodedString for $ty {
+                    fn encode(&self, encoding: StringEncoding) -> OwnedTerm {
+                        encoding.encode(self)
+                    }
+
+                    fn decode(
+                        term: OwnedTerm,
+                        encoding: StringEncoding,
+                    ) -> Result<Self, TermConversionError> {
+                        encoding.decode(term).map(Self::from)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_encoded_string!(String, Box<str>, Rc<str>, Arc<str>);
+
+    impl<T: EncodedString> EncodedString for Option<T> {
+        fn encode(&self, encoding: StringEncoding) -> OwnedTerm {
+            match self {
+                Some(value) => value.encode(encoding),
+                None => OwnedTerm::atom("undefined"),
+            }
+        }
+
+        fn decode(term: OwnedTerm, encoding: StringEncoding) -> Result<Self, TermConversionError> {
+            if term.is_undefined() {
+                return Ok(None);
+            }
+            T::decode(term, encoding).map(Some)
+        }
+
+        fn from_missing() -> Option<Self> {
+            Some(None)
+        }
+    }
+
+    pub fn tuple_elements(
+        term: OwnedTerm,
+        len: usize,
+    ) -> Result<IntoIter<OwnedTerm>, TermConversionError> {
+        match term {
+            OwnedTerm::Tuple(elements) if elements.len() == len => Ok(elements.into_iter()),
+            OwnedTerm::Tuple(elements) => Err(TermConversionError::WrongArity {
+                expected: len,
+                actual: elements.len(),
+            }),
+            _ => Err(super::wrong_type("Tuple", &term)),
+        }
+    }
+
+    /// The elements of a tuple that starts with the atom `tag`, without the tag.
+    pub fn record_elements(
+        term: OwnedTerm,
+        tag: &str,
+        len: usize,
+    ) -> Result<IntoIter<OwnedTerm>, TermConversionError> {
+        let mut elements = tuple_elements(term, len + 1)?;
+        match elements.next() {
+            Some(OwnedTerm::Atom(atom)) if atom == tag => Ok(elements),
+            Some(other) => Err(TermConversionError::UnknownTag(other.to_string())),
+            None => Err(TermConversionError::WrongArity {
+                expected: len + 1,
+                actual: 0,
+            }),
+        }
+    }
+
+    /// The tag of an atom or a tagged tuple.
+    pub fn variant_tag(term: &OwnedTerm) -> Result<String, TermConversionError> {
+        match term {
+            OwnedTerm::Atom(atom) => Ok(atom.name.to_string()),
+            OwnedTerm::Tuple(elements) => match elements.first() {
+                Some(OwnedTerm::Atom(atom)) => Ok(atom.name.to_string()),
+                _ => Err(super::wrong_type("Tagged Tuple", term)),
+            },
+            _ => Err(super::wrong_type("Atom or Tuple", term)),
+        }
+    }
+
+    pub fn next<T: FromTerm>(elements: &mut IntoIter<OwnedTerm>) -> Result<T, TermConversionError> {
+        let element = elements.next().ok_or(TermConversionError::OutOfRange)?;
+        T::from_term(element)
+    }
+
+    pub fn next_string<T: EncodedString>(
+        elements: &mut IntoIter<OwnedTerm>,
+        encoding: StringEncoding,
+    ) -> Result<T, TermConversionError> {
+        let element = elements.next().ok_or(TermConversionError::OutOfRange)?;
+        T::decode(element, encoding)
+    }
+
+    pub fn map_entries(
+        term: OwnedTerm,
+    ) -> Result<BTreeMap<OwnedTerm, OwnedTerm>, TermConversionError> {
+        match term {
+            OwnedTerm::Map(map) => Ok(map),
+            _ => Err(super::wrong_type("Map", &term)),
+        }
+    }
+
+    /// The `{Key, Value}` pairs of a proplist. The first pair for a key wins.
+    pub fn proplist_entries(
+        term: OwnedTerm,
+    ) -> Result<BTreeMap<OwnedTerm, OwnedTerm>, TermConversionError> {
+        let elements = match term {
+            OwnedTerm::List(elements) => elements,
+            OwnedTerm::Nil => Vec::new(),
+            _ => return Err(super::wrong_type("Proplist", &term)),
+        };
+        let mut entries = BTreeMap::new();
+        for element in elements {
+            match element {
+                OwnedTerm::Tuple(mut pair) if pair.len() == 2 => {
+                    let value = pair.pop().unwrap_or(OwnedTerm::Nil);
+                    let key = pair.pop().unwrap_or(OwnedTerm::Nil);
+                    entries.entry(key).or_insert(value);
+                }
+                OwnedTerm::Atom(atom) => {
+                    entries
+                        .entry(OwnedTerm::Atom(atom))
+                        .or_insert(OwnedTerm::boolean(true));
+                }
+                other => return Err(super::wrong_type("Proplist", &other)),
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn field<T: FromTerm>(
+        entries: &mut BTreeMap<OwnedTerm, OwnedTerm>,
+        key: &'static str,
+    ) -> Result<T, TermConversionError> {
+        match entries.remove(&OwnedTerm::atom(key)) {
+            Some(value) => T::from_term(value),
+            None => T::from_missing().ok_or(TermConversionError::MissingField(key)),
+        }
+    }
+
+    pub fn string_field<T: EncodedString>(
+        entries: &mut BTreeMap<OwnedTerm, OwnedTerm>,
+        key: &'static str,
+        encoding: StringEncoding,
+    ) -> Result<T, TermConversionError> {
+        match entries.remove(&OwnedTerm::atom(key)) {
+            Some(value) => T::decode(value, encoding),
+            None => T::from_missing().ok_or(TermConversionError::MissingField(key)),
+        }
+    }
+}
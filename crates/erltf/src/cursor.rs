@@ -0,0 +1,398 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Navigation over encoded terms without decoding them.
+//!
+//! A [`TermCursor`] points at a sub-term of an encoded buffer. Moving to a
+//! tuple element, list element or map value skips over the terms before it
+//! by reading their length prefixes, so only the selected sub-term is ever
+//! decoded.
+
+use crate::borrowed::BorrowedTerm;
+use crate::decoder::{DecodeOptions, decode_raw_term, decode_raw_term_borrowed};
+use crate::errors::{ContextualDecodeError, DecodeError, ParsingContext, PathSegment};
+use crate::streaming::layout;
+use crate::tags::{
+    COMPRESSED_EXT, LARGE_TUPLE_EXT, LIST_EXT, MAP_EXT, NIL_EXT, SMALL_TUPLE_EXT, VERSION,
+};
+use crate::term::OwnedTerm;
+
+/// A position in an encoded term.
+///
+/// ```
+/// use erltf::{TermCursor, encode, erl_atom, erl_int, erl_list, erl_tuple};
+///
+/// let queues = erl_list![
+///     erl_tuple![erl_atom!("queue"), erl_atom!("q1"), erl_int!(10)],
+///     erl_tuple![erl_atom!("queue"), erl_atom!("q2"), erl_int!(20)],
+/// ];
+/// let data = encode(&queues).unwrap();
+///
+/// let cursor = TermCursor::new(&data).unwrap();
+/// let mut depths = Vec::new();
+/// for element in cursor.elements().unwrap().unwrap() {
+///     let depth = element.unwrap().tuple_element(2).unwrap().unwrap();
+///     depths.push(depth.decode().unwrap());
+/// }
+/// assert_eq!(depths, vec![erl_int!(10), erl_int!(20)]);
+/// ```
+///
+/// Navigation methods return `Ok(None)` when the term has a different type
+/// or the element does not exist, and an error when the data is malformed.
+/// Lists encoded as `STRING_EXT` have no sub-terms to move into; decode them instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermCursor<'a> {
+    input: &'a [u8],
+    context: ParsingContext,
+}
+
+impl<'a> TermCursor<'a> {
+    /// A cursor at the root of a `term_to_binary` output.
+    ///
+    /// Compressed terms cannot be navigated without inflating them first.
+    pub fn new(data: &'a [u8]) -> Result<Self, ContextualDecodeError> {
+        let fail = |error| ContextualDecodeError::new(error, ParsingContext::new());
+        match data.first() {
+            None => Err(fail(DecodeError::UnexpectedEof)),
+            Some(&VERSION) => {
+                let cursor = Self {
+                    input: data,
+                    context: ParsingContext::with_offset(1),
+                };
+                if cursor.input.get(1) == Some(&COMPRESSED_EXT) {
+                    return Err(
+                        cursor.error(DecodeError::UnsupportedType("COMPRESSED_EXT".to_string()))
+                    );
+                }
+                cursor.tag()?;
+                Ok(cursor)
+            }
+            Some(&actual) => Err(fail(DecodeError::InvalidVersion {
+                expected: VERSION,
+                actual,
+            })),
+        }
+    }
+
+    /// The tag of the term under the cursor.
+    pub fn tag(&self) -> Result<u8, ContextualDecodeError> {
+        self.input
+            .get(self.context.byte_offset)
+            .copied()
+            .ok_or_else(|| self.error(DecodeError::UnexpectedEof))
+    }
+
+    /// The offset of the term in the buffer passed to [`TermCursor::new`].
+    pub fn byte_offset(&self) -> usize {
+        self.context.byte_offset
+    }
+
+    /// How the cursor got here from the root.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.context.path
+    }
+
+    /// The encoded bytes of the term, without the version byte.
+    pub fn as_bytes(&self) -> Result<&'a [u8], ContextualDecodeError> {
+        let start = self.context.byte_offset;
+        let end = term_end(self.input, start).map_err(|e| self.error(e))?;
+        Ok(&self.input[start..end])
+    }
+
+    /// Decodes the term under the cursor.
+    pub fn decode(&self) -> Result<OwnedTerm, ContextualDecodeError> {
+        decode_raw_term(self.as_bytes()?).map_err(|e| self.error(e))
+    }
+
+    /// Decodes the term under the cursor, borrowing from the buffer.
+    pub fn decode_borrowed(&self) -> Result<BorrowedTerm<'a>, ContextualDecodeError> {
+        decode_raw_term_borrowed(self.as_bytes()?).map_err(|e| {
+            let mut context = self.context.clone();
+            context.byte_offset += e.context.byte_offset;
+            context.path.extend(e.context.path);
+            ContextualDecodeError::new(e.error, context)
+        })
+    }
+
+    /// The number of elements of a tuple, list or map. The tail of an
+    /// improper list is not counted.
+    pub fn element_count(&self) -> Result<Option<usize>, ContextualDecodeError> {
+        Ok(self.header()?.map(|(_, count, _)| count))
+    }
+
+    pub fn tuple_element(&self, index: usize) -> Result<Option<Self>, ContextualDecodeError> {
+        match self.header()? {
+            Some((Kind::Tuple, count, _)) if index < count => self.nth_child(index).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn list_element(&self, index: usize) -> Result<Option<Self>, ContextualDecodeError> {
+        match self.header()? {
+            Some((Kind::List, count, _)) if index < count => self.nth_child(index).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The tail of an improper list.
+    pub fn list_tail(&self) -> Result<Option<Self>, ContextualDecodeError> {
+        match self.header()? {
+            Some((Kind::List, count, _)) => {
+                let tail = self.nth_child(count)?;
+                if tail.tag()? == NIL_EXT {
+                    return Ok(None);
+                }
+                Ok(Some(tail.with_segment(PathSegment::ImproperListTail)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The value stored under `key`. Only the keys are decoded.
+    pub fn map_get(&self, key: &OwnedTerm) -> Result<Option<Self>, ContextualDecodeError> {
+        let Some((Kind::Map, count, header)) = self.header()? else {
+            return Ok(None);
+        };
+        let wanted = BorrowedTerm::from(key);
+        let mut offset = self.context.byte_offset + header;
+        for _ in 0..count {
+            let key_cursor = self.at(offset, PathSegment::MapKey);
+            let value_offset = term_end(self.input, offset).map_err(|e| key_cursor.error(e))?;
+            if key_cursor.decode_borrowed()? == wanted {
+                return Ok(Some(
+                    self.at(value_offset, PathSegment::MapValue(key.to_string())),
+                ));
+            }
+            offset = term_end(self.input, value_offset).map_err(|e| self.error(e))?;
+        }
+        Ok(None)
+    }
+
+    /// Iterates over the elements of a tuple or a list.
+    pub fn elements(&self) -> Result<Option<Elements<'a>>, ContextualDecodeError> {
+        let elements = match self.header()? {
+            Some((kind @ (Kind::Tuple | Kind::List), count, header)) => Some(Elements {
+                parent: self.clone(),
+                kind,
+                offset: self.context.byte_offset + header,
+                index: 0,
+                count,
+            }),
+            _ => None,
+        };
+        Ok(elements)
+    }
+
+    /// Iterates over the key and value cursors of a map.
+    pub fn entries(&self) -> Result<Option<Entries<'a>>, ContextualDecodeError> {
+        let entries = match self.header()? {
+            Some((Kind::Map, count, header)) => Some(Entries {
+                parent: self.clone(),
+                offset: self.context.byte_offset + header,
+                remaining: count,
+            }),
+            _ => None,
+        };
+        Ok(entries)
+    }
+
+    /// The kind, element count and header length of a container.
+    fn header(&self) -> Result<Option<(Kind, usize, usize)>, ContextualDecodeError> {
+        let header = match self.tag()? {
+            SMALL_TUPLE_EXT => Some((Kind::Tuple, self.read_len(1)?, 2)),
+            LARGE_TUPLE_EXT => Some((Kind::Tuple, self.read_len(4)?, 5)),
+            LIST_EXT => Some((Kind::List, self.read_len(4)?, 5)),
+            NIL_EXT => Some((Kind::List, 0, 0)),
+            MAP_EXT => Some((Kind::Map, self.read_len(4)?, 5)),
+            _ => None,
+        };
+        Ok(header)
+    }
+
+    fn read_len(&self, width: usize) -> Result<usize, ContextualDecodeError> {
+        let start = self.context.byte_offset + 1;
+        let bytes = self
+            .input
+            .get(start..start + width)
+            .ok_or_else(|| self.error(DecodeError::UnexpectedEof))?;
+        Ok(bytes.iter().fold(0usize, |len, &b| (len << 8) | b as usize))
+    }
+
+    /// The `n`th sub-term of a tuple or list, counting the tail of a list last.
+    fn nth_child(&self, n: usize) -> Result<Self, ContextualDecodeError> {
+        let (kind, _, header) = self.header()?.ok_or_else(|| {
+            self.error(DecodeError::InvalidFormat(
+                "expected a tuple or a list".to_string(),
+            ))
+        })?;
+        let mut offset = self.context.byte_offset + header;
+        for _ in 0..n {
+            offset = term_end(self.input, offset).map_err(|e| self.error(e))?;
+        }
+        let segment = match kind {
+            Kind::Tuple => PathSegment::TupleElement(n),
+            _ => PathSegment::ListElement(n),
+        };
+        Ok(self.at(offset, segment))
+    }
+
+    fn at(&self, offset: usize, segment: PathSegment) -> Self {
+        let mut context = self.context.clone();
+        context.byte_offset = offset;
+        context.push(segment);
+        Self {
+            input: self.input,
+            context,
+        }
+    }
+
+    fn with_segment(mut self, segment: PathSegment) -> Self {
+        self.context.pop();
+        self.context.push(segment);
+        self
+    }
+
+    fn error(&self, error: DecodeError) -> ContextualDecodeError {
+        ContextualDecodeError::new(error, self.context.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Tuple,
+    List,
+    Map,
+}
+
+/// Cursors at the elements of a tuple or list, see [`TermCursor::elements`].
+#[derive(Debug, Clone)]
+pub struct Elements<'a> {
+    parent: TermCursor<'a>,
+    kind: Kind,
+    offset: usize,
+    index: usize,
+    count: usize,
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = Result<TermCursor<'a>, ContextualDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+        let segment = match self.kind {
+            Kind::Tuple => PathSegment::TupleElement(self.index),
+            _ => PathSegment::ListElement(self.index),
+        };
+        let cursor = self.parent.at(self.offset, segment);
+        match term_end(cursor.input, self.offset) {
+            Ok(end) => {
+                self.offset = end;
+                self.index += 1;
+                Some(Ok(cursor))
+            }
+            Err(e) => {
+                self.index = self.count;
+                Some(Err(cursor.error(e)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Cursors at the keys and values of a map, see [`TermCursor::entries`].
+/// Keys are decoded to label the path of their values.
+#[derive(Debug, Clone)]
+pub struct Entries<'a> {
+    parent: TermCursor<'a>,
+    offset: usize,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Result<(TermCursor<'a>, TermCursor<'a>), ContextualDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let key = self.parent.at(self.offset, PathSegment::MapKey);
+        let entry = key.decode().and_then(|decoded| {
+            let value_offset = term_end(key.input, self.offset).map_err(|e| key.error(e))?;
+            let value = self
+                .parent
+                .at(value_offset, PathSegment::MapValue(decoded.to_string()));
+            let end = term_end(key.input, value_offset).map_err(|e| value.error(e))?;
+            Ok((value, end))
+        });
+        match entry {
+            Ok((value, end)) => {
+                self.offset = end;
+                self.remaining -= 1;
+                Some(Ok((key, value)))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// The offset just past the term that starts at `offset`.
+///
+/// Keeps its own stack, so deeply nested input cannot overflow the thread's.
+fn term_end(input: &[u8], offset: usize) -> Result<usize, DecodeError> {
+    // Skipping only checks the structure; the size limits apply once a term is decoded.
+    let unbounded = DecodeOptions {
+        max_list_size: usize::MAX,
+        max_tuple_size: usize::MAX,
+        max_map_size: usize::MAX,
+        max_binary_size: usize::MAX,
+        ..DecodeOptions::default()
+    };
+    // Each frame holds the children still to skip and the trailer that follows them.
+    let mut stack: Vec<(u64, usize)> = vec![(1, 0)];
+    let mut end = offset;
+    while let Some(frame) = stack.last_mut() {
+        if frame.0 == 0 {
+            end += frame.1;
+            stack.pop();
+            continue;
+        }
+        frame.0 -= 1;
+        let rest = input.get(end..).ok_or(DecodeError::UnexpectedEof)?;
+        let layout = layout(rest, &unbounded)?.map_err(|_| DecodeError::UnexpectedEof)?;
+        if layout.compressed {
+            return Err(DecodeError::UnsupportedType("COMPRESSED_EXT".to_string()));
+        }
+        end += layout.header + layout.body;
+        if layout.children > 0 || layout.trailer > 0 {
+            stack.push((layout.children, layout.trailer));
+        }
+    }
+    if end > input.len() {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    Ok(end)
+}
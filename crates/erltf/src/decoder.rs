@@ -12,38 +12,293 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::atom_table;
 use crate::borrowed::BorrowedTerm;
 use crate::errors::{ContextualDecodeError, DecodeError, ParsingContext, PathSegment};
 use crate::tags::{
     ATOM_CACHE_REF, ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, COMPRESSED_EXT,
     DIST_FRAG_HEADER, DIST_HEADER, EXPORT_EXT, FLOAT_EXT, INTEGER_EXT, LARGE_BIG_EXT,
     LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT, NEW_FUN_EXT, NEW_PID_EXT,
-    NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT, REFERENCE_EXT,
-    SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT, SMALL_TUPLE_EXT,
-    STRING_EXT, V4_PORT_EXT, VERSION,
+    NEW_PORT_EXT, NEW_REFERENCE_EXT, NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT,
+    REFERENCE_EXT, SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT,
+    SMALL_TUPLE_EXT, STRING_EXT, V4_PORT_EXT, VERSION,
 };
 use crate::term::OwnedTerm;
 use crate::types::{
     Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
 };
+use bytes::Bytes;
 use flate2::read::ZlibDecoder;
 use nom::IResult;
 use nom::bytes::complete::take;
 use nom::error::{Error as NomError, ErrorKind};
 use nom::number::complete::{be_f64, be_i32, be_u8, be_u16, be_u32, be_u64};
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashMap};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Read;
+use std::mem::size_of;
 use std::str;
 
-const MAX_ATOM_SIZE: usize = 65535;
-const MAX_LIST_SIZE: usize = 10_000_000;
-const MAX_TUPLE_SIZE: usize = 10_000_000;
-const MAX_MAP_SIZE: usize = 1_000_000;
-const MAX_BINARY_SIZE: usize = 100_000_000;
+pub(crate) const MAX_ATOM_SIZE: usize = 65535;
+pub(crate) const MAX_LIST_SIZE: usize = 10_000_000;
+pub(crate) const MAX_TUPLE_SIZE: usize = 10_000_000;
+pub(crate) const MAX_MAP_SIZE: usize = 1_000_000;
+pub(crate) const MAX_BINARY_SIZE: usize = 100_000_000;
+
+/// Binaries up to this size are copied even when decoding into a shared
+/// buffer, like heap binaries in the Erlang runtime.
+pub const SHARED_BINARY_THRESHOLD: usize = 64;
 
 type NomResult<'a, T> = IResult<&'a [u8], T, NomError<&'a [u8]>>;
 
+/// Limits that bound the work and memory spent decoding a single term.
+///
+/// The defaults are what [`decode`] uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub max_atom_size: usize,
+    pub max_list_size: usize,
+    pub max_tuple_size: usize,
+    pub max_map_size: usize,
+    pub max_binary_size: usize,
+    /// How deeply lists, tuples, maps and funs may nest. `None` means no limit.
+    pub max_depth: Option<usize>,
+    /// An approximate cap on the bytes the decoded term allocates.
+    pub max_memory: Option<usize>,
+    /// The largest accepted ratio between a `COMPRESSED_EXT` term's declared
+    /// uncompressed size and its compressed size.
+    pub max_compression_ratio: Option<usize>,
+    /// Like `binary_to_term(B, [safe])`: funs, and atoms that are neither in
+    /// `known_atoms` nor predefined (`ok`, `error`, `true`, ...), are rejected.
+    pub safe: bool,
+    pub known_atoms: HashSet<Atom>,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            max_atom_size: MAX_ATOM_SIZE,
+            max_list_size: MAX_LIST_SIZE,
+            max_tuple_size: MAX_TUPLE_SIZE,
+            max_map_size: MAX_MAP_SIZE,
+            max_binary_size: MAX_BINARY_SIZE,
+            max_depth: None,
+            max_memory: None,
+            max_compression_ratio: None,
+            safe: false,
+            known_atoms: HashSet::new(),
+        }
+    }
+}
+
+impl DecodeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Equivalent of `binary_to_term(B, [safe])`, accepting `known_atoms`.
+    pub fn safe<I, S>(known_atoms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::new().with_safe(true).with_known_atoms(known_atoms)
+    }
+
+    pub fn with_max_atom_size(mut self, max: usize) -> Self {
+        self.max_atom_size = max;
+        self
+    }
+
+    pub fn with_max_list_size(mut self, max: usize) -> Self {
+        self.max_list_size = max;
+        self
+    }
+
+    pub fn with_max_tuple_size(mut self, max: usize) -> Self {
+        self.max_tuple_size = max;
+        self
+    }
+
+    pub fn with_max_map_size(mut self, max: usize) -> Self {
+        self.max_map_size = max;
+        self
+    }
+
+    pub fn with_max_binary_size(mut self, max: usize) -> Self {
+        self.max_binary_size = max;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    pub fn with_max_memory(mut self, max: usize) -> Self {
+        self.max_memory = Some(max);
+        self
+    }
+
+    pub fn with_max_compression_ratio(mut self, max: usize) -> Self {
+        self.max_compression_ratio = Some(max);
+        self
+    }
+
+    pub fn with_safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+
+    pub fn with_known_atoms<I, S>(mut self, atoms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.known_atoms
+            .extend(atoms.into_iter().map(|a| Atom::new(a.as_ref())));
+        self
+    }
+}
+
+/// The options of one decode call, the memory it has used so far, and the
+/// first limit it ran into.
+struct Limits<'o> {
+    options: &'o DecodeOptions,
+    memory: Cell<usize>,
+    error: Cell<Option<DecodeError>>,
+}
+
+impl<'o> Limits<'o> {
+    fn new(options: &'o DecodeOptions) -> Self {
+        Self {
+            options,
+            memory: Cell::new(0),
+            error: Cell::new(None),
+        }
+    }
+
+    fn fail<'a, T>(&self, input: &'a [u8], error: DecodeError) -> NomResult<'a, T> {
+        let first = self.error.take().unwrap_or(error);
+        self.error.set(Some(first));
+        Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)))
+    }
+
+    fn check_size<'a>(
+        &self,
+        input: &'a [u8],
+        size: usize,
+        max: usize,
+        error: fn(usize, usize) -> DecodeError,
+    ) -> NomResult<'a, ()> {
+        if size > max {
+            return self.fail(input, error(size, max));
+        }
+        Ok((input, ()))
+    }
+
+    /// Accounts for `bytes` of memory allocated by the decoded term.
+    fn charge<'a>(&self, input: &'a [u8], bytes: usize) -> NomResult<'a, ()> {
+        let used = self.memory.get().saturating_add(bytes);
+        if let Some(max) = self.options.max_memory
+            && used > max
+        {
+            return self.fail(input, DecodeError::MemoryLimitExceeded { max });
+        }
+        self.memory.set(used);
+        Ok((input, ()))
+    }
+
+    fn check_atom<'a>(&self, input: &'a [u8], atom: &Atom) -> NomResult<'a, ()> {
+        self.check_size(
+            input,
+            atom.len(),
+            self.options.max_atom_size,
+            |size, max| DecodeError::AtomTooLarge { size, max },
+        )?;
+        if self.options.safe && !atom.is_predefined() && !self.options.known_atoms.contains(atom) {
+            return self.fail(input, DecodeError::UnsafeAtom(atom.to_string()));
+        }
+        self.charge(input, atom.len())
+    }
+
+    /// Makes an atom through the process-wide atom table, honoring its overflow policy.
+    ///
+    /// Atoms are checked before they are interned, so rejected ones never reach the table.
+    /// In safe mode nothing is interned: known atoms are looked up instead.
+    fn intern<'a>(&self, input: &'a [u8], name: &str) -> NomResult<'a, Atom> {
+        self.check_size(
+            input,
+            name.len(),
+            self.options.max_atom_size,
+            |size, max| DecodeError::AtomTooLarge { size, max },
+        )?;
+        if self.options.safe {
+            let known = Atom::predefined(name)
+                .or_else(|| self.options.known_atoms.get(name).cloned())
+                .map(|atom| {
+                    atom_table::global()
+                        .and_then(|table| table.get(name))
+                        .unwrap_or(atom)
+                });
+            return match known {
+                Some(atom) => Ok((input, atom)),
+                None => self.fail(input, DecodeError::UnsafeAtom(name.to_string())),
+            };
+        }
+        match atom_table::global() {
+            Some(table) => match table.intern(name) {
+                Ok(atom) => Ok((input, atom)),
+                Err(full) => self.fail(input, full.into()),
+            },
+            None => Ok((input, Atom::new(name))),
+        }
+    }
+
+    /// The limit `e` was caused by, if any, or `e` itself.
+    fn take_error(&self, e: nom::Err<NomError<&[u8]>>) -> DecodeError {
+        self.error.take().unwrap_or_else(|| from_nom_error(e))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Context<'c> {
+    cache: &'c AtomCache,
+    shared: Option<&'c Bytes>,
+    limits: &'c Limits<'c>,
+    depth: usize,
+}
+
+impl<'c> Context<'c> {
+    fn new(cache: &'c AtomCache, shared: Option<&'c Bytes>, limits: &'c Limits<'c>) -> Self {
+        Self {
+            cache,
+            shared,
+            limits,
+            depth: 0,
+        }
+    }
+
+    fn nested<'a>(self, input: &'a [u8]) -> NomResult<'a, Self> {
+        let depth = self.depth + 1;
+        if let Some(max) = self.limits.options.max_depth
+            && depth > max
+        {
+            return self
+                .limits
+                .fail(input, DecodeError::DepthLimitExceeded { max });
+        }
+        Ok((input, Self { depth, ..self }))
+    }
+
+    /// Accounts for a container of `len` terms.
+    fn charge_terms<'a>(&self, input: &'a [u8], len: usize) -> NomResult<'a, ()> {
+        self.limits
+            .charge(input, len.saturating_mul(size_of::<OwnedTerm>()))
+    }
+}
+
 const ATOM_CACHE_SIZE: usize = 256;
 
 #[derive(Debug, Clone)]
@@ -82,8 +337,11 @@ impl Default for AtomCache {
 }
 
 pub fn decode(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
-    let cache = AtomCache::new();
-    let (remaining, term) = parse_versioned_term(data, &cache).map_err(from_nom_error)?;
+    decode_with_options(data, &DecodeOptions::default())
+}
+
+pub fn decode_with_options(data: &[u8], options: &DecodeOptions) -> Result<OwnedTerm, DecodeError> {
+    let (term, remaining) = decode_versioned(data, None, options)?;
 
     if !remaining.is_empty() {
         return Err(DecodeError::TrailingData(remaining.len()));
@@ -93,14 +351,55 @@ pub fn decode(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
 }
 
 pub fn decode_with_trailing(data: &[u8]) -> Result<(OwnedTerm, &[u8]), DecodeError> {
+    decode_versioned(data, None, &DecodeOptions::default())
+}
+
+/// Decodes a term whose binaries larger than [`SHARED_BINARY_THRESHOLD`]
+/// bytes are [`OwnedTerm::SharedBinary`] slices of `data` instead of copies.
+pub fn decode_shared(data: &Bytes) -> Result<OwnedTerm, DecodeError> {
+    decode_shared_with_options(data, &DecodeOptions::default())
+}
+
+pub fn decode_shared_with_options(
+    data: &Bytes,
+    options: &DecodeOptions,
+) -> Result<OwnedTerm, DecodeError> {
+    let (term, remaining) = decode_versioned(data, Some(data), options)?;
+
+    if !remaining.is_empty() {
+        return Err(DecodeError::TrailingData(remaining.len()));
+    }
+
+    Ok(term)
+}
+
+/// Like [`decode_shared`], returning the bytes after the term as a slice of `data`.
+pub fn decode_shared_with_trailing(data: &Bytes) -> Result<(OwnedTerm, Bytes), DecodeError> {
+    let (term, remaining) = decode_versioned(data, Some(data), &DecodeOptions::default())?;
+    Ok((term, data.slice_ref(remaining)))
+}
+
+fn decode_versioned<'a>(
+    data: &'a [u8],
+    shared: Option<&Bytes>,
+    options: &DecodeOptions,
+) -> Result<(OwnedTerm, &'a [u8]), DecodeError> {
     let cache = AtomCache::new();
-    let (remaining, term) = parse_versioned_term(data, &cache).map_err(from_nom_error)?;
-    Ok((term, remaining))
+    let limits = Limits::new(options);
+    match parse_versioned_term(data, Context::new(&cache, shared, &limits)) {
+        Ok((remaining, term)) => Ok((term, remaining)),
+        Err(e) => Err(limits.take_error(e)),
+    }
 }
 
 pub fn decode_raw_term(data: &[u8]) -> Result<OwnedTerm, DecodeError> {
     let cache = AtomCache::new();
-    let (remaining, term) = parse_term(data, &cache).map_err(from_nom_error)?;
+    let options = DecodeOptions::default();
+    let limits = Limits::new(&options);
+    let (remaining, term) = match parse_term(data, Context::new(&cache, None, &limits)) {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(limits.take_error(e)),
+    };
 
     if !remaining.is_empty() {
         return Err(DecodeError::TrailingData(remaining.len()));
@@ -114,11 +413,14 @@ pub fn decode_with_cache(
     data: &[u8],
 ) -> Result<(OwnedTerm, Option<(OwnedTerm, &[u8])>), DecodeError> {
     let mut cache = AtomCache::new();
-    let (remaining, term) =
-        parse_versioned_term_with_cache(data, &mut cache).map_err(from_nom_error)?;
+    let options = DecodeOptions::default();
+    let limits = Limits::new(&options);
+    let (remaining, term) = parse_versioned_term_with_cache(data, &mut cache, None, &limits)
+        .map_err(|e| limits.take_error(e))?;
 
     if !remaining.is_empty() {
-        let (new_remaining, payload) = parse_term(remaining, &cache).map_err(from_nom_error)?;
+        let (new_remaining, payload) = parse_term(remaining, Context::new(&cache, None, &limits))
+            .map_err(|e| limits.take_error(e))?;
         Ok((term, Some((payload, new_remaining))))
     } else {
         Ok((term, None))
@@ -129,10 +431,30 @@ pub fn decode_with_atom_cache(
     data: &[u8],
     cache: &mut AtomCache,
 ) -> Result<(OwnedTerm, Option<OwnedTerm>), DecodeError> {
-    let (remaining, term) = parse_versioned_term_with_cache(data, cache).map_err(from_nom_error)?;
+    decode_control_and_payload(data, cache, None)
+}
+
+/// Like [`decode_with_atom_cache`], with binaries shared as in [`decode_shared`].
+pub fn decode_shared_with_atom_cache(
+    data: &Bytes,
+    cache: &mut AtomCache,
+) -> Result<(OwnedTerm, Option<OwnedTerm>), DecodeError> {
+    decode_control_and_payload(data, cache, Some(data))
+}
+
+fn decode_control_and_payload(
+    data: &[u8],
+    cache: &mut AtomCache,
+    shared: Option<&Bytes>,
+) -> Result<(OwnedTerm, Option<OwnedTerm>), DecodeError> {
+    let options = DecodeOptions::default();
+    let limits = Limits::new(&options);
+    let (remaining, term) = parse_versioned_term_with_cache(data, cache, shared, &limits)
+        .map_err(|e| limits.take_error(e))?;
 
     if !remaining.is_empty() {
-        let (new_remaining, payload) = parse_term(remaining, cache).map_err(from_nom_error)?;
+        let (new_remaining, payload) = parse_term(remaining, Context::new(cache, shared, &limits))
+            .map_err(|e| limits.take_error(e))?;
         if !new_remaining.is_empty() {
             return Err(DecodeError::TrailingData(new_remaining.len()));
         }
@@ -219,17 +541,19 @@ fn from_nom_error(e: nom::Err<NomError<&[u8]>>) -> DecodeError {
     }
 }
 
-fn parse_versioned_term<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_versioned_term<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, version) = be_u8(input)?;
     if version != VERSION {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
     }
-    parse_term(input, cache)
+    parse_term(input, ctx)
 }
 
 fn parse_versioned_term_with_cache<'a>(
     input: &'a [u8],
     cache: &mut AtomCache,
+    shared: Option<&Bytes>,
+    limits: &Limits<'_>,
 ) -> NomResult<'a, OwnedTerm> {
     let (input, version) = be_u8(input)?;
     if version != VERSION {
@@ -238,59 +562,68 @@ fn parse_versioned_term_with_cache<'a>(
 
     let (input, tag) = be_u8(input)?;
     if tag == DIST_HEADER {
-        parse_dist_header_with_cache(input, cache)
+        parse_dist_header_with_cache(input, cache, shared, limits)
     } else {
-        parse_term_from_tag(input, tag, cache)
+        parse_term_from_tag(input, tag, Context::new(cache, shared, limits))
     }
 }
 
-fn parse_term<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_term<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, tag) = be_u8(input)?;
-    parse_term_from_tag(input, tag, cache)
+    parse_term_from_tag(input, tag, ctx)
 }
 
-fn parse_term_from_tag<'a>(
-    input: &'a [u8],
-    tag: u8,
-    cache: &AtomCache,
-) -> NomResult<'a, OwnedTerm> {
+fn parse_term_from_tag<'a>(input: &'a [u8], tag: u8, ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, ctx) = ctx.nested(input)?;
+    if ctx.limits.options.safe && matches!(tag, EXPORT_EXT | NEW_FUN_EXT) {
+        return ctx.limits.fail(input, DecodeError::UnsafeFun);
+    }
+    let (remaining, term) = parse_tagged(input, tag, ctx)?;
+    if let OwnedTerm::Atom(atom) = &term {
+        ctx.limits.check_atom(input, atom)?;
+    }
+    Ok((remaining, term))
+}
+
+fn parse_tagged<'a>(input: &'a [u8], tag: u8, ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     match tag {
         SMALL_INTEGER_EXT => parse_small_integer(input),
         INTEGER_EXT => parse_integer(input),
         FLOAT_EXT => parse_old_float(input),
         NEW_FLOAT_EXT => parse_new_float(input),
-        ATOM_EXT => parse_atom_latin1(input),
-        ATOM_UTF8_EXT => parse_atom_utf8(input),
-        SMALL_ATOM_UTF8_EXT => parse_small_atom_utf8(input),
-        SMALL_ATOM_EXT => parse_small_atom_latin1(input),
-        SMALL_TUPLE_EXT => parse_small_tuple(input, cache),
-        LARGE_TUPLE_EXT => parse_large_tuple(input, cache),
+        ATOM_EXT => parse_atom_latin1(input, ctx),
+        ATOM_UTF8_EXT => parse_atom_utf8(input, ctx),
+        SMALL_ATOM_UTF8_EXT => parse_small_atom_utf8(input, ctx),
+        SMALL_ATOM_EXT => parse_small_atom_latin1(input, ctx),
+        SMALL_TUPLE_EXT => parse_small_tuple(input, ctx),
+        LARGE_TUPLE_EXT => parse_large_tuple(input, ctx),
         NIL_EXT => Ok((input, OwnedTerm::Nil)),
-        STRING_EXT => parse_string_ext(input),
-        LIST_EXT => parse_list(input, cache),
-        BINARY_EXT => parse_binary(input),
-        BIT_BINARY_EXT => parse_bit_binary(input),
-        SMALL_BIG_EXT => parse_small_big(input),
-        LARGE_BIG_EXT => parse_large_big(input),
-        MAP_EXT => parse_map(input, cache),
-        NEW_PID_EXT => parse_new_pid(input, cache),
-        NEWER_REFERENCE_EXT => parse_newer_reference(input, cache),
-        V4_PORT_EXT => parse_v4_port(input, cache),
-        EXPORT_EXT => parse_export_ext(input, cache),
-        NEW_FUN_EXT => parse_new_fun_ext(input, cache),
+        STRING_EXT => parse_string_ext(input, ctx),
+        LIST_EXT => parse_list(input, ctx),
+        BINARY_EXT => parse_binary(input, ctx),
+        BIT_BINARY_EXT => parse_bit_binary(input, ctx),
+        SMALL_BIG_EXT => parse_small_big(input, ctx),
+        LARGE_BIG_EXT => parse_large_big(input, ctx),
+        MAP_EXT => parse_map(input, ctx),
+        NEW_PID_EXT => parse_new_pid(input, ctx),
+        NEWER_REFERENCE_EXT => parse_newer_reference(input, ctx),
+        NEW_PORT_EXT => parse_new_port(input, ctx),
+        V4_PORT_EXT => parse_v4_port(input, ctx),
+        EXPORT_EXT => parse_export_ext(input, ctx),
+        NEW_FUN_EXT => parse_new_fun_ext(input, ctx),
         DIST_HEADER => {
             log::error!("DIST_HEADER should not appear nested in terms");
             Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)))
         }
-        COMPRESSED_EXT => parse_compressed(input, cache),
-        REFERENCE_EXT => parse_reference_ext(input, cache),
-        PORT_EXT => parse_port_ext(input, cache),
-        PID_EXT => parse_pid_ext(input, cache),
-        NEW_REFERENCE_EXT => parse_new_reference_ext(input, cache),
-        LOCAL_EXT => parse_local_ext(input, cache),
+        COMPRESSED_EXT => parse_compressed(input, ctx),
+        REFERENCE_EXT => parse_reference_ext(input, ctx),
+        PORT_EXT => parse_port_ext(input, ctx),
+        PID_EXT => parse_pid_ext(input, ctx),
+        NEW_REFERENCE_EXT => parse_new_reference_ext(input, ctx),
+        LOCAL_EXT => parse_local_ext(input, ctx),
         ATOM_CACHE_REF => {
             let (input, cache_index) = be_u8(input)?;
-            if let Some(atom) = cache.get(cache_index) {
+            if let Some(atom) = ctx.cache.get(cache_index) {
                 log::debug!(
                     "Found ATOM_CACHE_REF index {} -> '{}'",
                     cache_index,
@@ -301,7 +634,7 @@ fn parse_term_from_tag<'a>(
                 log::error!(
                     "ATOM_CACHE_REF index {} not found in cache (cache size: {})",
                     cache_index,
-                    cache.len()
+                    ctx.cache.len()
                 );
                 Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)))
             }
@@ -313,30 +646,63 @@ fn parse_term_from_tag<'a>(
     }
 }
 
-fn parse_compressed<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_compressed<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (rest, uncompressed_size) = be_u32(input)?;
-
-    if uncompressed_size as usize > MAX_BINARY_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
+    let size = uncompressed_size as usize;
+    let limits = ctx.limits;
+
+    limits.check_size(input, size, limits.options.max_binary_size, |size, max| {
+        DecodeError::BinaryTooLarge { size, max }
+    })?;
+    // The compressed data is at most the rest of the input
+    let ratio_exceeded = |compressed: usize| {
+        limits
+            .options
+            .max_compression_ratio
+            .filter(|&max| size > compressed.saturating_mul(max))
+    };
+    if let Some(max) = ratio_exceeded(rest.len()) {
+        return limits.fail(input, DecodeError::CompressionRatioExceeded { max });
     }
+    limits.charge(input, size)?;
 
+    // Reading one byte past the declared size detects a lying header
     let mut decoder = ZlibDecoder::new(rest);
-    let mut decompressed = Vec::with_capacity(uncompressed_size as usize);
-    decoder
+    let mut decompressed = Vec::with_capacity(size);
+    (&mut decoder)
+        .take(uncompressed_size as u64 + 1)
         .read_to_end(&mut decompressed)
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Fail)))?;
+    if decompressed.len() != size {
+        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Verify)));
+    }
     let consumed = decoder.total_in() as usize;
+    if let Some(max) = ratio_exceeded(consumed) {
+        return limits.fail(input, DecodeError::CompressionRatioExceeded { max });
+    }
 
-    let owned_term = match parse_term(&decompressed, cache) {
-        Ok((_remaining, term)) => term,
-        Err(_) => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Fail))),
+    // Binaries in a compressed term can only share the decompressed buffer
+    let parsed = match ctx.shared {
+        Some(_) => {
+            let decompressed = Bytes::from(decompressed);
+            let ctx = Context {
+                shared: Some(&decompressed),
+                ..ctx
+            };
+            parse_term(&decompressed, ctx).map(|(_, term)| term).ok()
+        }
+        None => parse_term(&decompressed, ctx).map(|(_, term)| term).ok(),
+    };
+    let owned_term = match parsed {
+        Some(term) => term,
+        None => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Fail))),
     };
 
     Ok((&rest[consumed..], owned_term))
 }
 
-fn parse_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
+fn parse_reference_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = if let OwnedTerm::Atom(atom) = node_term {
         atom
     } else {
@@ -350,8 +716,8 @@ fn parse_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a,
     ))
 }
 
-fn parse_port_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
+fn parse_port_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = if let OwnedTerm::Atom(atom) = node_term {
         atom
     } else {
@@ -365,8 +731,8 @@ fn parse_port_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Owned
     ))
 }
 
-fn parse_pid_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
+fn parse_pid_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = if let OwnedTerm::Atom(atom) = node_term {
         atom
     } else {
@@ -381,9 +747,9 @@ fn parse_pid_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedT
     ))
 }
 
-fn parse_new_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_new_reference_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
-    let (input, node_term) = parse_term(input, cache)?;
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = if let OwnedTerm::Atom(atom) = node_term {
         atom
     } else {
@@ -403,11 +769,11 @@ fn parse_new_reference_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<
     ))
 }
 
-fn parse_local_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_local_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     // Record the start position to capture the entire LOCAL_EXT encoding
     let start = input;
     let (input, _hash) = be_u64(input)?;
-    let (remaining, term) = parse_term(input, cache)?;
+    let (remaining, term) = parse_term(input, ctx)?;
 
     // Calculate how many bytes the nested term consumed
     let nested_len = input.len() - remaining.len();
@@ -470,18 +836,17 @@ fn parse_new_float(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     Ok((input, OwnedTerm::Float(value)))
 }
 
-fn parse_atom_latin1(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_atom_latin1<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, bytes) = take(len as usize)(input)?;
-    let name = str::from_utf8(bytes)
-        .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let (input, atom) = ctx.limits.intern(input, &latin1_to_str(bytes))?;
+    Ok((input, OwnedTerm::Atom(atom)))
 }
 
-fn parse_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_atom_utf8<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
@@ -489,10 +854,11 @@ fn parse_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     let (input, bytes) = take(len as usize)(input)?;
     let name = str::from_utf8(bytes)
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let (input, atom) = ctx.limits.intern(input, name)?;
+    Ok((input, OwnedTerm::Atom(atom)))
 }
 
-fn parse_small_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_small_atom_utf8<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u8(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
@@ -500,28 +866,37 @@ fn parse_small_atom_utf8(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     let (input, bytes) = take(len as usize)(input)?;
     let name = str::from_utf8(bytes)
         .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let (input, atom) = ctx.limits.intern(input, name)?;
+    Ok((input, OwnedTerm::Atom(atom)))
 }
 
-fn parse_small_atom_latin1(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_small_atom_latin1<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u8(input)?;
     if len as usize > MAX_ATOM_SIZE {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, bytes) = take(len as usize)(input)?;
-    let name = str::from_utf8(bytes)
-        .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, OwnedTerm::Atom(Atom::new(name))))
+    let (input, atom) = ctx.limits.intern(input, &latin1_to_str(bytes))?;
+    Ok((input, OwnedTerm::Atom(atom)))
 }
 
 fn parse_dist_header_with_cache<'a>(
     input: &'a [u8],
     cache: &mut AtomCache,
+    shared: Option<&Bytes>,
+    limits: &Limits<'_>,
 ) -> NomResult<'a, OwnedTerm> {
+    let (input, ()) = parse_dist_header_atoms(input, cache)?;
+    parse_term(input, Context::new(cache, shared, limits))
+}
+
+/// Reads the atom cache references of a distribution header, adding new
+/// entries to `cache`.
+fn parse_dist_header_atoms<'a>(input: &'a [u8], cache: &mut AtomCache) -> NomResult<'a, ()> {
     let (input, num_atom_cache_refs) = be_u8(input)?;
 
     if num_atom_cache_refs == 0 {
-        return parse_term(input, cache);
+        return Ok((input, ()));
     }
 
     let flags_len = (num_atom_cache_refs as usize) / 2 + 1;
@@ -567,19 +942,17 @@ fn parse_dist_header_with_cache<'a>(
         }
     }
 
-    parse_term(input, cache)
+    Ok((input, ()))
 }
 
-fn parse_small_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_small_tuple<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, arity) = be_u8(input)?;
-    if arity as usize > MAX_TUPLE_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
-    }
+    check_tuple_size(input, arity as usize, ctx)?;
     let mut remaining = input;
     let mut elements = Vec::with_capacity(arity as usize);
 
     for _ in 0..arity {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         elements.push(term);
         remaining = new_remaining;
     }
@@ -587,16 +960,14 @@ fn parse_small_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Ow
     Ok((remaining, OwnedTerm::Tuple(elements)))
 }
 
-fn parse_large_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_large_tuple<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, arity) = be_u32(input)?;
-    if arity as usize > MAX_TUPLE_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
-    }
+    check_tuple_size(input, arity as usize, ctx)?;
     let mut remaining = input;
     let mut elements = Vec::with_capacity(arity as usize);
 
     for _ in 0..arity {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         elements.push(term);
         remaining = new_remaining;
     }
@@ -604,8 +975,29 @@ fn parse_large_tuple<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Ow
     Ok((remaining, OwnedTerm::Tuple(elements)))
 }
 
-fn parse_string_ext(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn check_tuple_size<'a>(input: &'a [u8], arity: usize, ctx: Context<'_>) -> NomResult<'a, ()> {
+    let max = ctx.limits.options.max_tuple_size;
+    ctx.limits
+        .check_size(input, arity, max, |size, max| DecodeError::TupleTooLarge {
+            size,
+            max,
+        })?;
+    ctx.charge_terms(input, arity)
+}
+
+fn check_list_size<'a>(input: &'a [u8], len: usize, ctx: Context<'_>) -> NomResult<'a, ()> {
+    let max = ctx.limits.options.max_list_size;
+    ctx.limits
+        .check_size(input, len, max, |size, max| DecodeError::ListTooLarge {
+            size,
+            max,
+        })?;
+    ctx.charge_terms(input, len)
+}
+
+fn parse_string_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
+    check_list_size(input, len as usize, ctx)?;
     let (input, bytes) = take(len as usize)(input)?;
     let elements: Vec<OwnedTerm> = bytes
         .iter()
@@ -614,21 +1006,19 @@ fn parse_string_ext(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     Ok((input, OwnedTerm::List(elements)))
 }
 
-fn parse_list<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_list<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u32(input)?;
-    if len as usize > MAX_LIST_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
-    }
+    check_list_size(input, len as usize, ctx)?;
     let mut remaining = input;
     let mut elements = Vec::with_capacity(len as usize);
 
     for _ in 0..len {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         elements.push(term);
         remaining = new_remaining;
     }
 
-    let (remaining, tail) = parse_term(remaining, cache)?;
+    let (remaining, tail) = parse_term(remaining, ctx)?;
 
     if tail == OwnedTerm::Nil {
         Ok((remaining, OwnedTerm::List(elements)))
@@ -643,20 +1033,33 @@ fn parse_list<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm
     }
 }
 
-fn parse_binary(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn check_binary_size<'a>(input: &'a [u8], len: usize, ctx: Context<'_>) -> NomResult<'a, ()> {
+    let max = ctx.limits.options.max_binary_size;
+    ctx.limits
+        .check_size(input, len, max, |size, max| DecodeError::BinaryTooLarge {
+            size,
+            max,
+        })
+}
+
+fn parse_binary<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u32(input)?;
-    if len as usize > MAX_BINARY_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
-    }
+    check_binary_size(input, len as usize, ctx)?;
     let (input, data) = take(len as usize)(input)?;
-    Ok((input, OwnedTerm::Binary(data.to_vec())))
+    match ctx.shared {
+        Some(buffer) if data.len() > SHARED_BINARY_THRESHOLD => {
+            Ok((input, OwnedTerm::SharedBinary(buffer.slice_ref(data))))
+        }
+        _ => {
+            ctx.limits.charge(input, data.len())?;
+            Ok((input, OwnedTerm::Binary(data.to_vec())))
+        }
+    }
 }
 
-fn parse_bit_binary(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_bit_binary<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u32(input)?;
-    if len as usize > MAX_BINARY_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
-    }
+    check_binary_size(input, len as usize, ctx)?;
     let (input, bits) = be_u8(input)?;
     if bits == 0 || bits > 8 {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Verify)));
@@ -665,6 +1068,7 @@ fn parse_bit_binary(input: &[u8]) -> NomResult<'_, OwnedTerm> {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Verify)));
     }
     let (input, bytes) = take(len as usize)(input)?;
+    ctx.limits.charge(input, bytes.len())?;
     Ok((
         input,
         OwnedTerm::BitBinary {
@@ -674,37 +1078,42 @@ fn parse_bit_binary(input: &[u8]) -> NomResult<'_, OwnedTerm> {
     ))
 }
 
-fn parse_small_big(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_small_big<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, n) = be_u8(input)?;
     let (input, sign) = be_u8(input)?;
     let (input, digits) = take(n as usize)(input)?;
+    ctx.limits.charge(input, digits.len())?;
     Ok((
         input,
         OwnedTerm::BigInt(BigInt::new(sign != 0, digits.to_vec())),
     ))
 }
 
-fn parse_large_big(input: &[u8]) -> NomResult<'_, OwnedTerm> {
+fn parse_large_big<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, n) = be_u32(input)?;
     let (input, sign) = be_u8(input)?;
     let (input, digits) = take(n as usize)(input)?;
+    ctx.limits.charge(input, digits.len())?;
     Ok((
         input,
         OwnedTerm::BigInt(BigInt::new(sign != 0, digits.to_vec())),
     ))
 }
 
-fn parse_map<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_map<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, arity) = be_u32(input)?;
-    if arity as usize > MAX_MAP_SIZE {
-        return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
-    }
+    let max = ctx.limits.options.max_map_size;
+    ctx.limits
+        .check_size(input, arity as usize, max, |size, max| {
+            DecodeError::MapTooLarge { size, max }
+        })?;
+    ctx.charge_terms(input, (arity as usize).saturating_mul(2))?;
     let mut remaining = input;
     let mut map = BTreeMap::new();
 
     for _ in 0..arity {
-        let (new_remaining, key) = parse_term(remaining, cache)?;
-        let (new_remaining, value) = parse_term(new_remaining, cache)?;
+        let (new_remaining, key) = parse_term(remaining, ctx)?;
+        let (new_remaining, value) = parse_term(new_remaining, ctx)?;
         map.insert(key, value);
         remaining = new_remaining;
     }
@@ -712,8 +1121,8 @@ fn parse_map<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm>
     Ok((remaining, OwnedTerm::Map(map)))
 }
 
-fn parse_new_pid<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
+fn parse_new_pid<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = match node_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -731,9 +1140,9 @@ fn parse_new_pid<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedT
     ))
 }
 
-fn parse_newer_reference<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_newer_reference<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, len) = be_u16(input)?;
-    let (input, node_term) = parse_term(input, cache)?;
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = match node_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -755,8 +1164,24 @@ fn parse_newer_reference<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a
     ))
 }
 
-fn parse_v4_port<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, node_term) = parse_term(input, cache)?;
+fn parse_new_port<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node_term) = parse_term(input, ctx)?;
+    let node = match node_term {
+        OwnedTerm::Atom(a) => a,
+        _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
+    };
+
+    let (input, id) = be_u32(input)?;
+    let (input, creation) = be_u32(input)?;
+
+    Ok((
+        input,
+        OwnedTerm::Port(ExternalPort::new(node, id as u64, creation)),
+    ))
+}
+
+fn parse_v4_port<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, node_term) = parse_term(input, ctx)?;
     let node = match node_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -771,20 +1196,20 @@ fn parse_v4_port<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedT
     ))
 }
 
-fn parse_export_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
-    let (input, module_term) = parse_term(input, cache)?;
+fn parse_export_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
+    let (input, module_term) = parse_term(input, ctx)?;
     let module = match module_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, function_term) = parse_term(input, cache)?;
+    let (input, function_term) = parse_term(input, ctx)?;
     let function = match function_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, arity_term) = parse_term(input, cache)?;
+    let (input, arity_term) = parse_term(input, ctx)?;
     let arity = match arity_term {
         OwnedTerm::Integer(i) if (0..=255).contains(&i) => i as u8,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -796,41 +1221,42 @@ fn parse_export_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Own
     ))
 }
 
-fn parse_new_fun_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, OwnedTerm> {
+fn parse_new_fun_ext<'a>(input: &'a [u8], ctx: Context<'_>) -> NomResult<'a, OwnedTerm> {
     let (input, _size) = be_u32(input)?;
     let (input, arity) = be_u8(input)?;
     let (input, uniq) = take(16usize)(input)?;
     let (input, index) = be_u32(input)?;
     let (input, num_free) = be_u32(input)?;
 
-    let (input, module_term) = parse_term(input, cache)?;
+    let (input, module_term) = parse_term(input, ctx)?;
     let module = match module_term {
         OwnedTerm::Atom(a) => a,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, old_index_term) = parse_term(input, cache)?;
+    let (input, old_index_term) = parse_term(input, ctx)?;
     let old_index = match old_index_term {
         OwnedTerm::Integer(i) if i >= 0 => i as u32,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, old_uniq_term) = parse_term(input, cache)?;
+    let (input, old_uniq_term) = parse_term(input, ctx)?;
     let old_uniq = match old_uniq_term {
         OwnedTerm::Integer(i) if i >= 0 => i as u32,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, pid_term) = parse_term(input, cache)?;
+    let (input, pid_term) = parse_term(input, ctx)?;
     let pid = match pid_term {
         OwnedTerm::Pid(p) => p,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
+    check_list_size(input, num_free as usize, ctx)?;
     let mut remaining = input;
     let mut free_vars = Vec::with_capacity(num_free as usize);
     for _ in 0..num_free {
-        let (new_remaining, term) = parse_term(remaining, cache)?;
+        let (new_remaining, term) = parse_term(remaining, ctx)?;
         free_vars.push(term);
         remaining = new_remaining;
     }
@@ -847,42 +1273,118 @@ fn parse_new_fun_ext<'a>(input: &'a [u8], cache: &AtomCache) -> NomResult<'a, Ow
 }
 
 pub fn decode_borrowed(data: &[u8]) -> Result<BorrowedTerm<'_>, ContextualDecodeError> {
-    let original_len = data.len();
+    let source = BorrowedSource::new(data, None);
     let mut ctx = ParsingContext::new();
 
-    let (remaining, term) = parse_versioned_term_borrowed(data, original_len, &mut ctx)
+    let (remaining, term) = parse_versioned_term_borrowed(data, source, &mut ctx)
         .map_err(|e| ContextualDecodeError::new(from_nom_error(e), ctx.clone()))?;
+    ensure_consumed_borrowed(remaining, source, ctx)?;
 
-    if !remaining.is_empty() {
-        ctx.byte_offset = original_len - remaining.len();
-        return Err(ContextualDecodeError::new(
-            DecodeError::TrailingData(remaining.len()),
-            ctx,
-        ));
+    Ok(term)
+}
+
+/// Like [`decode_with_atom_cache`], decoding the control message and payload
+/// of a distribution message as [`BorrowedTerm`]s.
+///
+/// Atoms read through `ATOM_CACHE_REF` borrow their names from `cache`, so the
+/// cache stays borrowed for as long as the returned terms are alive.
+#[allow(clippy::type_complexity)]
+pub fn decode_borrowed_with_atom_cache<'a>(
+    data: &'a [u8],
+    cache: &'a mut AtomCache,
+) -> Result<(BorrowedTerm<'a>, Option<BorrowedTerm<'a>>), ContextualDecodeError> {
+    let mut ctx = ParsingContext::new();
+
+    let mut input = data;
+    if let [VERSION, DIST_HEADER, rest @ ..] = data {
+        (input, ()) = parse_dist_header_atoms(rest, cache)
+            .map_err(|e| ContextualDecodeError::new(from_nom_error(e), ctx.clone()))?;
+    }
+    let cache: &'a AtomCache = cache;
+    let source = BorrowedSource::new(data, Some(cache));
+
+    let parsed = if input.len() == data.len() {
+        parse_versioned_term_borrowed(input, source, &mut ctx)
+    } else {
+        parse_term_borrowed(input, source, &mut ctx)
+    };
+    let (remaining, control) =
+        parsed.map_err(|e| ContextualDecodeError::new(from_nom_error(e), ctx.clone()))?;
+    if remaining.is_empty() {
+        return Ok((control, None));
     }
 
+    let (remaining, payload) = parse_term_borrowed(remaining, source, &mut ctx)
+        .map_err(|e| ContextualDecodeError::new(from_nom_error(e), ctx.clone()))?;
+    ensure_consumed_borrowed(remaining, source, ctx)?;
+
+    Ok((control, Some(payload)))
+}
+
+pub(crate) fn decode_raw_term_borrowed(
+    data: &[u8],
+) -> Result<BorrowedTerm<'_>, ContextualDecodeError> {
+    let source = BorrowedSource::new(data, None);
+    let mut ctx = ParsingContext::new();
+
+    let (remaining, term) = parse_term_borrowed(data, source, &mut ctx)
+        .map_err(|e| ContextualDecodeError::new(from_nom_error(e), ctx.clone()))?;
+    ensure_consumed_borrowed(remaining, source, ctx)?;
+
     Ok(term)
 }
 
+/// The input a borrowed decode started from, for error offsets, and the
+/// atom cache that `ATOM_CACHE_REF`s resolve against.
+#[derive(Clone, Copy)]
+struct BorrowedSource<'a> {
+    original_len: usize,
+    cache: Option<&'a AtomCache>,
+}
+
+impl<'a> BorrowedSource<'a> {
+    fn new(data: &[u8], cache: Option<&'a AtomCache>) -> Self {
+        Self {
+            original_len: data.len(),
+            cache,
+        }
+    }
+}
+
+fn ensure_consumed_borrowed(
+    remaining: &[u8],
+    source: BorrowedSource<'_>,
+    mut ctx: ParsingContext,
+) -> Result<(), ContextualDecodeError> {
+    if remaining.is_empty() {
+        return Ok(());
+    }
+    ctx.byte_offset = source.original_len - remaining.len();
+    Err(ContextualDecodeError::new(
+        DecodeError::TrailingData(remaining.len()),
+        ctx,
+    ))
+}
+
 fn parse_versioned_term_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, version) = be_u8(input)?;
-    ctx.byte_offset = original_len - input.len() - 1;
+    ctx.byte_offset = source.original_len - input.len() - 1;
     if version != VERSION {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag)));
     }
-    parse_term_borrowed(input, original_len, ctx)
+    parse_term_borrowed(input, source, ctx)
 }
 
 fn parse_term_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
-    ctx.byte_offset = original_len - input.len();
+    ctx.byte_offset = source.original_len - input.len();
     let (input, tag) = be_u8(input)?;
 
     match tag {
@@ -893,25 +1395,39 @@ fn parse_term_borrowed<'a>(
         ATOM_EXT => parse_atom_latin1_borrowed(input),
         ATOM_UTF8_EXT => parse_atom_utf8_borrowed(input),
         SMALL_ATOM_UTF8_EXT => parse_small_atom_utf8_borrowed(input),
-        SMALL_TUPLE_EXT => parse_small_tuple_borrowed(input, original_len, ctx),
-        LARGE_TUPLE_EXT => parse_large_tuple_borrowed(input, original_len, ctx),
+        SMALL_ATOM_EXT => parse_small_atom_latin1_borrowed(input),
+        SMALL_TUPLE_EXT => parse_small_tuple_borrowed(input, source, ctx),
+        LARGE_TUPLE_EXT => parse_large_tuple_borrowed(input, source, ctx),
         NIL_EXT => Ok((input, BorrowedTerm::Nil)),
         STRING_EXT => parse_string_ext_borrowed(input),
-        LIST_EXT => parse_list_borrowed(input, original_len, ctx),
+        LIST_EXT => parse_list_borrowed(input, source, ctx),
         BINARY_EXT => parse_binary_borrowed(input),
         BIT_BINARY_EXT => parse_bit_binary_borrowed(input),
         SMALL_BIG_EXT => parse_small_big_borrowed(input),
         LARGE_BIG_EXT => parse_large_big_borrowed(input),
-        MAP_EXT => parse_map_borrowed(input, original_len, ctx),
-        NEW_PID_EXT => parse_new_pid_borrowed(input, original_len, ctx),
-        NEWER_REFERENCE_EXT => parse_newer_reference_borrowed(input, original_len, ctx),
-        V4_PORT_EXT => parse_v4_port_borrowed(input, original_len, ctx),
-        EXPORT_EXT => parse_export_ext_borrowed(input, original_len, ctx),
-        NEW_FUN_EXT => parse_new_fun_ext_borrowed(input, original_len, ctx),
+        MAP_EXT => parse_map_borrowed(input, source, ctx),
+        NEW_PID_EXT => parse_new_pid_borrowed(input, source, ctx),
+        NEWER_REFERENCE_EXT => parse_newer_reference_borrowed(input, source, ctx),
+        NEW_PORT_EXT => parse_new_port_borrowed(input, source, ctx),
+        V4_PORT_EXT => parse_v4_port_borrowed(input, source, ctx),
+        EXPORT_EXT => parse_export_ext_borrowed(input, source, ctx),
+        NEW_FUN_EXT => parse_new_fun_ext_borrowed(input, source, ctx),
+        ATOM_CACHE_REF => parse_atom_cache_ref_borrowed(input, source),
         _ => Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     }
 }
 
+fn parse_atom_cache_ref_borrowed<'a>(
+    input: &'a [u8],
+    source: BorrowedSource<'a>,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, cache_index) = be_u8(input)?;
+    match source.cache.and_then(|cache| cache.get(cache_index)) {
+        Some(atom) => Ok((input, BorrowedTerm::Atom(Cow::Borrowed(atom.as_str())))),
+        None => Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
+    }
+}
+
 fn parse_small_integer_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
     let (input, value) = be_u8(input)?;
     Ok((input, BorrowedTerm::Integer(value as i64)))
@@ -944,9 +1460,21 @@ fn parse_atom_latin1_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
         return Err(nom::Err::Failure(NomError::new(input, ErrorKind::TooLarge)));
     }
     let (input, bytes) = take(len as usize)(input)?;
-    let name = str::from_utf8(bytes)
-        .map_err(|_| nom::Err::Failure(NomError::new(input, ErrorKind::Char)))?;
-    Ok((input, BorrowedTerm::Atom(Cow::Borrowed(name))))
+    Ok((input, BorrowedTerm::Atom(latin1_to_str(bytes))))
+}
+
+fn parse_small_atom_latin1_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
+    let (input, len) = be_u8(input)?;
+    let (input, bytes) = take(len as usize)(input)?;
+    Ok((input, BorrowedTerm::Atom(latin1_to_str(bytes))))
+}
+
+/// Latin-1 maps each byte to the code point of the same value.
+fn latin1_to_str(bytes: &[u8]) -> Cow<'_, str> {
+    match str::from_utf8(bytes) {
+        Ok(name) if bytes.is_ascii() => Cow::Borrowed(name),
+        _ => Cow::Owned(bytes.iter().map(|&b| b as char).collect()),
+    }
 }
 
 fn parse_atom_utf8_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
@@ -973,7 +1501,7 @@ fn parse_small_atom_utf8_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_
 
 fn parse_small_tuple_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, arity) = be_u8(input)?;
@@ -985,7 +1513,7 @@ fn parse_small_tuple_borrowed<'a>(
 
     for i in 0..arity {
         ctx.push(PathSegment::TupleElement(i as usize));
-        let (new_remaining, term) = parse_term_borrowed(remaining, original_len, ctx)?;
+        let (new_remaining, term) = parse_term_borrowed(remaining, source, ctx)?;
         ctx.pop();
         elements.push(term);
         remaining = new_remaining;
@@ -996,7 +1524,7 @@ fn parse_small_tuple_borrowed<'a>(
 
 fn parse_large_tuple_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, arity) = be_u32(input)?;
@@ -1008,7 +1536,7 @@ fn parse_large_tuple_borrowed<'a>(
 
     for i in 0..arity {
         ctx.push(PathSegment::TupleElement(i as usize));
-        let (new_remaining, term) = parse_term_borrowed(remaining, original_len, ctx)?;
+        let (new_remaining, term) = parse_term_borrowed(remaining, source, ctx)?;
         ctx.pop();
         elements.push(term);
         remaining = new_remaining;
@@ -1029,7 +1557,7 @@ fn parse_string_ext_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
 
 fn parse_list_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, len) = be_u32(input)?;
@@ -1041,14 +1569,14 @@ fn parse_list_borrowed<'a>(
 
     for i in 0..len {
         ctx.push(PathSegment::ListElement(i as usize));
-        let (new_remaining, term) = parse_term_borrowed(remaining, original_len, ctx)?;
+        let (new_remaining, term) = parse_term_borrowed(remaining, source, ctx)?;
         ctx.pop();
         elements.push(term);
         remaining = new_remaining;
     }
 
     ctx.push(PathSegment::ImproperListTail);
-    let (remaining, tail) = parse_term_borrowed(remaining, original_len, ctx)?;
+    let (remaining, tail) = parse_term_borrowed(remaining, source, ctx)?;
     ctx.pop();
 
     if tail == BorrowedTerm::Nil {
@@ -1117,7 +1645,7 @@ fn parse_large_big_borrowed(input: &[u8]) -> NomResult<'_, BorrowedTerm<'_>> {
 
 fn parse_map_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, arity) = be_u32(input)?;
@@ -1129,7 +1657,7 @@ fn parse_map_borrowed<'a>(
 
     for _ in 0..arity {
         ctx.push(PathSegment::MapKey);
-        let (new_remaining, key) = parse_term_borrowed(remaining, original_len, ctx)?;
+        let (new_remaining, key) = parse_term_borrowed(remaining, source, ctx)?;
         ctx.pop();
 
         let key_display = match &key {
@@ -1138,7 +1666,7 @@ fn parse_map_borrowed<'a>(
             _ => "?".to_string(),
         };
         ctx.push(PathSegment::MapValue(key_display));
-        let (new_remaining, value) = parse_term_borrowed(new_remaining, original_len, ctx)?;
+        let (new_remaining, value) = parse_term_borrowed(new_remaining, source, ctx)?;
         ctx.pop();
 
         map.insert(key, value);
@@ -1150,10 +1678,10 @@ fn parse_map_borrowed<'a>(
 
 fn parse_new_pid_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
-    let (input, node_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, node_term) = parse_term_borrowed(input, source, ctx)?;
     let node = match node_term {
         BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -1171,11 +1699,11 @@ fn parse_new_pid_borrowed<'a>(
 
 fn parse_newer_reference_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, len) = be_u16(input)?;
-    let (input, node_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, node_term) = parse_term_borrowed(input, source, ctx)?;
     let node = match node_term {
         BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -1197,12 +1725,32 @@ fn parse_newer_reference_borrowed<'a>(
     ))
 }
 
+fn parse_new_port_borrowed<'a>(
+    input: &'a [u8],
+    source: BorrowedSource<'a>,
+    ctx: &mut ParsingContext,
+) -> NomResult<'a, BorrowedTerm<'a>> {
+    let (input, node_term) = parse_term_borrowed(input, source, ctx)?;
+    let node = match node_term {
+        BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
+        _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
+    };
+
+    let (input, id) = be_u32(input)?;
+    let (input, creation) = be_u32(input)?;
+
+    Ok((
+        input,
+        BorrowedTerm::Port(ExternalPort::new(node, id as u64, creation)),
+    ))
+}
+
 fn parse_v4_port_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
-    let (input, node_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, node_term) = parse_term_borrowed(input, source, ctx)?;
     let node = match node_term {
         BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -1219,22 +1767,22 @@ fn parse_v4_port_borrowed<'a>(
 
 fn parse_export_ext_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
-    let (input, module_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, module_term) = parse_term_borrowed(input, source, ctx)?;
     let module = match module_term {
         BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, function_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, function_term) = parse_term_borrowed(input, source, ctx)?;
     let function = match function_term {
         BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, arity_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, arity_term) = parse_term_borrowed(input, source, ctx)?;
     let arity = match arity_term {
         BorrowedTerm::Integer(i) if (0..=255).contains(&i) => i as u8,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -1248,7 +1796,7 @@ fn parse_export_ext_borrowed<'a>(
 
 fn parse_new_fun_ext_borrowed<'a>(
     input: &'a [u8],
-    original_len: usize,
+    source: BorrowedSource<'a>,
     ctx: &mut ParsingContext,
 ) -> NomResult<'a, BorrowedTerm<'a>> {
     let (input, _size) = be_u32(input)?;
@@ -1257,25 +1805,25 @@ fn parse_new_fun_ext_borrowed<'a>(
     let (input, index) = be_u32(input)?;
     let (input, num_free) = be_u32(input)?;
 
-    let (input, module_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, module_term) = parse_term_borrowed(input, source, ctx)?;
     let module = match module_term {
         BorrowedTerm::Atom(a) => Atom::new(a.as_ref()),
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, old_index_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, old_index_term) = parse_term_borrowed(input, source, ctx)?;
     let old_index = match old_index_term {
         BorrowedTerm::Integer(i) if i >= 0 => i as u32,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, old_uniq_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, old_uniq_term) = parse_term_borrowed(input, source, ctx)?;
     let old_uniq = match old_uniq_term {
         BorrowedTerm::Integer(i) if i >= 0 => i as u32,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
     };
 
-    let (input, pid_term) = parse_term_borrowed(input, original_len, ctx)?;
+    let (input, pid_term) = parse_term_borrowed(input, source, ctx)?;
     let pid = match pid_term {
         BorrowedTerm::Pid(p) => p,
         _ => return Err(nom::Err::Failure(NomError::new(input, ErrorKind::Tag))),
@@ -1285,7 +1833,7 @@ fn parse_new_fun_ext_borrowed<'a>(
     let mut free_vars = Vec::with_capacity(num_free as usize);
     for i in 0..num_free {
         ctx.push(PathSegment::FunFreeVar(i as usize));
-        let (new_remaining, term) = parse_term_borrowed(remaining, original_len, ctx)?;
+        let (new_remaining, term) = parse_term_borrowed(remaining, source, ctx)?;
         ctx.pop();
         free_vars.push(term.to_owned());
         remaining = new_remaining;
@@ -12,64 +12,346 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::borrowed::BorrowedTerm;
+use crate::decoder::SHARED_BINARY_THRESHOLD;
 use crate::errors::EncodeError;
 use crate::tags::{
-    ATOM_CACHE_REF, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, DIST_HEADER, EXPORT_EXT,
-    INTEGER_EXT, LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT,
-    NEW_FUN_EXT, NEW_PID_EXT, NEWER_REFERENCE_EXT, NIL_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT,
-    SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, V4_PORT_EXT, VERSION,
+    ATOM_CACHE_REF, ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, COMPRESSED_EXT,
+    DIST_HEADER, EXPORT_EXT, FLOAT_EXT, INTEGER_EXT, LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT,
+    LOCAL_EXT, MAP_EXT, NEW_FLOAT_EXT, NEW_FUN_EXT, NEW_PID_EXT, NEW_PORT_EXT, NEWER_REFERENCE_EXT,
+    NIL_EXT, SMALL_ATOM_EXT, SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT,
+    SMALL_TUPLE_EXT, STRING_EXT, V4_PORT_EXT, VERSION,
 };
 use crate::term::OwnedTerm;
 use crate::types::{
     Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
 };
-use bytes::{BufMut, BytesMut};
+use crate::visit::TermVisitor;
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Write;
 
+/// The level `term_to_binary(T, [compressed])` uses.
+pub const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+const MAX_COMPRESSION_LEVEL: u32 = 9;
+/// The `minor_version` `term_to_binary/1` uses since OTP 26.
+pub const DEFAULT_MINOR_VERSION: u8 = 2;
+const MAX_MINOR_VERSION: u8 = 2;
+
+/// Options that control how terms are encoded.
+///
+/// The defaults match `term_to_binary/1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// zlib compression level, 0 through 9, like `{compressed, Level}`.
+    /// `None` and `Some(0)` both disable compression.
+    pub compression_level: Option<u32>,
+    /// Terms whose uncompressed encoding (without the version byte) is smaller
+    /// than this many bytes are never compressed.
+    pub compression_threshold: usize,
+    /// Produce the same bytes as `term_to_binary(T, [deterministic])` on OTP 26 and later:
+    /// map entries in map key order, bignums normalized, byte lists as `STRING_EXT`,
+    /// and no `LOCAL_EXT` pass-through.
+    pub deterministic: bool,
+    /// Like `{minor_version, Version}`: 0 writes floats as `FLOAT_EXT` strings,
+    /// 0 and 1 write atoms as Latin-1 when every character fits.
+    pub minor_version: u8,
+    /// Use `SMALL_ATOM_UTF8_EXT` and `SMALL_ATOM_EXT` for atoms shorter than 256 bytes.
+    pub small_atoms: bool,
+    /// Re-emit the `LOCAL_EXT` bytes preserved on decoded pids, ports and references.
+    /// When disabled, they are encoded from their fields as `NEW_PID_EXT` and friends.
+    pub preserve_local_ext: bool,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            compression_level: None,
+            compression_threshold: 0,
+            deterministic: false,
+            minor_version: DEFAULT_MINOR_VERSION,
+            small_atoms: true,
+            preserve_local_ext: true,
+        }
+    }
+}
+
+impl EncodeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Equivalent of `term_to_binary(T, [compressed])`.
+    pub fn compressed() -> Self {
+        Self::new().with_compression(DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    pub fn with_compression(mut self, level: u32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    pub fn with_compression_threshold(mut self, threshold: usize) -> Self {
+        self.compression_threshold = threshold;
+        self
+    }
+
+    /// Equivalent of `term_to_binary(T, [deterministic])`.
+    pub fn deterministic() -> Self {
+        Self::new().with_deterministic(true)
+    }
+
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    pub fn with_minor_version(mut self, minor_version: u8) -> Self {
+        self.minor_version = minor_version;
+        self
+    }
+
+    pub fn with_small_atoms(mut self, small_atoms: bool) -> Self {
+        self.small_atoms = small_atoms;
+        self
+    }
+
+    pub fn with_preserve_local_ext(mut self, preserve_local_ext: bool) -> Self {
+        self.preserve_local_ext = preserve_local_ext;
+        self
+    }
+}
+
 pub fn encode(term: &OwnedTerm) -> Result<Vec<u8>, EncodeError> {
+    encode_with_options(term, &EncodeOptions::default())
+}
+
+pub fn encode_with_options(
+    term: &OwnedTerm,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, EncodeError> {
+    if options.minor_version > MAX_MINOR_VERSION {
+        return Err(EncodeError::InvalidMinorVersion {
+            version: options.minor_version,
+        });
+    }
+
     let estimated_size = term.estimated_encoded_size() + 1;
     let capacity = estimated_size.max(64);
     let mut buf = BytesMut::with_capacity(capacity);
     buf.put_u8(VERSION);
-    encode_term(&mut buf, term)?;
+    encode_term_impl(&mut buf, term, Context::new(None, options))?;
+    finish_encoding(buf, options)
+}
+
+/// Encodes a term as a list of chunks that, concatenated, equal the output of
+/// [`encode`], like `erlang:term_to_iovec/1`.
+///
+/// [`OwnedTerm::SharedBinary`] payloads larger than [`SHARED_BINARY_THRESHOLD`]
+/// bytes become chunks of their own that share the term's buffer instead of being
+/// copied. Other binaries are copied into the surrounding chunks, so wrap large
+/// payloads in [`OwnedTerm::SharedBinary`] to avoid the copy. Pass the chunks to
+/// `write_vectored` to send the term without assembling it in one contiguous buffer.
+pub fn encode_vectored(term: &OwnedTerm) -> Result<Vec<Bytes>, EncodeError> {
+    encode_vectored_with_options(term, &EncodeOptions::default())
+}
+
+/// [`encode_vectored`] with options. A compressed term is returned as a single chunk.
+pub fn encode_vectored_with_options(
+    term: &OwnedTerm,
+    options: &EncodeOptions,
+) -> Result<Vec<Bytes>, EncodeError> {
+    if options.compression_level.is_some_and(|level| level > 0) {
+        return Ok(vec![Bytes::from(encode_with_options(term, options)?)]);
+    }
+    if options.minor_version > MAX_MINOR_VERSION {
+        return Err(EncodeError::InvalidMinorVersion {
+            version: options.minor_version,
+        });
+    }
+
+    let chunks = RefCell::new(Vec::new());
+    let mut buf = BytesMut::with_capacity(64);
+    buf.put_u8(VERSION);
+    encode_term_impl(
+        &mut buf,
+        term,
+        Context::new(None, options).with_chunks(&chunks),
+    )?;
+
+    let mut chunks = chunks.into_inner();
+    if !buf.is_empty() {
+        chunks.push(buf.freeze());
+    }
+    Ok(chunks)
+}
+
+/// Applies the compression settings of `options` to a fully encoded term.
+fn finish_encoding(buf: BytesMut, options: &EncodeOptions) -> Result<Vec<u8>, EncodeError> {
+    if let Some(level) = options.compression_level {
+        if level > MAX_COMPRESSION_LEVEL {
+            return Err(EncodeError::InvalidCompressionLevel { level });
+        }
+        if level > 0
+            && buf.len() > options.compression_threshold
+            && let Some(compressed) = compress(&buf[1..], level)?
+        {
+            return Ok(compressed);
+        }
+    }
+
     Ok(buf.to_vec())
 }
 
+/// Encodes a [`BorrowedTerm`] without converting it to an [`OwnedTerm`] first.
+///
+/// The output is identical to encoding `term.to_owned()`.
+pub fn encode_borrowed(term: &BorrowedTerm<'_>) -> Result<Vec<u8>, EncodeError> {
+    encode_borrowed_with_options(term, &EncodeOptions::default())
+}
+
+pub fn encode_borrowed_with_options(
+    term: &BorrowedTerm<'_>,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, EncodeError> {
+    if options.minor_version > MAX_MINOR_VERSION {
+        return Err(EncodeError::InvalidMinorVersion {
+            version: options.minor_version,
+        });
+    }
+
+    let mut buf = BytesMut::with_capacity(64);
+    buf.put_u8(VERSION);
+    encode_borrowed_impl(&mut buf, term, Context::new(None, options))?;
+    finish_encoding(buf, options)
+}
+
+pub fn encode_borrowed_to_writer<W: Write>(
+    term: &BorrowedTerm<'_>,
+    writer: &mut W,
+) -> Result<(), EncodeError> {
+    let encoded = encode_borrowed(term)?;
+    writer.write_all(&encoded)?;
+    Ok(())
+}
+
 pub fn encode_to_writer<W: Write>(term: &OwnedTerm, writer: &mut W) -> Result<(), EncodeError> {
-    let encoded = encode(term)?;
+    encode_to_writer_with_options(term, writer, &EncodeOptions::default())
+}
+
+pub fn encode_to_writer_with_options<W: Write>(
+    term: &OwnedTerm,
+    writer: &mut W,
+    options: &EncodeOptions,
+) -> Result<(), EncodeError> {
+    let encoded = encode_with_options(term, options)?;
     writer.write_all(&encoded)?;
     Ok(())
 }
 
+/// Wraps an encoded term (without the version byte) in COMPRESSED_EXT.
+///
+/// Like `term_to_binary/2`, returns `None` when compression would not
+/// make the result smaller.
+fn compress(uncompressed: &[u8], level: u32) -> Result<Option<Vec<u8>>, EncodeError> {
+    let Ok(uncompressed_size) = u32::try_from(uncompressed.len()) else {
+        return Ok(None);
+    };
+
+    let mut out = Vec::with_capacity(uncompressed.len() / 2 + 6);
+    out.put_u8(VERSION);
+    out.put_u8(COMPRESSED_EXT);
+    out.put_u32(uncompressed_size);
+
+    let mut encoder = ZlibEncoder::new(out, Compression::new(level));
+    encoder.write_all(uncompressed)?;
+    let out = encoder.finish()?;
+
+    if out.len() > uncompressed.len() {
+        Ok(None)
+    } else {
+        Ok(Some(out))
+    }
+}
+
 fn encode_term(buf: &mut BytesMut, term: &OwnedTerm) -> Result<(), EncodeError> {
-    encode_term_impl(buf, term, None)
+    encode_term_impl(buf, term, Context::new(None, &EncodeOptions::default()))
+}
+
+#[derive(Clone, Copy)]
+struct Context<'c, 'a> {
+    cache: Option<&'c HashMap<&'a Atom, u8>>,
+    /// Completed chunks when encoding with [`encode_vectored`]
+    chunks: Option<&'c RefCell<Vec<Bytes>>>,
+    deterministic: bool,
+    minor_version: u8,
+    small_atoms: bool,
+    local_ext: bool,
+}
+
+impl<'c, 'a> Context<'c, 'a> {
+    fn new(cache: Option<&'c HashMap<&'a Atom, u8>>, options: &EncodeOptions) -> Self {
+        Self {
+            cache,
+            chunks: None,
+            deterministic: options.deterministic,
+            minor_version: options.minor_version,
+            small_atoms: options.small_atoms,
+            local_ext: options.preserve_local_ext && !options.deterministic,
+        }
+    }
+
+    fn with_chunks(mut self, chunks: &'c RefCell<Vec<Bytes>>) -> Self {
+        self.chunks = Some(chunks);
+        self
+    }
 }
 
 fn encode_term_impl<'a>(
     buf: &mut BytesMut,
     term: &'a OwnedTerm,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    ctx: Context<'_, 'a>,
 ) -> Result<(), EncodeError> {
     match term {
-        OwnedTerm::Atom(atom) => encode_atom_impl(buf, atom, cache),
+        OwnedTerm::Atom(atom) => encode_atom_impl(buf, atom, ctx),
         OwnedTerm::Integer(i) => encode_integer(buf, *i),
+        OwnedTerm::Float(f) if ctx.minor_version == 0 => encode_old_float(buf, *f),
         OwnedTerm::Float(f) => encode_float(buf, *f),
         OwnedTerm::Binary(b) => encode_binary(buf, b),
+        OwnedTerm::SharedBinary(b) => match ctx.chunks {
+            Some(chunks) if b.len() > SHARED_BINARY_THRESHOLD => {
+                encode_binary_chunk(buf, b, chunks)
+            }
+            _ => encode_binary(buf, b),
+        },
+        OwnedTerm::BitBinary { bytes, bits } if ctx.deterministic => {
+            encode_bit_binary_normalized(buf, bytes, *bits)
+        }
         OwnedTerm::BitBinary { bytes, bits } => encode_bit_binary(buf, bytes, *bits),
         OwnedTerm::String(s) => encode_string(buf, s),
-        OwnedTerm::List(l) => encode_list_impl(buf, l, cache),
+        OwnedTerm::List(l) if ctx.deterministic && !l.is_empty() && is_byte_list(l) => {
+            encode_string_ext(
+                buf,
+                l.iter().map(|e| e.as_integer().unwrap_or_default() as u8),
+            )
+        }
+        OwnedTerm::List(l) => encode_list_impl(buf, l, ctx),
         OwnedTerm::ImproperList { elements, tail } => {
-            encode_improper_list_impl(buf, elements, tail, cache)
+            encode_improper_list_impl(buf, elements, tail, ctx)
         }
-        OwnedTerm::Map(m) => encode_map_impl(buf, m, cache),
-        OwnedTerm::Tuple(t) => encode_tuple_impl(buf, t, cache),
-        OwnedTerm::Pid(pid) => encode_pid_impl(buf, pid, cache),
-        OwnedTerm::Port(port) => encode_port_impl(buf, port, cache),
-        OwnedTerm::Reference(ref_) => encode_reference_impl(buf, ref_, cache),
+        OwnedTerm::Map(m) => encode_map_impl(buf, m, ctx),
+        OwnedTerm::Tuple(t) => encode_tuple_impl(buf, t, ctx),
+        OwnedTerm::Pid(pid) => encode_pid_impl(buf, pid, ctx),
+        OwnedTerm::Port(port) => encode_port_impl(buf, port, ctx),
+        OwnedTerm::Reference(ref_) => encode_reference_impl(buf, ref_, ctx),
+        OwnedTerm::BigInt(big) if ctx.deterministic => encode_bigint_normalized(buf, big),
         OwnedTerm::BigInt(big) => encode_bigint(buf, big),
-        OwnedTerm::ExternalFun(fun) => encode_export_ext_impl(buf, fun, cache),
-        OwnedTerm::InternalFun(fun) => encode_new_fun_ext_impl(buf, fun, cache),
+        OwnedTerm::ExternalFun(fun) => encode_export_ext_impl(buf, fun, ctx),
+        OwnedTerm::InternalFun(fun) => encode_new_fun_ext_impl(buf, fun, ctx),
         OwnedTerm::Nil => encode_nil(buf),
     }
 }
@@ -77,24 +359,45 @@ fn encode_term_impl<'a>(
 fn encode_atom_impl<'a>(
     buf: &mut BytesMut,
     atom: &'a Atom,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    ctx: Context<'_, 'a>,
 ) -> Result<(), EncodeError> {
-    if let Some(atom_index_map) = cache
+    if let Some(atom_index_map) = ctx.cache
         && let Some(&cache_index) = atom_index_map.get(&atom)
     {
         buf.put_u8(ATOM_CACHE_REF);
         buf.put_u8(cache_index);
         return Ok(());
     }
+    encode_atom_name(buf, &atom.name, ctx)
+}
 
-    let bytes = atom.name.as_bytes();
+fn encode_atom_name(
+    buf: &mut BytesMut,
+    name: &str,
+    ctx: Context<'_, '_>,
+) -> Result<(), EncodeError> {
+    let bytes = name.as_bytes();
     let len = bytes.len();
 
     if len > u16::MAX as usize {
         return Err(EncodeError::AtomTooLarge { size: len });
     }
 
-    if len > 255 {
+    if ctx.minor_version < 2
+        && let Some(latin1) = to_latin1(name)
+    {
+        if latin1.len() > 255 || !ctx.small_atoms {
+            buf.put_u8(ATOM_EXT);
+            buf.put_u16(latin1.len() as u16);
+        } else {
+            buf.put_u8(SMALL_ATOM_EXT);
+            buf.put_u8(latin1.len() as u8);
+        }
+        buf.put_slice(&latin1);
+        return Ok(());
+    }
+
+    if len > 255 || !ctx.small_atoms {
         buf.put_u8(ATOM_UTF8_EXT);
         buf.put_u16(len as u16);
     } else {
@@ -105,6 +408,10 @@ fn encode_atom_impl<'a>(
     Ok(())
 }
 
+fn to_latin1(name: &str) -> Option<Vec<u8>> {
+    name.chars().map(|c| u8::try_from(c).ok()).collect()
+}
+
 fn encode_integer(buf: &mut BytesMut, value: i64) -> Result<(), EncodeError> {
     if (0..=255).contains(&value) {
         buf.put_u8(SMALL_INTEGER_EXT);
@@ -146,6 +453,25 @@ fn encode_float(buf: &mut BytesMut, value: f64) -> Result<(), EncodeError> {
     Ok(())
 }
 
+/// FLOAT_EXT: the value formatted with `%.20e` in a zero-padded 31 byte field.
+fn encode_old_float(buf: &mut BytesMut, value: f64) -> Result<(), EncodeError> {
+    let formatted = format!("{:.20e}", value);
+    let text = match formatted.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let exponent: i32 = exponent.parse().unwrap_or(0);
+            let sign = if exponent < 0 { '-' } else { '+' };
+            format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+        }
+        None => formatted,
+    };
+    let mut field = [0u8; 31];
+    let len = text.len().min(field.len());
+    field[..len].copy_from_slice(&text.as_bytes()[..len]);
+    buf.put_u8(FLOAT_EXT);
+    buf.put_slice(&field);
+    Ok(())
+}
+
 fn encode_binary(buf: &mut BytesMut, data: &[u8]) -> Result<(), EncodeError> {
     let len =
         u32::try_from(data.len()).map_err(|_| EncodeError::BinaryTooLarge { size: data.len() })?;
@@ -155,7 +481,26 @@ fn encode_binary(buf: &mut BytesMut, data: &[u8]) -> Result<(), EncodeError> {
     Ok(())
 }
 
+/// Writes the `BINARY_EXT` header, then ends the current chunk and adds
+/// the payload as a chunk of its own.
+fn encode_binary_chunk(
+    buf: &mut BytesMut,
+    data: &Bytes,
+    chunks: &RefCell<Vec<Bytes>>,
+) -> Result<(), EncodeError> {
+    let len =
+        u32::try_from(data.len()).map_err(|_| EncodeError::BinaryTooLarge { size: data.len() })?;
+    buf.put_u8(BINARY_EXT);
+    buf.put_u32(len);
+
+    let mut chunks = chunks.borrow_mut();
+    chunks.push(buf.split().freeze());
+    chunks.push(data.clone());
+    Ok(())
+}
+
 fn encode_bit_binary(buf: &mut BytesMut, bytes: &[u8], bits: u8) -> Result<(), EncodeError> {
+    check_bits(bits)?;
     let len = u32::try_from(bytes.len())
         .map_err(|_| EncodeError::BinaryTooLarge { size: bytes.len() })?;
     buf.put_u8(BIT_BINARY_EXT);
@@ -165,6 +510,14 @@ fn encode_bit_binary(buf: &mut BytesMut, bytes: &[u8], bits: u8) -> Result<(), E
     Ok(())
 }
 
+/// `BIT_BINARY_EXT` counts the bits of the last byte from 1 to 8.
+fn check_bits(bits: u8) -> Result<(), EncodeError> {
+    if !(1..=8).contains(&bits) {
+        return Err(EncodeError::InvalidBitCount { bits });
+    }
+    Ok(())
+}
+
 fn encode_string(buf: &mut BytesMut, s: &str) -> Result<(), EncodeError> {
     encode_binary(buf, s.as_bytes())
 }
@@ -172,7 +525,7 @@ fn encode_string(buf: &mut BytesMut, s: &str) -> Result<(), EncodeError> {
 fn encode_list_impl<'a>(
     buf: &mut BytesMut,
     elements: &'a [OwnedTerm],
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    ctx: Context<'_, 'a>,
 ) -> Result<(), EncodeError> {
     if elements.is_empty() {
         return encode_nil(buf);
@@ -185,7 +538,7 @@ fn encode_list_impl<'a>(
     buf.put_u8(LIST_EXT);
     buf.put_u32(len);
     for elem in elements {
-        encode_term_impl(buf, elem, cache)?;
+        encode_term_impl(buf, elem, ctx)?;
     }
     encode_nil(buf)?;
     Ok(())
@@ -195,7 +548,7 @@ fn encode_improper_list_impl<'a>(
     buf: &mut BytesMut,
     elements: &'a [OwnedTerm],
     tail: &'a OwnedTerm,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    ctx: Context<'_, 'a>,
 ) -> Result<(), EncodeError> {
     let len = u32::try_from(elements.len()).map_err(|_| EncodeError::ListTooLarge {
         size: elements.len(),
@@ -204,33 +557,64 @@ fn encode_improper_list_impl<'a>(
     buf.put_u8(LIST_EXT);
     buf.put_u32(len);
     for elem in elements {
-        encode_term_impl(buf, elem, cache)?;
+        encode_term_impl(buf, elem, ctx)?;
     }
-    encode_term_impl(buf, tail, cache)?;
+    encode_term_impl(buf, tail, ctx)?;
     Ok(())
 }
 
 fn encode_map_impl<'a>(
     buf: &mut BytesMut,
     map: &'a BTreeMap<OwnedTerm, OwnedTerm>,
-    cache: Option<&HashMap<&'a Atom, u8>>,
+    ctx: Context<'_, 'a>,
 ) -> Result<(), EncodeError> {
     let len = u32::try_from(map.len()).map_err(|_| EncodeError::MapTooLarge { size: map.len() })?;
 
     buf.put_u8(MAP_EXT);
     buf.put_u32(len);
 
+    if ctx.deterministic {
+        let mut entries: Vec<_> = map.iter().collect();
+        entries.sort_by(|(a, _), (b, _)| compare_map_keys(a, b));
+        for (key, value) in entries {
+            encode_term_impl(buf, key, ctx)?;
+            encode_term_impl(buf, value, ctx)?;
+        }
+        return Ok(());
+    }
+
     for (key, value) in map.iter() {
-        encode_term_impl(buf, key, cache)?;
-        encode_term_impl(buf, value, cache)?;
+        encode_term_impl(buf, key, ctx)?;
+        encode_term_impl(buf, value, ctx)?;
     }
     Ok(())
 }
 
+/// Map key order: term order, except that all integers sort before all floats.
+fn compare_map_keys(a: &OwnedTerm, b: &OwnedTerm) -> Ordering {
+    match (a, b) {
+        (OwnedTerm::Integer(_) | OwnedTerm::BigInt(_), OwnedTerm::Float(_)) => Ordering::Less,
+        (OwnedTerm::Float(_), OwnedTerm::Integer(_) | OwnedTerm::BigInt(_)) => Ordering::Greater,
+        (OwnedTerm::Tuple(x), OwnedTerm::Tuple(y)) if x.len() == y.len() => x
+            .iter()
+            .zip(y)
+            .map(|(a, b)| compare_map_keys(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal),
+        (OwnedTerm::List(x), OwnedTerm::List(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(a, b)| compare_map_keys(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        _ => a.cmp(b),
+    }
+}
+
 fn encode_tuple_impl(
     buf: &mut BytesMut,
     elements: &[OwnedTerm],
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: Context<'_, '_>,
 ) -> Result<(), EncodeError> {
     if elements.len() <= 255 {
         buf.put_u8(SMALL_TUPLE_EXT);
@@ -243,7 +627,7 @@ fn encode_tuple_impl(
         buf.put_u32(len);
     }
     for elem in elements {
-        encode_term_impl(buf, elem, cache)?;
+        encode_term_impl(buf, elem, ctx)?;
     }
     Ok(())
 }
@@ -251,16 +635,18 @@ fn encode_tuple_impl(
 fn encode_pid_impl(
     buf: &mut BytesMut,
     pid: &ExternalPid,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: Context<'_, '_>,
 ) -> Result<(), EncodeError> {
     // If this PID was decoded from LOCAL_EXT, use the preserved bytes for transparent re-encoding.
     // Otherwise, encode as NEW_PID_EXT (which can be exactly reconstructed from parsed fields).
-    if let Some(local_bytes) = &pid.local_ext_bytes {
+    if let Some(local_bytes) = &pid.local_ext_bytes
+        && ctx.local_ext
+    {
         buf.put_u8(LOCAL_EXT);
         buf.put_slice(local_bytes);
     } else {
         buf.put_u8(NEW_PID_EXT);
-        encode_atom_impl(buf, &pid.node, cache)?;
+        encode_atom_impl(buf, &pid.node, ctx)?;
         buf.put_u32(pid.id);
         buf.put_u32(pid.serial);
         buf.put_u32(pid.creation);
@@ -271,15 +657,25 @@ fn encode_pid_impl(
 fn encode_port_impl(
     buf: &mut BytesMut,
     port: &ExternalPort,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: Context<'_, '_>,
 ) -> Result<(), EncodeError> {
     // Use preserved LOCAL_EXT bytes if available for transparent re-encoding
-    if let Some(ref local_ext_bytes) = port.local_ext_bytes {
+    if let Some(ref local_ext_bytes) = port.local_ext_bytes
+        && ctx.local_ext
+    {
         buf.put_u8(LOCAL_EXT);
         buf.put_slice(local_ext_bytes);
+    } else if ctx.deterministic
+        && let Ok(id) = u32::try_from(port.id)
+    {
+        // OTP only uses V4_PORT_EXT for ids that do not fit in 32 bits
+        buf.put_u8(NEW_PORT_EXT);
+        encode_atom_impl(buf, &port.node, ctx)?;
+        buf.put_u32(id);
+        buf.put_u32(port.creation);
     } else {
         buf.put_u8(V4_PORT_EXT);
-        encode_atom_impl(buf, &port.node, cache)?;
+        encode_atom_impl(buf, &port.node, ctx)?;
         buf.put_u64(port.id);
         buf.put_u32(port.creation);
     }
@@ -289,10 +685,12 @@ fn encode_port_impl(
 fn encode_reference_impl(
     buf: &mut BytesMut,
     ref_: &ExternalReference,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: Context<'_, '_>,
 ) -> Result<(), EncodeError> {
     // Use preserved LOCAL_EXT bytes if available for transparent re-encoding
-    if let Some(ref local_ext_bytes) = ref_.local_ext_bytes {
+    if let Some(ref local_ext_bytes) = ref_.local_ext_bytes
+        && ctx.local_ext
+    {
         buf.put_u8(LOCAL_EXT);
         buf.put_slice(local_ext_bytes);
     } else {
@@ -302,7 +700,7 @@ fn encode_reference_impl(
 
         buf.put_u8(NEWER_REFERENCE_EXT);
         buf.put_u16(len);
-        encode_atom_impl(buf, &ref_.node, cache)?;
+        encode_atom_impl(buf, &ref_.node, ctx)?;
         buf.put_u32(ref_.creation);
         for id in &ref_.ids {
             buf.put_u32(*id);
@@ -325,6 +723,60 @@ fn encode_bigint(buf: &mut BytesMut, big: &BigInt) -> Result<(), EncodeError> {
     Ok(())
 }
 
+/// Encodes a bignum the way OTP would: values that fit are encoded as
+/// integers, and leading zero bytes are dropped.
+fn encode_bigint_normalized(buf: &mut BytesMut, big: &BigInt) -> Result<(), EncodeError> {
+    if let Ok(value) = i128::try_from(big)
+        && let Ok(value) = i64::try_from(value)
+    {
+        return encode_integer(buf, value);
+    }
+    let len = big
+        .digits
+        .iter()
+        .rposition(|&d| d != 0)
+        .map_or(0, |i| i + 1);
+    encode_bigint(buf, &BigInt::new(big.sign, big.digits[..len].to_vec()))
+}
+
+fn is_byte_list(elements: &[OwnedTerm]) -> bool {
+    elements.len() <= u16::MAX as usize
+        && elements
+            .iter()
+            .all(|e| matches!(e, OwnedTerm::Integer(i) if (0..=255).contains(i)))
+}
+
+/// Writes the elements of a list that passed [`is_byte_list`] as `STRING_EXT`.
+fn encode_string_ext(
+    buf: &mut BytesMut,
+    bytes: impl ExactSizeIterator<Item = u8>,
+) -> Result<(), EncodeError> {
+    buf.put_u8(STRING_EXT);
+    buf.put_u16(bytes.len() as u16);
+    for byte in bytes {
+        buf.put_u8(byte);
+    }
+    Ok(())
+}
+
+/// Encodes a bitstring with its unused trailing bits cleared, and as a
+/// binary when the last byte is complete.
+fn encode_bit_binary_normalized(
+    buf: &mut BytesMut,
+    bytes: &[u8],
+    bits: u8,
+) -> Result<(), EncodeError> {
+    check_bits(bits)?;
+    if bits == 8 || bytes.is_empty() {
+        return encode_binary(buf, bytes);
+    }
+    let mut bytes = bytes.to_vec();
+    if let Some(last) = bytes.last_mut() {
+        *last &= 0xffu8 << (8 - bits);
+    }
+    encode_bit_binary(buf, &bytes, bits)
+}
+
 fn encode_nil(buf: &mut BytesMut) -> Result<(), EncodeError> {
     buf.put_u8(NIL_EXT);
     Ok(())
@@ -333,11 +785,11 @@ fn encode_nil(buf: &mut BytesMut) -> Result<(), EncodeError> {
 fn encode_export_ext_impl(
     buf: &mut BytesMut,
     fun: &ExternalFun,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: Context<'_, '_>,
 ) -> Result<(), EncodeError> {
     buf.put_u8(EXPORT_EXT);
-    encode_atom_impl(buf, &fun.module, cache)?;
-    encode_atom_impl(buf, &fun.function, cache)?;
+    encode_atom_impl(buf, &fun.module, ctx)?;
+    encode_atom_impl(buf, &fun.function, ctx)?;
     encode_integer(buf, fun.arity as i64)?;
     Ok(())
 }
@@ -345,8 +797,13 @@ fn encode_export_ext_impl(
 fn encode_new_fun_ext_impl(
     buf: &mut BytesMut,
     fun: &InternalFun,
-    cache: Option<&HashMap<&Atom, u8>>,
+    ctx: Context<'_, '_>,
 ) -> Result<(), EncodeError> {
+    // The size prefix covers the free variables, so they are encoded in one piece
+    let ctx = Context {
+        chunks: None,
+        ..ctx
+    };
     let mut temp_buf = BytesMut::new();
 
     temp_buf.put_u8(fun.arity);
@@ -354,13 +811,13 @@ fn encode_new_fun_ext_impl(
     temp_buf.put_u32(fun.index);
     temp_buf.put_u32(fun.num_free);
 
-    encode_atom_impl(&mut temp_buf, &fun.module, cache)?;
+    encode_atom_impl(&mut temp_buf, &fun.module, ctx)?;
     encode_integer(&mut temp_buf, fun.old_index as i64)?;
     encode_integer(&mut temp_buf, fun.old_uniq as i64)?;
-    encode_pid_impl(&mut temp_buf, &fun.pid, cache)?;
+    encode_pid_impl(&mut temp_buf, &fun.pid, ctx)?;
 
     for var in &fun.free_vars {
-        encode_term_impl(&mut temp_buf, var, cache)?;
+        encode_term_impl(&mut temp_buf, var, ctx)?;
     }
 
     buf.put_u8(NEW_FUN_EXT);
@@ -370,50 +827,139 @@ fn encode_new_fun_ext_impl(
     Ok(())
 }
 
-fn collect_atoms<'a>(term: &'a OwnedTerm, atoms: &mut HashSet<&'a Atom>) {
+fn encode_borrowed_impl(
+    buf: &mut BytesMut,
+    term: &BorrowedTerm<'_>,
+    ctx: Context<'_, '_>,
+) -> Result<(), EncodeError> {
     match term {
-        OwnedTerm::Atom(atom) => {
-            atoms.insert(atom);
+        BorrowedTerm::Atom(name) => encode_atom_name(buf, name, ctx),
+        BorrowedTerm::Integer(i) => encode_integer(buf, *i),
+        BorrowedTerm::Float(f) if ctx.minor_version == 0 => encode_old_float(buf, *f),
+        BorrowedTerm::Float(f) => encode_float(buf, *f),
+        BorrowedTerm::Binary(b) => encode_binary(buf, b),
+        BorrowedTerm::BitBinary { bytes, bits } if ctx.deterministic => {
+            encode_bit_binary_normalized(buf, bytes, *bits)
         }
-        OwnedTerm::Tuple(elements) | OwnedTerm::List(elements) => {
-            for elem in elements {
-                collect_atoms(elem, atoms);
-            }
+        BorrowedTerm::BitBinary { bytes, bits } => encode_bit_binary(buf, bytes, *bits),
+        BorrowedTerm::String(s) => encode_string(buf, s),
+        BorrowedTerm::List(l) if ctx.deterministic && !l.is_empty() && is_borrowed_byte_list(l) => {
+            encode_string_ext(
+                buf,
+                l.iter().map(|e| e.as_integer().unwrap_or_default() as u8),
+            )
         }
-        OwnedTerm::ImproperList { elements, tail } => {
-            for elem in elements {
-                collect_atoms(elem, atoms);
-            }
-            collect_atoms(tail, atoms);
+        BorrowedTerm::List(l) if l.is_empty() => encode_nil(buf),
+        BorrowedTerm::List(l) => {
+            encode_borrowed_elements(buf, l, ctx)?;
+            encode_nil(buf)
         }
-        OwnedTerm::Map(map) => {
-            for (key, value) in map {
-                collect_atoms(key, atoms);
-                collect_atoms(value, atoms);
-            }
+        BorrowedTerm::ImproperList { elements, tail } => {
+            encode_borrowed_elements(buf, elements, ctx)?;
+            encode_borrowed_impl(buf, tail, ctx)
         }
-        OwnedTerm::Pid(pid) => {
-            atoms.insert(&pid.node);
+        BorrowedTerm::Map(m) => {
+            let len =
+                u32::try_from(m.len()).map_err(|_| EncodeError::MapTooLarge { size: m.len() })?;
+            buf.put_u8(MAP_EXT);
+            buf.put_u32(len);
+
+            let mut entries: Vec<_> = m.iter().collect();
+            if ctx.deterministic {
+                entries.sort_by(|(a, _), (b, _)| compare_borrowed_map_keys(a, b));
+            }
+            for (key, value) in entries {
+                encode_borrowed_impl(buf, key, ctx)?;
+                encode_borrowed_impl(buf, value, ctx)?;
+            }
+            Ok(())
         }
-        OwnedTerm::Port(port) => {
-            atoms.insert(&port.node);
+        BorrowedTerm::Tuple(t) => {
+            if t.len() <= 255 {
+                buf.put_u8(SMALL_TUPLE_EXT);
+                buf.put_u8(t.len() as u8);
+            } else {
+                let len = u32::try_from(t.len())
+                    .map_err(|_| EncodeError::TupleTooLarge { size: t.len() })?;
+                buf.put_u8(LARGE_TUPLE_EXT);
+                buf.put_u32(len);
+            }
+            for elem in t {
+                encode_borrowed_impl(buf, elem, ctx)?;
+            }
+            Ok(())
         }
-        OwnedTerm::Reference(ref_) => {
-            atoms.insert(&ref_.node);
+        BorrowedTerm::Pid(pid) => encode_pid_impl(buf, pid, ctx),
+        BorrowedTerm::Port(port) => encode_port_impl(buf, port, ctx),
+        BorrowedTerm::Reference(ref_) => encode_reference_impl(buf, ref_, ctx),
+        BorrowedTerm::BigInt(big) if ctx.deterministic => encode_bigint_normalized(buf, big),
+        BorrowedTerm::BigInt(big) => encode_bigint(buf, big),
+        BorrowedTerm::ExternalFun(fun) => encode_export_ext_impl(buf, fun, ctx),
+        BorrowedTerm::InternalFun(fun) => encode_new_fun_ext_impl(buf, fun, ctx),
+        BorrowedTerm::Nil => encode_nil(buf),
+    }
+}
+
+/// Writes the `LIST_EXT` header and elements, leaving the tail to the caller.
+fn encode_borrowed_elements(
+    buf: &mut BytesMut,
+    elements: &[BorrowedTerm<'_>],
+    ctx: Context<'_, '_>,
+) -> Result<(), EncodeError> {
+    let len = u32::try_from(elements.len()).map_err(|_| EncodeError::ListTooLarge {
+        size: elements.len(),
+    })?;
+
+    buf.put_u8(LIST_EXT);
+    buf.put_u32(len);
+    for elem in elements {
+        encode_borrowed_impl(buf, elem, ctx)?;
+    }
+    Ok(())
+}
+
+fn is_borrowed_byte_list(elements: &[BorrowedTerm<'_>]) -> bool {
+    elements.len() <= u16::MAX as usize
+        && elements
+            .iter()
+            .all(|e| matches!(e, BorrowedTerm::Integer(i) if (0..=255).contains(i)))
+}
+
+/// [`compare_map_keys`] for borrowed terms.
+fn compare_borrowed_map_keys(a: &BorrowedTerm<'_>, b: &BorrowedTerm<'_>) -> Ordering {
+    match (a, b) {
+        (BorrowedTerm::Integer(_) | BorrowedTerm::BigInt(_), BorrowedTerm::Float(_)) => {
+            Ordering::Less
         }
-        OwnedTerm::ExternalFun(fun) => {
-            atoms.insert(&fun.module);
-            atoms.insert(&fun.function);
+        (BorrowedTerm::Float(_), BorrowedTerm::Integer(_) | BorrowedTerm::BigInt(_)) => {
+            Ordering::Greater
         }
-        OwnedTerm::InternalFun(fun) => {
-            atoms.insert(&fun.module);
-            atoms.insert(&fun.pid.node);
-            for var in &fun.free_vars {
-                collect_atoms(var, atoms);
-            }
+        (BorrowedTerm::Tuple(x), BorrowedTerm::Tuple(y)) if x.len() == y.len() => x
+            .iter()
+            .zip(y)
+            .map(|(a, b)| compare_borrowed_map_keys(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal),
+        (BorrowedTerm::List(x), BorrowedTerm::List(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(a, b)| compare_borrowed_map_keys(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        _ => a.cmp(b),
+    }
+}
+
+fn collect_atoms<'a>(term: &'a OwnedTerm, atoms: &mut HashSet<&'a Atom>) {
+    struct Collector<'s, 'a>(&'s mut HashSet<&'a Atom>);
+
+    impl<'a> TermVisitor<'a> for Collector<'_, 'a> {
+        fn visit_atom(&mut self, atom: &'a Atom) {
+            self.0.insert(atom);
         }
-        _ => {}
     }
+
+    Collector(atoms).visit_term(term);
 }
 
 fn encode_term_with_cache<'a>(
@@ -421,7 +967,11 @@ fn encode_term_with_cache<'a>(
     term: &'a OwnedTerm,
     atom_index_map: &HashMap<&'a Atom, u8>,
 ) -> Result<(), EncodeError> {
-    encode_term_impl(buf, term, Some(atom_index_map))
+    encode_term_impl(
+        buf,
+        term,
+        Context::new(Some(atom_index_map), &EncodeOptions::default()),
+    )
 }
 
 pub fn encode_with_dist_header(term: &OwnedTerm) -> Result<Vec<u8>, EncodeError> {
@@ -507,3 +1057,212 @@ pub fn encode_with_dist_header_multi(terms: &[&OwnedTerm]) -> Result<Vec<u8>, En
 
     Ok(buf.to_vec())
 }
+
+/// The number of bytes [`encode_with_options`] produces for `term` without
+/// compression, computed without encoding it, like `erlang:external_size/2`.
+///
+/// `compression_level` is ignored: the result is exact for uncompressed output,
+/// and an upper bound when compression is enabled, since terms that would not
+/// shrink are left uncompressed.
+pub fn encoded_size(term: &OwnedTerm, options: &EncodeOptions) -> Result<usize, EncodeError> {
+    if options.minor_version > MAX_MINOR_VERSION {
+        return Err(EncodeError::InvalidMinorVersion {
+            version: options.minor_version,
+        });
+    }
+    Ok(1 + term_size(term, Context::new(None, options))?)
+}
+
+/// The exact number of bytes [`encode_with_dist_header_multi`] produces for `terms`.
+pub fn encoded_size_with_dist_header(terms: &[&OwnedTerm]) -> Result<usize, EncodeError> {
+    let mut atom_set = HashSet::new();
+    for term in terms {
+        collect_atoms(term, &mut atom_set);
+    }
+    if atom_set.len() > 255 {
+        return Err(EncodeError::TooManyAtoms {
+            count: atom_set.len(),
+        });
+    }
+
+    let options = EncodeOptions::default();
+    let mut size = 1;
+    let atom_index_map: HashMap<&Atom, u8> = atom_set.iter().map(|atom| (*atom, 0)).collect();
+    let ctx = if atom_set.is_empty() {
+        Context::new(None, &options)
+    } else {
+        let long_atoms = atom_set.iter().any(|a| a.name.len() > 255);
+        let length_size = if long_atoms { 2 } else { 1 };
+        size += 2 + atom_set.len() / 2 + 1;
+        size += atom_set
+            .iter()
+            .map(|atom| 1 + length_size + atom.name.len())
+            .sum::<usize>();
+        Context::new(Some(&atom_index_map), &options)
+    };
+
+    for term in terms {
+        size += term_size(term, ctx)?;
+    }
+    Ok(size)
+}
+
+/// Mirrors `encode_term_impl`.
+fn term_size<'a>(term: &'a OwnedTerm, ctx: Context<'_, 'a>) -> Result<usize, EncodeError> {
+    let size = match term {
+        OwnedTerm::Atom(atom) => atom_size(atom, ctx)?,
+        OwnedTerm::Integer(i) => integer_size(*i),
+        OwnedTerm::Float(_) if ctx.minor_version == 0 => 32,
+        OwnedTerm::Float(_) => 9,
+        OwnedTerm::Binary(b) => binary_size(b.len())?,
+        OwnedTerm::SharedBinary(b) => binary_size(b.len())?,
+        OwnedTerm::BitBinary { bytes, bits } => {
+            check_bits(*bits)?;
+            if ctx.deterministic && (*bits == 8 || bytes.is_empty()) {
+                binary_size(bytes.len())?
+            } else {
+                1 + binary_size(bytes.len())?
+            }
+        }
+        OwnedTerm::String(s) => binary_size(s.len())?,
+        OwnedTerm::List(l) if ctx.deterministic && !l.is_empty() && is_byte_list(l) => 3 + l.len(),
+        OwnedTerm::List(l) if l.is_empty() => 1,
+        OwnedTerm::List(l) => list_size(l, ctx)? + 1,
+        OwnedTerm::ImproperList { elements, tail } => {
+            list_size(elements, ctx)? + term_size(tail, ctx)?
+        }
+        OwnedTerm::Map(m) => {
+            u32::try_from(m.len()).map_err(|_| EncodeError::MapTooLarge { size: m.len() })?;
+            let mut size = 5;
+            for (key, value) in m {
+                size += term_size(key, ctx)? + term_size(value, ctx)?;
+            }
+            size
+        }
+        OwnedTerm::Tuple(t) => {
+            let header = if t.len() <= 255 {
+                2
+            } else {
+                u32::try_from(t.len()).map_err(|_| EncodeError::TupleTooLarge { size: t.len() })?;
+                5
+            };
+            let mut size = header;
+            for elem in t {
+                size += term_size(elem, ctx)?;
+            }
+            size
+        }
+        OwnedTerm::Pid(pid) => pid_size(pid, ctx)?,
+        OwnedTerm::Port(port) => match &port.local_ext_bytes {
+            Some(bytes) if ctx.local_ext => 1 + bytes.len(),
+            _ if ctx.deterministic && port.id <= u32::MAX as u64 => {
+                1 + atom_size(&port.node, ctx)? + 8
+            }
+            _ => 1 + atom_size(&port.node, ctx)? + 12,
+        },
+        OwnedTerm::Reference(ref_) => match &ref_.local_ext_bytes {
+            Some(bytes) if ctx.local_ext => 1 + bytes.len(),
+            _ => {
+                u16::try_from(ref_.ids.len()).map_err(|_| EncodeError::ReferenceTooLarge {
+                    size: ref_.ids.len(),
+                })?;
+                3 + atom_size(&ref_.node, ctx)? + 4 + 4 * ref_.ids.len()
+            }
+        },
+        OwnedTerm::BigInt(big) if ctx.deterministic => {
+            if let Ok(value) = i128::try_from(big)
+                && let Ok(value) = i64::try_from(value)
+            {
+                integer_size(value)
+            } else {
+                let len = big
+                    .digits
+                    .iter()
+                    .rposition(|&d| d != 0)
+                    .map_or(0, |i| i + 1);
+                bigint_size(len)
+            }
+        }
+        OwnedTerm::BigInt(big) => bigint_size(big.digits.len()),
+        OwnedTerm::ExternalFun(fun) => {
+            1 + atom_size(&fun.module, ctx)?
+                + atom_size(&fun.function, ctx)?
+                + integer_size(fun.arity as i64)
+        }
+        OwnedTerm::InternalFun(fun) => {
+            let mut size = 1 + 4 + 1 + 16 + 4 + 4;
+            size += atom_size(&fun.module, ctx)?;
+            size += integer_size(fun.old_index as i64);
+            size += integer_size(fun.old_uniq as i64);
+            size += pid_size(&fun.pid, ctx)?;
+            for var in &fun.free_vars {
+                size += term_size(var, ctx)?;
+            }
+            size
+        }
+        OwnedTerm::Nil => 1,
+    };
+    Ok(size)
+}
+
+fn atom_size<'a>(atom: &'a Atom, ctx: Context<'_, 'a>) -> Result<usize, EncodeError> {
+    if let Some(atom_index_map) = ctx.cache
+        && atom_index_map.contains_key(&atom)
+    {
+        return Ok(2);
+    }
+
+    let len = atom.name.len();
+    if len > u16::MAX as usize {
+        return Err(EncodeError::AtomTooLarge { size: len });
+    }
+
+    let len = if ctx.minor_version < 2 && atom.name.chars().all(|c| u8::try_from(c).is_ok()) {
+        atom.name.chars().count()
+    } else {
+        len
+    };
+    if len > 255 || !ctx.small_atoms {
+        Ok(3 + len)
+    } else {
+        Ok(2 + len)
+    }
+}
+
+fn integer_size(value: i64) -> usize {
+    if (0..=255).contains(&value) {
+        2
+    } else if value >= i32::MIN as i64 && value <= i32::MAX as i64 {
+        5
+    } else {
+        let significant_len = (64 - value.unsigned_abs().leading_zeros() as usize).div_ceil(8);
+        3 + significant_len
+    }
+}
+
+fn binary_size(len: usize) -> Result<usize, EncodeError> {
+    u32::try_from(len).map_err(|_| EncodeError::BinaryTooLarge { size: len })?;
+    Ok(5 + len)
+}
+
+fn bigint_size(len: usize) -> usize {
+    if len <= 255 { 3 + len } else { 6 + len }
+}
+
+fn list_size<'a>(elements: &'a [OwnedTerm], ctx: Context<'_, 'a>) -> Result<usize, EncodeError> {
+    u32::try_from(elements.len()).map_err(|_| EncodeError::ListTooLarge {
+        size: elements.len(),
+    })?;
+    let mut size = 5;
+    for elem in elements {
+        size += term_size(elem, ctx)?;
+    }
+    Ok(size)
+}
+
+fn pid_size<'a>(pid: &'a ExternalPid, ctx: Context<'_, 'a>) -> Result<usize, EncodeError> {
+    match &pid.local_ext_bytes {
+        Some(bytes) if ctx.local_ext => Ok(1 + bytes.len()),
+        _ => Ok(1 + atom_size(&pid.node, ctx)? + 12),
+    }
+}
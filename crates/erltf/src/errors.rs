@@ -25,6 +25,8 @@ pub enum Error {
     Decode(#[from] DecodeError),
     #[error("encode error: {0}")]
     Encode(#[from] EncodeError),
+    #[error("parse error: {0}")]
+    Parse(#[from] ParseError),
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -61,6 +63,35 @@ pub enum DecodeError {
     TrailingData(usize),
     #[error("invalid PID format: {0}")]
     InvalidPidFormat(String),
+    #[error("nesting depth exceeds {max}")]
+    DepthLimitExceeded { max: usize },
+    #[error("decoded term would use more than {max} bytes")]
+    MemoryLimitExceeded { max: usize },
+    #[error("compressed term expands more than {max} times")]
+    CompressionRatioExceeded { max: usize },
+    #[error("atom not allowed in safe mode: {0}")]
+    UnsafeAtom(String),
+    #[error("funs are not allowed in safe mode")]
+    UnsafeFun,
+    #[error("atom table is full ({max} atoms)")]
+    AtomTableFull { max: usize },
+    #[error("I/O error: {message}")]
+    IoError {
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+}
+
+impl From<std::io::Error> for DecodeError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::UnexpectedEof => DecodeError::UnexpectedEof,
+            kind => DecodeError::IoError {
+                kind,
+                message: error.to_string(),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -174,6 +205,94 @@ pub enum EncodeError {
     IoError(#[from] std::io::Error),
     #[error("buffer overflow")]
     BufferOverflow,
+    #[error("invalid compression level: {level} (max 9)")]
+    InvalidCompressionLevel { level: u32 },
+    #[error("invalid minor version: {version} (max 2)")]
+    InvalidMinorVersion { version: u8 },
+    #[error("invalid number of bits in the last byte of a bitstring: {bits} (expected 1 to 8)")]
+    InvalidBitCount { bits: u8 },
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    #[error("unexpected character '{0}'")]
+    UnexpectedChar(char),
+    #[error("invalid integer: {0}")]
+    InvalidInteger(String),
+    #[error("invalid float: {0}")]
+    InvalidFloat(String),
+    #[error("invalid escape sequence")]
+    InvalidEscape,
+    #[error("invalid binary segment: {0}")]
+    InvalidBinarySegment(String),
+    #[error("invalid {0} literal")]
+    InvalidLiteral(&'static str),
+    #[error("variable {0} is not a term")]
+    UnexpectedVariable(String),
+    #[error("atom too large: {size} characters (max 255)")]
+    AtomTooLarge { size: usize },
+    #[error("nesting depth exceeds {max}")]
+    DepthLimitExceeded { max: usize },
+}
+
+/// A syntax error in Erlang term text, with a 1-based line and column.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, line: usize, column: usize) -> Self {
+        ParseError { kind, line, column }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SelectorErrorKind {
+    #[error("unexpected end of selector")]
+    UnexpectedEof,
+    #[error("unexpected character '{0}'")]
+    UnexpectedChar(char),
+    #[error("invalid index: {0}")]
+    InvalidIndex(String),
+    #[error("invalid key: {0}")]
+    InvalidKey(ParseError),
+}
+
+/// A syntax error in a selector, with the byte offset where it was found.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{kind} at offset {offset}")]
+pub struct SelectorError {
+    pub kind: SelectorErrorKind,
+    pub offset: usize,
+}
+
+impl SelectorError {
+    pub fn new(kind: SelectorErrorKind, offset: usize) -> Self {
+        SelectorError { kind, offset }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ParseBigIntError {
+    #[error("cannot parse integer from empty string")]
+    Empty,
+    #[error("invalid digit found in string")]
+    InvalidDigit,
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -185,6 +304,44 @@ pub enum TermConversionError {
     },
     #[error("value out of range for target type")]
     OutOfRange,
+    #[error("missing field {0}")]
+    MissingField(&'static str),
+    #[error("expected a tuple of {expected} elements, got {actual}")]
+    WrongArity { expected: usize, actual: usize },
+    #[error("unknown tag {0}")]
+    UnknownTag(String),
+    #[error("unknown field {0}")]
+    UnknownField(String),
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("atom table is full ({max} atoms)")]
+pub struct AtomTableFull {
+    pub max: usize,
+}
+
+impl From<AtomTableFull> for DecodeError {
+    fn from(error: AtomTableFull) -> Self {
+        DecodeError::AtomTableFull { max: error.max }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum BitSyntaxError {
+    #[error("segment needs {needed} bits, {remaining} remaining")]
+    UnexpectedEnd { needed: usize, remaining: usize },
+    #[error("invalid float size: {0} bits")]
+    InvalidFloatSize(usize),
+    #[error("float {0} does not fit the segment")]
+    FloatOutOfRange(f64),
+    #[error("invalid {0} code point")]
+    InvalidUnicode(&'static str),
+    #[error("{bits} bits remaining, not a whole number of bytes")]
+    NotByteAligned { bits: usize },
+    #[error("segment size {size} exceeds {max} bits")]
+    SizeTooLarge { size: usize, max: usize },
+    #[error("term is not a binary or bitstring")]
+    NotABitstring,
 }
 
 impl From<Utf8Error> for DecodeError {
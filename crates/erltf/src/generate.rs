@@ -0,0 +1,600 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Random terms for property tests and fuzzing.
+//!
+//! With the `proptest` feature, [`OwnedTerm`], [`BorrowedTerm`], [`Atom`],
+//! [`ExternalPid`], [`ExternalReference`] and [`BigInt`] implement
+//! `proptest::arbitrary::Arbitrary`, and [`term`] and its siblings return
+//! strategies. With the `arbitrary` feature, the same types implement
+//! `arbitrary::Arbitrary`, and [`arbitrary_term`] builds terms from fuzzer input.
+//!
+//! Both are configured with [`GenerateOptions`]. By default any term can be
+//! generated, including ones such as `NaN` floats that do not survive an
+//! encode/decode round trip; [`GenerateOptions::round_trippable`] rules those out.
+//!
+//! [`OwnedTerm`]: crate::OwnedTerm
+//! [`BorrowedTerm`]: crate::BorrowedTerm
+//! [`Atom`]: crate::types::Atom
+//! [`ExternalPid`]: crate::types::ExternalPid
+//! [`ExternalReference`]: crate::types::ExternalReference
+//! [`BigInt`]: crate::types::BigInt
+
+use crate::types::{BigInt, Sign};
+
+#[cfg(feature = "proptest")]
+pub use self::strategies::{atom, bigint, borrowed_term, pid, port, reference, term};
+#[cfg(feature = "arbitrary")]
+pub use self::unstructured::arbitrary_term;
+
+/// Controls the shape of generated terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// How deeply lists, tuples and maps nest.
+    pub max_depth: u32,
+    /// The most elements in a list or tuple, or entries in a map.
+    pub max_len: usize,
+    /// The most bytes in a binary or bitstring.
+    pub max_binary_len: usize,
+    /// Only generate terms that `erltf::encode` accepts and that `erltf::decode`
+    /// turns back into an equal term: integers fit in an `i32`, floats are finite,
+    /// lists are non-empty and there are no [`OwnedTerm::String`](crate::OwnedTerm::String)s.
+    pub round_trippable: bool,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_len: 8,
+            max_binary_len: 32,
+            round_trippable: false,
+        }
+    }
+}
+
+impl GenerateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn round_trippable() -> Self {
+        Self::default().with_round_trippable(true)
+    }
+
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn with_max_binary_len(mut self, max_binary_len: usize) -> Self {
+        self.max_binary_len = max_binary_len;
+        self
+    }
+
+    pub fn with_round_trippable(mut self, round_trippable: bool) -> Self {
+        self.round_trippable = round_trippable;
+        self
+    }
+
+    /// Lists that decode back equal are never empty: `[]` decodes as `Nil`.
+    fn min_list_len(&self) -> usize {
+        usize::from(self.round_trippable)
+    }
+
+    /// Encoders never write high zero digits or a negative zero, so
+    /// round-trippable bignums are trimmed and zero is positive.
+    fn shape_bigint(&self, mut big: BigInt) -> BigInt {
+        if self.round_trippable {
+            let len = big
+                .digits
+                .iter()
+                .rposition(|&d| d != 0)
+                .map_or(0, |i| i + 1);
+            big.digits.truncate(len);
+            if len == 0 {
+                big.sign = Sign::Positive;
+            }
+        }
+        big
+    }
+}
+
+/// The longest atom, in characters, that can be encoded.
+#[cfg(feature = "arbitrary")]
+const MAX_ATOM_CHARS: usize = 255;
+
+/// `NEW_FUN_EXT` stores these as integers that decode as `i32`.
+const MAX_FUN_INDEX: u32 = i32::MAX as u32;
+
+#[cfg(feature = "proptest")]
+mod strategies {
+    use super::{GenerateOptions, MAX_FUN_INDEX};
+    use crate::borrowed::BorrowedTerm;
+    use crate::term::OwnedTerm;
+    use crate::types::{
+        Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
+    };
+    use bytes::Bytes;
+    use proptest::arbitrary::Arbitrary;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest::strategy::Union;
+
+    /// Atoms of up to 255 characters, mostly plain lowercase names.
+    pub fn atom() -> BoxedStrategy<Atom> {
+        prop_oneof![
+            3 => "[a-z][a-zA-Z0-9_@]{0,20}",
+            1 => "\\PC{0,40}",
+        ]
+        .prop_map(Atom::new)
+        .boxed()
+    }
+
+    fn node_atom() -> impl Strategy<Value = Atom> {
+        "[a-z][a-z0-9_]{0,10}@[a-z][a-z0-9-]{0,10}".prop_map(Atom::new)
+    }
+
+    pub fn pid() -> BoxedStrategy<ExternalPid> {
+        (node_atom(), any::<u32>(), any::<u32>(), any::<u32>())
+            .prop_map(|(node, id, serial, creation)| ExternalPid::new(node, id, serial, creation))
+            .boxed()
+    }
+
+    pub fn port() -> BoxedStrategy<ExternalPort> {
+        (node_atom(), any::<u64>(), any::<u32>())
+            .prop_map(|(node, id, creation)| ExternalPort::new(node, id, creation))
+            .boxed()
+    }
+
+    pub fn reference() -> BoxedStrategy<ExternalReference> {
+        (node_atom(), any::<u32>(), vec(any::<u32>(), 1..=5))
+            .prop_map(|(node, creation, ids)| ExternalReference::new(node, creation, ids))
+            .boxed()
+    }
+
+    pub fn bigint() -> BoxedStrategy<BigInt> {
+        (any::<bool>(), vec(any::<u8>(), 1..=16))
+            .prop_map(|(negative, digits)| BigInt::new(negative, digits))
+            .boxed()
+    }
+
+    fn integer(options: GenerateOptions) -> BoxedStrategy<i64> {
+        if options.round_trippable {
+            any::<i32>().prop_map(i64::from).boxed()
+        } else {
+            any::<i64>().boxed()
+        }
+    }
+
+    fn binary(options: GenerateOptions) -> impl Strategy<Value = Vec<u8>> {
+        vec(any::<u8>(), 0..=options.max_binary_len)
+    }
+
+    /// Small terms that funs close over.
+    fn free_var(options: GenerateOptions) -> impl Strategy<Value = OwnedTerm> {
+        prop_oneof![
+            integer(options).prop_map(OwnedTerm::Integer),
+            atom().prop_map(OwnedTerm::Atom),
+            binary(options).prop_map(OwnedTerm::Binary),
+        ]
+    }
+
+    fn internal_fun(options: GenerateOptions) -> impl Strategy<Value = InternalFun> {
+        (
+            any::<u8>(),
+            any::<[u8; 16]>(),
+            any::<u32>(),
+            atom(),
+            0..MAX_FUN_INDEX,
+            0..MAX_FUN_INDEX,
+            pid(),
+            vec(free_var(options), 0..=options.max_len.min(4)),
+        )
+            .prop_map(
+                |(arity, uniq, index, module, old_index, old_uniq, pid, free_vars)| {
+                    InternalFun::new(
+                        arity,
+                        uniq,
+                        index,
+                        free_vars.len() as u32,
+                        module,
+                        old_index,
+                        old_uniq,
+                        pid,
+                        free_vars,
+                    )
+                },
+            )
+    }
+
+    fn leaf(options: GenerateOptions) -> BoxedStrategy<OwnedTerm> {
+        let float = if options.round_trippable {
+            any::<f64>()
+                .prop_filter("finite float", |f| f.is_finite())
+                .boxed()
+        } else {
+            any::<f64>().boxed()
+        };
+        let max_bits: u8 = if options.round_trippable { 7 } else { 8 };
+
+        let mut leaves = vec![
+            integer(options).prop_map(OwnedTerm::Integer).boxed(),
+            float.prop_map(OwnedTerm::Float).boxed(),
+            atom().prop_map(OwnedTerm::Atom).boxed(),
+            binary(options).prop_map(OwnedTerm::Binary).boxed(),
+            (
+                vec(any::<u8>(), 1..=options.max_binary_len.max(1)),
+                1..=max_bits,
+            )
+                .prop_map(|(bytes, bits)| OwnedTerm::BitBinary { bytes, bits })
+                .boxed(),
+            bigint()
+                .prop_map(move |big| OwnedTerm::BigInt(options.shape_bigint(big)))
+                .boxed(),
+            pid().prop_map(OwnedTerm::Pid).boxed(),
+            port().prop_map(OwnedTerm::Port).boxed(),
+            reference().prop_map(OwnedTerm::Reference).boxed(),
+            (atom(), atom(), any::<u8>())
+                .prop_map(|(m, f, a)| OwnedTerm::ExternalFun(ExternalFun::new(m, f, a)))
+                .boxed(),
+            internal_fun(options)
+                .prop_map(|fun| OwnedTerm::InternalFun(Box::new(fun)))
+                .boxed(),
+            Just(OwnedTerm::Nil).boxed(),
+        ];
+        if !options.round_trippable {
+            leaves.push("\\PC{0,20}".prop_map(OwnedTerm::String).boxed());
+            leaves.push(
+                binary(options)
+                    .prop_map(|b| OwnedTerm::SharedBinary(Bytes::from(b)))
+                    .boxed(),
+            );
+        }
+        Union::new(leaves).boxed()
+    }
+
+    /// Terms nested up to `options.max_depth` levels deep.
+    pub fn term(options: GenerateOptions) -> BoxedStrategy<OwnedTerm> {
+        let max_len = options.max_len;
+        let min_list_len = options.min_list_len();
+        let branch_size = max_len.max(1) as u32;
+
+        leaf(options)
+            .prop_recursive(options.max_depth, 256, branch_size, move |inner| {
+                let tail = if options.round_trippable {
+                    leaf(options)
+                        .prop_filter("improper list tail", |t| {
+                            !matches!(t, OwnedTerm::Nil | OwnedTerm::List(_))
+                        })
+                        .boxed()
+                } else {
+                    inner.clone()
+                };
+                prop_oneof![
+                    vec(inner.clone(), min_list_len..=max_len.max(min_list_len))
+                        .prop_map(OwnedTerm::List),
+                    vec(inner.clone(), 0..=max_len).prop_map(OwnedTerm::Tuple),
+                    (vec(inner.clone(), 1..=max_len.max(1)), tail).prop_map(|(elements, tail)| {
+                        OwnedTerm::ImproperList {
+                            elements,
+                            tail: Box::new(tail),
+                        }
+                    }),
+                    vec((inner.clone(), inner), 0..=max_len)
+                        .prop_map(|entries| OwnedTerm::Map(entries.into_iter().collect())),
+                ]
+            })
+            .boxed()
+    }
+
+    pub fn borrowed_term(options: GenerateOptions) -> BoxedStrategy<BorrowedTerm<'static>> {
+        term(options).prop_map(BorrowedTerm::from).boxed()
+    }
+
+    impl Arbitrary for OwnedTerm {
+        type Parameters = GenerateOptions;
+        type Strategy = BoxedStrategy<OwnedTerm>;
+
+        fn arbitrary_with(options: GenerateOptions) -> Self::Strategy {
+            term(options)
+        }
+    }
+
+    impl Arbitrary for BorrowedTerm<'static> {
+        type Parameters = GenerateOptions;
+        type Strategy = BoxedStrategy<BorrowedTerm<'static>>;
+
+        fn arbitrary_with(options: GenerateOptions) -> Self::Strategy {
+            borrowed_term(options)
+        }
+    }
+
+    impl Arbitrary for Atom {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Atom>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            atom()
+        }
+    }
+
+    impl Arbitrary for ExternalPid {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<ExternalPid>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            pid()
+        }
+    }
+
+    impl Arbitrary for ExternalReference {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<ExternalReference>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            reference()
+        }
+    }
+
+    impl Arbitrary for BigInt {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<BigInt>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            bigint()
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod unstructured {
+    use super::{GenerateOptions, MAX_ATOM_CHARS, MAX_FUN_INDEX};
+    use crate::borrowed::BorrowedTerm;
+    use crate::term::OwnedTerm;
+    use crate::types::{
+        Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
+    };
+    use arbitrary::{Arbitrary, Result, Unstructured};
+    use bytes::Bytes;
+    use std::collections::BTreeMap;
+
+    /// Leaf kinds that always round-trip, followed by those that may not.
+    const ROUND_TRIPPABLE_LEAVES: usize = 12;
+    const ALL_LEAVES: usize = 14;
+    const CONTAINERS: usize = 4;
+
+    /// Builds a term from fuzzer input, nested up to `options.max_depth` levels deep.
+    pub fn arbitrary_term(
+        u: &mut Unstructured<'_>,
+        options: &GenerateOptions,
+    ) -> Result<OwnedTerm> {
+        term_at(u, options, 0)
+    }
+
+    fn term_at(
+        u: &mut Unstructured<'_>,
+        options: &GenerateOptions,
+        depth: u32,
+    ) -> Result<OwnedTerm> {
+        let leaves = if options.round_trippable {
+            ROUND_TRIPPABLE_LEAVES
+        } else {
+            ALL_LEAVES
+        };
+        let containers = if depth < options.max_depth {
+            CONTAINERS
+        } else {
+            0
+        };
+        let kind = u.choose_index(leaves + containers)?;
+        if kind < leaves {
+            leaf(u, options, kind)
+        } else {
+            container(u, options, depth + 1, kind - leaves)
+        }
+    }
+
+    fn leaf(u: &mut Unstructured<'_>, options: &GenerateOptions, kind: usize) -> Result<OwnedTerm> {
+        Ok(match kind {
+            0 => {
+                if options.round_trippable {
+                    OwnedTerm::Integer(i64::from(u.arbitrary::<i32>()?))
+                } else {
+                    OwnedTerm::Integer(u.arbitrary()?)
+                }
+            }
+            1 => {
+                let value: f64 = u.arbitrary()?;
+                if options.round_trippable && !value.is_finite() {
+                    OwnedTerm::Float(0.0)
+                } else {
+                    OwnedTerm::Float(value)
+                }
+            }
+            2 => OwnedTerm::Atom(atom(u)?),
+            3 => OwnedTerm::Binary(binary(u, options)?),
+            4 => {
+                let mut bytes = binary(u, options)?;
+                if bytes.is_empty() {
+                    bytes.push(u.arbitrary()?);
+                }
+                let max_bits = if options.round_trippable { 7 } else { 8 };
+                OwnedTerm::BitBinary {
+                    bytes,
+                    bits: u.int_in_range(1..=max_bits)?,
+                }
+            }
+            5 => OwnedTerm::BigInt(options.shape_bigint(bigint(u)?)),
+            6 => OwnedTerm::Pid(pid(u)?),
+            7 => OwnedTerm::Port(ExternalPort::new(atom(u)?, u.arbitrary()?, u.arbitrary()?)),
+            8 => OwnedTerm::Reference(reference(u)?),
+            9 => OwnedTerm::ExternalFun(ExternalFun::new(atom(u)?, atom(u)?, u.arbitrary()?)),
+            10 => OwnedTerm::InternalFun(Box::new(internal_fun(u, options)?)),
+            11 => OwnedTerm::Nil,
+            12 => OwnedTerm::String(u.arbitrary()?),
+            _ => OwnedTerm::SharedBinary(Bytes::from(binary(u, options)?)),
+        })
+    }
+
+    fn container(
+        u: &mut Unstructured<'_>,
+        options: &GenerateOptions,
+        depth: u32,
+        kind: usize,
+    ) -> Result<OwnedTerm> {
+        let min_list_len = options.min_list_len();
+        Ok(match kind {
+            0 => {
+                let len = u.int_in_range(min_list_len..=options.max_len.max(min_list_len))?;
+                OwnedTerm::List(elements(u, options, depth, len)?)
+            }
+            1 => {
+                let len = u.int_in_range(0..=options.max_len)?;
+                OwnedTerm::Tuple(elements(u, options, depth, len)?)
+            }
+            2 => {
+                let len = u.int_in_range(1..=options.max_len.max(1))?;
+                let elements = elements(u, options, depth, len)?;
+                let mut tail = term_at(u, options, depth)?;
+                if options.round_trippable && matches!(tail, OwnedTerm::Nil | OwnedTerm::List(_)) {
+                    tail = OwnedTerm::Atom(Atom::new(Atom::UNDEFINED));
+                }
+                OwnedTerm::ImproperList {
+                    elements,
+                    tail: Box::new(tail),
+                }
+            }
+            _ => {
+                let len = u.int_in_range(0..=options.max_len)?;
+                let mut map = BTreeMap::new();
+                for _ in 0..len {
+                    let key = term_at(u, options, depth)?;
+                    let value = term_at(u, options, depth)?;
+                    map.insert(key, value);
+                }
+                OwnedTerm::Map(map)
+            }
+        })
+    }
+
+    fn elements(
+        u: &mut Unstructured<'_>,
+        options: &GenerateOptions,
+        depth: u32,
+        len: usize,
+    ) -> Result<Vec<OwnedTerm>> {
+        (0..len).map(|_| term_at(u, options, depth)).collect()
+    }
+
+    fn binary(u: &mut Unstructured<'_>, options: &GenerateOptions) -> Result<Vec<u8>> {
+        let len = u.arbitrary_len::<u8>()?.min(options.max_binary_len);
+        Ok(u.bytes(len)?.to_vec())
+    }
+
+    fn atom(u: &mut Unstructured<'_>) -> Result<Atom> {
+        let name: String = u.arbitrary()?;
+        Ok(Atom::new(
+            name.chars().take(MAX_ATOM_CHARS).collect::<String>(),
+        ))
+    }
+
+    fn bigint(u: &mut Unstructured<'_>) -> Result<BigInt> {
+        let negative: bool = u.arbitrary()?;
+        let len = u.int_in_range(1..=16)?;
+        let digits = (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<Result<Vec<u8>>>()?;
+        Ok(BigInt::new(negative, digits))
+    }
+
+    fn pid(u: &mut Unstructured<'_>) -> Result<ExternalPid> {
+        Ok(ExternalPid::new(
+            atom(u)?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        ))
+    }
+
+    fn reference(u: &mut Unstructured<'_>) -> Result<ExternalReference> {
+        let len = u.int_in_range(1..=5)?;
+        let ids = (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<Result<Vec<u32>>>()?;
+        Ok(ExternalReference::new(atom(u)?, u.arbitrary()?, ids))
+    }
+
+    fn internal_fun(u: &mut Unstructured<'_>, options: &GenerateOptions) -> Result<InternalFun> {
+        let num_free = u.int_in_range(0..=options.max_len.min(4))?;
+        let free_vars = (0..num_free)
+            .map(|_| {
+                let kind = [0, 2, 3][u.choose_index(3)?];
+                leaf(u, options, kind)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(InternalFun::new(
+            u.arbitrary()?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+            num_free as u32,
+            atom(u)?,
+            u.int_in_range(0..=MAX_FUN_INDEX)?,
+            u.int_in_range(0..=MAX_FUN_INDEX)?,
+            pid(u)?,
+            free_vars,
+        ))
+    }
+
+    impl<'a> Arbitrary<'a> for OwnedTerm {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            arbitrary_term(u, &GenerateOptions::default())
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for BorrowedTerm<'a> {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            arbitrary_term(u, &GenerateOptions::default()).map(BorrowedTerm::from)
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Atom {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            atom(u)
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for ExternalPid {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            pid(u)
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for ExternalReference {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            reference(u)
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for BigInt {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            bigint(u)
+        }
+    }
+}
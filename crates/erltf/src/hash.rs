@@ -0,0 +1,424 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Portable term hashing compatible with `erlang:phash2/1,2`.
+//!
+//! [`phash2`] and [`phash2_range`] reproduce the `make_hash2` algorithm of
+//! the Erlang runtime, so a Rust node and an Erlang node pick the same bucket
+//! for the same key. The hash only depends on the value of a term: maps hash
+//! the same regardless of key order, a [`OwnedTerm::SharedBinary`] hashes like
+//! a [`OwnedTerm::Binary`], and integers hash the same whether they are stored
+//! as [`OwnedTerm::Integer`] or [`OwnedTerm::BigInt`].
+//!
+//! ```
+//! use erltf::hash::{phash2, phash2_range};
+//! use erltf::{OwnedTerm, erl_atom};
+//!
+//! assert_eq!(phash2(&erl_atom!("a")), 97);
+//! assert_eq!(phash2(&OwnedTerm::Nil), 113427502);
+//! assert!(phash2_range(&erl_atom!("a"), 16) < 16);
+//! ```
+
+use crate::term::OwnedTerm;
+use crate::types::{Atom, BigInt};
+
+const HCONST: u32 = 0x9e37_79b9;
+const HCONST_2: u32 = HCONST.wrapping_mul(2);
+const HCONST_3: u32 = HCONST.wrapping_mul(3);
+const HCONST_4: u32 = HCONST.wrapping_mul(4);
+const HCONST_5: u32 = HCONST.wrapping_mul(5);
+const HCONST_6: u32 = HCONST.wrapping_mul(6);
+const HCONST_7: u32 = HCONST.wrapping_mul(7);
+const HCONST_9: u32 = HCONST.wrapping_mul(9);
+const HCONST_10: u32 = HCONST.wrapping_mul(10);
+const HCONST_11: u32 = HCONST.wrapping_mul(11);
+const HCONST_12: u32 = HCONST.wrapping_mul(12);
+const HCONST_13: u32 = HCONST.wrapping_mul(13);
+const HCONST_14: u32 = HCONST.wrapping_mul(14);
+const HCONST_15: u32 = HCONST.wrapping_mul(15);
+const HCONST_16: u32 = HCONST.wrapping_mul(16);
+const HCONST_19: u32 = HCONST.wrapping_mul(19);
+
+/// The runtime's type tag for `[]`, mixed in when nil is not the first term hashed.
+const NIL_DEF: u32 = 2;
+/// The hash of `[]` on its own: the result of mixing `NIL_DEF` into a zero hash.
+const NIL_HASH: u32 = 3_468_870_702;
+
+/// Returns `erlang:phash2(Term)`: the term's hash in the range `0..2^27`.
+pub fn phash2(term: &OwnedTerm) -> u32 {
+    make_hash2(term) & ((1 << 27) - 1)
+}
+
+/// Returns `erlang:phash2(Term, Range)`: the term's hash in the range `0..range`.
+///
+/// # Panics
+///
+/// Panics if `range` is zero, for which `erlang:phash2/2` raises `badarg`.
+/// Use [`make_hash2`] for the `Range = 1 bsl 32` case.
+pub fn phash2_range(term: &OwnedTerm, range: u32) -> u32 {
+    assert!(range > 0, "phash2 range must be positive");
+    make_hash2(term) % range
+}
+
+/// Returns the full 32-bit hash, equal to `erlang:phash2(Term, 1 bsl 32)`.
+pub fn make_hash2(term: &OwnedTerm) -> u32 {
+    let mut hasher = Hasher {
+        hash: 0,
+        xor_pairs: 0,
+        stack: Vec::new(),
+    };
+    hasher.run(Work::Term(term));
+    hasher.hash
+}
+
+/// Returns the hash the runtime keeps in its atom table: `hashpjw` over the
+/// atom's name, with two-byte UTF-8 sequences folded to Latin-1 code points.
+pub fn atom_hash(atom: &Atom) -> u32 {
+    let name = atom.as_str().as_bytes();
+    let mut h: u32 = 0;
+    let mut i = 0;
+    while i < name.len() {
+        let mut v = name[i] as u32;
+        i += 1;
+        if i < name.len() && (v & 0xfe) == 0xc2 && (name[i] & 0xc0) == 0x80 {
+            v = ((v << 6) | (name[i] as u32 & 0x3f)) & 0xff;
+            i += 1;
+        }
+        h = (h << 4).wrapping_add(v);
+        let g = h & 0xf000_0000;
+        if g != 0 {
+            h ^= g >> 24;
+            h ^= g;
+        }
+    }
+    h
+}
+
+/// Pending work, mirroring the runtime's explicit stack.
+enum Work<'a> {
+    Term(&'a OwnedTerm),
+    /// The remaining cells of a list, never empty, and its tail (`None` for `[]`)
+    List(&'a [OwnedTerm], Option<&'a OwnedTerm>),
+    /// Folds the hash of one key-value pair into the map's pair hashes
+    MapPair,
+    /// Restores the outer hash once all pairs of a map are hashed
+    MapTail {
+        hash: u32,
+        xor_pairs: u32,
+    },
+}
+
+struct Hasher<'a> {
+    hash: u32,
+    xor_pairs: u32,
+    stack: Vec<Work<'a>>,
+}
+
+impl<'a> Hasher<'a> {
+    fn run(&mut self, mut current: Work<'a>) {
+        loop {
+            if let Some(next) = self.step(current) {
+                current = next;
+                continue;
+            }
+            loop {
+                match self.stack.pop() {
+                    None => return,
+                    Some(Work::MapPair) => {
+                        self.xor_pairs ^= self.hash;
+                        self.hash = 0;
+                    }
+                    Some(Work::MapTail { hash, xor_pairs }) => {
+                        self.hash = hash;
+                        self.uint32_hash(self.xor_pairs, HCONST_19);
+                        self.xor_pairs = xor_pairs;
+                    }
+                    Some(work) => {
+                        current = work;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Hashes one item. Returns the item to continue with directly, or `None`
+    /// when the next item comes from the stack.
+    fn step(&mut self, work: Work<'a>) -> Option<Work<'a>> {
+        let term = match work {
+            Work::Term(term) => term,
+            Work::List(elements, tail) => return self.list(elements, tail),
+            Work::MapPair | Work::MapTail { .. } => unreachable!("handled by the caller"),
+        };
+        match term {
+            OwnedTerm::Atom(atom) => {
+                let value = atom_hash(atom);
+                if self.hash == 0 {
+                    self.hash = value;
+                } else {
+                    self.uint32_hash(value, HCONST_3);
+                }
+            }
+            OwnedTerm::Nil => self.nil(),
+            OwnedTerm::Integer(value) => self.integer(*value),
+            OwnedTerm::BigInt(big) => self.big_int(big),
+            OwnedTerm::Float(value) => {
+                let value = if *value == 0.0 { 0.0f64 } else { *value };
+                let bits = value.to_bits();
+                self.uint32_hash_2((bits >> 32) as u32, bits as u32, HCONST_12);
+            }
+            OwnedTerm::Binary(bytes) => self.binary(bytes, 0, 0),
+            OwnedTerm::SharedBinary(bytes) => self.binary(bytes, 0, 0),
+            OwnedTerm::String(s) => self.binary(s.as_bytes(), 0, 0),
+            OwnedTerm::BitBinary { bytes, bits } => match bytes.split_last() {
+                Some((&last, whole)) if *bits < 8 => self.binary(whole, *bits, last),
+                _ => self.binary(bytes, 0, 0),
+            },
+            OwnedTerm::Pid(pid) => self.uint32_hash(pid.id, HCONST_5),
+            OwnedTerm::Port(port) => {
+                self.uint32_hash_2(port.id as u32, (port.id >> 32) as u32, HCONST_6)
+            }
+            OwnedTerm::Reference(reference) => {
+                let first = reference.ids.first().copied().unwrap_or(0);
+                self.uint32_hash(first, HCONST_7);
+            }
+            OwnedTerm::Tuple(elements) => {
+                self.uint32_hash(elements.len() as u32, HCONST_9);
+                if let Some((first, rest)) = elements.split_first() {
+                    self.stack.extend(rest.iter().rev().map(Work::Term));
+                    return Some(Work::Term(first));
+                }
+            }
+            OwnedTerm::List(elements) => {
+                if elements.is_empty() {
+                    self.nil();
+                } else {
+                    return Some(Work::List(elements, None));
+                }
+            }
+            OwnedTerm::ImproperList { elements, tail } => {
+                if elements.is_empty() {
+                    return Some(Work::Term(tail));
+                }
+                return Some(Work::List(elements, Some(tail)));
+            }
+            OwnedTerm::Map(map) => {
+                self.uint32_hash(map.len() as u32, HCONST_16);
+                if !map.is_empty() {
+                    // Pairs are hashed independently of each other and combined
+                    // with xor, so that the order of keys does not matter
+                    self.stack.push(Work::MapTail {
+                        hash: self.hash,
+                        xor_pairs: self.xor_pairs,
+                    });
+                    self.hash = 0;
+                    self.xor_pairs = 0;
+                    for (key, value) in map.iter().rev() {
+                        self.stack.push(Work::MapPair);
+                        self.stack.push(Work::Term(value));
+                        self.stack.push(Work::Term(key));
+                    }
+                }
+            }
+            OwnedTerm::ExternalFun(fun) => {
+                self.uint32_hash_2(fun.arity as u32, atom_hash(&fun.module), HCONST);
+                self.uint32_hash(atom_hash(&fun.function), HCONST_14);
+            }
+            OwnedTerm::InternalFun(fun) => {
+                let free_vars = &fun.free_vars;
+                self.uint32_hash_2(free_vars.len() as u32, atom_hash(&fun.module), HCONST);
+                self.uint32_hash_2(fun.index, fun.old_uniq, HCONST);
+                if let Some((first, rest)) = free_vars.split_first() {
+                    self.stack.extend(rest.iter().rev().map(Work::Term));
+                    return Some(Work::Term(first));
+                }
+            }
+        }
+        None
+    }
+
+    /// Hashes runs of byte-sized integers four at a time, the way the runtime
+    /// speeds up strings, and continues with the first element that is not a byte.
+    fn list(&mut self, elements: &'a [OwnedTerm], tail: Option<&'a OwnedTerm>) -> Option<Work<'a>> {
+        let mut count = 0;
+        let mut packed: u32 = 0;
+        let mut bytes = 0;
+        for element in elements {
+            let OwnedTerm::Integer(byte @ 0..=255) = element else {
+                break;
+            };
+            packed = (packed << 8).wrapping_add(*byte as u32);
+            if count == 3 {
+                self.uint32_hash(packed, HCONST_4);
+                count = 0;
+                packed = 0;
+            } else {
+                count += 1;
+            }
+            bytes += 1;
+        }
+        if count > 0 {
+            self.uint32_hash(packed, HCONST_4);
+        }
+
+        match elements.get(bytes) {
+            Some(head) => {
+                let rest = &elements[bytes + 1..];
+                self.stack.push(match (rest.is_empty(), tail) {
+                    (false, _) => Work::List(rest, tail),
+                    (true, Some(tail)) => Work::Term(tail),
+                    (true, None) => Work::Term(&OwnedTerm::Nil),
+                });
+                Some(Work::Term(head))
+            }
+            None => match tail {
+                Some(tail) => Some(Work::Term(tail)),
+                None => {
+                    self.nil();
+                    None
+                }
+            },
+        }
+    }
+
+    fn nil(&mut self) {
+        if self.hash == 0 {
+            self.hash = NIL_HASH;
+        } else {
+            self.uint32_hash(NIL_DEF, HCONST_2);
+        }
+    }
+
+    /// Integers that fit in 28 bits are hashed as immediates, larger ones as bignums.
+    fn integer(&mut self, value: i64) {
+        if (-(1 << 27)..(1 << 27)).contains(&value) {
+            let value = value as i32;
+            if value < 0 {
+                // The runtime mixes negative numbers twice
+                self.uint32_hash(value.unsigned_abs(), HCONST);
+            }
+            self.uint32_hash(value as u32, HCONST);
+        } else {
+            let magnitude = value.unsigned_abs();
+            let con = if value < 0 { HCONST_10 } else { HCONST_11 };
+            self.uint32_hash_2(magnitude as u32, (magnitude >> 32) as u32, con);
+        }
+    }
+
+    fn big_int(&mut self, big: &BigInt) {
+        let significant = big
+            .digits
+            .iter()
+            .rposition(|&d| d != 0)
+            .map_or(0, |i| i + 1);
+        let digits = &big.digits[..significant];
+
+        if digits.len() <= 8 {
+            let magnitude = digits
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &d| (acc << 8) | d as u64);
+            let small = if big.sign.is_negative() {
+                magnitude <= 1 << 27
+            } else {
+                magnitude < 1 << 27
+            };
+            if small {
+                let value = magnitude as i64;
+                self.integer(if big.sign.is_negative() {
+                    -value
+                } else {
+                    value
+                });
+                return;
+            }
+        }
+
+        let con = if big.sign.is_negative() {
+            HCONST_10
+        } else {
+            HCONST_11
+        };
+        for word in digits.chunks(8) {
+            let word = word
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &d| (acc << 8) | d as u64);
+            self.uint32_hash_2(word as u32, (word >> 32) as u32, con);
+        }
+    }
+
+    /// Hashes the whole bytes of a bitstring, then the `bits` high bits of `last`.
+    fn binary(&mut self, bytes: &[u8], bits: u8, last: u8) {
+        let con = HCONST_13.wrapping_add(self.hash);
+        if bytes.is_empty() && bits == 0 {
+            self.hash = con;
+            return;
+        }
+        self.hash = block_hash(bytes, con);
+        if bits > 0 {
+            self.uint32_hash_2(bits as u32, last as u32 >> (8 - bits), HCONST_15);
+        }
+    }
+
+    fn uint32_hash_2(&mut self, x: u32, y: u32, aconst: u32) {
+        let mut a = aconst.wrapping_add(x);
+        let mut b = aconst.wrapping_add(y);
+        mix(&mut a, &mut b, &mut self.hash);
+    }
+
+    fn uint32_hash(&mut self, x: u32, aconst: u32) {
+        self.uint32_hash_2(x, 0, aconst);
+    }
+}
+
+/// Bob Jenkins' `lookup2` mix of three 32-bit values.
+fn mix(a: &mut u32, b: &mut u32, c: &mut u32) {
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 13);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 8);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 13);
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 12);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 16);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 5);
+    *a = a.wrapping_sub(*b).wrapping_sub(*c) ^ (*c >> 3);
+    *b = b.wrapping_sub(*c).wrapping_sub(*a) ^ (*a << 10);
+    *c = c.wrapping_sub(*a).wrapping_sub(*b) ^ (*b >> 15);
+}
+
+/// Bob Jenkins' `lookup2` hash of a byte block, seeded with `initval`.
+fn block_hash(bytes: &[u8], initval: u32) -> u32 {
+    let word = |k: &[u8]| u32::from_le_bytes([k[0], k[1], k[2], k[3]]);
+    let mut a = HCONST;
+    let mut b = HCONST;
+    let mut c = initval;
+
+    let mut chunks = bytes.chunks_exact(12);
+    for k in &mut chunks {
+        a = a.wrapping_add(word(&k[0..4]));
+        b = b.wrapping_add(word(&k[4..8]));
+        c = c.wrapping_add(word(&k[8..12]));
+        mix(&mut a, &mut b, &mut c);
+    }
+
+    // The low byte of `c` is reserved for the length
+    let mut rest = [0u8; 12];
+    let remainder = chunks.remainder();
+    rest[..remainder.len()].copy_from_slice(remainder);
+    c = c.wrapping_add(bytes.len() as u32);
+    a = a.wrapping_add(word(&rest[0..4]));
+    b = b.wrapping_add(word(&rest[4..8]));
+    c = c.wrapping_add(word(&[0, rest[8], rest[9], rest[10]]));
+    mix(&mut a, &mut b, &mut c);
+    c
+}
@@ -12,24 +12,68 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "tokio")]
+pub mod async_io;
+pub mod atom_table;
+pub mod bits;
 pub mod borrowed;
+pub mod convert;
+pub mod cursor;
 pub mod decoder;
 pub mod encoder;
 pub mod errors;
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub mod generate;
+pub mod hash;
+pub mod parser;
+pub mod pretty;
+pub mod records;
+pub mod selector;
+pub mod streaming;
 pub mod tags;
 pub mod term;
 pub mod types;
+pub mod visit;
 
+pub use atom_table::{AtomTable, OverflowPolicy};
 pub use borrowed::BorrowedTerm;
-pub use decoder::{AtomCache, decode, decode_borrowed, decode_with_atom_cache};
+pub use convert::{FromTerm, IntoTerm, StringEncoding};
+pub use cursor::TermCursor;
+pub use decoder::{
+    AtomCache, DecodeOptions, SHARED_BINARY_THRESHOLD, decode, decode_borrowed,
+    decode_borrowed_with_atom_cache, decode_shared, decode_shared_with_atom_cache,
+    decode_shared_with_options, decode_shared_with_trailing, decode_with_atom_cache,
+    decode_with_options,
+};
 pub use encoder::{
-    encode, encode_to_writer, encode_with_dist_header, encode_with_dist_header_multi,
+    EncodeOptions, encode, encode_borrowed, encode_borrowed_to_writer,
+    encode_borrowed_with_options, encode_to_writer, encode_to_writer_with_options, encode_vectored,
+    encode_vectored_with_options, encode_with_dist_header, encode_with_dist_header_multi,
+    encode_with_options, encoded_size, encoded_size_with_dist_header,
 };
 pub use errors::{
-    ContextualDecodeError, DecodeError, EncodeError, Error, ParsingContext, PathSegment, Result,
+    AtomTableFull, BitSyntaxError, ContextualDecodeError, DecodeError, EncodeError, Error,
+    ParseError, ParseErrorKind, ParsingContext, PathSegment, Result, SelectorError,
+    SelectorErrorKind,
 };
-pub use term::{KeyValueAccess, OwnedTerm};
+pub use parser::{MAX_PARSE_DEPTH, parse, parse_with_node};
+pub use pretty::{PrettyOptions, pretty_print};
+pub use records::{RecordDef, RecordField, RecordRegistry, parse_records};
+pub use selector::Selector;
+pub use streaming::{StreamDecoder, StreamItem};
+pub use term::{Inspect, KeyValueAccess, KeyValueTerm, OwnedTerm};
 pub use types::{Atom, BigInt, ExternalPid, ExternalPort, ExternalReference, Mfa, Sign};
+pub use visit::{Subterms, TermVisitor, TermVisitorMut};
+
+#[cfg(feature = "tokio")]
+pub use async_io::{
+    decode_from_async_reader, decode_from_async_reader_with_options, encode_to_async_writer,
+    encode_to_async_writer_with_options,
+};
+#[cfg(feature = "derive")]
+pub use erltf_derive::{FromTerm, IntoTerm};
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub use generate::GenerateOptions;
 
 #[macro_export]
 macro_rules! erl_tuple {
@@ -76,3 +120,223 @@ macro_rules! erl_int {
         $crate::OwnedTerm::Integer($val as i64)
     };
 }
+
+/// Matches an [`OwnedTerm`] against an Erlang-like pattern and returns the
+/// bound variables: `None` if the term does not match, `Some(())` if the
+/// pattern binds nothing, `Some(var)` for one variable and a tuple of them
+/// in pattern order otherwise.
+///
+/// * `_` matches anything and `Name` binds the sub-term as `&OwnedTerm`. Like in Erlang,
+///   variables start with a capital letter or `_`; a lowercase name such as `ok` does not compile,
+///   and neither does a variable that appears twice
+/// * `:ok` and `:"$gen_call"` match atoms
+/// * `42`, `-1`, `1.5` and `true` match equal literals, `<<"text">>` matches a binary
+/// * `{A, B}` matches a tuple of exactly that arity
+/// * `[A, B]` matches a proper list of exactly that length, and `[]` the empty list
+/// * `[Head | Tail]` binds the remaining elements as `&[OwnedTerm]`
+/// * `#{:name => Name, 1 => _}` matches a map that has at least these keys
+///
+/// ```
+/// use erltf::{erl_atom, erl_int, erl_match, erl_tuple};
+/// use erltf::types::{ExternalPid, ExternalReference};
+/// use erltf::{Atom, OwnedTerm};
+///
+/// let pid = OwnedTerm::Pid(ExternalPid::new(Atom::new("a@host"), 1, 0, 1));
+/// let reference = OwnedTerm::Reference(ExternalReference::new(Atom::new("a@host"), 1, vec![1, 2, 3]));
+/// let message = erl_tuple![
+///     erl_atom!("$gen_call"),
+///     erl_tuple![pid.clone(), reference.clone()],
+///     erl_tuple![erl_atom!("get"), erl_int!(7)]
+/// ];
+///
+/// let (from, tag, key) = erl_match!(message, {:"$gen_call", {From, Tag}, {:get, Key}}).unwrap();
+/// assert_eq!((from, tag, key), (&pid, &reference, &erl_int!(7)));
+/// assert!(erl_match!(message, {:"$gen_cast", _}).is_none());
+/// ```
+///
+/// `ok` would silently bind any first element, so it is rejected in favor of `:ok`:
+///
+/// ```compile_fail
+/// use erltf::{erl_atom, erl_int, erl_match, erl_tuple};
+///
+/// let term = erl_tuple![erl_atom!("error"), erl_int!(1)];
+/// erl_match!(term, {ok, Value});
+/// ```
+///
+/// A repeated variable would not check that both sub-terms are equal, so it is rejected too:
+///
+/// ```compile_fail
+/// use erltf::{erl_int, erl_match, erl_tuple};
+///
+/// let term = erl_tuple![erl_int!(1), erl_int!(2)];
+/// erl_match!(term, {X, X});
+/// ```
+#[macro_export]
+macro_rules! erl_match {
+    // A single pattern matched against the term bound to `$t`
+    (@m $t:ident {$($body:tt)*} _) => {
+        { $($body)* }
+    };
+    (@m $t:ident {$($body:tt)*} : $a:ident) => {
+        if $t.is_atom_with_name(stringify!($a)) { $($body)* }
+    };
+    (@m $t:ident {$($body:tt)*} : $a:literal) => {
+        if $t.is_atom_with_name($a) { $($body)* }
+    };
+    (@m $t:ident {$($body:tt)*} << $s:literal >>) => {
+        if $t.as_binary() == Some($s.as_bytes()) { $($body)* }
+    };
+    (@m $t:ident {$($body:tt)*} $l:literal) => {
+        if *$t == $crate::OwnedTerm::from($l) { $($body)* }
+    };
+    (@m $t:ident {$($body:tt)*} $v:ident) => {{
+        $crate::erl_match!(@var $v);
+        #[allow(non_snake_case)]
+        let $v = $t;
+        $($body)*
+    }};
+    (@m $t:ident {$($body:tt)*} { $($elems:tt)* }) => {
+        if let $crate::OwnedTerm::Tuple(__elems) = $t {
+            $crate::erl_match!(@seq __elems (0usize) {$($body)*} [] $($elems)*);
+        }
+    };
+    (@m $t:ident {$($body:tt)*} [ $($elems:tt)* ]) => {{
+        let __list: Option<&[$crate::OwnedTerm]> = match $t {
+            $crate::OwnedTerm::List(elements) => Some(elements),
+            $crate::OwnedTerm::Nil => Some(&[]),
+            _ => None,
+        };
+        if let Some(__list) = __list {
+            $crate::erl_match!(@seq __list (0usize) {$($body)*} [] $($elems)*);
+        }
+    }};
+    (@m $t:ident {$($body:tt)*} # { $($entries:tt)* }) => {
+        if let $crate::OwnedTerm::Map(__map) = $t {
+            $crate::erl_match!(@map __map {$($body)*} $($entries)*);
+        }
+    };
+
+    // Comma-separated element patterns of a tuple or list
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} []) => {
+        if $elems.len() == $i { $($body)* }
+    };
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} [] | _) => {
+        { $($body)* }
+    };
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} [] | $tail:ident) => {{
+        $crate::erl_match!(@var $tail);
+        #[allow(non_snake_case)]
+        let $tail = &$elems[$i..];
+        $($body)*
+    }};
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} [$($cur:tt)+]) => {
+        $crate::erl_match!(@seq $elems ($i) {$($body)*} [$($cur)+] ,)
+    };
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} [$($cur:tt)+] | $($tail:tt)+) => {
+        if let Some(__element) = $elems.get($i) {
+            $crate::erl_match!(@m __element {
+                $crate::erl_match!(@seq $elems ($i + 1) {$($body)*} [] | $($tail)+);
+            } $($cur)+);
+        }
+    };
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} [$($cur:tt)+] , $($rest:tt)*) => {
+        if let Some(__element) = $elems.get($i) {
+            $crate::erl_match!(@m __element {
+                $crate::erl_match!(@seq $elems ($i + 1) {$($body)*} [] $($rest)*);
+            } $($cur)+);
+        }
+    };
+    (@seq $elems:ident ($i:expr) {$($body:tt)*} [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erl_match!(@seq $elems ($i) {$($body)*} [$($cur)* $next] $($rest)*)
+    };
+
+    // `key => pattern` entries of a map
+    (@map $m:ident {$($body:tt)*}) => {
+        { $($body)* }
+    };
+    (@map $m:ident {$($body:tt)*} , $($rest:tt)*) => {
+        $crate::erl_match!(@map $m {$($body)*} $($rest)*)
+    };
+    (@map $m:ident {$($body:tt)*} : $k:ident => $($rest:tt)*) => {
+        $crate::erl_match!(@value $m ($crate::OwnedTerm::atom(stringify!($k))) {$($body)*} [] $($rest)*)
+    };
+    (@map $m:ident {$($body:tt)*} : $k:literal => $($rest:tt)*) => {
+        $crate::erl_match!(@value $m ($crate::OwnedTerm::atom($k)) {$($body)*} [] $($rest)*)
+    };
+    (@map $m:ident {$($body:tt)*} << $k:literal >> => $($rest:tt)*) => {
+        $crate::erl_match!(@value $m ($crate::OwnedTerm::Binary($k.as_bytes().to_vec())) {$($body)*} [] $($rest)*)
+    };
+    (@map $m:ident {$($body:tt)*} $k:literal => $($rest:tt)*) => {
+        $crate::erl_match!(@value $m ($crate::OwnedTerm::from($k)) {$($body)*} [] $($rest)*)
+    };
+    (@value $m:ident ($key:expr) {$($body:tt)*} [$($cur:tt)+]) => {
+        $crate::erl_match!(@value $m ($key) {$($body)*} [$($cur)+] ,)
+    };
+    (@value $m:ident ($key:expr) {$($body:tt)*} [$($cur:tt)+] , $($rest:tt)*) => {
+        if let Some(__value) = $m.get(&$key) {
+            $crate::erl_match!(@m __value {
+                $crate::erl_match!(@map $m {$($body)*} $($rest)*);
+            } $($cur)+);
+        }
+    };
+    (@value $m:ident ($key:expr) {$($body:tt)*} [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erl_match!(@value $m ($key) {$($body)*} [$($cur)* $next] $($rest)*)
+    };
+
+    // Rejects lowercase names, which in Erlang would be atoms
+    (@var $v:ident) => {
+        const _: () = assert!(
+            matches!(stringify!($v).as_bytes()[0], b'A'..=b'Z' | b'_'),
+            concat!(
+                "erl_match! variables start with a capital letter or `_`, use `:",
+                stringify!($v),
+                "` to match an atom"
+            )
+        );
+    };
+
+    // Collects the variables a pattern binds, in order
+    (@vars $term:tt [$($pattern:tt)*] [$($var:ident)*]) => {{
+        // A variable bound twice is rejected like a repeated closure parameter
+        #[allow(non_snake_case, unused_variables)]
+        let _ = |$($var: ()),*| {};
+        let __term: &$crate::OwnedTerm = &$term;
+        // Irrefutable patterns never reach the final `None`
+        #[allow(unreachable_code)]
+        let __bindings = 'matched: {
+            $crate::erl_match!(@m __term {
+                break 'matched Some($crate::erl_match!(@bindings $($var),*));
+            } $($pattern)*);
+            None
+        };
+        __bindings
+    }};
+    (@vars $term:tt $pattern:tt [$($var:ident)*] _ $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)*] $($rest)*)
+    };
+    (@vars $term:tt $pattern:tt [$($var:ident)*] : $a:tt $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)*] $($rest)*)
+    };
+    (@vars $term:tt $pattern:tt [$($var:ident)*] $l:literal $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)*] $($rest)*)
+    };
+    (@vars $term:tt $pattern:tt [$($var:ident)*] $v:ident $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)* $v] $($rest)*)
+    };
+    (@vars $term:tt $pattern:tt [$($var:ident)*] { $($inner:tt)* } $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)*] $($inner)* $($rest)*)
+    };
+    (@vars $term:tt $pattern:tt [$($var:ident)*] [ $($inner:tt)* ] $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)*] $($inner)* $($rest)*)
+    };
+    (@vars $term:tt $pattern:tt [$($var:ident)*] $other:tt $($rest:tt)*) => {
+        $crate::erl_match!(@vars $term $pattern [$($var)*] $($rest)*)
+    };
+
+    (@bindings $var:ident) => { $var };
+    (@bindings $($var:ident),*) => { ($($var),*) };
+
+    ($term:expr, $($pattern:tt)+) => {
+        $crate::erl_match!(@vars ($term) [$($pattern)+] [] $($pattern)+)
+    };
+}
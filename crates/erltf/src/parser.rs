@@ -0,0 +1,776 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A parser for terms written in Erlang syntax.
+//!
+//! Accepts what an Erlang shell prints: atoms, integers (including bignums,
+//! `16#FF` and `$c`), floats, strings (as charlists), binaries, tuples,
+//! lists, maps, external funs, and `<0.1.0>`, `#Ref<...>` and `#Port<...>`
+//! literals. A trailing `.` is allowed. Terms nested more than
+//! [`MAX_PARSE_DEPTH`] levels deep are rejected.
+//!
+//! Pids, references and ports have no node in their printed form, so
+//! they are created on the node passed to [`parse_with_node`]
+//! (`nonode@nohost` for [`parse`]) with creation 0.
+
+use crate::errors::{ParseError, ParseErrorKind};
+use crate::term::OwnedTerm;
+use crate::types::{Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+const DEFAULT_NODE: &str = "nonode@nohost";
+const MAX_ATOM_CHARS: usize = 255;
+/// How deeply tuples, lists and maps may nest before parsing fails
+/// instead of overflowing the stack.
+pub const MAX_PARSE_DEPTH: usize = 256;
+
+/// Parses a single term written in Erlang syntax.
+///
+/// ```
+/// use erltf::OwnedTerm;
+///
+/// let term = erltf::parse("{ok, [1, <<\"bin\">>, #{a => 1}]}").unwrap();
+/// assert_eq!(term.tuple_get(0), Some(&OwnedTerm::atom("ok")));
+/// ```
+pub fn parse(input: &str) -> Result<OwnedTerm, ParseError> {
+    parse_with_node(input, Atom::new(DEFAULT_NODE))
+}
+
+/// Like [`parse`] but creates pids, references and ports on `node`.
+pub fn parse_with_node(input: &str, node: Atom) -> Result<OwnedTerm, ParseError> {
+    let mut parser = Parser::new(input, node);
+    parser.skip_whitespace();
+    let term = parser.parse_term()?;
+    parser.skip_whitespace();
+    if parser.peek() == Some('.') {
+        parser.bump();
+        parser.skip_whitespace();
+    }
+    match parser.peek() {
+        None => Ok(term),
+        Some(c) => Err(parser.error(ParseErrorKind::UnexpectedChar(c))),
+    }
+}
+
+impl FromStr for OwnedTerm {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    node: Atom,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str, node: Atom) -> Self {
+        Parser {
+            input,
+            pos: 0,
+            node,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.pos, kind)
+    }
+
+    fn error_at(&self, pos: usize, kind: ParseErrorKind) -> ParseError {
+        let consumed = &self.input[..pos];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        let column = consumed[line_start..].chars().count() + 1;
+        ParseError::new(kind, line, column)
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(ParseErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.input[self.pos..].chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, expected: &str) -> bool {
+        if self.input[self.pos..].starts_with(expected) {
+            self.pos += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '%' {
+                while let Some(c) = self.bump() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<OwnedTerm, ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_PARSE_DEPTH {
+            return Err(self.error(ParseErrorKind::DepthLimitExceeded {
+                max: MAX_PARSE_DEPTH,
+            }));
+        }
+        let term = self.parse_nested_term();
+        self.depth -= 1;
+        term
+    }
+
+    fn parse_nested_term(&mut self) -> Result<OwnedTerm, ParseError> {
+        self.skip_whitespace();
+        let Some(c) = self.peek() else {
+            return Err(self.error(ParseErrorKind::UnexpectedEof));
+        };
+
+        match c {
+            '{' => self.parse_tuple(),
+            '[' => self.parse_list(),
+            '"' => self.parse_string(),
+            '\'' => self.parse_atom().map(OwnedTerm::Atom),
+            '#' => self.parse_hash(),
+            '<' if self.peek_nth(1) == Some('<') => self.parse_binary(),
+            '<' => self.parse_pid(),
+            '$' => self.parse_char(false),
+            '-' | '+' => self.parse_signed(),
+            '0'..='9' => self.parse_number(false),
+            c if c.is_lowercase() => self.parse_unquoted_atom_or_fun(),
+            c if c.is_uppercase() || c == '_' => {
+                let start = self.pos;
+                let name = self.take_identifier();
+                Err(self.error_at(start, ParseErrorKind::UnexpectedVariable(name.to_string())))
+            }
+            c => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+        }
+    }
+
+    fn parse_signed(&mut self) -> Result<OwnedTerm, ParseError> {
+        let negative = self.bump() == Some('-');
+        self.skip_whitespace();
+        match self.peek() {
+            Some('0'..='9') => self.parse_number(negative),
+            Some('$') => self.parse_char(negative),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn take_identifier(&mut self) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' || c == '@' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse_unquoted_atom_or_fun(&mut self) -> Result<OwnedTerm, ParseError> {
+        let start = self.pos;
+        let name = self.take_identifier();
+        if name == "fun" {
+            return self.parse_external_fun();
+        }
+        self.make_atom(start, name)
+    }
+
+    fn make_atom(&self, start: usize, name: &str) -> Result<OwnedTerm, ParseError> {
+        let size = name.chars().count();
+        if size > MAX_ATOM_CHARS {
+            return Err(self.error_at(start, ParseErrorKind::AtomTooLarge { size }));
+        }
+        Ok(OwnedTerm::Atom(Atom::new(name)))
+    }
+
+    fn parse_atom(&mut self) -> Result<Atom, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let term = match self.peek() {
+            Some('\'') => {
+                let name = self.parse_quoted('\'')?;
+                self.make_atom(start, &name)?
+            }
+            Some(c) if c.is_lowercase() => {
+                let name = self.take_identifier();
+                self.make_atom(start, name)?
+            }
+            _ => return Err(self.unexpected()),
+        };
+        match term {
+            OwnedTerm::Atom(atom) => Ok(atom),
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_external_fun(&mut self) -> Result<OwnedTerm, ParseError> {
+        let module = self.parse_atom()?;
+        self.expect(':')?;
+        let function = self.parse_atom()?;
+        self.expect('/')?;
+        self.skip_whitespace();
+        let start = self.pos;
+        let arity = self.take_digits(10);
+        let arity = arity
+            .parse::<u8>()
+            .map_err(|_| self.error_at(start, ParseErrorKind::InvalidLiteral("fun")))?;
+        Ok(OwnedTerm::ExternalFun(ExternalFun::new(
+            module, function, arity,
+        )))
+    }
+
+    fn parse_tuple(&mut self) -> Result<OwnedTerm, ParseError> {
+        self.bump();
+        let elements = self.parse_sequence('}')?;
+        Ok(OwnedTerm::Tuple(elements))
+    }
+
+    fn parse_sequence(&mut self, close: char) -> Result<Vec<OwnedTerm>, ParseError> {
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.eat(close) {
+            return Ok(elements);
+        }
+        loop {
+            elements.push(self.parse_term()?);
+            self.skip_whitespace();
+            if self.eat(',') {
+                continue;
+            }
+            if self.eat(close) {
+                return Ok(elements);
+            }
+            return Err(self.unexpected());
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<OwnedTerm, ParseError> {
+        self.bump();
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(OwnedTerm::Nil);
+        }
+        loop {
+            elements.push(self.parse_term()?);
+            self.skip_whitespace();
+            if self.eat(',') {
+                continue;
+            }
+            if self.eat('|') {
+                let tail = self.parse_term()?;
+                self.expect(']')?;
+                return Ok(match tail {
+                    OwnedTerm::Nil => OwnedTerm::List(elements),
+                    OwnedTerm::List(rest) => {
+                        elements.extend(rest);
+                        OwnedTerm::List(elements)
+                    }
+                    OwnedTerm::ImproperList {
+                        elements: rest,
+                        tail,
+                    } => {
+                        elements.extend(rest);
+                        OwnedTerm::ImproperList { elements, tail }
+                    }
+                    tail => OwnedTerm::improper_list(elements, tail),
+                });
+            }
+            if self.eat(']') {
+                return Ok(OwnedTerm::List(elements));
+            }
+            return Err(self.unexpected());
+        }
+    }
+
+    fn parse_hash(&mut self) -> Result<OwnedTerm, ParseError> {
+        let start = self.pos;
+        self.bump();
+        if self.eat('{') {
+            return self.parse_map_body();
+        }
+        if self.eat_str("Ref<") {
+            let parts = self.parse_dotted_numbers('>')?;
+            if parts.len() < 2 || parts[0] != 0 {
+                return Err(self.error_at(start, ParseErrorKind::InvalidLiteral("reference")));
+            }
+            let ids = parts[1..]
+                .iter()
+                .rev()
+                .map(|&id| u32::try_from(id))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| self.error_at(start, ParseErrorKind::InvalidLiteral("reference")))?;
+            return Ok(OwnedTerm::Reference(ExternalReference::new(
+                self.node.clone(),
+                0,
+                ids,
+            )));
+        }
+        if self.eat_str("Port<") {
+            let parts = self.parse_dotted_numbers('>')?;
+            if parts.len() != 2 || parts[0] != 0 {
+                return Err(self.error_at(start, ParseErrorKind::InvalidLiteral("port")));
+            }
+            return Ok(OwnedTerm::Port(ExternalPort::new(
+                self.node.clone(),
+                parts[1],
+                0,
+            )));
+        }
+        Err(self.unexpected())
+    }
+
+    fn parse_map_body(&mut self) -> Result<OwnedTerm, ParseError> {
+        let mut map = BTreeMap::new();
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(OwnedTerm::Map(map));
+        }
+        loop {
+            let key = self.parse_term()?;
+            self.skip_whitespace();
+            if !self.eat_str("=>") {
+                return Err(self.unexpected());
+            }
+            let value = self.parse_term()?;
+            map.insert(key, value);
+            self.skip_whitespace();
+            if self.eat(',') {
+                continue;
+            }
+            if self.eat('}') {
+                return Ok(OwnedTerm::Map(map));
+            }
+            return Err(self.unexpected());
+        }
+    }
+
+    fn parse_pid(&mut self) -> Result<OwnedTerm, ParseError> {
+        let start = self.pos;
+        self.bump();
+        let parts = self.parse_dotted_numbers('>')?;
+        if parts.len() != 3 || parts[0] != 0 {
+            return Err(self.error_at(start, ParseErrorKind::InvalidLiteral("pid")));
+        }
+        let id = u32::try_from(parts[1]);
+        let serial = u32::try_from(parts[2]);
+        match (id, serial) {
+            (Ok(id), Ok(serial)) => Ok(OwnedTerm::Pid(ExternalPid::new(
+                self.node.clone(),
+                id,
+                serial,
+                0,
+            ))),
+            _ => Err(self.error_at(start, ParseErrorKind::InvalidLiteral("pid"))),
+        }
+    }
+
+    fn parse_dotted_numbers(&mut self, close: char) -> Result<Vec<u64>, ParseError> {
+        let mut parts = Vec::new();
+        loop {
+            let start = self.pos;
+            let digits = self.take_digits(10);
+            let value = digits
+                .parse::<u64>()
+                .map_err(|_| self.error_at(start, ParseErrorKind::InvalidInteger(digits.into())))?;
+            parts.push(value);
+            if self.eat('.') {
+                continue;
+            }
+            if self.eat(close) {
+                return Ok(parts);
+            }
+            return Err(self.unexpected());
+        }
+    }
+
+    fn take_digits(&mut self, radix: u32) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_digit(radix) || (c == '_' && self.pos > start) {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse_number(&mut self, negative: bool) -> Result<OwnedTerm, ParseError> {
+        let start = self.pos;
+        let digits = self.take_digits(10);
+
+        if self.peek() == Some('#') {
+            self.bump();
+            let radix = digits
+                .replace('_', "")
+                .parse::<u32>()
+                .ok()
+                .filter(|r| (2..=36).contains(r))
+                .ok_or_else(|| {
+                    self.error_at(start, ParseErrorKind::InvalidInteger(digits.into()))
+                })?;
+            let value = self.take_digits(radix);
+            if value.is_empty() {
+                return Err(self.unexpected());
+            }
+            return self.make_integer(start, negative, value, radix);
+        }
+
+        if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+            self.take_digits(10);
+            if matches!(self.peek(), Some('e' | 'E')) {
+                self.bump();
+                if matches!(self.peek(), Some('+' | '-')) {
+                    self.bump();
+                }
+                self.take_digits(10);
+            }
+            let text = self.input[start..self.pos].replace('_', "");
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| self.error_at(start, ParseErrorKind::InvalidFloat(text.clone())))?;
+            return Ok(OwnedTerm::Float(if negative { -value } else { value }));
+        }
+
+        self.make_integer(start, negative, digits, 10)
+    }
+
+    fn make_integer(
+        &self,
+        start: usize,
+        negative: bool,
+        digits: &str,
+        radix: u32,
+    ) -> Result<OwnedTerm, ParseError> {
+        if digits.ends_with('_') || digits.contains("__") {
+            return Err(self.error_at(start, ParseErrorKind::InvalidInteger(digits.into())));
+        }
+
+        let mut magnitude: Vec<u8> = Vec::new();
+        for c in digits.chars().filter(|&c| c != '_') {
+            let mut carry = c.to_digit(radix).unwrap_or(0);
+            for byte in magnitude.iter_mut() {
+                let value = (*byte as u32) * radix + carry;
+                *byte = value as u8;
+                carry = value >> 8;
+            }
+            while carry > 0 {
+                magnitude.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        Ok(integer_from_magnitude(negative, magnitude))
+    }
+
+    fn parse_char(&mut self, negative: bool) -> Result<OwnedTerm, ParseError> {
+        self.bump();
+        let c = match self.bump() {
+            Some('\\') => self.parse_escape()?,
+            Some(c) => c,
+            None => return Err(self.error(ParseErrorKind::UnexpectedEof)),
+        };
+        let value = c as i64;
+        Ok(OwnedTerm::Integer(if negative { -value } else { value }))
+    }
+
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        let start = self.pos;
+        let Some(c) = self.bump() else {
+            return Err(self.error(ParseErrorKind::UnexpectedEof));
+        };
+        let escaped = match c {
+            'b' => '\u{8}',
+            'd' => '\u{7f}',
+            'e' => '\u{1b}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            's' => ' ',
+            't' => '\t',
+            'v' => '\u{b}',
+            '^' => {
+                let c = self
+                    .bump()
+                    .ok_or_else(|| self.error(ParseErrorKind::UnexpectedEof))?;
+                char::from_u32((c as u32) & 0x1f)
+                    .ok_or_else(|| self.error_at(start, ParseErrorKind::InvalidEscape))?
+            }
+            'x' => {
+                let hex = if self.eat('{') {
+                    let hex = self.take_digits(16);
+                    if !self.eat('}') {
+                        return Err(self.error_at(start, ParseErrorKind::InvalidEscape));
+                    }
+                    hex
+                } else {
+                    let hex_start = self.pos;
+                    for _ in 0..2 {
+                        if self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                            self.bump();
+                        }
+                    }
+                    &self.input[hex_start..self.pos]
+                };
+                u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error_at(start, ParseErrorKind::InvalidEscape))?
+            }
+            '0'..='7' => {
+                let mut value = c.to_digit(8).unwrap_or(0);
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) => {
+                            value = value * 8 + d;
+                            self.bump();
+                        }
+                        None => break,
+                    }
+                }
+                char::from_u32(value)
+                    .ok_or_else(|| self.error_at(start, ParseErrorKind::InvalidEscape))?
+            }
+            other => other,
+        };
+        Ok(escaped)
+    }
+
+    fn parse_quoted(&mut self, quote: char) -> Result<String, ParseError> {
+        self.bump();
+        let mut result = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == quote => return Ok(result),
+                Some('\\') => result.push(self.parse_escape()?),
+                Some(c) => result.push(c),
+                None => return Err(self.error(ParseErrorKind::UnexpectedEof)),
+            }
+        }
+    }
+
+    /// Parses one or more adjacent string literals, like the Erlang compiler does.
+    fn parse_adjacent_strings(&mut self) -> Result<String, ParseError> {
+        let mut result = self.parse_quoted('"')?;
+        loop {
+            let checkpoint = self.pos;
+            self.skip_whitespace();
+            if self.peek() == Some('"') {
+                result.push_str(&self.parse_quoted('"')?);
+            } else {
+                self.pos = checkpoint;
+                return Ok(result);
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<OwnedTerm, ParseError> {
+        let s = self.parse_adjacent_strings()?;
+        if s.is_empty() {
+            Ok(OwnedTerm::Nil)
+        } else {
+            Ok(OwnedTerm::charlist(s))
+        }
+    }
+
+    fn parse_binary(&mut self) -> Result<OwnedTerm, ParseError> {
+        self.pos += 2;
+        let mut bytes = Vec::new();
+        self.skip_whitespace();
+        if self.eat_str(">>") {
+            return Ok(OwnedTerm::Binary(bytes));
+        }
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.peek() {
+                Some('"') => {
+                    let s = self.parse_adjacent_strings()?;
+                    match self.parse_segment_type()? {
+                        Some("utf8") => bytes.extend_from_slice(s.as_bytes()),
+                        Some("latin1") | None => {
+                            for c in s.chars() {
+                                let b = u8::try_from(c as u32).map_err(|_| {
+                                    self.error_at(
+                                        start,
+                                        ParseErrorKind::InvalidBinarySegment(format!(
+                                            "character '{}' does not fit in a byte, use /utf8",
+                                            c
+                                        )),
+                                    )
+                                })?;
+                                bytes.push(b);
+                            }
+                        }
+                        Some(other) => {
+                            return Err(self.error_at(
+                                start,
+                                ParseErrorKind::InvalidBinarySegment(other.to_string()),
+                            ));
+                        }
+                    }
+                }
+                Some('0'..='9' | '-' | '+' | '$') => {
+                    let value = match self.parse_term()? {
+                        OwnedTerm::Integer(i) => i,
+                        _ => {
+                            return Err(self.error_at(
+                                start,
+                                ParseErrorKind::InvalidBinarySegment("integer expected".into()),
+                            ));
+                        }
+                    };
+                    let bits = self.parse_segment_size()?;
+                    if let Some(other) = self.parse_segment_type()? {
+                        return Err(self.error_at(
+                            start,
+                            ParseErrorKind::InvalidBinarySegment(other.to_string()),
+                        ));
+                    }
+                    // Like Erlang, a segment keeps the low bits of the value
+                    if bits < 8 {
+                        self.skip_whitespace();
+                        if !self.eat_str(">>") {
+                            return Err(self.error_at(
+                                start,
+                                ParseErrorKind::InvalidBinarySegment(
+                                    "only the last segment can be smaller than 8 bits".into(),
+                                ),
+                            ));
+                        }
+                        let value = (value as u8) & ((1u8 << bits) - 1);
+                        bytes.push(value << (8 - bits));
+                        return Ok(OwnedTerm::BitBinary { bytes, bits });
+                    }
+                    bytes.push(value as u8);
+                }
+                _ => return Err(self.unexpected()),
+            }
+            self.skip_whitespace();
+            if self.eat(',') {
+                continue;
+            }
+            if self.eat_str(">>") {
+                return Ok(OwnedTerm::Binary(bytes));
+            }
+            return Err(self.unexpected());
+        }
+    }
+
+    fn parse_segment_size(&mut self) -> Result<u8, ParseError> {
+        self.skip_whitespace();
+        if !self.eat(':') {
+            return Ok(8);
+        }
+        self.skip_whitespace();
+        let start = self.pos;
+        let digits = self.take_digits(10);
+        match digits.parse::<u8>() {
+            Ok(bits @ 1..=8) => Ok(bits),
+            _ => Err(self.error_at(
+                start,
+                ParseErrorKind::InvalidBinarySegment(format!(
+                    "unsupported segment size {}",
+                    digits
+                )),
+            )),
+        }
+    }
+
+    fn parse_segment_type(&mut self) -> Result<Option<&'a str>, ParseError> {
+        self.skip_whitespace();
+        if self.eat('/') {
+            self.skip_whitespace();
+            let type_name = self.take_identifier();
+            if type_name.is_empty() {
+                return Err(self.unexpected());
+            }
+            Ok(Some(type_name))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn integer_from_magnitude(negative: bool, mut magnitude: Vec<u8>) -> OwnedTerm {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+
+    if magnitude.len() <= 8 {
+        let mut value = 0u64;
+        for (i, &byte) in magnitude.iter().enumerate() {
+            value |= (byte as u64) << (i * 8);
+        }
+        if !negative && value <= i64::MAX as u64 {
+            return OwnedTerm::Integer(value as i64);
+        }
+        if negative && value <= i64::MAX as u64 + 1 {
+            return OwnedTerm::Integer((value as i64).wrapping_neg());
+        }
+    }
+
+    OwnedTerm::BigInt(BigInt::new(negative, magnitude))
+}
@@ -0,0 +1,508 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A pretty printer that renders terms the way `io_lib:format("~p", [Term])` does.
+//!
+//! Unlike the `Display` implementation of [`OwnedTerm`], the output is valid
+//! Erlang syntax and can be parsed back with [`crate::parse`]. Internal funs
+//! are the exception: like in Erlang, they print as `#Fun<...>`.
+//!
+//! Setting a depth limit truncates the output like `~P` does,
+//! replacing the elided parts with `...`.
+
+use crate::term::OwnedTerm;
+use crate::types::Atom;
+use std::fmt::{self, Write};
+
+pub const DEFAULT_LINE_WIDTH: usize = 80;
+
+const RESERVED_WORDS: &[&str] = &[
+    "after", "and", "andalso", "band", "begin", "bnot", "bor", "bsl", "bsr", "bxor", "case",
+    "catch", "cond", "div", "else", "end", "fun", "if", "let", "maybe", "not", "of", "or",
+    "orelse", "receive", "rem", "try", "when", "xor",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Containers that do not fit into this many columns are broken across lines.
+    pub line_width: usize,
+    /// The `~P` depth limit, `None` means unlimited.
+    pub depth: Option<usize>,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        PrettyOptions {
+            line_width: DEFAULT_LINE_WIDTH,
+            depth: None,
+        }
+    }
+}
+
+impl PrettyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_line_width(mut self, line_width: usize) -> Self {
+        self.line_width = line_width;
+        self
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+}
+
+/// Renders a term in Erlang syntax.
+///
+/// ```
+/// use erltf::{PrettyOptions, erl_atom, erl_int, erl_list, erl_tuple, pretty_print};
+///
+/// let term = erl_tuple![erl_atom!("ok"), erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]];
+/// assert_eq!(pretty_print(&term, &PrettyOptions::default()), "{ok,[1,2,3]}");
+/// assert_eq!(pretty_print(&term, &PrettyOptions::new().with_depth(4)), "{ok,[1|...]}");
+/// ```
+pub fn pretty_print(term: &OwnedTerm, options: &PrettyOptions) -> String {
+    let mut printer = Printer {
+        out: String::new(),
+        column: 0,
+        line_width: options.line_width,
+    };
+    printer.layout(term, options.depth, 0);
+    printer.out
+}
+
+type Depth = Option<usize>;
+
+enum Item<'a> {
+    Term(&'a OwnedTerm, Depth),
+    Pair(&'a OwnedTerm, &'a OwnedTerm, Depth),
+    Text(String),
+    Ellipsis,
+}
+
+struct Container<'a> {
+    open: &'static str,
+    close: &'static str,
+    items: Vec<Item<'a>>,
+    tail: Option<Item<'a>>,
+}
+
+/// The depth of the `index`-th element of a container printed at `depth`,
+/// or `None` if the element and everything after it is elided.
+fn element_depth(depth: Depth, index: usize) -> Option<Depth> {
+    match depth {
+        None => Some(None),
+        Some(d) => match d.saturating_sub(index + 1) {
+            0 => None,
+            d => Some(Some(d)),
+        },
+    }
+}
+
+fn container(term: &OwnedTerm, depth: Depth) -> Option<Container<'_>> {
+    let (open, close) = match term {
+        OwnedTerm::Tuple(elements) if !elements.is_empty() => ("{", "}"),
+        OwnedTerm::List(elements) if printable_charlist(elements).is_none() => ("[", "]"),
+        OwnedTerm::ImproperList { .. } => ("[", "]"),
+        OwnedTerm::Map(map) if !map.is_empty() => ("#{", "}"),
+        OwnedTerm::Binary(bytes) if !bytes.is_empty() && printable_binary(bytes).is_none() => {
+            ("<<", ">>")
+        }
+        OwnedTerm::SharedBinary(bytes)
+            if !bytes.is_empty() && printable_binary(bytes).is_none() =>
+        {
+            ("<<", ">>")
+        }
+        OwnedTerm::BitBinary { bytes, .. } if !bytes.is_empty() => ("<<", ">>"),
+        _ => return None,
+    };
+
+    let mut c = Container {
+        open,
+        close,
+        items: Vec::new(),
+        tail: None,
+    };
+    if depth == Some(1) {
+        c.items.push(Item::Ellipsis);
+        return Some(c);
+    }
+
+    match term {
+        OwnedTerm::Tuple(elements) => {
+            for (i, element) in elements.iter().enumerate() {
+                match element_depth(depth, i) {
+                    Some(d) => c.items.push(Item::Term(element, d)),
+                    None => {
+                        c.items.push(Item::Ellipsis);
+                        break;
+                    }
+                }
+            }
+        }
+        OwnedTerm::List(elements) => push_list_items(&mut c, elements, None, depth),
+        OwnedTerm::ImproperList { elements, tail } => {
+            push_list_items(&mut c, elements, Some(tail), depth)
+        }
+        OwnedTerm::Map(map) => {
+            for (i, (key, value)) in map.iter().enumerate() {
+                match element_depth(depth, i) {
+                    Some(d) => c.items.push(Item::Pair(key, value, d)),
+                    None => {
+                        c.items.push(Item::Ellipsis);
+                        break;
+                    }
+                }
+            }
+        }
+        OwnedTerm::Binary(bytes) => push_byte_items(&mut c, bytes, 8, depth),
+        OwnedTerm::SharedBinary(bytes) => push_byte_items(&mut c, bytes, 8, depth),
+        OwnedTerm::BitBinary { bytes, bits } => push_byte_items(&mut c, bytes, *bits, depth),
+        _ => unreachable!(),
+    }
+    Some(c)
+}
+
+fn push_list_items<'a>(
+    c: &mut Container<'a>,
+    elements: &'a [OwnedTerm],
+    tail: Option<&'a OwnedTerm>,
+    depth: Depth,
+) {
+    for (i, element) in elements.iter().enumerate() {
+        match element_depth(depth, i) {
+            Some(d) => c.items.push(Item::Term(element, d)),
+            None => {
+                c.tail = Some(Item::Ellipsis);
+                return;
+            }
+        }
+    }
+    if let Some(tail) = tail {
+        c.tail = Some(match element_depth(depth, elements.len()) {
+            Some(d) => Item::Term(tail, d),
+            None => Item::Ellipsis,
+        });
+    }
+}
+
+fn push_byte_items(c: &mut Container<'_>, bytes: &[u8], last_bits: u8, depth: Depth) {
+    for (i, &byte) in bytes.iter().enumerate() {
+        if element_depth(depth, i).is_none() {
+            c.items.push(Item::Ellipsis);
+            return;
+        }
+        let text = if i + 1 == bytes.len() && last_bits < 8 {
+            format!("{}:{}", byte >> (8 - last_bits), last_bits)
+        } else {
+            byte.to_string()
+        };
+        c.items.push(Item::Text(text));
+    }
+}
+
+fn write_flat<W: Write>(w: &mut W, term: &OwnedTerm, depth: Depth) -> fmt::Result {
+    if depth == Some(0) {
+        return w.write_str("...");
+    }
+    match container(term, depth) {
+        Some(c) => write_container_flat(w, &c),
+        None => write_simple(w, term),
+    }
+}
+
+fn write_container_flat<W: Write>(w: &mut W, c: &Container<'_>) -> fmt::Result {
+    w.write_str(c.open)?;
+    for (i, item) in c.items.iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write_item_flat(w, item)?;
+    }
+    if let Some(tail) = &c.tail {
+        w.write_char('|')?;
+        write_item_flat(w, tail)?;
+    }
+    w.write_str(c.close)
+}
+
+fn write_item_flat<W: Write>(w: &mut W, item: &Item<'_>) -> fmt::Result {
+    match item {
+        Item::Term(term, depth) => write_flat(w, term, *depth),
+        Item::Pair(key, value, depth) => {
+            write_flat(w, key, *depth)?;
+            w.write_str(" => ")?;
+            write_flat(w, value, *depth)
+        }
+        Item::Text(text) => w.write_str(text),
+        Item::Ellipsis => w.write_str("..."),
+    }
+}
+
+fn write_simple<W: Write>(w: &mut W, term: &OwnedTerm) -> fmt::Result {
+    match term {
+        OwnedTerm::Atom(atom) => write_atom(w, atom),
+        OwnedTerm::Integer(i) => write!(w, "{}", i),
+        OwnedTerm::Float(f) => write_float(w, *f),
+        OwnedTerm::BigInt(big) => write!(w, "{}", big),
+        OwnedTerm::Binary(bytes) => write_binary(w, bytes),
+        OwnedTerm::SharedBinary(bytes) => write_binary(w, bytes),
+        OwnedTerm::String(s) => write_binary(w, s.as_bytes()),
+        OwnedTerm::BitBinary { .. } => w.write_str("<<>>"),
+        OwnedTerm::List(elements) => match printable_charlist(elements) {
+            Some(s) => write_quoted(w, &s, '"'),
+            None => w.write_str("[]"),
+        },
+        OwnedTerm::Nil => w.write_str("[]"),
+        OwnedTerm::Tuple(_) => w.write_str("{}"),
+        OwnedTerm::Map(_) => w.write_str("#{}"),
+        OwnedTerm::Pid(pid) => write!(w, "<0.{}.{}>", pid.id, pid.serial),
+        OwnedTerm::Port(port) => write!(w, "#Port<0.{}>", port.id),
+        OwnedTerm::Reference(reference) => {
+            w.write_str("#Ref<0")?;
+            for id in reference.ids.iter().rev() {
+                write!(w, ".{}", id)?;
+            }
+            w.write_char('>')
+        }
+        OwnedTerm::ExternalFun(fun) => {
+            w.write_str("fun ")?;
+            write_atom(w, &fun.module)?;
+            w.write_char(':')?;
+            write_atom(w, &fun.function)?;
+            write!(w, "/{}", fun.arity)
+        }
+        OwnedTerm::InternalFun(fun) => {
+            w.write_str("#Fun<")?;
+            write_atom(w, &fun.module)?;
+            write!(w, ".{}.{}>", fun.old_index, fun.old_uniq)
+        }
+        OwnedTerm::ImproperList { .. } => unreachable!(),
+    }
+}
+
+fn write_atom<W: Write>(w: &mut W, atom: &Atom) -> fmt::Result {
+    if atom_needs_quotes(&atom.name) {
+        write_quoted(w, &atom.name, '\'')
+    } else {
+        w.write_str(&atom.name)
+    }
+}
+
+fn is_latin1_lowercase(c: char) -> bool {
+    c.is_ascii_lowercase() || (('\u{df}'..='\u{ff}').contains(&c) && c != '\u{f7}')
+}
+
+fn atom_needs_quotes(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if is_latin1_lowercase(c) => {}
+        _ => return true,
+    }
+    let valid = chars.all(|c| {
+        c == '_' || c == '@' || ((c as u32) < 256 && c.is_alphanumeric() && c != '\u{aa}')
+    });
+    !valid || RESERVED_WORDS.contains(&name)
+}
+
+fn write_quoted<W: Write>(w: &mut W, s: &str, quote: char) -> fmt::Result {
+    w.write_char(quote)?;
+    for c in s.chars() {
+        match c {
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            '\u{b}' => w.write_str("\\v")?,
+            '\u{8}' => w.write_str("\\b")?,
+            '\u{c}' => w.write_str("\\f")?,
+            '\u{1b}' => w.write_str("\\e")?,
+            '\u{7f}' => w.write_str("\\d")?,
+            c if c == quote => {
+                w.write_char('\\')?;
+                w.write_char(c)?;
+            }
+            c if c.is_control() => write!(w, "\\x{{{:X}}}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char(quote)
+}
+
+pub(crate) fn write_float<W: Write>(w: &mut W, f: f64) -> fmt::Result {
+    let s = format!("{:?}", f);
+    // Erlang requires a fractional part before the exponent: 1.0e300, not 1e300
+    match s.find('e') {
+        Some(pos) if !s[..pos].contains('.') => write!(w, "{}.0{}", &s[..pos], &s[pos..]),
+        _ => w.write_str(&s),
+    }
+}
+
+fn write_binary<W: Write>(w: &mut W, bytes: &[u8]) -> fmt::Result {
+    match printable_binary(bytes) {
+        Some((s, true)) => {
+            w.write_str("<<")?;
+            write_quoted(w, s, '"')?;
+            w.write_str("/utf8>>")
+        }
+        Some((s, false)) => {
+            w.write_str("<<")?;
+            write_quoted(w, s, '"')?;
+            w.write_str(">>")
+        }
+        None => w.write_str("<<>>"),
+    }
+}
+
+fn is_printable_char(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\t' | '\u{b}' | '\u{8}' | '\u{c}' | '\u{1b}'
+    ) || !c.is_control()
+}
+
+/// Returns the string a printable binary holds and whether it needs a `/utf8` suffix.
+fn printable_binary(bytes: &[u8]) -> Option<(&str, bool)> {
+    let s = std::str::from_utf8(bytes).ok()?;
+    if s.is_empty() || !s.chars().all(is_printable_char) {
+        return None;
+    }
+    Some((s, !s.is_ascii()))
+}
+
+fn printable_charlist(elements: &[OwnedTerm]) -> Option<String> {
+    if elements.is_empty() {
+        return None;
+    }
+    elements
+        .iter()
+        .map(|e| match e {
+            OwnedTerm::Integer(i) => u32::try_from(*i)
+                .ok()
+                .and_then(char::from_u32)
+                .filter(|&c| is_printable_char(c)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A writer that gives up once more than `budget` characters have been written,
+/// so checking whether a large term fits on a line stays cheap.
+struct Bounded {
+    budget: usize,
+}
+
+impl Write for Bounded {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.chars().count();
+        if n > self.budget {
+            return Err(fmt::Error);
+        }
+        self.budget -= n;
+        Ok(())
+    }
+}
+
+struct Printer {
+    out: String,
+    column: usize,
+    line_width: usize,
+}
+
+impl Printer {
+    fn push(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.column += s.chars().count();
+    }
+
+    fn newline(&mut self, indent: usize) {
+        self.out.push('\n');
+        self.out.extend(std::iter::repeat_n(' ', indent));
+        self.column = indent;
+    }
+
+    fn fits(&self, term: &OwnedTerm, depth: Depth, trailing: usize) -> bool {
+        let budget = self
+            .line_width
+            .saturating_sub(self.column)
+            .saturating_sub(trailing);
+        write_flat(&mut Bounded { budget }, term, depth).is_ok()
+    }
+
+    fn layout(&mut self, term: &OwnedTerm, depth: Depth, trailing: usize) {
+        if depth != Some(0)
+            && !self.fits(term, depth, trailing)
+            && let Some(c) = container(term, depth)
+            && c.items.len() + usize::from(c.tail.is_some()) > 1
+        {
+            self.layout_container(&c, trailing);
+            return;
+        }
+        let mut s = String::new();
+        let _ = write_flat(&mut s, term, depth);
+        self.push(&s);
+    }
+
+    fn layout_container(&mut self, c: &Container<'_>, trailing: usize) {
+        self.push(c.open);
+        // Like io_lib_pretty, tagged tuples keep their tag on the first line:
+        // {ok,[...],
+        //     [...]}
+        let tagged = c.open == "{"
+            && c.items.len() > 1
+            && matches!(c.items[0], Item::Term(OwnedTerm::Atom(_), _));
+        if tagged {
+            self.layout_item(&c.items[0], 1);
+            self.push(",");
+        }
+        let indent = self.column;
+        let last = c.items.len() - 1;
+        let skip = usize::from(tagged);
+        for (i, item) in c.items.iter().enumerate().skip(skip) {
+            if i > skip {
+                self.push(",");
+                self.newline(indent);
+            }
+            let item_trailing = if i == last && c.tail.is_none() {
+                c.close.len() + trailing
+            } else {
+                1
+            };
+            self.layout_item(item, item_trailing);
+        }
+        if let Some(tail) = &c.tail {
+            self.push("|");
+            self.layout_item(tail, c.close.len() + trailing);
+        }
+        self.push(c.close);
+    }
+
+    fn layout_item(&mut self, item: &Item<'_>, trailing: usize) {
+        match item {
+            Item::Term(term, depth) => self.layout(term, *depth, trailing),
+            Item::Pair(key, value, depth) => {
+                let mut s = String::new();
+                let _ = write_flat(&mut s, key, *depth);
+                self.push(&s);
+                self.push(" => ");
+                self.layout(value, *depth, trailing);
+            }
+            Item::Text(text) => self.push(text),
+            Item::Ellipsis => self.push("..."),
+        }
+    }
+}
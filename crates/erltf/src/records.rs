@@ -0,0 +1,544 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Record definitions parsed from Erlang header (`.hrl`) files.
+//!
+//! Records travel over the wire as tagged tuples: `#user{name = <<"joe">>, age = 42}`
+//! is `{user, <<"joe">>, 42}`. A [`RecordRegistry`] knows the field names of
+//! each record and converts between those tuples and maps keyed by field name.
+//!
+//! ```
+//! use erltf::{OwnedTerm, RecordRegistry};
+//!
+//! let registry = RecordRegistry::from_hrl(
+//!     "-record(user, {name :: binary(), age = 0 :: non_neg_integer()}).",
+//! )
+//! .unwrap();
+//!
+//! let record = erltf::parse("{user, <<\"joe\">>, 42}").unwrap();
+//! let map = registry.to_map(&record).unwrap();
+//! assert_eq!(map.map_get_atom_key("age"), Some(&OwnedTerm::integer(42)));
+//! assert_eq!(registry.from_map("user", &map).unwrap(), record);
+//! ```
+
+use crate::errors::{ParseError, ParseErrorKind, TermConversionError};
+use crate::parser::parse;
+use crate::term::OwnedTerm;
+use crate::types::Atom;
+use std::collections::BTreeMap;
+
+/// A single record field and its default value.
+///
+/// `default` is `None` for fields without a default and for defaults that are
+/// expressions rather than literal terms (function calls, macros, nested
+/// record constructors). Such fields are `undefined` in new records,
+/// matching what Erlang does for fields without a default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordField {
+    pub name: Atom,
+    pub default: Option<OwnedTerm>,
+}
+
+/// The definition of a record: its name and fields in declaration order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordDef {
+    pub name: Atom,
+    pub fields: Vec<RecordField>,
+}
+
+impl RecordDef {
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        RecordDef {
+            name: Atom::new(name),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn with_field<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.fields.push(RecordField {
+            name: Atom::new(name),
+            default: None,
+        });
+        self
+    }
+
+    pub fn with_default<S: AsRef<str>>(mut self, name: S, default: OwnedTerm) -> Self {
+        self.fields.push(RecordField {
+            name: Atom::new(name),
+            default: Some(default),
+        });
+        self
+    }
+
+    /// The size of the record tuple, including the tag.
+    pub fn arity(&self) -> usize {
+        self.fields.len() + 1
+    }
+
+    /// The position of `field` in the record tuple, counting the tag as 0.
+    pub fn field_position(&self, field: &str) -> Option<usize> {
+        self.fields
+            .iter()
+            .position(|f| f.name == field)
+            .map(|index| index + 1)
+    }
+
+    /// Whether `term` is a tuple tagged with this record's name and of its arity.
+    pub fn matches(&self, term: &OwnedTerm) -> bool {
+        match term {
+            OwnedTerm::Tuple(elements) => {
+                elements.len() == self.arity()
+                    && matches!(&elements[0], OwnedTerm::Atom(tag) if *tag == self.name)
+            }
+            _ => false,
+        }
+    }
+
+    /// A record with every field set to its default, or `undefined`.
+    pub fn default_record(&self) -> OwnedTerm {
+        let mut elements = Vec::with_capacity(self.arity());
+        elements.push(OwnedTerm::Atom(self.name.clone()));
+        elements.extend(self.fields.iter().map(default_value));
+        OwnedTerm::Tuple(elements)
+    }
+
+    /// Converts a record tuple to a map from field name atoms to values.
+    pub fn to_map(&self, term: &OwnedTerm) -> Result<OwnedTerm, TermConversionError> {
+        let OwnedTerm::Tuple(elements) = term else {
+            return Err(TermConversionError::WrongType {
+                expected: "Tuple",
+                actual: term.type_name(),
+            });
+        };
+        if elements.len() != self.arity() {
+            return Err(TermConversionError::WrongArity {
+                expected: self.arity(),
+                actual: elements.len(),
+            });
+        }
+        match &elements[0] {
+            OwnedTerm::Atom(tag) if *tag == self.name => {}
+            other => return Err(TermConversionError::UnknownTag(other.to_string())),
+        }
+
+        let map = self
+            .fields
+            .iter()
+            .zip(&elements[1..])
+            .map(|(field, value)| (OwnedTerm::Atom(field.name.clone()), value.clone()))
+            .collect();
+        Ok(OwnedTerm::Map(map))
+    }
+
+    /// Builds a record tuple from a map keyed by field name atoms.
+    ///
+    /// Fields missing from the map take their default value. Keys that are
+    /// not fields of this record are rejected.
+    pub fn from_map(&self, map: &OwnedTerm) -> Result<OwnedTerm, TermConversionError> {
+        let OwnedTerm::Map(entries) = map else {
+            return Err(TermConversionError::WrongType {
+                expected: "Map",
+                actual: map.type_name(),
+            });
+        };
+
+        let OwnedTerm::Tuple(mut elements) = self.default_record() else {
+            unreachable!("default_record always returns a tuple")
+        };
+        for (key, value) in entries {
+            let position = match key {
+                OwnedTerm::Atom(name) => self.field_position(name),
+                _ => None,
+            };
+            let Some(position) = position else {
+                return Err(TermConversionError::UnknownField(key.to_string()));
+            };
+            elements[position] = value.clone();
+        }
+        Ok(OwnedTerm::Tuple(elements))
+    }
+}
+
+fn default_value(field: &RecordField) -> OwnedTerm {
+    field
+        .default
+        .clone()
+        .unwrap_or_else(|| OwnedTerm::atom(Atom::UNDEFINED))
+}
+
+/// A set of record definitions, looked up by record name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordRegistry {
+    records: BTreeMap<Atom, RecordDef>,
+}
+
+impl RecordRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with every record declared in `source`.
+    pub fn from_hrl(source: &str) -> Result<Self, ParseError> {
+        let mut registry = Self::new();
+        registry.add_hrl(source)?;
+        Ok(registry)
+    }
+
+    /// Adds every record declared in `source`, replacing definitions with the same name.
+    pub fn add_hrl(&mut self, source: &str) -> Result<(), ParseError> {
+        for def in parse_records(source)? {
+            self.insert(def);
+        }
+        Ok(())
+    }
+
+    pub fn insert(&mut self, def: RecordDef) {
+        self.records.insert(def.name.clone(), def);
+    }
+
+    pub fn with_record(mut self, def: RecordDef) -> Self {
+        self.insert(def);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RecordDef> {
+        self.records.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RecordDef> {
+        self.records.values()
+    }
+
+    /// Finds the definition `term` is an instance of, if any.
+    pub fn lookup(&self, term: &OwnedTerm) -> Option<&RecordDef> {
+        let tag = term.tuple_get(0)?.as_atom()?;
+        self.records.get(tag).filter(|def| def.matches(term))
+    }
+
+    /// Converts a record tuple of any registered record to a field-name map.
+    pub fn to_map(&self, term: &OwnedTerm) -> Result<OwnedTerm, TermConversionError> {
+        match self.lookup(term) {
+            Some(def) => def.to_map(term),
+            None => match term.tuple_get(0) {
+                Some(tag) => Err(TermConversionError::UnknownTag(tag.to_string())),
+                None => Err(TermConversionError::WrongType {
+                    expected: "Tuple",
+                    actual: term.type_name(),
+                }),
+            },
+        }
+    }
+
+    /// Builds a `name` record from a field-name map.
+    pub fn from_map(&self, name: &str, map: &OwnedTerm) -> Result<OwnedTerm, TermConversionError> {
+        self.get(name)
+            .ok_or_else(|| TermConversionError::UnknownTag(name.to_string()))?
+            .from_map(map)
+    }
+
+    /// Replaces every registered record within `term`, at any depth, with a field-name map.
+    ///
+    /// Combine with [`OwnedTerm::to_map_recursive`] to also turn proplists into maps.
+    pub fn records_to_maps(&self, term: &OwnedTerm) -> OwnedTerm {
+        match term {
+            OwnedTerm::Tuple(elements) => match self.lookup(term) {
+                Some(def) => {
+                    let map = def
+                        .fields
+                        .iter()
+                        .zip(&elements[1..])
+                        .map(|(field, value)| {
+                            (
+                                OwnedTerm::Atom(field.name.clone()),
+                                self.records_to_maps(value),
+                            )
+                        })
+                        .collect();
+                    OwnedTerm::Map(map)
+                }
+                None => {
+                    OwnedTerm::Tuple(elements.iter().map(|e| self.records_to_maps(e)).collect())
+                }
+            },
+            OwnedTerm::List(elements) => {
+                OwnedTerm::List(elements.iter().map(|e| self.records_to_maps(e)).collect())
+            }
+            OwnedTerm::ImproperList { elements, tail } => OwnedTerm::ImproperList {
+                elements: elements.iter().map(|e| self.records_to_maps(e)).collect(),
+                tail: Box::new(self.records_to_maps(tail)),
+            },
+            OwnedTerm::Map(entries) => OwnedTerm::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.clone(), self.records_to_maps(v)))
+                    .collect(),
+            ),
+            _ => term.clone(),
+        }
+    }
+}
+
+/// Parses every `-record(...)` declaration in Erlang header source.
+///
+/// Type annotations are skipped, as are all other attributes and forms.
+/// Default values are kept when they are literal terms.
+pub fn parse_records(source: &str) -> Result<Vec<RecordDef>, ParseError> {
+    let mut scanner = Scanner {
+        src: source,
+        pos: 0,
+    };
+    let mut records = Vec::new();
+    loop {
+        scanner.skip_trivia();
+        if scanner.rest().is_empty() {
+            return Ok(records);
+        }
+        if scanner.starts_with_keyword("-record") {
+            records.push(scanner.record()?);
+        } else {
+            scanner.skip_form()?;
+        }
+    }
+}
+
+struct Scanner<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        let consumed = &self.src[..self.pos];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        let column = consumed[line_start..].chars().count() + 1;
+        ParseError::new(kind, line, column)
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(ParseErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_trivia();
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_trivia(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '%' {
+                self.skip_comment();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn starts_with_keyword(&self, keyword: &str) -> bool {
+        self.rest()
+            .strip_prefix(keyword)
+            .is_some_and(|after| !after.starts_with(is_atom_char))
+    }
+
+    fn record(&mut self) -> Result<RecordDef, ParseError> {
+        self.pos += "-record".len();
+        self.expect('(')?;
+        self.skip_trivia();
+        let mut def = RecordDef::new(self.atom()?);
+        self.expect(',')?;
+        self.expect('{')?;
+
+        self.skip_trivia();
+        if self.peek() == Some('}') {
+            self.bump();
+        } else {
+            loop {
+                self.skip_trivia();
+                def.fields.push(self.field()?);
+                self.skip_trivia();
+                match self.peek() {
+                    Some(',') => {
+                        self.bump();
+                    }
+                    Some('}') => {
+                        self.bump();
+                        break;
+                    }
+                    _ => return Err(self.unexpected()),
+                }
+            }
+        }
+
+        self.expect(')')?;
+        self.expect('.')?;
+        Ok(def)
+    }
+
+    fn field(&mut self) -> Result<RecordField, ParseError> {
+        let name = Atom::new(self.atom()?);
+        self.skip_trivia();
+
+        let mut default = None;
+        if self.peek() == Some('=') {
+            self.bump();
+            let start = self.pos;
+            self.scan_balanced(|rest| is_field_end(rest) || rest.starts_with("::"))?;
+            default = parse(self.src[start..self.pos].trim()).ok();
+        }
+        if self.rest().starts_with("::") {
+            self.pos += 2;
+            self.scan_balanced(is_field_end)?;
+        }
+        Ok(RecordField { name, default })
+    }
+
+    fn atom(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some('\'') => self.quoted('\''),
+            Some(c) if c.is_ascii_lowercase() => {
+                let start = self.pos;
+                while self.peek().is_some_and(is_atom_char) {
+                    self.bump();
+                }
+                Ok(self.src[start..self.pos].to_string())
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn quoted(&mut self, delimiter: char) -> Result<String, ParseError> {
+        self.bump();
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some(c) => text.push(c),
+                    None => return Err(self.error(ParseErrorKind::UnexpectedEof)),
+                },
+                Some(c) if c == delimiter => return Ok(text),
+                Some(c) => text.push(c),
+                None => return Err(self.error(ParseErrorKind::UnexpectedEof)),
+            }
+        }
+    }
+
+    /// Advances to the first position outside brackets, strings and comments
+    /// where `stop` holds for the remaining input.
+    fn scan_balanced(&mut self, stop: impl Fn(&str) -> bool) -> Result<(), ParseError> {
+        let mut depth = 0usize;
+        loop {
+            let rest = self.rest();
+            let Some(c) = rest.chars().next() else {
+                return Err(self.error(ParseErrorKind::UnexpectedEof));
+            };
+            if depth == 0 && stop(rest) {
+                return Ok(());
+            }
+            match c {
+                '%' => self.skip_comment(),
+                '"' | '\'' => {
+                    self.quoted(c)?;
+                }
+                '$' => {
+                    self.bump();
+                    if self.bump() == Some('\\') {
+                        self.bump();
+                    }
+                }
+                '<' if rest.starts_with("<<") => {
+                    depth += 1;
+                    self.pos += 2;
+                }
+                '>' if depth > 0 && rest.starts_with(">>") => {
+                    depth -= 1;
+                    self.pos += 2;
+                }
+                '(' | '[' | '{' => {
+                    depth += 1;
+                    self.bump();
+                }
+                ')' | ']' | '}' => {
+                    if depth == 0 {
+                        return Err(self.unexpected());
+                    }
+                    depth -= 1;
+                    self.bump();
+                }
+                _ => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    fn skip_form(&mut self) -> Result<(), ParseError> {
+        self.scan_balanced(|rest| {
+            rest.strip_prefix('.').is_some_and(|after| {
+                after.is_empty() || after.starts_with(char::is_whitespace) || after.starts_with('%')
+            })
+        })?;
+        self.bump();
+        Ok(())
+    }
+}
+
+fn is_atom_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '@'
+}
+
+fn is_field_end(rest: &str) -> bool {
+    rest.starts_with(',') || rest.starts_with('}')
+}
@@ -0,0 +1,336 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Selectors for nested access to terms, in a JSONPath-like syntax.
+//!
+//! A selector is a sequence of steps, optionally preceded by `$`:
+//!
+//! * `.name` or `.'quoted name'` looks up an atom key in a map or a proplist
+//! * `#{Key}` looks up any key written in Erlang syntax, e.g. `#{<<"name">>}` or `#{1}`
+//! * `[2]` is the element at a zero-based index of a tuple or a list
+//! * `[*]` or `.*` are all elements of a tuple or a list, or all values of a map
+//!
+//! A selector that does not start with `$` may start with a bare key:
+//! `listeners[*].port` is the same as `$.listeners[*].port`.
+
+use crate::errors::{SelectorError, SelectorErrorKind, TermConversionError};
+use crate::parser::parse;
+use crate::term::OwnedTerm;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A parsed selector.
+///
+/// ```
+/// use erltf::{Selector, erl_atom, erl_int, erl_list, erl_tuple};
+///
+/// let status = erl_list![
+///     erl_tuple![erl_atom!("listeners"), erl_list![
+///         erl_tuple![erl_atom!("amqp"), erl_int!(5672)],
+///         erl_tuple![erl_atom!("http"), erl_int!(15672)]
+///     ]]
+/// ];
+/// let selector: Selector = "$.listeners[*][1]".parse().unwrap();
+/// let ports: Vec<i64> = selector.extract(&status).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(ports, vec![5672, 15672]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    steps: Arc<[Step]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Key(OwnedTerm),
+    Index(usize),
+    Wildcard,
+}
+
+impl Selector {
+    pub fn parse(input: &str) -> Result<Self, SelectorError> {
+        SelectorParser { input, pos: 0 }.parse()
+    }
+
+    /// All sub-terms of `term` the selector matches, in term order.
+    pub fn select<'a>(&self, term: &'a OwnedTerm) -> Matches<'a> {
+        Matches {
+            steps: Arc::clone(&self.steps),
+            stack: vec![(term, 0)],
+        }
+    }
+
+    /// The first sub-term of `term` the selector matches.
+    pub fn first<'a>(&self, term: &'a OwnedTerm) -> Option<&'a OwnedTerm> {
+        self.select(term).next()
+    }
+
+    /// The matches converted to `T`.
+    pub fn extract<'a, T>(&self, term: &'a OwnedTerm) -> Extract<'a, T>
+    where
+        T: TryFrom<OwnedTerm, Error = TermConversionError>,
+    {
+        self.select(term).extract()
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Selector::parse(s)
+    }
+}
+
+/// The sub-terms matched by a [`Selector`].
+#[derive(Debug, Clone)]
+pub struct Matches<'a> {
+    steps: Arc<[Step]>,
+    stack: Vec<(&'a OwnedTerm, usize)>,
+}
+
+impl<'a> Matches<'a> {
+    /// Converts every match to `T`.
+    pub fn extract<T>(self) -> Extract<'a, T>
+    where
+        T: TryFrom<OwnedTerm, Error = TermConversionError>,
+    {
+        Extract {
+            matches: self,
+            target: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = &'a OwnedTerm;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((term, depth)) = self.stack.pop() {
+            let Some(step) = self.steps.get(depth) else {
+                return Some(term);
+            };
+            let depth = depth + 1;
+            match step {
+                Step::Key(key) => {
+                    if let Some(value) = lookup(term, key) {
+                        self.stack.push((value, depth));
+                    }
+                }
+                Step::Index(index) => {
+                    if let Some(element) = elements(term).and_then(|e| e.get(*index)) {
+                        self.stack.push((element, depth));
+                    }
+                }
+                Step::Wildcard => match term {
+                    OwnedTerm::Map(map) => {
+                        self.stack.extend(map.values().rev().map(|v| (v, depth)));
+                    }
+                    _ => {
+                        let children = elements(term).unwrap_or_default();
+                        self.stack.extend(children.iter().rev().map(|e| (e, depth)));
+                    }
+                },
+            }
+        }
+        None
+    }
+}
+
+/// The matches of a [`Selector`] converted to `T`, see [`Matches::extract`].
+#[derive(Debug, Clone)]
+pub struct Extract<'a, T> {
+    matches: Matches<'a>,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<T> Iterator for Extract<'_, T>
+where
+    T: TryFrom<OwnedTerm, Error = TermConversionError>,
+{
+    type Item = Result<T, TermConversionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.matches.next().map(|term| T::try_from(term.clone()))
+    }
+}
+
+fn elements(term: &OwnedTerm) -> Option<&[OwnedTerm]> {
+    match term {
+        OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => Some(elements),
+        _ => None,
+    }
+}
+
+/// A map value, or the value of the first `{Key, Value}` pair of a proplist.
+fn lookup<'a>(term: &'a OwnedTerm, key: &OwnedTerm) -> Option<&'a OwnedTerm> {
+    match term {
+        OwnedTerm::Map(map) => map.get(key),
+        OwnedTerm::List(elements) => elements.iter().find_map(|element| match element {
+            OwnedTerm::Tuple(pair) if pair.len() == 2 && &pair[0] == key => Some(&pair[1]),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+struct SelectorParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl SelectorParser<'_> {
+    fn parse(mut self) -> Result<Selector, SelectorError> {
+        let mut steps = Vec::new();
+        if self.peek() == Some('$') {
+            self.pos += 1;
+        } else if self.peek().is_some_and(is_name_char) {
+            steps.push(self.name());
+        }
+        while let Some(c) = self.peek() {
+            let step = match c {
+                '.' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('*') => {
+                            self.pos += 1;
+                            Step::Wildcard
+                        }
+                        Some('\'') => self.quoted_name()?,
+                        Some('#') => self.key()?,
+                        Some(c) if is_name_char(c) => self.name(),
+                        Some(c) => return Err(self.error(SelectorErrorKind::UnexpectedChar(c))),
+                        None => return Err(self.error(SelectorErrorKind::UnexpectedEof)),
+                    }
+                }
+                '[' => self.index()?,
+                '#' => self.key()?,
+                c => return Err(self.error(SelectorErrorKind::UnexpectedChar(c))),
+            };
+            steps.push(step);
+        }
+        Ok(Selector {
+            steps: steps.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn error(&self, kind: SelectorErrorKind) -> SelectorError {
+        SelectorError::new(kind, self.pos)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SelectorError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(self.error(SelectorErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(SelectorErrorKind::UnexpectedEof)),
+        }
+    }
+
+    fn name(&mut self) -> Step {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        self.pos += len;
+        Step::Key(OwnedTerm::atom(&rest[..len]))
+    }
+
+    fn quoted_name(&mut self) -> Result<Step, SelectorError> {
+        self.expect('\'')?;
+        let mut name = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error(SelectorErrorKind::UnexpectedEof))?;
+            self.pos += c.len_utf8();
+            match c {
+                '\'' => return Ok(Step::Key(OwnedTerm::atom(name))),
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error(SelectorErrorKind::UnexpectedEof))?;
+                    self.pos += escaped.len_utf8();
+                    name.push(escaped);
+                }
+                c => name.push(c),
+            }
+        }
+    }
+
+    fn index(&mut self) -> Result<Step, SelectorError> {
+        self.expect('[')?;
+        if self.peek() == Some('*') {
+            self.pos += 1;
+            self.expect(']')?;
+            return Ok(Step::Wildcard);
+        }
+        let start = self.pos;
+        let rest = &self.input[start..];
+        let len = rest.find(']').ok_or_else(|| {
+            SelectorError::new(SelectorErrorKind::UnexpectedEof, self.input.len())
+        })?;
+        let digits = rest[..len].trim();
+        let index = digits.parse().map_err(|_| {
+            SelectorError::new(SelectorErrorKind::InvalidIndex(digits.to_string()), start)
+        })?;
+        self.pos += len + 1;
+        Ok(Step::Index(index))
+    }
+
+    /// `#{Key}`, where the key is a term in Erlang syntax.
+    fn key(&mut self) -> Result<Step, SelectorError> {
+        self.expect('#')?;
+        self.expect('{')?;
+        let start = self.pos;
+        let mut depth = 1;
+        let mut quote = None;
+        let mut escaped = false;
+        for (offset, c) in self.input[start..].char_indices() {
+            match quote {
+                Some(_) if escaped => escaped = false,
+                Some(_) if c == '\\' => escaped = true,
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None => match c {
+                    '"' | '\'' => quote = Some(c),
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            let key = parse(&self.input[start..start + offset]).map_err(|e| {
+                                SelectorError::new(SelectorErrorKind::InvalidKey(e), start)
+                            })?;
+                            self.pos = start + offset + 1;
+                            return Ok(Step::Key(key));
+                        }
+                    }
+                    _ => {}
+                },
+            }
+        }
+        Err(SelectorError::new(
+            SelectorErrorKind::UnexpectedEof,
+            self.input.len(),
+        ))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '@'
+}
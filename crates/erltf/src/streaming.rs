@@ -0,0 +1,437 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A push-style decoder for terms that arrive in chunks.
+//!
+//! [`StreamDecoder`] buffers only the term currently being decoded. It scans
+//! the structure of that term as bytes arrive, remembering where it stopped,
+//! and decodes the term once all of its bytes are present. A stream of
+//! concatenated `term_to_binary` outputs decodes in a single pass.
+
+use crate::decoder::{DecodeOptions, decode_with_options};
+use crate::errors::DecodeError;
+use crate::tags::{
+    ATOM_CACHE_REF, ATOM_EXT, ATOM_UTF8_EXT, BINARY_EXT, BIT_BINARY_EXT, COMPRESSED_EXT,
+    EXPORT_EXT, FLOAT_EXT, INTEGER_EXT, LARGE_BIG_EXT, LARGE_TUPLE_EXT, LIST_EXT, LOCAL_EXT,
+    MAP_EXT, NEW_FLOAT_EXT, NEW_FUN_EXT, NEW_PID_EXT, NEW_PORT_EXT, NEW_REFERENCE_EXT,
+    NEWER_REFERENCE_EXT, NIL_EXT, PID_EXT, PORT_EXT, REFERENCE_EXT, SMALL_ATOM_EXT,
+    SMALL_ATOM_UTF8_EXT, SMALL_BIG_EXT, SMALL_INTEGER_EXT, SMALL_TUPLE_EXT, STRING_EXT,
+    V4_PORT_EXT, VERSION,
+};
+use crate::term::OwnedTerm;
+use bytes::{Buf, BytesMut};
+use flate2::{Decompress, FlushDecompress, Status};
+
+const INFLATE_SCRATCH_SIZE: usize = 32 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem {
+    /// A complete term.
+    Term(OwnedTerm),
+    /// At least this many more bytes must be pushed before the next term can be produced.
+    NeedMore(usize),
+}
+
+/// Decodes a stream of version-prefixed terms pushed in arbitrary chunks.
+///
+/// ```
+/// use erltf::{StreamDecoder, StreamItem, encode, erl_atom, erl_int};
+///
+/// let mut data = encode(&erl_atom!("hello")).unwrap();
+/// data.extend(encode(&erl_int!(1000)).unwrap());
+///
+/// let mut decoder = StreamDecoder::new();
+/// decoder.push(&data[..4]);
+/// assert!(matches!(decoder.next_term().unwrap(), StreamItem::NeedMore(_)));
+///
+/// decoder.push(&data[4..]);
+/// assert_eq!(decoder.next_term().unwrap(), StreamItem::Term(erl_atom!("hello")));
+/// assert_eq!(decoder.next_term().unwrap(), StreamItem::Term(erl_int!(1000)));
+/// assert_eq!(decoder.next_term().unwrap(), StreamItem::NeedMore(1));
+/// ```
+///
+/// After an error the decoder cannot be resumed.
+#[derive(Debug, Default)]
+pub struct StreamDecoder {
+    buffer: BytesMut,
+    scanner: Scanner,
+    options: DecodeOptions,
+}
+
+impl StreamDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A decoder that applies `options` to every term. Size limits are
+    /// checked while scanning, before the term is buffered in full.
+    pub fn with_options(options: DecodeOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Appends a chunk of input.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete term, or how many more bytes it needs.
+    pub fn next_term(&mut self) -> Result<StreamItem, DecodeError> {
+        match self.scanner.scan(&self.buffer, &self.options)? {
+            Some(needed) => Ok(StreamItem::NeedMore(needed)),
+            None => {
+                let len = self.scanner.offset;
+                let term = decode_with_options(&self.buffer[..len], &self.options)?;
+                self.buffer.advance(len);
+                self.scanner = Scanner::default();
+                Ok(StreamItem::Term(term))
+            }
+        }
+    }
+
+    /// The buffer [`StreamDecoder::push`] appends to, for readers that fill it in place.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn buffer_mut(&mut self) -> &mut BytesMut {
+        &mut self.buffer
+    }
+
+    /// The number of bytes pushed but not yet returned as terms.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Checks that the input ended on a term boundary.
+    pub fn finish(self) -> Result<(), DecodeError> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::UnexpectedEof)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Frame {
+    /// This many more terms follow.
+    Terms(u64),
+    /// This many raw bytes follow.
+    Bytes(usize),
+    /// A zlib stream of unknown length follows.
+    Compressed(Box<Inflater>),
+}
+
+/// Inflates a compressed term to find where it ends. The scratch buffer is
+/// kept because compressed input may arrive a byte at a time.
+struct Inflater {
+    decompress: Decompress,
+    scratch: Vec<u8>,
+}
+
+impl std::fmt::Debug for Inflater {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Inflater")
+            .field("total_in", &self.decompress.total_in())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Tracks how far into the current term the input has been scanned.
+#[derive(Debug, Default)]
+struct Scanner {
+    offset: usize,
+    started: bool,
+    stack: Vec<Frame>,
+}
+
+impl Scanner {
+    /// Advances over `buffer`, returning `None` once the term is complete
+    /// or the number of missing bytes otherwise.
+    fn scan(
+        &mut self,
+        buffer: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<Option<usize>, DecodeError> {
+        if !self.started {
+            let Some(&version) = buffer.first() else {
+                return Ok(Some(1));
+            };
+            if version != VERSION {
+                return Err(DecodeError::InvalidVersion {
+                    expected: VERSION,
+                    actual: version,
+                });
+            }
+            self.offset = 1;
+            self.started = true;
+            self.stack.push(Frame::Terms(1));
+        }
+
+        loop {
+            let available = buffer.len() - self.offset;
+            let Some(frame) = self.stack.last_mut() else {
+                return Ok(None);
+            };
+            match frame {
+                Frame::Terms(0) => {
+                    self.stack.pop();
+                }
+                Frame::Bytes(n) => {
+                    if available < *n {
+                        return Ok(Some(*n - available));
+                    }
+                    self.offset += *n;
+                    self.stack.pop();
+                }
+                Frame::Compressed(inflater) => {
+                    if !inflate(inflater, &buffer[self.offset..], &mut self.offset)? {
+                        return Ok(Some(1));
+                    }
+                    self.stack.pop();
+                }
+                Frame::Terms(remaining) => {
+                    let layout = match layout(&buffer[self.offset..], options)? {
+                        Ok(layout) => layout,
+                        Err(needed) => return Ok(Some(needed)),
+                    };
+                    *remaining -= 1;
+                    self.offset += layout.header;
+                    if layout.trailer > 0 {
+                        self.stack.push(Frame::Bytes(layout.trailer));
+                    }
+                    if layout.children > 0 {
+                        self.stack.push(Frame::Terms(layout.children));
+                    }
+                    if layout.body > 0 {
+                        self.stack.push(Frame::Bytes(layout.body));
+                    }
+                    if layout.compressed {
+                        self.stack.push(Frame::Compressed(Box::new(Inflater {
+                            decompress: Decompress::new(true),
+                            scratch: vec![0u8; INFLATE_SCRATCH_SIZE],
+                        })));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Feeds `input` to the inflater, returning whether the zlib stream has ended.
+/// The output is discarded: the complete term is inflated again when decoded.
+fn inflate(inflater: &mut Inflater, input: &[u8], offset: &mut usize) -> Result<bool, DecodeError> {
+    let Inflater {
+        decompress,
+        scratch,
+    } = inflater;
+    let mut consumed = 0;
+    loop {
+        let (in_before, out_before) = (decompress.total_in(), decompress.total_out());
+        let status = decompress
+            .decompress(&input[consumed..], scratch, FlushDecompress::None)
+            .map_err(|e| DecodeError::InvalidFormat(format!("invalid compressed term: {}", e)))?;
+        let read = (decompress.total_in() - in_before) as usize;
+        consumed += read;
+        *offset += read;
+        if status == Status::StreamEnd {
+            return Ok(true);
+        }
+        if read == 0 && decompress.total_out() == out_before {
+            return Ok(false);
+        }
+    }
+}
+
+/// How a term is laid out after its tag: `header` bytes (including the tag),
+/// then `body` raw bytes, then `children` nested terms, then `trailer` raw bytes.
+#[derive(Default)]
+pub(crate) struct Layout {
+    pub(crate) header: usize,
+    pub(crate) body: usize,
+    pub(crate) children: u64,
+    pub(crate) trailer: usize,
+    pub(crate) compressed: bool,
+}
+
+fn fixed(body: usize) -> Layout {
+    Layout {
+        header: 1,
+        body,
+        ..Layout::default()
+    }
+}
+
+fn check_size(
+    size: usize,
+    max: usize,
+    error: fn(usize, usize) -> DecodeError,
+) -> Result<usize, DecodeError> {
+    if size > max {
+        Err(error(size, max))
+    } else {
+        Ok(size)
+    }
+}
+
+/// Reads the layout of the term at the start of `input`. The inner `Err`
+/// holds the number of bytes missing to read the header.
+pub(crate) fn layout(
+    input: &[u8],
+    options: &DecodeOptions,
+) -> Result<Result<Layout, usize>, DecodeError> {
+    let Some(&tag) = input.first() else {
+        return Ok(Err(1));
+    };
+    let prefix = match tag {
+        ATOM_EXT | ATOM_UTF8_EXT | STRING_EXT | NEW_REFERENCE_EXT | NEWER_REFERENCE_EXT => 2,
+        SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT | SMALL_TUPLE_EXT | SMALL_BIG_EXT => 1,
+        LARGE_TUPLE_EXT | LIST_EXT | BINARY_EXT | BIT_BINARY_EXT | LARGE_BIG_EXT | MAP_EXT
+        | NEW_FUN_EXT | COMPRESSED_EXT => 4,
+        _ => 0,
+    };
+    if input.len() < 1 + prefix {
+        return Ok(Err(1 + prefix - input.len()));
+    }
+    let len = match prefix {
+        1 => input[1] as usize,
+        2 => u16::from_be_bytes([input[1], input[2]]) as usize,
+        4 => u32::from_be_bytes([input[1], input[2], input[3], input[4]]) as usize,
+        _ => 0,
+    };
+    let header = 1 + prefix;
+
+    let layout = match tag {
+        SMALL_INTEGER_EXT | ATOM_CACHE_REF => fixed(1),
+        INTEGER_EXT => fixed(4),
+        NEW_FLOAT_EXT => fixed(8),
+        FLOAT_EXT => fixed(31),
+        NIL_EXT => fixed(0),
+        ATOM_EXT | ATOM_UTF8_EXT | SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT | STRING_EXT => Layout {
+            header,
+            body: len,
+            ..Layout::default()
+        },
+        SMALL_BIG_EXT | LARGE_BIG_EXT => Layout {
+            header,
+            body: len + 1,
+            ..Layout::default()
+        },
+        BINARY_EXT | BIT_BINARY_EXT => {
+            let len = check_size(len, options.max_binary_size, |size, max| {
+                DecodeError::BinaryTooLarge { size, max }
+            })?;
+            Layout {
+                header,
+                body: len + usize::from(tag == BIT_BINARY_EXT),
+                ..Layout::default()
+            }
+        }
+        SMALL_TUPLE_EXT | LARGE_TUPLE_EXT => {
+            let len = check_size(len, options.max_tuple_size, |size, max| {
+                DecodeError::TupleTooLarge { size, max }
+            })?;
+            Layout {
+                header,
+                children: len as u64,
+                ..Layout::default()
+            }
+        }
+        LIST_EXT => {
+            let len = check_size(len, options.max_list_size, |size, max| {
+                DecodeError::ListTooLarge { size, max }
+            })?;
+            // The elements and the tail
+            Layout {
+                header,
+                children: len as u64 + 1,
+                ..Layout::default()
+            }
+        }
+        MAP_EXT => {
+            let len = check_size(len, options.max_map_size, |size, max| {
+                DecodeError::MapTooLarge { size, max }
+            })?;
+            Layout {
+                header,
+                children: 2 * len as u64,
+                ..Layout::default()
+            }
+        }
+        PID_EXT => node_then(9),
+        NEW_PID_EXT => node_then(12),
+        PORT_EXT | REFERENCE_EXT => node_then(5),
+        NEW_PORT_EXT => node_then(8),
+        V4_PORT_EXT => node_then(12),
+        NEW_REFERENCE_EXT => Layout {
+            header,
+            children: 1,
+            trailer: 1 + 4 * len,
+            ..Layout::default()
+        },
+        NEWER_REFERENCE_EXT => Layout {
+            header,
+            children: 1,
+            trailer: 4 + 4 * len,
+            ..Layout::default()
+        },
+        EXPORT_EXT => Layout {
+            header: 1,
+            children: 3,
+            ..Layout::default()
+        },
+        // The size includes the size field itself
+        NEW_FUN_EXT => match len.checked_sub(4) {
+            Some(body) => Layout {
+                header,
+                body,
+                ..Layout::default()
+            },
+            None => {
+                return Err(DecodeError::InvalidFormat(format!(
+                    "invalid fun size: {}",
+                    len
+                )));
+            }
+        },
+        LOCAL_EXT => Layout {
+            header: 1,
+            body: 8,
+            children: 1,
+            ..Layout::default()
+        },
+        COMPRESSED_EXT => {
+            check_size(len, options.max_binary_size, |size, max| {
+                DecodeError::BinaryTooLarge { size, max }
+            })?;
+            Layout {
+                header,
+                compressed: true,
+                ..Layout::default()
+            }
+        }
+        _ => return Err(DecodeError::InvalidTag(tag)),
+    };
+    Ok(Ok(layout))
+}
+
+/// Pids, ports and old-style references: a node atom followed by fixed-size fields.
+fn node_then(trailer: usize) -> Layout {
+    Layout {
+        header: 1,
+        children: 1,
+        trailer,
+        ..Layout::default()
+    }
+}
@@ -14,8 +14,11 @@
 
 use crate::errors::TermConversionError;
 use crate::types::{
-    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun, Mfa, Sign,
+    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun, Mfa,
+    compare_bigint, compare_bigint_float, compare_bigint_int, compare_bitstrings,
+    compare_float_bigint, compare_float_int, compare_int_bigint, compare_int_float,
 };
+use bytes::Bytes;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
@@ -24,7 +27,7 @@ use std::mem::discriminant;
 use std::ops::Index;
 use std::sync::{Arc, OnceLock};
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, Default)]
 pub enum OwnedTerm {
     Atom(Atom),
     Integer(i64),
@@ -33,6 +36,9 @@ pub enum OwnedTerm {
     Port(ExternalPort),
     Reference(ExternalReference),
     Binary(Vec<u8>),
+    /// A binary that shares the buffer it was decoded from, see [`crate::decode_shared`].
+    /// Equal to, ordered and hashed like a [`OwnedTerm::Binary`] with the same bytes.
+    SharedBinary(Bytes),
     BitBinary {
         bytes: Vec<u8>,
         bits: u8,
@@ -54,6 +60,9 @@ pub enum OwnedTerm {
 
 /// Provides unified key-value access over both Erlang maps and proplists.
 ///
+/// Implemented for [`OwnedTerm`] and [`crate::BorrowedTerm`]; values are
+/// returned as the same kind of term.
+///
 /// # Example
 /// ```
 /// use erltf::{OwnedTerm, KeyValueAccess};
@@ -72,8 +81,12 @@ pub enum OwnedTerm {
 /// assert_eq!(proplist.kv_get_atom_string("name"), Some("value".to_string()));
 /// ```
 pub trait KeyValueAccess {
+    /// The term type values are returned as; implementors that previously
+    /// returned `&OwnedTerm` from `kv_get` declare `type Value = OwnedTerm;`
+    type Value: KeyValueTerm + ?Sized;
+
     /// Retrieve the value associated with an atom key, returning the raw term
-    fn kv_get(&self, key: &str) -> Option<&OwnedTerm>;
+    fn kv_get(&self, key: &str) -> Option<&Self::Value>;
 
     /// Retrieve an i64 value by key
     fn kv_get_i64(&self, key: &str) -> Option<i64> {
@@ -96,7 +109,7 @@ pub trait KeyValueAccess {
     }
 
     /// Retrieve an atom value by key
-    fn kv_get_atom(&self, key: &str) -> Option<&Atom> {
+    fn kv_get_atom(&self, key: &str) -> Option<&<Self::Value as KeyValueTerm>::Atom> {
         self.kv_get(key).and_then(|t| t.as_atom())
     }
 
@@ -150,7 +163,50 @@ pub trait KeyValueAccess {
     }
 }
 
+/// The accessors [`KeyValueAccess`] needs from the values it returns.
+pub trait KeyValueTerm {
+    /// How atoms are represented: [`Atom`] for owned terms, `str` for borrowed ones.
+    type Atom: fmt::Display + ?Sized;
+
+    fn as_integer(&self) -> Option<i64>;
+    fn as_bool(&self) -> Option<bool>;
+    fn as_atom(&self) -> Option<&Self::Atom>;
+    fn as_erlang_string(&self) -> Option<String>;
+    fn as_pid(&self) -> Option<&ExternalPid>;
+    fn format_as_mfa(&self) -> Option<String>;
+}
+
+impl KeyValueTerm for OwnedTerm {
+    type Atom = Atom;
+
+    fn as_integer(&self) -> Option<i64> {
+        OwnedTerm::as_integer(self)
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        OwnedTerm::as_bool(self)
+    }
+
+    fn as_atom(&self) -> Option<&Atom> {
+        OwnedTerm::as_atom(self)
+    }
+
+    fn as_erlang_string(&self) -> Option<String> {
+        OwnedTerm::as_erlang_string(self)
+    }
+
+    fn as_pid(&self) -> Option<&ExternalPid> {
+        OwnedTerm::as_pid(self)
+    }
+
+    fn format_as_mfa(&self) -> Option<String> {
+        OwnedTerm::format_as_mfa(self)
+    }
+}
+
 impl KeyValueAccess for OwnedTerm {
+    type Value = OwnedTerm;
+
     fn kv_get(&self, key: &str) -> Option<&OwnedTerm> {
         match self {
             OwnedTerm::Map(_) => self.map_get_atom_key(key),
@@ -286,6 +342,7 @@ impl OwnedTerm {
     pub fn as_binary(&self) -> Option<&[u8]> {
         match self {
             OwnedTerm::Binary(b) => Some(b),
+            OwnedTerm::SharedBinary(b) => Some(b),
             _ => None,
         }
     }
@@ -350,6 +407,8 @@ impl OwnedTerm {
         }
     }
 
+    /// Returns `None` for [`OwnedTerm::SharedBinary`], whose buffer may be shared
+    /// with other terms; replace it with an [`OwnedTerm::Binary`] first to modify it.
     #[inline]
     pub fn as_binary_mut(&mut self) -> Option<&mut Vec<u8>> {
         match self {
@@ -444,7 +503,7 @@ impl OwnedTerm {
             OwnedTerm::Pid(_) => "Pid",
             OwnedTerm::Port(_) => "Port",
             OwnedTerm::Reference(_) => "Reference",
-            OwnedTerm::Binary(_) => "Binary",
+            OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => "Binary",
             OwnedTerm::BitBinary { .. } => "BitBinary",
             OwnedTerm::String(_) => "String",
             OwnedTerm::List(_) => "List",
@@ -607,7 +666,10 @@ impl OwnedTerm {
             OwnedTerm::Tuple(elements) if elements.len() == 2 => {
                 matches!(
                     &elements[0],
-                    OwnedTerm::Atom(_) | OwnedTerm::Binary(_) | OwnedTerm::String(_)
+                    OwnedTerm::Atom(_)
+                        | OwnedTerm::Binary(_)
+                        | OwnedTerm::SharedBinary(_)
+                        | OwnedTerm::String(_)
                 )
             }
             OwnedTerm::Atom(_) => true,
@@ -730,6 +792,10 @@ impl OwnedTerm {
                                     let s = String::from_utf8_lossy(b);
                                     OwnedTerm::Atom(Atom::new(s.as_ref()))
                                 }
+                                OwnedTerm::SharedBinary(b) => {
+                                    let s = String::from_utf8_lossy(b);
+                                    OwnedTerm::Atom(Atom::new(s.as_ref()))
+                                }
                                 OwnedTerm::String(s) => OwnedTerm::Atom(Atom::new(s)),
                                 _ => return None,
                             };
@@ -750,6 +816,10 @@ impl OwnedTerm {
                             let s = String::from_utf8_lossy(b);
                             OwnedTerm::Atom(Atom::new(s.as_ref()))
                         }
+                        OwnedTerm::SharedBinary(b) => {
+                            let s = String::from_utf8_lossy(b);
+                            OwnedTerm::Atom(Atom::new(s.as_ref()))
+                        }
                         OwnedTerm::String(s) => OwnedTerm::Atom(Atom::new(s)),
                         _ => continue,
                     };
@@ -896,6 +966,7 @@ impl OwnedTerm {
             }
             OwnedTerm::String(s) => Some(s.clone()),
             OwnedTerm::Binary(b) => Some(String::from_utf8_lossy(b).to_string()),
+            OwnedTerm::SharedBinary(b) => Some(String::from_utf8_lossy(b).to_string()),
             _ => None,
         }
     }
@@ -984,6 +1055,7 @@ impl OwnedTerm {
             OwnedTerm::Nil => Some(String::new()),
             OwnedTerm::String(s) => Some(s.clone()),
             OwnedTerm::Binary(b) => Some(String::from_utf8_lossy(b).to_string()),
+            OwnedTerm::SharedBinary(b) => Some(String::from_utf8_lossy(b).to_string()),
             _ => None,
         }
     }
@@ -1167,6 +1239,7 @@ impl OwnedTerm {
     pub fn try_into_binary(self) -> Result<Vec<u8>, TermConversionError> {
         match self {
             OwnedTerm::Binary(b) => Ok(b),
+            OwnedTerm::SharedBinary(b) => Ok(b.into()),
             OwnedTerm::String(s) => Ok(s.into_bytes()),
             _ => Err(TermConversionError::WrongType {
                 expected: "Binary or String",
@@ -1181,6 +1254,9 @@ impl OwnedTerm {
             OwnedTerm::Binary(b) => {
                 String::from_utf8(b).map_err(|_| TermConversionError::OutOfRange)
             }
+            OwnedTerm::SharedBinary(b) => {
+                String::from_utf8(b.into()).map_err(|_| TermConversionError::OutOfRange)
+            }
             _ => Err(TermConversionError::WrongType {
                 expected: "String or Binary",
                 actual: self.type_name(),
@@ -1205,6 +1281,7 @@ impl OwnedTerm {
             OwnedTerm::Tuple(t) => t.len(),
             OwnedTerm::Map(m) => m.len(),
             OwnedTerm::Binary(b) => b.len(),
+            OwnedTerm::SharedBinary(b) => b.len(),
             OwnedTerm::String(s) => s.len(),
             OwnedTerm::Nil => 0,
             _ => 0,
@@ -1218,6 +1295,7 @@ impl OwnedTerm {
             OwnedTerm::Tuple(t) => t.is_empty(),
             OwnedTerm::Map(m) => m.is_empty(),
             OwnedTerm::Binary(b) => b.is_empty(),
+            OwnedTerm::SharedBinary(b) => b.is_empty(),
             OwnedTerm::String(s) => s.is_empty(),
             OwnedTerm::Nil => true,
             _ => false,
@@ -1240,6 +1318,7 @@ impl OwnedTerm {
             }
             OwnedTerm::Float(_) => 9,
             OwnedTerm::Binary(b) => 5 + b.len(),
+            OwnedTerm::SharedBinary(b) => 5 + b.len(),
             OwnedTerm::BitBinary { bytes, .. } => 6 + bytes.len(),
             OwnedTerm::String(s) => 5 + s.len(),
             OwnedTerm::List(l) => {
@@ -1330,6 +1409,39 @@ impl From<u8> for OwnedTerm {
     }
 }
 
+impl From<u64> for OwnedTerm {
+    fn from(i: u64) -> Self {
+        match i64::try_from(i) {
+            Ok(i) => OwnedTerm::Integer(i),
+            Err(_) => OwnedTerm::BigInt(BigInt::from(i)),
+        }
+    }
+}
+
+impl From<i128> for OwnedTerm {
+    fn from(i: i128) -> Self {
+        match i64::try_from(i) {
+            Ok(i) => OwnedTerm::Integer(i),
+            Err(_) => OwnedTerm::BigInt(BigInt::from(i)),
+        }
+    }
+}
+
+impl From<u128> for OwnedTerm {
+    fn from(i: u128) -> Self {
+        match i64::try_from(i) {
+            Ok(i) => OwnedTerm::Integer(i),
+            Err(_) => OwnedTerm::BigInt(BigInt::from(i)),
+        }
+    }
+}
+
+impl From<BigInt> for OwnedTerm {
+    fn from(big: BigInt) -> Self {
+        OwnedTerm::BigInt(big)
+    }
+}
+
 impl From<bool> for OwnedTerm {
     fn from(b: bool) -> Self {
         OwnedTerm::boolean(b)
@@ -1354,6 +1466,22 @@ impl From<Vec<u8>> for OwnedTerm {
     }
 }
 
+impl From<Bytes> for OwnedTerm {
+    fn from(b: Bytes) -> Self {
+        OwnedTerm::SharedBinary(b)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<num_bigint::BigInt> for OwnedTerm {
+    fn from(n: num_bigint::BigInt) -> Self {
+        match i64::try_from(&n) {
+            Ok(i) => OwnedTerm::Integer(i),
+            Err(_) => OwnedTerm::BigInt(BigInt::from(n)),
+        }
+    }
+}
+
 impl From<String> for OwnedTerm {
     fn from(s: String) -> Self {
         OwnedTerm::String(s)
@@ -1462,6 +1590,9 @@ impl TryFrom<OwnedTerm> for String {
             OwnedTerm::Binary(b) => {
                 String::from_utf8(b).map_err(|_| TermConversionError::OutOfRange)
             }
+            OwnedTerm::SharedBinary(b) => {
+                String::from_utf8(b.into()).map_err(|_| TermConversionError::OutOfRange)
+            }
             _ => Err(TermConversionError::WrongType {
                 expected: "String or Binary",
                 actual: term.type_name(),
@@ -1476,6 +1607,7 @@ impl TryFrom<OwnedTerm> for Vec<u8> {
     fn try_from(term: OwnedTerm) -> Result<Self, Self::Error> {
         match term {
             OwnedTerm::Binary(b) => Ok(b),
+            OwnedTerm::SharedBinary(b) => Ok(b.into()),
             OwnedTerm::String(s) => Ok(s.into_bytes()),
             _ => Err(TermConversionError::WrongType {
                 expected: "Binary or String",
@@ -1557,14 +1689,66 @@ impl TryFrom<OwnedTerm> for u8 {
     }
 }
 
+impl TryFrom<OwnedTerm> for i128 {
+    type Error = TermConversionError;
+
+    fn try_from(term: OwnedTerm) -> Result<Self, Self::Error> {
+        match term {
+            OwnedTerm::Integer(i) => Ok(i as i128),
+            OwnedTerm::BigInt(big) => i128::try_from(&big),
+            _ => Err(TermConversionError::WrongType {
+                expected: "Integer",
+                actual: term.type_name(),
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl TryFrom<OwnedTerm> for num_bigint::BigInt {
+    type Error = TermConversionError;
+
+    fn try_from(term: OwnedTerm) -> Result<Self, Self::Error> {
+        match term {
+            OwnedTerm::Integer(i) => Ok(num_bigint::BigInt::from(i)),
+            OwnedTerm::BigInt(big) => Ok(num_bigint::BigInt::from(big)),
+            _ => Err(TermConversionError::WrongType {
+                expected: "Integer",
+                actual: term.type_name(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<OwnedTerm> for u128 {
+    type Error = TermConversionError;
+
+    fn try_from(term: OwnedTerm) -> Result<Self, Self::Error> {
+        match term {
+            OwnedTerm::Integer(i) if i >= 0 => Ok(i as u128),
+            OwnedTerm::Integer(_) => Err(TermConversionError::OutOfRange),
+            OwnedTerm::BigInt(big) => u128::try_from(&big),
+            _ => Err(TermConversionError::WrongType {
+                expected: "Integer",
+                actual: term.type_name(),
+            }),
+        }
+    }
+}
+
 impl Hash for OwnedTerm {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        discriminant(self).hash(state);
+        // Shared binaries are equal to owned ones and must hash the same
+        match self {
+            OwnedTerm::SharedBinary(_) => discriminant(&OwnedTerm::Binary(Vec::new())).hash(state),
+            _ => discriminant(self).hash(state),
+        }
 
         match self {
             OwnedTerm::Atom(a) => a.hash(state),
             OwnedTerm::Integer(i) => i.hash(state),
             OwnedTerm::Binary(b) => b.hash(state),
+            OwnedTerm::SharedBinary(b) => b[..].hash(state),
             OwnedTerm::String(s) => s.hash(state),
             OwnedTerm::Pid(p) => p.hash(state),
             OwnedTerm::Port(p) => p.hash(state),
@@ -1633,7 +1817,56 @@ const fn term_type_order(t: &OwnedTerm) -> u8 {
         OwnedTerm::Tuple(_) => 6,
         OwnedTerm::Map(_) => 7,
         OwnedTerm::Nil | OwnedTerm::List(_) | OwnedTerm::ImproperList { .. } => 8,
-        OwnedTerm::Binary(_) | OwnedTerm::BitBinary { .. } | OwnedTerm::String(_) => 9,
+        OwnedTerm::Binary(_)
+        | OwnedTerm::SharedBinary(_)
+        | OwnedTerm::BitBinary { .. }
+        | OwnedTerm::String(_) => 9,
+    }
+}
+
+impl PartialEq for OwnedTerm {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (OwnedTerm::Atom(a), OwnedTerm::Atom(b)) => a == b,
+            (OwnedTerm::Integer(a), OwnedTerm::Integer(b)) => a == b,
+            (OwnedTerm::Float(a), OwnedTerm::Float(b)) => a == b,
+            (OwnedTerm::Pid(a), OwnedTerm::Pid(b)) => a == b,
+            (OwnedTerm::Port(a), OwnedTerm::Port(b)) => a == b,
+            (OwnedTerm::Reference(a), OwnedTerm::Reference(b)) => a == b,
+            (
+                OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_),
+                OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_),
+            ) => self.as_binary() == other.as_binary(),
+            (
+                OwnedTerm::BitBinary {
+                    bytes: a,
+                    bits: abits,
+                },
+                OwnedTerm::BitBinary {
+                    bytes: b,
+                    bits: bbits,
+                },
+            ) => a == b && abits == bbits,
+            (OwnedTerm::String(a), OwnedTerm::String(b)) => a == b,
+            (OwnedTerm::List(a), OwnedTerm::List(b)) => a == b,
+            (
+                OwnedTerm::ImproperList {
+                    elements: a,
+                    tail: atail,
+                },
+                OwnedTerm::ImproperList {
+                    elements: b,
+                    tail: btail,
+                },
+            ) => a == b && atail == btail,
+            (OwnedTerm::Map(a), OwnedTerm::Map(b)) => a == b,
+            (OwnedTerm::Tuple(a), OwnedTerm::Tuple(b)) => a == b,
+            (OwnedTerm::BigInt(a), OwnedTerm::BigInt(b)) => a == b,
+            (OwnedTerm::ExternalFun(a), OwnedTerm::ExternalFun(b)) => a == b,
+            (OwnedTerm::InternalFun(a), OwnedTerm::InternalFun(b)) => a == b,
+            (OwnedTerm::Nil, OwnedTerm::Nil) => true,
+            _ => false,
+        }
     }
 }
 
@@ -1734,52 +1967,19 @@ impl Ord for OwnedTerm {
                     }
                     Ordering::Equal
                 }),
-                (OwnedTerm::Nil, OwnedTerm::Nil) => Ordering::Equal,
-                (OwnedTerm::List(a), OwnedTerm::List(b)) => {
-                    for (x, y) in a.iter().zip(b.iter()) {
-                        match x.cmp(y) {
-                            Ordering::Equal => continue,
-                            other => return other,
-                        }
-                    }
-                    a.len().cmp(&b.len())
-                }
-                (OwnedTerm::List(a), OwnedTerm::Nil) => {
-                    if a.is_empty() {
-                        Ordering::Equal
-                    } else {
-                        Ordering::Greater
-                    }
-                }
-                (OwnedTerm::Nil, OwnedTerm::List(b)) => {
-                    if b.is_empty() {
-                        Ordering::Equal
-                    } else {
-                        Ordering::Less
-                    }
-                }
                 (
-                    OwnedTerm::ImproperList {
-                        elements: a,
-                        tail: ta,
-                    },
-                    OwnedTerm::ImproperList {
-                        elements: b,
-                        tail: tb,
-                    },
-                ) => {
-                    for (x, y) in a.iter().zip(b.iter()) {
-                        match x.cmp(y) {
-                            Ordering::Equal => continue,
-                            other => return other,
-                        }
-                    }
-                    a.len().cmp(&b.len()).then_with(|| ta.cmp(tb))
-                }
+                    OwnedTerm::Nil | OwnedTerm::List(_) | OwnedTerm::ImproperList { .. },
+                    OwnedTerm::Nil | OwnedTerm::List(_) | OwnedTerm::ImproperList { .. },
+                ) => compare_lists(self, other),
                 (OwnedTerm::Binary(a), OwnedTerm::Binary(b)) => a.cmp(b),
                 (OwnedTerm::String(a), OwnedTerm::String(b)) => a.cmp(b),
                 (OwnedTerm::Binary(a), OwnedTerm::String(b)) => a.as_slice().cmp(b.as_bytes()),
                 (OwnedTerm::String(a), OwnedTerm::Binary(b)) => a.as_bytes().cmp(b.as_slice()),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::SharedBinary(b)) => a.cmp(b),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::Binary(b)) => a[..].cmp(b.as_slice()),
+                (OwnedTerm::Binary(a), OwnedTerm::SharedBinary(b)) => a.as_slice().cmp(&b[..]),
+                (OwnedTerm::SharedBinary(a), OwnedTerm::String(b)) => a[..].cmp(b.as_bytes()),
+                (OwnedTerm::String(a), OwnedTerm::SharedBinary(b)) => a.as_bytes().cmp(&b[..]),
                 (
                     OwnedTerm::BitBinary {
                         bytes: a,
@@ -1790,6 +1990,13 @@ impl Ord for OwnedTerm {
                         bits: bbits,
                     },
                 ) => a.cmp(b).then_with(|| abits.cmp(bbits)),
+                (OwnedTerm::BitBinary { bytes, bits }, binary) => {
+                    compare_bitstrings(bytes, *bits, binary_bytes(binary), 8)
+                        .then(Ordering::Greater)
+                }
+                (binary, OwnedTerm::BitBinary { bytes, bits }) => {
+                    compare_bitstrings(binary_bytes(binary), 8, bytes, *bits).then(Ordering::Less)
+                }
                 _ => Ordering::Equal,
             },
             other => other,
@@ -1803,6 +2010,89 @@ impl PartialOrd for OwnedTerm {
     }
 }
 
+impl OwnedTerm {
+    /// The term itself and all of its sub-terms, depth first, without recursion.
+    pub fn subterms(&self) -> crate::visit::Subterms<'_> {
+        crate::visit::Subterms::new(self)
+    }
+
+    /// Folds `f` over the term and all of its sub-terms, in [`OwnedTerm::subterms`] order.
+    ///
+    /// ```
+    /// use erltf::{erl_atom, erl_int, erl_list, erl_tuple};
+    ///
+    /// let term = erl_list![erl_int!(1), erl_tuple![erl_atom!("n"), erl_int!(2)]];
+    /// let sum = term.fold(0, |acc, t| acc + t.as_integer().unwrap_or(0));
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &OwnedTerm) -> B,
+    {
+        self.subterms().fold(init, f)
+    }
+
+    /// Rebuilds the term bottom-up: `f` is applied to every sub-term after its
+    /// children have been transformed, and last to the term itself.
+    ///
+    /// Like [`OwnedTerm::subterms`], this does not recurse, so it handles
+    /// arbitrarily deep terms. Dropping a term that deep still recurses,
+    /// so the caller must take it apart iteratively before dropping it.
+    ///
+    /// ```
+    /// use erltf::{OwnedTerm, erl_atom, erl_list, erl_tuple};
+    ///
+    /// let term = erl_list![erl_tuple![erl_atom!("pid"), erl_atom!("secret")]];
+    /// let redacted = term.transform(|t| match t {
+    ///     OwnedTerm::Atom(a) if a == "secret" => OwnedTerm::atom("redacted"),
+    ///     other => other,
+    /// });
+    /// assert_eq!(redacted, erl_list![erl_tuple![erl_atom!("pid"), erl_atom!("redacted")]]);
+    /// ```
+    pub fn transform<F>(self, f: F) -> OwnedTerm
+    where
+        F: FnMut(OwnedTerm) -> OwnedTerm,
+    {
+        crate::visit::transform(self, f)
+    }
+
+    /// Renders the term in Erlang syntax, like `io_lib:format("~p", [Term])`.
+    ///
+    /// Unlike `Display`, the output can be parsed back with [`crate::parse`].
+    pub fn to_pretty_string(&self) -> String {
+        crate::pretty::pretty_print(self, &crate::pretty::PrettyOptions::default())
+    }
+
+    /// The sub-terms matched by a [`crate::Selector`] such as `$.listeners[*].port`.
+    ///
+    /// ```
+    /// use erltf::{erl_atom, erl_int, erl_list, erl_map};
+    ///
+    /// let term = erl_map! {
+    ///     erl_atom!("listeners") => erl_list![
+    ///         erl_map! { erl_atom!("port") => erl_int!(5672) },
+    ///         erl_map! { erl_atom!("port") => erl_int!(5671) }
+    ///     ]
+    /// };
+    /// let ports: Vec<_> = term.select("$.listeners[*].port").unwrap().collect();
+    /// assert_eq!(ports, vec![&erl_int!(5672), &erl_int!(5671)]);
+    /// ```
+    pub fn select(
+        &self,
+        selector: &str,
+    ) -> Result<crate::selector::Matches<'_>, crate::errors::SelectorError> {
+        Ok(crate::selector::Selector::parse(selector)?.select(self))
+    }
+
+    /// The first sub-term matched by a [`crate::Selector`].
+    pub fn select_first(
+        &self,
+        selector: &str,
+    ) -> Result<Option<&OwnedTerm>, crate::errors::SelectorError> {
+        Ok(self.select(selector)?.next())
+    }
+}
+
 impl fmt::Display for OwnedTerm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1810,6 +2100,7 @@ impl fmt::Display for OwnedTerm {
             OwnedTerm::Integer(i) => write!(f, "{}", i),
             OwnedTerm::Float(fl) => write!(f, "{}", fl),
             OwnedTerm::Binary(b) => write!(f, "<<{} bytes>>", b.len()),
+            OwnedTerm::SharedBinary(b) => write!(f, "<<{} bytes>>", b.len()),
             OwnedTerm::BitBinary { bytes, bits } => {
                 write!(f, "<<{} bytes, {} bits>>", bytes.len(), bits)
             }
@@ -1848,10 +2139,7 @@ impl fmt::Display for OwnedTerm {
             OwnedTerm::Pid(p) => write!(f, "<{}.{}.{}>", p.id, p.serial, p.creation),
             OwnedTerm::Port(p) => write!(f, "#Port<{}>", p.id),
             OwnedTerm::Reference(r) => write!(f, "#Ref<{:?}>", r.ids),
-            OwnedTerm::BigInt(big) => {
-                let sign = if big.sign.is_negative() { "-" } else { "" };
-                write!(f, "{}BigInt<{} bytes>", sign, big.digits.len())
-            }
+            OwnedTerm::BigInt(big) => write!(f, "{}", big),
             OwnedTerm::ExternalFun(fun) => write!(
                 f,
                 "fun {}:{}/{}",
@@ -1872,6 +2160,300 @@ impl fmt::Display for OwnedTerm {
     }
 }
 
+impl OwnedTerm {
+    /// Returns a value that formats the term the way Elixir's `inspect/2` does:
+    /// `%{name: "x"}`, `:ok`, `MyApp.Struct`, `[a: 1]`.
+    ///
+    /// ```
+    /// use erltf::{erl_atom, erl_int, erl_map};
+    ///
+    /// let term = erl_map! { erl_atom!("name") => erl_atom!("Elixir.MyApp.Worker") };
+    /// assert_eq!(term.inspect().to_string(), "%{name: MyApp.Worker}");
+    /// ```
+    pub fn inspect(&self) -> Inspect<'_> {
+        Inspect(self)
+    }
+}
+
+/// Formats an [`OwnedTerm`] in Elixir syntax, see [`OwnedTerm::inspect`].
+pub struct Inspect<'a>(&'a OwnedTerm);
+
+impl fmt::Display for Inspect<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        inspect_term(f, self.0)
+    }
+}
+
+fn inspect_term(f: &mut fmt::Formatter<'_>, term: &OwnedTerm) -> fmt::Result {
+    match term {
+        OwnedTerm::Atom(a) => inspect_atom(f, a),
+        OwnedTerm::Integer(i) => write!(f, "{}", i),
+        OwnedTerm::Float(fl) => crate::pretty::write_float(f, *fl),
+        OwnedTerm::BigInt(big) => write!(f, "{}", big),
+        OwnedTerm::Binary(b) => inspect_binary(f, b),
+        OwnedTerm::SharedBinary(b) => inspect_binary(f, b),
+        OwnedTerm::String(s) => inspect_binary(f, s.as_bytes()),
+        OwnedTerm::BitBinary { bytes, bits } => {
+            write!(f, "<<")?;
+            for (i, byte) in bytes.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                if i + 1 == bytes.len() && *bits < 8 {
+                    write!(f, "{}::size({})", byte >> (8 - bits), bits)?;
+                } else {
+                    write!(f, "{}", byte)?;
+                }
+            }
+            write!(f, ">>")
+        }
+        OwnedTerm::Nil => write!(f, "[]"),
+        OwnedTerm::List(elements) => {
+            if let Some(chars) = ascii_charlist(elements) {
+                write!(f, "~c")?;
+                return inspect_quoted(f, &chars);
+            }
+            if term.is_proplist() && elements.iter().all(is_keyword_pair) {
+                write!(f, "[")?;
+                inspect_keyword_pairs(f, elements.iter().map(|e| (&e[0], &e[1])))?;
+                return write!(f, "]");
+            }
+            write!(f, "[")?;
+            inspect_separated(f, elements)?;
+            write!(f, "]")
+        }
+        OwnedTerm::ImproperList { elements, tail } => {
+            write!(f, "[")?;
+            inspect_separated(f, elements)?;
+            write!(f, " | ")?;
+            inspect_term(f, tail)?;
+            write!(f, "]")
+        }
+        OwnedTerm::Tuple(elements) => {
+            write!(f, "{{")?;
+            inspect_separated(f, elements)?;
+            write!(f, "}}")
+        }
+        OwnedTerm::Map(map) => {
+            let struct_name = match map.get(&OwnedTerm::Atom(Atom::new("__struct__"))) {
+                Some(OwnedTerm::Atom(name)) => Some(name),
+                _ => None,
+            };
+            match struct_name {
+                Some(name) => {
+                    write!(f, "%")?;
+                    inspect_module(f, name)?;
+                    write!(f, "{{")?;
+                    let fields = map.iter().filter(|(k, _)| match k {
+                        OwnedTerm::Atom(a) => a.as_str() != "__struct__",
+                        _ => true,
+                    });
+                    inspect_map_pairs(f, fields)?;
+                }
+                None => {
+                    write!(f, "%{{")?;
+                    inspect_map_pairs(f, map.iter())?;
+                }
+            }
+            write!(f, "}}")
+        }
+        OwnedTerm::Pid(p) => write!(f, "#PID<0.{}.{}>", p.id, p.serial),
+        OwnedTerm::Port(p) => write!(f, "#Port<0.{}>", p.id),
+        OwnedTerm::Reference(r) => {
+            write!(f, "#Reference<0")?;
+            for id in r.ids.iter().rev() {
+                write!(f, ".{}", id)?;
+            }
+            write!(f, ">")
+        }
+        OwnedTerm::ExternalFun(fun) => {
+            write!(f, "&")?;
+            inspect_module(f, &fun.module)?;
+            write!(f, ".")?;
+            if is_elixir_identifier(fun.function.as_str()) {
+                write!(f, "{}", fun.function.as_str())?;
+            } else {
+                inspect_quoted(f, fun.function.as_str())?;
+            }
+            write!(f, "/{}", fun.arity)
+        }
+        OwnedTerm::InternalFun(fun) => {
+            write!(
+                f,
+                "#Function<{}.{}/{} in ",
+                fun.old_index, fun.old_uniq, fun.arity
+            )?;
+            inspect_module(f, &fun.module)?;
+            write!(f, ">")
+        }
+    }
+}
+
+fn inspect_separated(f: &mut fmt::Formatter<'_>, elements: &[OwnedTerm]) -> fmt::Result {
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        inspect_term(f, element)?;
+    }
+    Ok(())
+}
+
+fn is_keyword_pair(element: &OwnedTerm) -> bool {
+    matches!(element, OwnedTerm::Tuple(t) if t.len() == 2 && t[0].is_atom())
+}
+
+fn inspect_keyword_pairs<'a>(
+    f: &mut fmt::Formatter<'_>,
+    pairs: impl Iterator<Item = (&'a OwnedTerm, &'a OwnedTerm)>,
+) -> fmt::Result {
+    for (i, (key, value)) in pairs.enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        if let OwnedTerm::Atom(a) = key {
+            if is_elixir_identifier(a.as_str()) {
+                write!(f, "{}: ", a.as_str())?;
+            } else {
+                inspect_quoted(f, a.as_str())?;
+                write!(f, ": ")?;
+            }
+        }
+        inspect_term(f, value)?;
+    }
+    Ok(())
+}
+
+fn inspect_map_pairs<'a>(
+    f: &mut fmt::Formatter<'_>,
+    pairs: impl Iterator<Item = (&'a OwnedTerm, &'a OwnedTerm)> + Clone,
+) -> fmt::Result {
+    if pairs.clone().all(|(k, _)| k.is_atom()) {
+        return inspect_keyword_pairs(f, pairs);
+    }
+    for (i, (key, value)) in pairs.enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        inspect_term(f, key)?;
+        write!(f, " => ")?;
+        inspect_term(f, value)?;
+    }
+    Ok(())
+}
+
+fn inspect_atom(f: &mut fmt::Formatter<'_>, atom: &Atom) -> fmt::Result {
+    let name = atom.as_str();
+    match name {
+        "nil" | "true" | "false" => write!(f, "{}", name),
+        _ if elixir_alias(name).is_some() => inspect_module(f, atom),
+        _ if is_elixir_identifier(name) => write!(f, ":{}", name),
+        _ => {
+            write!(f, ":")?;
+            inspect_quoted(f, name)
+        }
+    }
+}
+
+/// Erlang modules print as atoms (`:lists`), Elixir ones as aliases (`Enum`).
+fn inspect_module(f: &mut fmt::Formatter<'_>, module: &Atom) -> fmt::Result {
+    match elixir_alias(module.as_str()) {
+        Some(alias) => write!(f, "{}", alias),
+        None => inspect_atom(f, module),
+    }
+}
+
+fn elixir_alias(name: &str) -> Option<&str> {
+    if name == "Elixir" {
+        return Some(name);
+    }
+    let alias = name.strip_prefix("Elixir.")?;
+    let valid = alias.split('.').all(|segment| {
+        let mut chars = segment.chars();
+        chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    valid.then_some(alias)
+}
+
+fn is_elixir_identifier(name: &str) -> bool {
+    let body = name.strip_suffix(['?', '!']).unwrap_or(name);
+    let mut chars = body.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@')
+}
+
+fn inspect_binary(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    match std::str::from_utf8(bytes) {
+        Ok(s)
+            if s.chars()
+                .all(|c| !c.is_control() || is_escapable_control(c)) =>
+        {
+            inspect_quoted(f, s)
+        }
+        _ => {
+            write!(f, "<<")?;
+            for (i, byte) in bytes.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", byte)?;
+            }
+            write!(f, ">>")
+        }
+    }
+}
+
+fn is_escapable_control(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\t' | '\u{b}' | '\u{8}' | '\u{c}' | '\u{1b}' | '\u{7}'
+    )
+}
+
+/// Like Elixir, only non-empty ASCII charlists print as `~c"..."`.
+fn ascii_charlist(elements: &[OwnedTerm]) -> Option<String> {
+    if elements.is_empty() {
+        return None;
+    }
+    elements
+        .iter()
+        .map(|e| match e {
+            OwnedTerm::Integer(i @ 32..=126) => Some(*i as u8 as char),
+            OwnedTerm::Integer(i) => u32::try_from(*i)
+                .ok()
+                .and_then(char::from_u32)
+                .filter(|&c| is_escapable_control(c)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn inspect_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            '\u{b}' => write!(f, "\\v")?,
+            '\u{8}' => write!(f, "\\b")?,
+            '\u{c}' => write!(f, "\\f")?,
+            '\u{1b}' => write!(f, "\\e")?,
+            '\u{7}' => write!(f, "\\a")?,
+            '#' if chars.peek() == Some(&'{') => write!(f, "\\#")?,
+            c if c.is_control() => write!(f, "\\x{{{:X}}}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
 pub enum OwnedTermIter<'a> {
     Slice(std::slice::Iter<'a, OwnedTerm>),
     Empty,
@@ -2129,109 +2711,72 @@ impl OwnedTerm {
     }
 }
 
-fn compare_int_bigint(i: i64, big: &BigInt) -> Ordering {
-    if big.digits.is_empty() {
-        return i.cmp(&0);
-    }
-
-    if big.sign.is_negative() {
-        if i >= 0 {
-            return Ordering::Greater;
-        }
-        if big.digits.len() > 8 {
-            return Ordering::Greater;
-        }
-        let abs_i = i.wrapping_neg() as u64;
-        let big_val = bigint_to_u64(big);
-        abs_i.cmp(&big_val).reverse()
-    } else {
-        if i < 0 {
-            return Ordering::Less;
-        }
-        if big.digits.len() > 8 {
-            return Ordering::Less;
-        }
-        let abs_i = i as u64;
-        let big_val = bigint_to_u64(big);
-        abs_i.cmp(&big_val)
+fn list_parts(term: &OwnedTerm) -> Option<(&[OwnedTerm], Option<&OwnedTerm>)> {
+    match term {
+        OwnedTerm::Nil => Some((&[], None)),
+        OwnedTerm::List(elements) => Some((elements, None)),
+        OwnedTerm::ImproperList { elements, tail } => Some((elements, Some(tail))),
+        _ => None,
     }
 }
 
-fn compare_bigint_int(big: &BigInt, i: i64) -> Ordering {
-    compare_int_bigint(i, big).reverse()
-}
-
-fn compare_bigint(a: &BigInt, b: &BigInt) -> Ordering {
-    match (a.sign, b.sign) {
-        (Sign::Positive, Sign::Negative) => Ordering::Greater,
-        (Sign::Negative, Sign::Positive) => Ordering::Less,
-        (Sign::Positive, Sign::Positive) => a
-            .digits
-            .len()
-            .cmp(&b.digits.len())
-            .then_with(|| a.digits.cmp(&b.digits)),
-        (Sign::Negative, Sign::Negative) => a
-            .digits
-            .len()
-            .cmp(&b.digits.len())
-            .then_with(|| a.digits.cmp(&b.digits))
-            .reverse(),
+/// Compares lists cell by cell, like Erlang, so that proper and improper lists
+/// sharing a prefix are ordered by what follows it.
+fn compare_lists(a: &OwnedTerm, b: &OwnedTerm) -> Ordering {
+    match (list_parts(a), list_parts(b)) {
+        (Some((a, a_tail)), Some((b, b_tail))) => compare_list_cells(a, a_tail, b, b_tail),
+        _ => Ordering::Equal,
     }
 }
 
-fn bigint_to_u64(big: &BigInt) -> u64 {
-    let mut result = 0u64;
-    for (i, &byte) in big.digits.iter().enumerate().take(8) {
-        result |= (byte as u64) << (i * 8);
+fn compare_list_cells(
+    a: &[OwnedTerm],
+    a_tail: Option<&OwnedTerm>,
+    b: &[OwnedTerm],
+    b_tail: Option<&OwnedTerm>,
+) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        match x.cmp(y) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
     }
-    result
-}
-
-fn compare_int_float(i: i64, f: f64) -> Ordering {
-    if f.is_nan() {
-        return Ordering::Less;
+    let common = a.len().min(b.len());
+    if a.len() > common {
+        compare_tail_with_cells(b_tail, &a[common..], a_tail).reverse()
+    } else if b.len() > common {
+        compare_tail_with_cells(a_tail, &b[common..], b_tail)
+    } else {
+        match (a_tail, b_tail) {
+            (None, None) => Ordering::Equal,
+            (Some(a), None) => a.cmp(&OwnedTerm::Nil),
+            (None, Some(b)) => OwnedTerm::Nil.cmp(b),
+            (Some(a), Some(b)) => a.cmp(b),
+        }
     }
-    let i_as_f = i as f64;
-    i_as_f.partial_cmp(&f).unwrap_or(Ordering::Equal)
 }
 
-fn compare_float_int(f: f64, i: i64) -> Ordering {
-    compare_int_float(i, f).reverse()
-}
-
-fn compare_bigint_float(big: &BigInt, f: f64) -> Ordering {
-    if f.is_nan() {
-        return Ordering::Less;
+/// Compares the tail of one list with the remaining, non-empty cells of another.
+fn compare_tail_with_cells(
+    tail: Option<&OwnedTerm>,
+    cells: &[OwnedTerm],
+    cells_tail: Option<&OwnedTerm>,
+) -> Ordering {
+    match tail {
+        None => Ordering::Less,
+        Some(tail) => match list_parts(tail) {
+            Some((elements, tail)) => compare_list_cells(elements, tail, cells, cells_tail),
+            None => term_type_order(tail).cmp(&term_type_order(&OwnedTerm::Nil)),
+        },
     }
-    let big_as_f = bigint_to_f64(big);
-    big_as_f.partial_cmp(&f).unwrap_or(Ordering::Equal)
-}
-
-fn compare_float_bigint(f: f64, big: &BigInt) -> Ordering {
-    compare_bigint_float(big, f).reverse()
 }
 
-fn bigint_to_f64(big: &BigInt) -> f64 {
-    let mut result = 0f64;
-    let mut scale = 1.0f64;
-
-    for &byte in big.digits.iter() {
-        let contribution = (byte as f64) * scale;
-        if contribution.is_infinite() || scale.is_infinite() {
-            return if big.sign.is_negative() {
-                f64::NEG_INFINITY
-            } else {
-                f64::INFINITY
-            };
-        }
-        result += contribution;
-        scale *= 256.0;
-    }
-
-    if big.sign.is_negative() {
-        -result
-    } else {
-        result
+fn binary_bytes(term: &OwnedTerm) -> &[u8] {
+    match term {
+        OwnedTerm::Binary(bytes) | OwnedTerm::BitBinary { bytes, .. } => bytes,
+        OwnedTerm::SharedBinary(bytes) => bytes,
+        OwnedTerm::String(s) => s.as_bytes(),
+        _ => &[],
     }
 }
 
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::errors::DecodeError;
+use crate::atom_table;
+use crate::errors::{DecodeError, ParseBigIntError, TermConversionError};
 use crate::term::OwnedTerm;
 use bytes::Bytes;
 use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::fmt;
 use std::ops::Deref;
+use std::str::FromStr;
 use std::sync::{Arc, LazyLock};
 
 const COMMON_ATOMS: [(&str, usize); 14] = [
@@ -69,15 +72,18 @@ impl Atom {
     pub const NORMAL: &'static str = "normal";
     pub const SHUTDOWN: &'static str = "shutdown";
 
+    /// Goes through the [process-wide atom table](crate::atom_table) if one is installed.
+    /// When the table is full, the atom owns its name regardless of the overflow policy.
     pub fn new<S: AsRef<str>>(name: S) -> Self {
         let name_ref = name.as_ref();
 
-        for (atom_str, idx) in &COMMON_ATOMS {
-            if *atom_str == name_ref {
-                return Atom {
-                    name: CACHED_ATOMS[*idx].clone(),
-                };
-            }
+        if let Some(atom) = Self::predefined(name_ref) {
+            return atom;
+        }
+        if let Some(table) = atom_table::global() {
+            return table
+                .intern(name_ref)
+                .unwrap_or_else(|_| Self::from_shared(Arc::from(name_ref)));
         }
 
         Atom {
@@ -85,11 +91,36 @@ impl Atom {
         }
     }
 
+    pub(crate) fn predefined(name: &str) -> Option<Self> {
+        COMMON_ATOMS
+            .iter()
+            .find(|(atom_str, _)| *atom_str == name)
+            .map(|(_, idx)| Atom {
+                name: CACHED_ATOMS[*idx].clone(),
+            })
+    }
+
+    pub(crate) fn from_shared(name: Arc<str>) -> Self {
+        Atom { name }
+    }
+
+    /// Whether both atoms share one allocation, as interned and predefined atoms
+    /// with the same name do. Equality checks this before comparing names.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Atom) -> bool {
+        Arc::ptr_eq(&self.name, &other.name)
+    }
+
     #[inline]
     pub fn as_str(&self) -> &str {
         &self.name
     }
 
+    /// Whether this is one of the common atoms every decoded copy shares.
+    pub(crate) fn is_predefined(&self) -> bool {
+        COMMON_ATOMS.iter().any(|(name, _)| *name == self.as_str())
+    }
+
     #[inline]
     #[must_use]
     pub fn len(&self) -> usize {
@@ -245,6 +276,403 @@ impl BigInt {
             digits,
         }
     }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.digits.iter().all(|&d| d == 0)
+    }
+
+    /// True for values below zero; a zero magnitude is never negative.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.sign.is_negative() && !self.is_zero()
+    }
+
+    /// The nearest `f64`, or an infinity if the value is out of range.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = Self::magnitude_to_f64(self.significant_digits());
+        if self.is_negative() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Rounds once: the top 64 bits are converted with any lower set bits
+    /// folded into the last one, so values just above a tie still round up.
+    fn magnitude_to_f64(digits: &[u8]) -> f64 {
+        let Some(&msd) = digits.last() else {
+            return 0.0;
+        };
+        let bit_len = digits.len() * 8 - msd.leading_zeros() as usize;
+        if bit_len <= 64 {
+            return digits
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &d| acc << 8 | d as u64) as f64;
+        }
+
+        let (low, high) = digits.split_at(digits.len() - 9);
+        let high = high
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &d| acc << 8 | d as u128);
+        let dropped = 8 - msd.leading_zeros();
+        let sticky = high & ((1 << dropped) - 1) != 0 || low.iter().any(|&d| d != 0);
+        let top = (high >> dropped) as u64 | sticky as u64;
+
+        let exponent = bit_len - 64;
+        if exponent > 1023 {
+            return f64::INFINITY;
+        }
+        (top as f64) * f64::from_bits((1023 + exponent as u64) << 52)
+    }
+
+    /// Digits without the most significant zero bytes.
+    fn significant_digits(&self) -> &[u8] {
+        let len = self
+            .digits
+            .iter()
+            .rposition(|&d| d != 0)
+            .map_or(0, |i| i + 1);
+        &self.digits[..len]
+    }
+
+    fn magnitude_u128(&self) -> Option<u128> {
+        let digits = self.significant_digits();
+        if digits.len() > 16 {
+            return None;
+        }
+        Some(
+            digits
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &d| (acc << 8) | d as u128),
+        )
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(n: u128) -> Self {
+        let mut digits = n.to_le_bytes().to_vec();
+        let len = digits.iter().rposition(|&d| d != 0).map_or(0, |i| i + 1);
+        digits.truncate(len);
+        BigInt::new(Sign::Positive, digits)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(n: i128) -> Self {
+        let mut big = BigInt::from(n.unsigned_abs());
+        big.sign = Sign::from(n < 0);
+        big
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(n: u64) -> Self {
+        BigInt::from(n as u128)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> Self {
+        BigInt::from(n as i128)
+    }
+}
+
+impl TryFrom<&BigInt> for u128 {
+    type Error = TermConversionError;
+
+    fn try_from(big: &BigInt) -> Result<Self, Self::Error> {
+        if big.is_negative() {
+            return Err(TermConversionError::OutOfRange);
+        }
+        big.magnitude_u128().ok_or(TermConversionError::OutOfRange)
+    }
+}
+
+impl TryFrom<&BigInt> for i128 {
+    type Error = TermConversionError;
+
+    fn try_from(big: &BigInt) -> Result<Self, Self::Error> {
+        let magnitude = big
+            .magnitude_u128()
+            .ok_or(TermConversionError::OutOfRange)?;
+        let value = if big.is_negative() {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        };
+        value.ok_or(TermConversionError::OutOfRange)
+    }
+}
+
+impl TryFrom<BigInt> for u128 {
+    type Error = TermConversionError;
+
+    fn try_from(big: BigInt) -> Result<Self, Self::Error> {
+        u128::try_from(&big)
+    }
+}
+
+impl TryFrom<BigInt> for i128 {
+    type Error = TermConversionError;
+
+    fn try_from(big: BigInt) -> Result<Self, Self::Error> {
+        i128::try_from(&big)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<&BigInt> for num_bigint::BigInt {
+    fn from(big: &BigInt) -> Self {
+        let sign = if big.is_zero() {
+            num_bigint::Sign::NoSign
+        } else if big.sign.is_negative() {
+            num_bigint::Sign::Minus
+        } else {
+            num_bigint::Sign::Plus
+        };
+        num_bigint::BigInt::from_bytes_le(sign, &big.digits)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<BigInt> for num_bigint::BigInt {
+    fn from(big: BigInt) -> Self {
+        num_bigint::BigInt::from(&big)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<&num_bigint::BigInt> for BigInt {
+    fn from(n: &num_bigint::BigInt) -> Self {
+        let (sign, mut digits) = n.to_bytes_le();
+        if sign == num_bigint::Sign::NoSign {
+            digits.clear();
+        }
+        BigInt::new(sign == num_bigint::Sign::Minus, digits)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<num_bigint::BigInt> for BigInt {
+    fn from(n: num_bigint::BigInt) -> Self {
+        BigInt::from(&n)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(
+            !self.is_negative(),
+            "",
+            &magnitude_to_decimal(self.significant_digits()),
+        )
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    /// Parses an optionally signed decimal integer, e.g. `-340282366920938463463374607431768211456`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, decimal) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if decimal.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+
+        // Little-endian base 256 magnitude, multiplied by 10 per decimal digit
+        let mut digits: Vec<u8> = Vec::with_capacity(decimal.len() / 2 + 1);
+        for c in decimal.bytes() {
+            if !c.is_ascii_digit() {
+                return Err(ParseBigIntError::InvalidDigit);
+            }
+            let mut carry = (c - b'0') as u32;
+            for d in digits.iter_mut() {
+                let v = *d as u32 * 10 + carry;
+                *d = v as u8;
+                carry = v >> 8;
+            }
+            if carry > 0 {
+                digits.push(carry as u8);
+            }
+        }
+
+        Ok(BigInt::new(negative, digits))
+    }
+}
+
+/// Converts a little-endian magnitude to its decimal representation.
+fn magnitude_to_decimal(digits: &[u8]) -> String {
+    const CHUNK: u64 = 1_000_000_000;
+
+    let mut be: Vec<u8> = digits
+        .iter()
+        .rev()
+        .copied()
+        .skip_while(|&b| b == 0)
+        .collect();
+    if be.is_empty() {
+        return "0".to_string();
+    }
+
+    let mut chunks = Vec::new();
+    while !be.is_empty() {
+        let mut remainder = 0u64;
+        let mut quotient = Vec::with_capacity(be.len());
+        for &byte in &be {
+            let current = (remainder << 8) | byte as u64;
+            let digit = current / CHUNK;
+            remainder = current % CHUNK;
+            if !quotient.is_empty() || digit != 0 {
+                quotient.push(digit as u8);
+            }
+        }
+        chunks.push(remainder);
+        be = quotient;
+    }
+
+    let mut s = chunks.pop().unwrap_or(0).to_string();
+    for chunk in chunks.iter().rev() {
+        s.push_str(&format!("{:09}", chunk));
+    }
+    s
+}
+
+// Number comparisons follow Erlang term order: integers and floats compare
+// by their exact numeric values.
+
+pub(crate) fn compare_bigint(a: &BigInt, b: &BigInt) -> Ordering {
+    // Equal values with different representations (leading zero bytes, a negative
+    // zero) are ordered by their representation to stay consistent with `Eq`
+    compare_bigint_values(a, b)
+        .then_with(|| a.digits.len().cmp(&b.digits.len()))
+        .then_with(|| b.sign.is_negative().cmp(&a.sign.is_negative()))
+}
+
+fn compare_bigint_values(a: &BigInt, b: &BigInt) -> Ordering {
+    match (a.is_negative(), b.is_negative()) {
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+        (negative, _) => {
+            let ordering = compare_magnitudes(a.significant_digits(), b.significant_digits());
+            if negative {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+    }
+}
+
+/// Compares bitstrings bit by bit, like Erlang. `a_bits` and `b_bits` are the number of
+/// significant bits in the last byte, 8 for binaries.
+pub(crate) fn compare_bitstrings(a: &[u8], a_bits: u8, b: &[u8], b_bits: u8) -> Ordering {
+    let bit_len = |bytes: &[u8], bits: u8| match bytes.len() {
+        0 => 0,
+        len => (len - 1) * 8 + usize::from(bits),
+    };
+    let (a_len, b_len) = (bit_len(a, a_bits), bit_len(b, b_bits));
+    let common = a_len.min(b_len);
+    let full = common / 8;
+    match a[..full].cmp(&b[..full]) {
+        Ordering::Equal => {}
+        other => return other,
+    }
+    let rest = common % 8;
+    if rest > 0 {
+        let mask = 0xFFu8 << (8 - rest);
+        match (a[full] & mask).cmp(&(b[full] & mask)) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+    }
+    a_len.cmp(&b_len)
+}
+
+pub(crate) fn compare_int_bigint(i: i64, big: &BigInt) -> Ordering {
+    compare_bigint_values(&BigInt::from(i), big)
+}
+
+pub(crate) fn compare_bigint_int(big: &BigInt, i: i64) -> Ordering {
+    compare_int_bigint(i, big).reverse()
+}
+
+pub(crate) fn compare_int_float(i: i64, f: f64) -> Ordering {
+    // Integers up to 2^53 convert to f64 exactly
+    if i.unsigned_abs() <= 1 << 53 {
+        if f.is_nan() {
+            return Ordering::Less;
+        }
+        return (i as f64).partial_cmp(&f).unwrap_or(Ordering::Equal);
+    }
+    compare_bigint_float(&BigInt::from(i), f)
+}
+
+pub(crate) fn compare_float_int(f: f64, i: i64) -> Ordering {
+    compare_int_float(i, f).reverse()
+}
+
+pub(crate) fn compare_bigint_float(big: &BigInt, f: f64) -> Ordering {
+    if f.is_nan() {
+        return Ordering::Less;
+    }
+    if f.is_infinite() {
+        return if f > 0.0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+    }
+
+    let truncated = f.trunc();
+    let integral = BigInt::new(truncated < 0.0, integral_f64_magnitude(truncated));
+    compare_bigint_values(big, &integral).then_with(|| {
+        // Equal integral parts: the fraction of `f` decides
+        0.0.partial_cmp(&(f - truncated)).unwrap_or(Ordering::Equal)
+    })
+}
+
+pub(crate) fn compare_float_bigint(f: f64, big: &BigInt) -> Ordering {
+    compare_bigint_float(big, f).reverse()
+}
+
+fn compare_magnitudes(a: &[u8], b: &[u8]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// The exact little-endian magnitude of a finite float with no fractional part.
+fn integral_f64_magnitude(f: f64) -> Vec<u8> {
+    let bits = f.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    if exponent == 0 {
+        // Zero or subnormal: both truncate to zero
+        return Vec::new();
+    }
+    let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+    let shift = exponent - 1075;
+    if shift <= 0 {
+        return BigInt::from(mantissa >> (-shift).min(63)).digits;
+    }
+
+    let shift = shift as usize;
+    let mut digits = vec![0u8; shift / 8];
+    let mut carry = 0u16;
+    for byte in mantissa.to_le_bytes() {
+        let v = ((byte as u16) << (shift % 8)) | carry;
+        digits.push(v as u8);
+        carry = v >> 8;
+    }
+    digits.push(carry as u8);
+    digits
 }
 
 /// Represents an Erlang PID originating from a remote node.
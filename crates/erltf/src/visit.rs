@@ -0,0 +1,352 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generic traversal of terms.
+//!
+//! [`TermVisitor`] and [`TermVisitorMut`] walk a term recursively, calling a
+//! hook for every sub-term, atom, binary and pid along the way. Override the
+//! hooks of interest; the defaults keep walking. To stop descending into a
+//! term, override `visit_term` and skip the call to [`walk_term`].
+//!
+//! [`OwnedTerm::subterms`], [`OwnedTerm::fold`] and [`OwnedTerm::transform`]
+//! keep their own stack instead of recursing, so they do not overflow the
+//! thread's stack on arbitrarily deep terms. Dropping such a term still
+//! recurses, as does everything else that walks it, including the visitors.
+//!
+//! ```
+//! use erltf::visit::{TermVisitor, TermVisitorMut, walk_term_mut};
+//! use erltf::types::Atom;
+//! use erltf::{OwnedTerm, erl_atom, erl_list, erl_tuple};
+//!
+//! struct CountAtoms(usize);
+//!
+//! impl TermVisitor<'_> for CountAtoms {
+//!     fn visit_atom(&mut self, _atom: &Atom) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! struct Redact;
+//!
+//! impl TermVisitorMut for Redact {
+//!     fn visit_term_mut(&mut self, term: &mut OwnedTerm) {
+//!         if term.as_binary().is_some() {
+//!             *term = OwnedTerm::binary(b"***".to_vec());
+//!         } else {
+//!             walk_term_mut(self, term);
+//!         }
+//!     }
+//! }
+//!
+//! let mut term = erl_list![
+//!     erl_tuple![erl_atom!("user"), OwnedTerm::binary(b"joe".to_vec())],
+//!     erl_tuple![erl_atom!("password"), OwnedTerm::binary(b"secret".to_vec())]
+//! ];
+//!
+//! let mut counter = CountAtoms(0);
+//! counter.visit_term(&term);
+//! assert_eq!(counter.0, 2);
+//!
+//! Redact.visit_term_mut(&mut term);
+//! assert_eq!(term.proplist_get_atom_key("password"), Some(&OwnedTerm::binary(b"***".to_vec())));
+//! ```
+
+use crate::term::OwnedTerm;
+use crate::types::{
+    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
+};
+use std::collections::BTreeMap;
+use std::mem;
+
+/// A read-only, recursive walk over a term. `'t` is the lifetime of the term
+/// being visited, so visitors can keep references into it.
+pub trait TermVisitor<'t> {
+    fn visit_term(&mut self, term: &'t OwnedTerm) {
+        walk_term(self, term);
+    }
+
+    fn visit_atom(&mut self, _atom: &'t Atom) {}
+
+    fn visit_integer(&mut self, _value: i64) {}
+
+    fn visit_float(&mut self, _value: f64) {}
+
+    fn visit_bigint(&mut self, _value: &'t BigInt) {}
+
+    /// Called for `Binary` and `SharedBinary`.
+    fn visit_binary(&mut self, _bytes: &'t [u8]) {}
+
+    /// Called for a `BitBinary`, whose last byte holds only `bits` bits.
+    /// Defaults to [`TermVisitor::visit_binary`] with all of its bytes.
+    fn visit_bitstring(&mut self, bytes: &'t [u8], _bits: u8) {
+        self.visit_binary(bytes);
+    }
+
+    fn visit_string(&mut self, _value: &'t str) {}
+
+    fn visit_pid(&mut self, pid: &'t ExternalPid) {
+        self.visit_atom(&pid.node);
+    }
+
+    fn visit_port(&mut self, port: &'t ExternalPort) {
+        self.visit_atom(&port.node);
+    }
+
+    fn visit_reference(&mut self, reference: &'t ExternalReference) {
+        self.visit_atom(&reference.node);
+    }
+
+    fn visit_external_fun(&mut self, fun: &'t ExternalFun) {
+        self.visit_atom(&fun.module);
+        self.visit_atom(&fun.function);
+    }
+
+    fn visit_internal_fun(&mut self, fun: &'t InternalFun) {
+        self.visit_atom(&fun.module);
+        self.visit_pid(&fun.pid);
+        for var in &fun.free_vars {
+            self.visit_term(var);
+        }
+    }
+}
+
+/// Calls the hook for `term` itself, or `visit_term` on each of its children.
+pub fn walk_term<'t, V>(visitor: &mut V, term: &'t OwnedTerm)
+where
+    V: TermVisitor<'t> + ?Sized,
+{
+    match term {
+        OwnedTerm::Atom(atom) => visitor.visit_atom(atom),
+        OwnedTerm::Integer(value) => visitor.visit_integer(*value),
+        OwnedTerm::Float(value) => visitor.visit_float(*value),
+        OwnedTerm::BigInt(value) => visitor.visit_bigint(value),
+        OwnedTerm::Binary(bytes) => visitor.visit_binary(bytes),
+        OwnedTerm::SharedBinary(bytes) => visitor.visit_binary(bytes),
+        OwnedTerm::BitBinary { bytes, bits } => visitor.visit_bitstring(bytes, *bits),
+        OwnedTerm::String(value) => visitor.visit_string(value),
+        OwnedTerm::Pid(pid) => visitor.visit_pid(pid),
+        OwnedTerm::Port(port) => visitor.visit_port(port),
+        OwnedTerm::Reference(reference) => visitor.visit_reference(reference),
+        OwnedTerm::ExternalFun(fun) => visitor.visit_external_fun(fun),
+        OwnedTerm::InternalFun(fun) => visitor.visit_internal_fun(fun),
+        OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
+            for element in elements {
+                visitor.visit_term(element);
+            }
+        }
+        OwnedTerm::ImproperList { elements, tail } => {
+            for element in elements {
+                visitor.visit_term(element);
+            }
+            visitor.visit_term(tail);
+        }
+        OwnedTerm::Map(map) => {
+            for (key, value) in map {
+                visitor.visit_term(key);
+                visitor.visit_term(value);
+            }
+        }
+        OwnedTerm::Nil => {}
+    }
+}
+
+/// A recursive walk that can modify terms in place.
+///
+/// Map keys are visited too; maps are rebuilt afterwards so that they stay
+/// ordered, and keys that become equal are merged.
+pub trait TermVisitorMut {
+    fn visit_term_mut(&mut self, term: &mut OwnedTerm) {
+        walk_term_mut(self, term);
+    }
+
+    fn visit_atom_mut(&mut self, _atom: &mut Atom) {}
+
+    fn visit_pid_mut(&mut self, pid: &mut ExternalPid) {
+        self.visit_atom_mut(&mut pid.node);
+    }
+
+    fn visit_port_mut(&mut self, port: &mut ExternalPort) {
+        self.visit_atom_mut(&mut port.node);
+    }
+
+    fn visit_reference_mut(&mut self, reference: &mut ExternalReference) {
+        self.visit_atom_mut(&mut reference.node);
+    }
+}
+
+/// Like [`walk_term`], for [`TermVisitorMut`].
+pub fn walk_term_mut<V>(visitor: &mut V, term: &mut OwnedTerm)
+where
+    V: TermVisitorMut + ?Sized,
+{
+    match term {
+        OwnedTerm::Atom(atom) => visitor.visit_atom_mut(atom),
+        OwnedTerm::Pid(pid) => visitor.visit_pid_mut(pid),
+        OwnedTerm::Port(port) => visitor.visit_port_mut(port),
+        OwnedTerm::Reference(reference) => visitor.visit_reference_mut(reference),
+        OwnedTerm::ExternalFun(fun) => {
+            visitor.visit_atom_mut(&mut fun.module);
+            visitor.visit_atom_mut(&mut fun.function);
+        }
+        OwnedTerm::InternalFun(fun) => {
+            visitor.visit_atom_mut(&mut fun.module);
+            visitor.visit_pid_mut(&mut fun.pid);
+            for var in &mut fun.free_vars {
+                visitor.visit_term_mut(var);
+            }
+        }
+        OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
+            for element in elements {
+                visitor.visit_term_mut(element);
+            }
+        }
+        OwnedTerm::ImproperList { elements, tail } => {
+            for element in elements {
+                visitor.visit_term_mut(element);
+            }
+            visitor.visit_term_mut(tail);
+        }
+        OwnedTerm::Map(map) => {
+            *map = mem::take(map)
+                .into_iter()
+                .map(|(mut key, mut value)| {
+                    visitor.visit_term_mut(&mut key);
+                    visitor.visit_term_mut(&mut value);
+                    (key, value)
+                })
+                .collect();
+        }
+        _ => {}
+    }
+}
+
+/// Iterator over a term and all of its sub-terms, depth first, parents
+/// before children. Returned by [`OwnedTerm::subterms`].
+///
+/// Map keys come before their values. The free variables of funs are included.
+pub struct Subterms<'t> {
+    stack: Vec<&'t OwnedTerm>,
+}
+
+impl<'t> Subterms<'t> {
+    pub(crate) fn new(term: &'t OwnedTerm) -> Self {
+        Subterms { stack: vec![term] }
+    }
+}
+
+impl<'t> Iterator for Subterms<'t> {
+    type Item = &'t OwnedTerm;
+
+    fn next(&mut self) -> Option<&'t OwnedTerm> {
+        let term = self.stack.pop()?;
+        match term {
+            OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
+                self.stack.extend(elements.iter().rev());
+            }
+            OwnedTerm::ImproperList { elements, tail } => {
+                self.stack.push(tail);
+                self.stack.extend(elements.iter().rev());
+            }
+            OwnedTerm::Map(map) => {
+                for (key, value) in map.iter().rev() {
+                    self.stack.push(value);
+                    self.stack.push(key);
+                }
+            }
+            OwnedTerm::InternalFun(fun) => {
+                self.stack.extend(fun.free_vars.iter().rev());
+            }
+            _ => {}
+        }
+        Some(term)
+    }
+}
+
+/// Rebuilds a term bottom-up, children first, without recursion.
+pub(crate) fn transform<F>(term: OwnedTerm, mut f: F) -> OwnedTerm
+where
+    F: FnMut(OwnedTerm) -> OwnedTerm,
+{
+    enum Frame {
+        Enter(OwnedTerm),
+        List(usize),
+        Tuple(usize),
+        ImproperList(usize),
+        Map(usize),
+        InternalFun(Box<InternalFun>, usize),
+    }
+
+    let mut work = vec![Frame::Enter(term)];
+    let mut done: Vec<OwnedTerm> = Vec::new();
+
+    while let Some(frame) = work.pop() {
+        let rebuilt = match frame {
+            Frame::Enter(term) => {
+                let (frame, children) = match term {
+                    OwnedTerm::List(elements) => (Frame::List(elements.len()), elements),
+                    OwnedTerm::Tuple(elements) => (Frame::Tuple(elements.len()), elements),
+                    OwnedTerm::ImproperList { mut elements, tail } => {
+                        elements.push(*tail);
+                        (Frame::ImproperList(elements.len()), elements)
+                    }
+                    OwnedTerm::Map(map) => {
+                        let len = map.len();
+                        let children = map.into_iter().flat_map(|(k, v)| [k, v]).collect();
+                        (Frame::Map(len * 2), children)
+                    }
+                    OwnedTerm::InternalFun(mut fun) => {
+                        let free_vars = mem::take(&mut fun.free_vars);
+                        (Frame::InternalFun(fun, free_vars.len()), free_vars)
+                    }
+                    leaf => {
+                        done.push(f(leaf));
+                        continue;
+                    }
+                };
+                work.push(frame);
+                work.extend(children.into_iter().rev().map(Frame::Enter));
+                continue;
+            }
+            Frame::List(len) => OwnedTerm::List(take_last(&mut done, len)),
+            Frame::Tuple(len) => OwnedTerm::Tuple(take_last(&mut done, len)),
+            Frame::ImproperList(len) => {
+                let mut elements = take_last(&mut done, len);
+                let tail = elements.pop().unwrap_or(OwnedTerm::Nil);
+                OwnedTerm::ImproperList {
+                    elements,
+                    tail: Box::new(tail),
+                }
+            }
+            Frame::Map(len) => {
+                let mut entries = take_last(&mut done, len).into_iter();
+                let mut map = BTreeMap::new();
+                while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                    map.insert(key, value);
+                }
+                OwnedTerm::Map(map)
+            }
+            Frame::InternalFun(mut fun, len) => {
+                fun.free_vars = take_last(&mut done, len);
+                OwnedTerm::InternalFun(fun)
+            }
+        };
+        done.push(f(rebuilt));
+    }
+
+    done.pop().unwrap_or(OwnedTerm::Nil)
+}
+
+fn take_last(done: &mut Vec<OwnedTerm>, len: usize) -> Vec<OwnedTerm> {
+    done.split_off(done.len() - len)
+}
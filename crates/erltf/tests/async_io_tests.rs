@@ -0,0 +1,188 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "tokio")]
+
+use bytes::Bytes;
+use erltf::{
+    DecodeError, DecodeOptions, EncodeOptions, OwnedTerm, decode_from_async_reader,
+    decode_from_async_reader_with_options, encode, encode_to_async_writer,
+    encode_to_async_writer_with_options, encode_with_options, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
+use tokio::io::AsyncWriteExt;
+
+fn sample() -> OwnedTerm {
+    erl_tuple![
+        erl_atom!("reply"),
+        erl_map! { erl_atom!("id") => erl_int!(42) },
+        erl_list![OwnedTerm::binary(b"abc".to_vec()), OwnedTerm::Float(1.5)],
+        OwnedTerm::SharedBinary(Bytes::from(vec![9u8; 5000]))
+    ]
+}
+
+#[tokio::test]
+async fn test_encode_to_async_writer_matches_encode() {
+    let term = sample();
+    let mut out = Vec::new();
+    encode_to_async_writer(&term, &mut out).await.unwrap();
+    assert_eq!(out, encode(&term).unwrap());
+
+    let options = EncodeOptions::compressed();
+    let mut out = Vec::new();
+    encode_to_async_writer_with_options(&term, &mut out, &options)
+        .await
+        .unwrap();
+    assert_eq!(out, encode_with_options(&term, &options).unwrap());
+}
+
+#[tokio::test]
+async fn test_decode_from_async_reader_does_not_over_read() {
+    let first = encode(&sample()).unwrap();
+    let second = encode(&erl_atom!("next")).unwrap();
+    let mut stream = first.clone();
+    stream.extend_from_slice(&second);
+
+    let mut reader = stream.as_slice();
+    let term = decode_from_async_reader(&mut reader).await.unwrap();
+    assert_eq!(term, Some(sample()));
+    assert_eq!(reader, second.as_slice());
+}
+
+#[tokio::test]
+async fn test_decode_from_async_reader_reads_back_to_back_terms() {
+    let terms = vec![
+        erl_atom!("start"),
+        sample(),
+        OwnedTerm::Nil,
+        erl_list![erl_int!(1), erl_int!(2)],
+    ];
+    let mut stream = Vec::new();
+    for term in &terms {
+        encode_to_async_writer(term, &mut stream).await.unwrap();
+    }
+    // A compressed term has no length prefix; the one after it must be intact
+    encode_to_async_writer_with_options(&sample(), &mut stream, &EncodeOptions::compressed())
+        .await
+        .unwrap();
+    encode_to_async_writer(&erl_atom!("end"), &mut stream)
+        .await
+        .unwrap();
+
+    let mut reader = stream.as_slice();
+    let mut decoded = Vec::new();
+    while let Some(term) = decode_from_async_reader(&mut reader).await.unwrap() {
+        decoded.push(term);
+    }
+
+    let mut expected = terms;
+    expected.push(sample());
+    expected.push(erl_atom!("end"));
+    assert_eq!(decoded, expected);
+}
+
+#[tokio::test]
+async fn test_decode_from_async_reader_with_small_reads() {
+    let encoded = encode(&sample()).unwrap();
+    let (mut writer, mut reader) = tokio::io::duplex(3);
+
+    let write = async move {
+        writer.write_all(&encoded).await.unwrap();
+        writer.write_all(&encoded).await.unwrap();
+    };
+    let read = async {
+        let first = decode_from_async_reader(&mut reader).await.unwrap();
+        let second = decode_from_async_reader(&mut reader).await.unwrap();
+        let end = decode_from_async_reader(&mut reader).await.unwrap();
+        (first, second, end)
+    };
+
+    let ((), (first, second, end)) = tokio::join!(write, read);
+    assert_eq!(first, Some(sample()));
+    assert_eq!(second, Some(sample()));
+    assert_eq!(end, None);
+}
+
+#[tokio::test]
+async fn test_decode_from_async_reader_errors() {
+    let encoded = encode(&sample()).unwrap();
+    let mut truncated = &encoded[..encoded.len() - 1];
+    assert_eq!(
+        decode_from_async_reader(&mut truncated).await,
+        Err(DecodeError::UnexpectedEof)
+    );
+
+    let mut invalid: &[u8] = &[130, 97, 1];
+    assert_eq!(
+        decode_from_async_reader(&mut invalid).await,
+        Err(DecodeError::InvalidVersion {
+            expected: 131,
+            actual: 130
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_decode_from_async_reader_with_options() {
+    let encoded = encode(&sample()).unwrap();
+    let options = DecodeOptions::new().with_max_binary_size(1000);
+    let mut reader = encoded.as_slice();
+    assert_eq!(
+        decode_from_async_reader_with_options(&mut reader, &options).await,
+        Err(DecodeError::BinaryTooLarge {
+            size: 5000,
+            max: 1000
+        })
+    );
+
+    let options = DecodeOptions::new().with_max_depth(1);
+    let mut reader = encoded.as_slice();
+    assert_eq!(
+        decode_from_async_reader_with_options(&mut reader, &options).await,
+        Err(DecodeError::DepthLimitExceeded { max: 1 })
+    );
+}
+
+#[tokio::test]
+async fn test_decode_from_async_reader_large_compressed_term() {
+    // Poorly compressible, so the compressed input is long and read a byte at a time
+    let mut state = 0x2545_f491u32;
+    let payload: Vec<u8> = (0..256 * 1024)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % 64) as u8
+        })
+        .collect();
+    let term = erl_tuple![erl_atom!("blob"), OwnedTerm::Binary(payload)];
+    let mut stream = Vec::new();
+    encode_to_async_writer_with_options(&term, &mut stream, &EncodeOptions::compressed())
+        .await
+        .unwrap();
+    assert_eq!(stream[1], 80);
+    encode_to_async_writer(&erl_atom!("next"), &mut stream)
+        .await
+        .unwrap();
+
+    let mut reader = stream.as_slice();
+    assert_eq!(
+        decode_from_async_reader(&mut reader).await.unwrap(),
+        Some(term)
+    );
+    assert_eq!(
+        decode_from_async_reader(&mut reader).await.unwrap(),
+        Some(erl_atom!("next"))
+    );
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::atom_table::{self, AtomTable, OverflowPolicy};
+use erltf::decoder::{AtomCache, decode_with_cache};
+use erltf::{
+    Atom, AtomTableFull, DecodeError, DecodeOptions, OwnedTerm, decode, decode_with_atom_cache,
+    decode_with_options, encode, erl_atom, erl_list, erl_tuple,
+};
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_intern_shares_allocations() {
+    let table = AtomTable::new(10, OverflowPolicy::Error);
+    let a = table.intern("session").unwrap();
+    let b = table.intern(&String::from("session")).unwrap();
+
+    assert!(a.ptr_eq(&b));
+    assert_eq!(a, b);
+    assert_eq!(table.len(), 1);
+    assert!(table.contains("session"));
+    assert!(table.get("session").unwrap().ptr_eq(&a));
+    assert_eq!(table.get("other"), None);
+}
+
+#[test]
+fn test_predefined_atoms_are_not_counted() {
+    let table = AtomTable::new(0, OverflowPolicy::Error);
+    let ok = table.intern("ok").unwrap();
+
+    assert!(ok.ptr_eq(&Atom::new("ok")));
+    assert!(table.is_empty());
+    assert!(!table.contains("ok"));
+    assert!(table.get("ok").is_some());
+}
+
+#[test]
+fn test_overflow_policies() {
+    let table = AtomTable::new(2, OverflowPolicy::Error);
+    table.intern("a").unwrap();
+    table.intern("b").unwrap();
+
+    assert_eq!(table.intern("c"), Err(AtomTableFull { max: 2 }));
+    // Names already in the table are still found
+    assert!(table.intern("a").is_ok());
+
+    table.set_overflow_policy(OverflowPolicy::Uninterned);
+    assert_eq!(table.overflow_policy(), OverflowPolicy::Uninterned);
+    let c1 = table.intern("c").unwrap();
+    let c2 = table.intern("c").unwrap();
+    assert_eq!(c1, c2);
+    assert!(!c1.ptr_eq(&c2));
+    assert!(!table.contains("c"));
+    assert_eq!(table.len(), 2);
+
+    table.set_max_atoms(3);
+    let c3 = table.intern("c").unwrap();
+    assert!(c3.ptr_eq(&table.intern("c").unwrap()));
+    assert_eq!(table.len(), 3);
+}
+
+#[test]
+fn test_purge_unused() {
+    let table = AtomTable::new(2, OverflowPolicy::Error);
+    let kept = table.intern("kept").unwrap();
+    table.intern("dropped").unwrap();
+
+    assert_eq!(table.purge_unused(), 1);
+    assert_eq!(table.len(), 1);
+    assert!(table.contains("kept"));
+    assert!(table.intern("another").is_ok());
+    assert!(table.intern("kept").unwrap().ptr_eq(&kept));
+}
+
+#[test]
+fn test_concurrent_interning_respects_max_atoms() {
+    let table = Arc::new(AtomTable::new(100, OverflowPolicy::Error));
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            let table = Arc::clone(&table);
+            thread::spawn(move || {
+                (0..50)
+                    .filter(|i| {
+                        table
+                            .intern(&format!("atom_{}", (t * 50 + i) % 150))
+                            .is_ok()
+                    })
+                    .count()
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(table.len(), 100);
+    let shared = table
+        .get("atom_0")
+        .or_else(|| table.get("atom_149"))
+        .unwrap();
+    assert!(shared.ptr_eq(&table.intern(shared.as_str()).unwrap()));
+}
+
+// The only test in this binary that installs the process-wide table
+#[test]
+fn test_decoder_uses_the_global_table() {
+    atom_table::install(AtomTable::new(1000, OverflowPolicy::Error)).unwrap();
+    assert!(atom_table::install(AtomTable::default()).is_err());
+    let table = atom_table::global().unwrap();
+
+    let record = erl_tuple![erl_atom!("user_record"), erl_atom!("active")];
+    let encoded = encode(&erl_list![record.clone(), record]).unwrap();
+    let decoded = decode(&encoded).unwrap();
+    let OwnedTerm::List(records) = &decoded else {
+        panic!("expected a list");
+    };
+    let tags: Vec<&Atom> = records
+        .iter()
+        .map(|r| r.as_tuple().unwrap()[0].as_atom().unwrap())
+        .collect();
+    assert!(tags[0].ptr_eq(tags[1]));
+    assert!(tags[0].ptr_eq(&Atom::new("user_record")));
+    assert!(table.contains("user_record"));
+
+    table.set_max_atoms(table.len());
+    let unseen = encode(&erl_atom!("unseen")).unwrap();
+    assert_eq!(
+        decode(&unseen),
+        Err(DecodeError::AtomTableFull { max: table.len() })
+    );
+
+    // Atom::new never fails and falls back to an uninterned atom
+    assert!(!Atom::new("unseen").ptr_eq(&Atom::new("unseen")));
+
+    table.set_overflow_policy(OverflowPolicy::Uninterned);
+    assert_eq!(decode(&unseen).unwrap(), erl_atom!("unseen"));
+    assert!(!table.contains("unseen"));
+
+    // The cache-aware decoders report a full table too
+    table.set_overflow_policy(OverflowPolicy::Error);
+    let full = DecodeError::AtomTableFull { max: table.len() };
+    assert_eq!(decode_with_cache(&unseen).unwrap_err(), full);
+    assert_eq!(
+        decode_with_atom_cache(&unseen, &mut AtomCache::new()).unwrap_err(),
+        full
+    );
+
+    // Safe mode rejects unknown atoms before they reach the table
+    table.set_max_atoms(1000);
+    let options = DecodeOptions::safe(["user_record"]);
+    // Encoded by hand, since building the atom would intern it
+    let mut hostile = vec![131, 119, 7];
+    hostile.extend_from_slice(b"hostile");
+    assert_eq!(
+        decode_with_options(&hostile, &options),
+        Err(DecodeError::UnsafeAtom("hostile".to_string()))
+    );
+    assert!(!table.contains("hostile"));
+
+    let known = decode_with_options(&encode(&erl_atom!("user_record")).unwrap(), &options).unwrap();
+    assert!(known.as_atom().unwrap().ptr_eq(&Atom::new("user_record")));
+}
@@ -0,0 +1,242 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::{ParseBigIntError, TermConversionError};
+use erltf::types::BigInt;
+use erltf::{OwnedTerm, decode, encode, erl_int};
+use proptest::prelude::*;
+use std::cmp::Ordering;
+
+// ============================================================================
+// Decimal formatting and parsing
+// ============================================================================
+
+#[test]
+fn test_bigint_display() {
+    let two_to_64 = BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(two_to_64.to_string(), "18446744073709551616");
+    assert_eq!(
+        BigInt::new(true, vec![0xff; 16]).to_string(),
+        "-340282366920938463463374607431768211455"
+    );
+    assert_eq!(BigInt::new(true, vec![]).to_string(), "0");
+    assert_eq!(BigInt::new(false, vec![5, 0, 0]).to_string(), "5");
+    assert_eq!(format!("{:>6}", BigInt::from(-42i64)), "   -42");
+}
+
+#[test]
+fn test_bigint_from_str() {
+    assert_eq!(
+        "18446744073709551616".parse::<BigInt>().unwrap(),
+        BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0, 1])
+    );
+    assert_eq!(
+        "-256".parse::<BigInt>().unwrap(),
+        BigInt::new(true, vec![0, 1])
+    );
+    assert_eq!("+7".parse::<BigInt>().unwrap(), BigInt::from(7u64));
+    assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+    assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+    assert_eq!("12a".parse::<BigInt>(), Err(ParseBigIntError::InvalidDigit));
+}
+
+#[test]
+fn test_owned_term_display_uses_decimal() {
+    let term = OwnedTerm::BigInt(BigInt::from(u128::MAX));
+    assert_eq!(term.to_string(), u128::MAX.to_string());
+}
+
+// ============================================================================
+// Primitive conversions
+// ============================================================================
+
+#[test]
+fn test_bigint_to_i128_and_u128() {
+    assert_eq!(i128::try_from(BigInt::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(i128::try_from(BigInt::from(i128::MAX)), Ok(i128::MAX));
+    assert_eq!(u128::try_from(BigInt::from(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(
+        i128::try_from(BigInt::from(u128::MAX)),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert_eq!(
+        u128::try_from(BigInt::from(-1i64)),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert_eq!(
+        u128::try_from(BigInt::new(false, vec![1; 17])),
+        Err(TermConversionError::OutOfRange)
+    );
+    // Leading zero bytes do not count towards the size
+    assert_eq!(
+        u128::try_from(BigInt::new(false, {
+            let mut digits = vec![1];
+            digits.resize(20, 0);
+            digits
+        })),
+        Ok(1)
+    );
+    assert_eq!(i128::try_from(BigInt::new(true, vec![0])), Ok(0));
+}
+
+#[test]
+fn test_owned_term_from_wide_integers() {
+    assert_eq!(OwnedTerm::from(42i128), erl_int!(42));
+    assert_eq!(
+        OwnedTerm::from(i64::MIN as i128),
+        OwnedTerm::Integer(i64::MIN)
+    );
+    assert_eq!(
+        OwnedTerm::from(u64::MAX),
+        OwnedTerm::BigInt(BigInt::from(u64::MAX))
+    );
+    assert_eq!(
+        OwnedTerm::from(i128::MIN),
+        OwnedTerm::BigInt(BigInt::from(i128::MIN))
+    );
+    assert_eq!(OwnedTerm::from(7u128), erl_int!(7));
+}
+
+#[test]
+fn test_owned_term_to_wide_integers() {
+    assert_eq!(i128::try_from(erl_int!(-5)), Ok(-5));
+    assert_eq!(u128::try_from(OwnedTerm::from(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(
+        u128::try_from(erl_int!(-5)),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert!(matches!(
+        i128::try_from(OwnedTerm::Float(1.0)),
+        Err(TermConversionError::WrongType { .. })
+    ));
+}
+
+#[test]
+fn test_wide_integers_roundtrip_through_codec() {
+    for value in [u64::MAX as i128 + 1, i128::MIN, -(1i128 << 70)] {
+        let term = OwnedTerm::from(value);
+        let decoded = decode(&encode(&term).unwrap()).unwrap();
+        assert_eq!(i128::try_from(decoded), Ok(value));
+    }
+}
+
+#[test]
+fn test_bigint_to_f64() {
+    assert_eq!(BigInt::from(1u64 << 60).to_f64(), (1u64 << 60) as f64);
+    assert_eq!(BigInt::from(-3i64).to_f64(), -3.0);
+    assert_eq!(BigInt::new(false, vec![0xff; 200]).to_f64(), f64::INFINITY);
+}
+
+#[test]
+fn test_bigint_to_f64_rounds_once() {
+    // Accumulating byte by byte rounds 2^53 + 1 down to 2^53 first and then
+    // loses the half-ulp tie-breaker; both must round up.
+    for value in [0x2000000000000180u128, (1 << 64) + (1 << 11) + 1] {
+        assert_eq!(BigInt::from(value).to_f64(), value as f64);
+        assert_eq!(BigInt::from(-(value as i128)).to_f64(), -(value as f64));
+    }
+    assert_eq!(BigInt::from(u128::MAX).to_f64(), u128::MAX as f64);
+}
+
+// ============================================================================
+// Ordering
+// ============================================================================
+
+#[test]
+fn test_bigint_ordering_compares_most_significant_digits_first() {
+    let a = OwnedTerm::BigInt(BigInt::new(false, vec![0xff, 0, 0, 0, 0, 0, 0, 0, 1]));
+    let b = OwnedTerm::BigInt(BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0, 2]));
+    assert!(a < b);
+
+    let neg_a = OwnedTerm::BigInt(BigInt::new(true, vec![0xff, 0, 0, 0, 0, 0, 0, 0, 1]));
+    let neg_b = OwnedTerm::BigInt(BigInt::new(true, vec![0, 0, 0, 0, 0, 0, 0, 0, 2]));
+    assert!(neg_a > neg_b);
+}
+
+#[test]
+fn test_bigint_ordering_ignores_leading_zero_bytes() {
+    let padded = OwnedTerm::BigInt(BigInt::new(false, vec![5, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+    assert_eq!(padded.cmp(&erl_int!(5)), Ordering::Equal);
+    assert!(padded < erl_int!(6));
+    let negative_zero = OwnedTerm::BigInt(BigInt::new(true, vec![0]));
+    assert_eq!(negative_zero.cmp(&erl_int!(0)), Ordering::Equal);
+}
+
+#[test]
+fn test_bigint_float_ordering_is_exact() {
+    let two_to_64 = OwnedTerm::BigInt(BigInt::from(1u128 << 64));
+    let plus_one = OwnedTerm::BigInt(BigInt::from((1u128 << 64) + 1));
+    let float = OwnedTerm::Float(18446744073709551616.0);
+
+    assert_eq!(two_to_64.cmp(&float), Ordering::Equal);
+    assert!(plus_one > float);
+    assert!(float < plus_one);
+    assert!(OwnedTerm::Float(1.0e300) > plus_one);
+    assert!(OwnedTerm::Float(f64::NEG_INFINITY) < two_to_64);
+    assert!(OwnedTerm::BigInt(BigInt::from(-(1i128 << 64))) < OwnedTerm::Float(-1.8e19));
+}
+
+#[test]
+fn test_integer_float_ordering_is_exact() {
+    // 2^53 + 1 is not representable as f64 and rounds to 2^53
+    let int = erl_int!((1i64 << 53) + 1);
+    let float = OwnedTerm::Float((1u64 << 53) as f64);
+    assert!(int > float);
+    assert_eq!(erl_int!(3).cmp(&OwnedTerm::Float(3.0)), Ordering::Equal);
+    assert!(erl_int!(i64::MAX) < OwnedTerm::Float(9.3e18));
+    assert!(erl_int!(-3) > OwnedTerm::Float(-3.5));
+}
+
+proptest! {
+    #[test]
+    fn test_prop_bigint_decimal_roundtrip(value in any::<i128>()) {
+        let big = BigInt::from(value);
+        prop_assert_eq!(big.to_string(), value.to_string());
+        prop_assert_eq!(value.to_string().parse::<BigInt>().unwrap(), big);
+    }
+
+    #[test]
+    fn test_prop_wide_integer_ordering(a in any::<i128>(), b in any::<i128>()) {
+        prop_assert_eq!(OwnedTerm::from(a).cmp(&OwnedTerm::from(b)), a.cmp(&b));
+    }
+
+    #[test]
+    fn test_prop_bigint_float_ordering(a in -(1i64 << 52)..(1i64 << 52)) {
+        let big = OwnedTerm::BigInt(BigInt::from(a));
+        prop_assert_eq!(big.cmp(&OwnedTerm::Float(a as f64)), Ordering::Equal);
+        prop_assert!(big < OwnedTerm::Float(a as f64 + 0.5));
+        prop_assert!(big > OwnedTerm::Float(a as f64 - 0.5));
+    }
+}
+
+// ============================================================================
+// num-bigint
+// ============================================================================
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn test_num_bigint_conversions() {
+    let n: num_bigint::BigInt = "-123456789012345678901234567890".parse().unwrap();
+    let big = BigInt::from(&n);
+    assert_eq!(big.to_string(), "-123456789012345678901234567890");
+    assert_eq!(num_bigint::BigInt::from(&big), n);
+
+    let term = OwnedTerm::from(n.clone());
+    assert_eq!(num_bigint::BigInt::try_from(term), Ok(n));
+    assert_eq!(OwnedTerm::from(num_bigint::BigInt::from(42)), erl_int!(42));
+    assert_eq!(
+        num_bigint::BigInt::from(BigInt::new(true, vec![])),
+        num_bigint::BigInt::from(0)
+    );
+}
@@ -0,0 +1,326 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::bits::{BitBuilder, BitReader, Endianness, Signedness};
+use erltf::{BigInt, BitSyntaxError, OwnedTerm, Sign, decode, encode, erl_atom, erl_int};
+
+#[test]
+fn test_whole_bytes_build_a_binary() {
+    // <<1, 2:16, "ab">>
+    let term = BitBuilder::new()
+        .integer(1, 8, Endianness::Big)
+        .integer(2, 16, Endianness::Big)
+        .binary(b"ab")
+        .build()
+        .unwrap();
+    assert_eq!(term, OwnedTerm::Binary(vec![1, 0, 2, b'a', b'b']));
+}
+
+#[test]
+fn test_partial_bytes_build_a_bit_binary() {
+    // <<5:3, 1:2>> =:= <<2#10101:5>>
+    let term = BitBuilder::new()
+        .integer(5, 3, Endianness::Big)
+        .integer(1, 2, Endianness::Big)
+        .build()
+        .unwrap();
+    assert_eq!(
+        term,
+        OwnedTerm::BitBinary {
+            bytes: vec![0b1010_1000],
+            bits: 5
+        }
+    );
+    assert_eq!(decode(&encode(&term).unwrap()).unwrap(), term);
+}
+
+#[test]
+fn test_integer_segments_match_erlang() {
+    // <<16#123:12/little>> =:= <<16#23, 1:4>>
+    let little = BitBuilder::new()
+        .integer(0x123, 12, Endianness::Little)
+        .build()
+        .unwrap();
+    assert_eq!(
+        little,
+        OwnedTerm::BitBinary {
+            bytes: vec![0x23, 0x10],
+            bits: 4
+        }
+    );
+
+    // <<16#123:12>> =:= <<16#12, 3:4>>
+    let big = BitBuilder::new()
+        .integer(0x123, 12, Endianness::Big)
+        .build()
+        .unwrap();
+    assert_eq!(
+        big,
+        OwnedTerm::BitBinary {
+            bytes: vec![0x12, 0x30],
+            bits: 4
+        }
+    );
+
+    // <<-1:4, 256:8>> keeps only the low bits
+    let truncated = BitBuilder::new()
+        .integer(-1, 4, Endianness::Big)
+        .integer(256, 8, Endianness::Big)
+        .build()
+        .unwrap();
+    assert_eq!(
+        truncated,
+        OwnedTerm::BitBinary {
+            bytes: vec![0xf0, 0x00],
+            bits: 4
+        }
+    );
+}
+
+#[test]
+fn test_native_endianness() {
+    let native = BitBuilder::new()
+        .integer(0x0102, 16, Endianness::Native)
+        .build()
+        .unwrap();
+    let expected = if cfg!(target_endian = "little") {
+        vec![2, 1]
+    } else {
+        vec![1, 2]
+    };
+    assert_eq!(native, OwnedTerm::Binary(expected));
+}
+
+#[test]
+fn test_integer_round_trip_at_any_offset() {
+    for offset in 0..8 {
+        for size in [1usize, 7, 8, 12, 31, 64] {
+            for endianness in [Endianness::Big, Endianness::Little] {
+                let value = if size == 1 {
+                    -1
+                } else {
+                    3i64.wrapping_sub(1 << (size - 1))
+                };
+                let term = BitBuilder::new()
+                    .integer(0, offset, Endianness::Big)
+                    .integer(value, size, endianness)
+                    .integer(1, 1, Endianness::Big)
+                    .build()
+                    .unwrap();
+
+                let mut reader = BitReader::from_term(&term).unwrap();
+                reader.read_u64(offset, Endianness::Big).unwrap();
+                assert_eq!(reader.read_i64(size, endianness).unwrap(), value);
+                assert_eq!(reader.read_u64(1, Endianness::Big).unwrap(), 1);
+                assert!(reader.is_empty());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_read_integer_wide_segments() {
+    let term = BitBuilder::new()
+        .integer(u64::MAX, 72, Endianness::Little)
+        .integer(-2, 100, Endianness::Big)
+        .build()
+        .unwrap();
+
+    let mut reader = BitReader::from_term(&term).unwrap();
+    assert_eq!(
+        reader
+            .read_integer(72, Endianness::Little, Signedness::Unsigned)
+            .unwrap(),
+        OwnedTerm::from(u64::MAX as i128)
+    );
+    assert_eq!(
+        reader
+            .read_integer(100, Endianness::Big, Signedness::Signed)
+            .unwrap(),
+        erl_int!(-2)
+    );
+
+    let mut reader = BitReader::new(&[0xff; 20]);
+    assert_eq!(
+        reader
+            .read_integer(160, Endianness::Big, Signedness::Unsigned)
+            .unwrap(),
+        OwnedTerm::BigInt(BigInt::new(Sign::Positive, vec![0xff; 20]))
+    );
+}
+
+#[test]
+fn test_big_integer_segments() {
+    let value = BigInt::new(
+        Sign::Negative,
+        vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+    );
+    let term = BitBuilder::new()
+        .big_integer(&value, 140, Endianness::Big)
+        .build()
+        .unwrap();
+
+    let mut reader = BitReader::from_term(&term).unwrap();
+    assert_eq!(
+        reader
+            .read_integer(140, Endianness::Big, Signedness::Signed)
+            .unwrap(),
+        OwnedTerm::BigInt(value)
+    );
+}
+
+#[test]
+fn test_float_segments() {
+    let term = BitBuilder::new()
+        .float(1.5, 64, Endianness::Big)
+        .float(-0.25, 32, Endianness::Little)
+        .float(65504.0, 16, Endianness::Big)
+        .float(5.960464477539063e-8, 16, Endianness::Big)
+        .build()
+        .unwrap();
+    let OwnedTerm::Binary(bytes) = &term else {
+        panic!("expected a binary");
+    };
+    assert_eq!(&bytes[..8], &1.5f64.to_be_bytes());
+    assert_eq!(&bytes[8..12], &(-0.25f32).to_le_bytes());
+    assert_eq!(&bytes[12..], &[0x7b, 0xff, 0x00, 0x01]);
+
+    let mut reader = BitReader::from_term(&term).unwrap();
+    assert_eq!(reader.read_float(64, Endianness::Big).unwrap(), 1.5);
+    assert_eq!(reader.read_float(32, Endianness::Little).unwrap(), -0.25);
+    assert_eq!(reader.read_float(16, Endianness::Big).unwrap(), 65504.0);
+    assert_eq!(
+        reader.read_float(16, Endianness::Big).unwrap(),
+        5.960464477539063e-8
+    );
+}
+
+#[test]
+fn test_float_errors() {
+    assert_eq!(
+        BitBuilder::new().float(1.0, 24, Endianness::Big).build(),
+        Err(BitSyntaxError::InvalidFloatSize(24))
+    );
+    assert_eq!(
+        BitBuilder::new()
+            .float(65520.0, 16, Endianness::Big)
+            .build(),
+        Err(BitSyntaxError::FloatOutOfRange(65520.0))
+    );
+    assert_eq!(
+        BitBuilder::new().float(1e300, 32, Endianness::Big).build(),
+        Err(BitSyntaxError::FloatOutOfRange(1e300))
+    );
+
+    // NaN does not match a float segment and the reader does not advance
+    let mut reader = BitReader::new(&[0x7f, 0xc0, 0, 0]);
+    assert!(matches!(
+        reader.read_float(32, Endianness::Big),
+        Err(BitSyntaxError::FloatOutOfRange(_))
+    ));
+    assert_eq!(reader.remaining_bits(), 32);
+}
+
+#[test]
+fn test_unicode_segments() {
+    let term = BitBuilder::new()
+        .utf8('é')
+        .utf16('😀', Endianness::Big)
+        .utf16('a', Endianness::Little)
+        .utf32('ß', Endianness::Big)
+        .build()
+        .unwrap();
+    assert_eq!(
+        term,
+        OwnedTerm::Binary(vec![
+            0xc3, 0xa9, 0xd8, 0x3d, 0xde, 0x00, b'a', 0, 0, 0, 0, 0xdf
+        ])
+    );
+
+    let mut reader = BitReader::from_term(&term).unwrap();
+    assert_eq!(reader.read_utf8().unwrap(), 'é');
+    assert_eq!(reader.read_utf16(Endianness::Big).unwrap(), '😀');
+    assert_eq!(reader.read_utf16(Endianness::Little).unwrap(), 'a');
+    assert_eq!(reader.read_utf32(Endianness::Big).unwrap(), 'ß');
+    assert!(reader.is_empty());
+
+    let mut invalid = BitReader::new(&[0xed, 0xa0, 0x80]);
+    assert_eq!(
+        invalid.read_utf8(),
+        Err(BitSyntaxError::InvalidUnicode("utf8"))
+    );
+    assert_eq!(invalid.remaining_bits(), 24);
+    assert_eq!(
+        BitReader::new(&[0, 0x11, 0, 0]).read_utf32(Endianness::Big),
+        Err(BitSyntaxError::InvalidUnicode("utf32"))
+    );
+}
+
+#[test]
+fn test_binary_and_bitstring_tails() {
+    let tail = OwnedTerm::BitBinary {
+        bytes: vec![0xab, 0xc0],
+        bits: 3,
+    };
+    let term = BitBuilder::new()
+        .integer(1, 4, Endianness::Big)
+        .binary(b"xy")
+        .term(&tail)
+        .build()
+        .unwrap();
+    assert_eq!(
+        term,
+        OwnedTerm::BitBinary {
+            bytes: vec![0x17, 0x87, 0x9a, 0xbc],
+            bits: 7
+        }
+    );
+
+    let mut reader = BitReader::from_term(&term).unwrap();
+    assert_eq!(reader.read_u64(4, Endianness::Big).unwrap(), 1);
+    assert_eq!(reader.read_binary(2).unwrap(), b"xy");
+    assert_eq!(
+        reader.rest_binary(),
+        Err(BitSyntaxError::NotByteAligned { bits: 11 })
+    );
+    assert_eq!(reader.rest(), tail);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn test_reader_errors() {
+    let mut reader = BitReader::new(&[1, 2]);
+    assert_eq!(
+        reader.read_u64(17, Endianness::Big),
+        Err(BitSyntaxError::UnexpectedEnd {
+            needed: 17,
+            remaining: 16
+        })
+    );
+    assert_eq!(
+        reader.read_u64(65, Endianness::Big),
+        Err(BitSyntaxError::SizeTooLarge { size: 65, max: 64 })
+    );
+    assert_eq!(reader.rest_binary().unwrap(), vec![1, 2]);
+    assert_eq!(reader.rest(), OwnedTerm::Binary(vec![]));
+
+    assert!(matches!(
+        BitReader::from_term(&erl_atom!("bits")),
+        Err(BitSyntaxError::NotABitstring)
+    ));
+    assert_eq!(
+        BitBuilder::new().term(&erl_atom!("bits")).build(),
+        Err(BitSyntaxError::NotABitstring)
+    );
+}
@@ -24,8 +24,8 @@ fn test_borrowed_owned_equivalence_basic_types() {
     let owned_int = OwnedTerm::integer(42);
     let borrowed_int = BorrowedTerm::Integer(42);
 
-    let owned_float = OwnedTerm::float(3.14);
-    let borrowed_float = BorrowedTerm::Float(3.14);
+    let owned_float = OwnedTerm::float(2.75);
+    let borrowed_float = BorrowedTerm::Float(2.75);
 
     assert_eq!(
         owned_int.cmp(&owned_float),
@@ -46,7 +46,7 @@ fn test_borrowed_owned_equivalence_strings_vs_binaries() {
 
 #[test]
 fn test_borrowed_owned_equivalence_type_ordering() {
-    let owned_terms = vec![
+    let owned_terms = [
         OwnedTerm::binary(vec![1]),
         OwnedTerm::integer(5),
         OwnedTerm::atom("test"),
@@ -55,7 +55,7 @@ fn test_borrowed_owned_equivalence_type_ordering() {
         OwnedTerm::list(vec![OwnedTerm::integer(1)]),
     ];
 
-    let borrowed_terms = vec![
+    let borrowed_terms = [
         BorrowedTerm::Binary(Cow::Borrowed(&[1])),
         BorrowedTerm::Integer(5),
         BorrowedTerm::Atom(Cow::Borrowed("test")),
@@ -247,3 +247,28 @@ fn test_negative_bigint_larger_than_8_bytes_vs_integer() {
 
     assert_eq!(large_negative_bigint.cmp(&min_int), Ordering::Less);
 }
+
+#[test]
+fn test_proper_vs_improper_list_ordering() {
+    let improper = BorrowedTerm::ImproperList {
+        elements: vec![BorrowedTerm::Integer(0)],
+        tail: Box::new(BorrowedTerm::Integer(0)),
+    };
+    let proper = BorrowedTerm::List(vec![BorrowedTerm::Integer(0), BorrowedTerm::Integer(1)]);
+
+    assert_eq!(improper.cmp(&proper), Ordering::Less);
+    assert_eq!(proper.cmp(&improper), Ordering::Greater);
+    assert_eq!(BorrowedTerm::Nil.cmp(&improper), Ordering::Less);
+}
+
+#[test]
+fn test_binary_vs_bit_binary_ordering() {
+    let binary = BorrowedTerm::Binary(Cow::Borrowed(&[1, 2]));
+    let bit_binary = BorrowedTerm::BitBinary {
+        bytes: Cow::Borrowed(&[1, 2, 0b1000_0000]),
+        bits: 1,
+    };
+
+    assert_eq!(binary.cmp(&bit_binary), Ordering::Less);
+    assert_eq!(bit_binary.cmp(&binary), Ordering::Greater);
+}
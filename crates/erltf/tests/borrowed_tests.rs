@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use erltf::{BorrowedTerm, ParsingContext, PathSegment, decode, decode_borrowed, encode};
+use erltf::types::{Atom, BigInt, ExternalPid};
+use erltf::{
+    AtomCache, BorrowedTerm, EncodeOptions, KeyValueAccess, OwnedTerm, ParsingContext, PathSegment,
+    decode, decode_borrowed, decode_borrowed_with_atom_cache, decode_with_atom_cache, encode,
+    encode_borrowed, encode_borrowed_to_writer, encode_borrowed_with_options,
+    encode_with_dist_header_multi, encode_with_options, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
 use std::borrow::Cow;
 
 #[test]
@@ -185,3 +192,165 @@ fn test_parse_context_path_display() {
     let path = ctx.display_path();
     assert_eq!(path, "root[0][5].key");
 }
+
+fn mixed_term() -> OwnedTerm {
+    let pid = ExternalPid::new(Atom::new("node@host"), 42, 0, 3);
+    erl_tuple![
+        erl_atom!("msg"),
+        OwnedTerm::Pid(pid),
+        erl_map! {
+            erl_atom!("count") => erl_int!(1_000_000),
+            erl_int!(1) => OwnedTerm::Float(1.5),
+            OwnedTerm::Float(2.0) => OwnedTerm::binary(b"payload".to_vec())
+        },
+        OwnedTerm::charlist("abc"),
+        OwnedTerm::improper_list(vec![erl_int!(1)], erl_atom!("tail")),
+        OwnedTerm::BitBinary {
+            bytes: vec![0xff, 0xf0],
+            bits: 4
+        },
+        OwnedTerm::BigInt(BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0, 1])),
+        erl_atom!("caf\u{e9}"),
+        erl_list![]
+    ]
+}
+
+#[test]
+fn test_encode_borrowed_matches_owned_encoding() {
+    let term = mixed_term();
+    let data = encode(&term).unwrap();
+    let borrowed = decode_borrowed(&data).unwrap();
+
+    assert_eq!(encode_borrowed(&borrowed).unwrap(), data);
+
+    for options in [
+        EncodeOptions::deterministic(),
+        EncodeOptions::new().with_minor_version(0),
+        EncodeOptions::compressed().with_compression_threshold(0),
+    ] {
+        assert_eq!(
+            encode_borrowed_with_options(&borrowed, &options).unwrap(),
+            encode_with_options(&borrowed.to_owned(), &options).unwrap()
+        );
+    }
+
+    let mut written = Vec::new();
+    encode_borrowed_to_writer(&borrowed, &mut written).unwrap();
+    assert_eq!(written, data);
+}
+
+#[test]
+fn test_encode_borrowed_deterministic_empty_list_is_nil() {
+    let empty = BorrowedTerm::List(vec![]);
+    assert_eq!(
+        encode_borrowed_with_options(&empty, &EncodeOptions::deterministic()).unwrap(),
+        vec![131, 106]
+    );
+}
+
+#[test]
+fn test_encode_borrowed_filtered_without_owned_copies() {
+    let term = erl_list![
+        erl_tuple![erl_atom!("keep"), OwnedTerm::binary(vec![1; 128])],
+        erl_tuple![erl_atom!("drop"), erl_int!(2)],
+        erl_tuple![erl_atom!("keep"), erl_int!(3)]
+    ];
+    let data = encode(&term).unwrap();
+
+    let BorrowedTerm::List(elements) = decode_borrowed(&data).unwrap() else {
+        panic!("expected a list");
+    };
+    let kept: Vec<BorrowedTerm<'_>> = elements
+        .into_iter()
+        .filter(|e| {
+            e.tuple_get(0)
+                .is_some_and(|tag| tag.is_atom_with_name("keep"))
+        })
+        .collect();
+    let filtered = BorrowedTerm::List(kept);
+    assert!(filtered.is_borrowed());
+
+    assert_eq!(
+        decode(&encode_borrowed(&filtered).unwrap()).unwrap(),
+        erl_list![
+            erl_tuple![erl_atom!("keep"), OwnedTerm::binary(vec![1; 128])],
+            erl_tuple![erl_atom!("keep"), erl_int!(3)]
+        ]
+    );
+}
+
+#[test]
+fn test_decode_borrowed_with_atom_cache() {
+    let control = erl_tuple![erl_int!(6), erl_atom!(""), erl_atom!("rex")];
+    let payload = erl_tuple![
+        erl_atom!("call"),
+        erl_atom!("rabbit"),
+        erl_atom!("status"),
+        erl_list![],
+        erl_atom!("user")
+    ];
+    let encoded = encode_with_dist_header_multi(&[&control, &payload]).unwrap();
+
+    let mut owned_cache = AtomCache::new();
+    let (owned_control, owned_payload) =
+        decode_with_atom_cache(&encoded, &mut owned_cache).unwrap();
+
+    let mut cache = AtomCache::new();
+    let (borrowed_control, borrowed_payload) =
+        decode_borrowed_with_atom_cache(&encoded, &mut cache).unwrap();
+    assert_eq!(borrowed_control.to_owned(), owned_control);
+    assert_eq!(borrowed_payload.map(|p| p.to_owned()), owned_payload);
+    assert_eq!(owned_control, control);
+
+    let plain = encode(&control).unwrap();
+    let mut cache = AtomCache::new();
+    let (control_only, none) = decode_borrowed_with_atom_cache(&plain, &mut cache).unwrap();
+    assert_eq!(control_only.to_owned(), control);
+    assert!(none.is_none());
+}
+
+#[test]
+fn test_decode_borrowed_with_missing_cache_entry() {
+    // ATOM_CACHE_REF to an index that was never populated
+    let data = [131, 68, 0, 82, 7];
+    let mut cache = AtomCache::new();
+    assert!(decode_borrowed_with_atom_cache(&data, &mut cache).is_err());
+    assert!(decode_borrowed(&[131, 82, 7]).is_err());
+}
+
+#[test]
+fn test_borrowed_key_value_access() {
+    let pid = ExternalPid::new(Atom::new("node@host"), 1, 2, 3);
+    let info = erl_map! {
+        erl_atom!("name") => OwnedTerm::binary(b"worker".to_vec()),
+        erl_atom!("count") => erl_int!(7),
+        erl_atom!("active") => erl_atom!("true"),
+        erl_atom!("status") => erl_atom!("running"),
+        erl_atom!("pid") => OwnedTerm::Pid(pid.clone()),
+        erl_atom!("initial_call") => erl_tuple![erl_atom!("gen_server"), erl_atom!("init_it"), erl_int!(6)]
+    };
+    let proplist = erl_list![
+        erl_tuple![erl_atom!("count"), erl_int!(7)],
+        erl_tuple![erl_atom!("label"), OwnedTerm::charlist("abc")]
+    ];
+
+    let map_data = encode(&info).unwrap();
+    let map = decode_borrowed(&map_data).unwrap();
+    assert_eq!(map.kv_get_i64("count"), Some(7));
+    assert_eq!(map.kv_get_bool("active"), Some(true));
+    assert_eq!(map.kv_get_atom("status"), Some("running"));
+    assert_eq!(map.kv_get_string("name"), Some("worker".to_string()));
+    assert_eq!(map.kv_get_pid("pid"), Some(&pid));
+    assert_eq!(
+        map.kv_get_mfa_string("initial_call"),
+        Some("gen_server:init_it/6".to_string())
+    );
+    assert_eq!(map.kv_get_i64_or("missing", -1), -1);
+
+    let list_data = encode(&proplist).unwrap();
+    let list = decode_borrowed(&list_data).unwrap();
+    assert!(list.is_proplist());
+    assert_eq!(list.kv_get_i64("count"), Some(7));
+    assert_eq!(list.kv_get_string("label"), Some("abc".to_string()));
+    assert_eq!(list.kv_get("name"), None);
+}
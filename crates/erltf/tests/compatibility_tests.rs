@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use erltf::types::{Atom, ExternalPid, ExternalPort, ExternalReference};
+use erltf::types::{Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference};
 use erltf::{
-    AtomCache, OwnedTerm, decode, decode_with_atom_cache, encode, encode_with_dist_header_multi,
-    erl_atom, erl_int, erl_list, erl_tuple,
+    AtomCache, EncodeError, EncodeOptions, OwnedTerm, decode, decode_borrowed,
+    decode_with_atom_cache, encode, encode_to_writer_with_options, encode_with_dist_header_multi,
+    encode_with_options, encoded_size, erl_atom, erl_int, erl_list, erl_map, erl_tuple,
 };
 use std::io::Write;
 
@@ -474,6 +475,89 @@ fn test_decompress_nested_structure() {
     assert_eq!(decoded, nested);
 }
 
+#[test]
+fn test_encode_compressed_large_binary() {
+    let binary_term = OwnedTerm::Binary(vec![42u8; 10000]);
+    let uncompressed = encode(&binary_term).unwrap();
+    let compressed = encode_with_options(&binary_term, &EncodeOptions::compressed()).unwrap();
+
+    assert_eq!(compressed[0], 131);
+    assert_eq!(compressed[1], 80);
+    assert_eq!(
+        u32::from_be_bytes(compressed[2..6].try_into().unwrap()) as usize,
+        uncompressed.len() - 1
+    );
+    assert!(compressed.len() < uncompressed.len());
+
+    let decoded = decode(&compressed).expect("Failed to decode compressed binary");
+    assert_eq!(decoded, binary_term);
+}
+
+#[test]
+fn test_encode_compressed_nested_structure() {
+    let rows: Vec<OwnedTerm> = (0..500)
+        .map(|i| erl_tuple![erl_atom!("row"), erl_int!(i), erl_atom!("undefined")])
+        .collect();
+    let term = OwnedTerm::List(rows);
+
+    for level in 1..=9 {
+        let options = EncodeOptions::new().with_compression(level);
+        let compressed = encode_with_options(&term, &options).unwrap();
+        assert_eq!(compressed[1], 80);
+        assert_eq!(decode(&compressed).unwrap(), term);
+    }
+}
+
+#[test]
+fn test_encode_compressed_below_threshold_stays_uncompressed() {
+    let binary_term = OwnedTerm::Binary(vec![0u8; 1000]);
+    let options = EncodeOptions::compressed().with_compression_threshold(2000);
+
+    let encoded = encode_with_options(&binary_term, &options).unwrap();
+    assert_eq!(encoded, encode(&binary_term).unwrap());
+
+    let options = EncodeOptions::compressed().with_compression_threshold(1000);
+    let encoded = encode_with_options(&binary_term, &options).unwrap();
+    assert_eq!(encoded[1], 80);
+}
+
+#[test]
+fn test_encode_compressed_falls_back_when_not_smaller() {
+    let atom = erl_atom!("ok");
+    let encoded = encode_with_options(&atom, &EncodeOptions::compressed()).unwrap();
+    assert_eq!(encoded, encode(&atom).unwrap());
+}
+
+#[test]
+fn test_encode_compression_level_zero_disables_compression() {
+    let binary_term = OwnedTerm::Binary(vec![7u8; 4096]);
+    let options = EncodeOptions::new().with_compression(0);
+    let encoded = encode_with_options(&binary_term, &options).unwrap();
+    assert_eq!(encoded, encode(&binary_term).unwrap());
+}
+
+#[test]
+fn test_encode_invalid_compression_level() {
+    let options = EncodeOptions::new().with_compression(10);
+    let result = encode_with_options(&erl_atom!("ok"), &options);
+    assert!(matches!(
+        result,
+        Err(EncodeError::InvalidCompressionLevel { level: 10 })
+    ));
+}
+
+#[test]
+fn test_encode_to_writer_compressed() {
+    let binary_term = OwnedTerm::Binary(vec![1u8; 8192]);
+    let mut out = Vec::new();
+    encode_to_writer_with_options(&binary_term, &mut out, &EncodeOptions::compressed()).unwrap();
+    assert_eq!(
+        out,
+        encode_with_options(&binary_term, &EncodeOptions::compressed()).unwrap()
+    );
+    assert_eq!(decode(&out).unwrap(), binary_term);
+}
+
 // ============================================================================
 // Distribution Header Tests
 // ============================================================================
@@ -536,3 +620,317 @@ fn test_dist_header_roundtrip() {
         panic!("Expected outer tuple, got {:?}", decoded_payload);
     }
 }
+
+// ============================================================================
+// Encoding Option Tests
+// ============================================================================
+
+#[test]
+fn test_minor_version_1_uses_latin1_atoms() {
+    let options = EncodeOptions::new().with_minor_version(1);
+    let encoded = encode_with_options(&erl_atom!("ok"), &options).unwrap();
+    assert_eq!(encoded, vec![131, 115, 2, 111, 107]);
+
+    let latin1 = erl_atom!("caf\u{e9}");
+    let encoded = encode_with_options(&latin1, &options).unwrap();
+    assert_eq!(encoded, vec![131, 115, 4, 99, 97, 102, 0xe9]);
+    assert_eq!(decode(&encoded).unwrap(), latin1);
+    assert_eq!(
+        decode_borrowed(&encoded).unwrap().atom_name(),
+        Some("caf\u{e9}")
+    );
+
+    // Atoms outside Latin-1 still need UTF-8
+    let encoded = encode_with_options(&erl_atom!("\u{65e5}"), &options).unwrap();
+    assert_eq!(encoded, vec![131, 119, 3, 0xe6, 0x97, 0xa5]);
+}
+
+#[test]
+fn test_minor_version_2_uses_utf8_atoms() {
+    let options = EncodeOptions::new().with_minor_version(2);
+    let encoded = encode_with_options(&erl_atom!("caf\u{e9}"), &options).unwrap();
+    assert_eq!(encoded, vec![131, 119, 5, 99, 97, 102, 0xc3, 0xa9]);
+    assert_eq!(encoded, encode(&erl_atom!("caf\u{e9}")).unwrap());
+}
+
+#[test]
+fn test_minor_version_0_uses_float_ext() {
+    let options = EncodeOptions::new().with_minor_version(0);
+    let encoded = encode_with_options(&OwnedTerm::Float(1.5), &options).unwrap();
+    let mut expected = vec![131, 99];
+    expected.extend(b"1.50000000000000000000e+00");
+    expected.resize(33, 0);
+    assert_eq!(encoded, expected);
+    assert_eq!(decode(&encoded).unwrap(), OwnedTerm::Float(1.5));
+
+    for value in [-0.001, 6.02214076e23, 1.0e-300, 0.1] {
+        let encoded = encode_with_options(&OwnedTerm::Float(value), &options).unwrap();
+        assert_eq!(encoded.len(), 33);
+        assert_eq!(decode(&encoded).unwrap(), OwnedTerm::Float(value));
+    }
+
+    let encoded = encode_with_options(&erl_atom!("ok"), &options).unwrap();
+    assert_eq!(encoded, vec![131, 115, 2, 111, 107]);
+}
+
+#[test]
+fn test_small_atoms_disabled() {
+    let options = EncodeOptions::new().with_small_atoms(false);
+    assert_eq!(
+        encode_with_options(&erl_atom!("ok"), &options).unwrap(),
+        vec![131, 118, 0, 2, 111, 107]
+    );
+    assert_eq!(
+        encode_with_options(&erl_atom!("ok"), &options.with_minor_version(1)).unwrap(),
+        vec![131, 100, 0, 2, 111, 107]
+    );
+}
+
+#[test]
+fn test_invalid_minor_version() {
+    let options = EncodeOptions::new().with_minor_version(3);
+    assert!(matches!(
+        encode_with_options(&erl_atom!("ok"), &options),
+        Err(EncodeError::InvalidMinorVersion { version: 3 })
+    ));
+}
+
+#[test]
+fn test_local_ext_can_be_reencoded() {
+    let options = EncodeOptions::new().with_preserve_local_ext(false);
+
+    let pid = decode(&make_local_ext_pid_bytes()).unwrap();
+    let encoded = encode_with_options(&pid, &options).unwrap();
+    assert_eq!(encoded[1], 88);
+    assert_eq!(encoded, encode(&pid_without_local_ext(&pid)).unwrap());
+
+    let port = decode(&make_local_ext_port_bytes()).unwrap();
+    assert_eq!(encode_with_options(&port, &options).unwrap()[1], 120);
+
+    let reference = decode(&make_local_ext_reference_bytes()).unwrap();
+    assert_eq!(encode_with_options(&reference, &options).unwrap()[1], 90);
+
+    // Preserved by default
+    assert_eq!(encode(&pid).unwrap(), make_local_ext_pid_bytes());
+}
+
+fn pid_without_local_ext(term: &OwnedTerm) -> OwnedTerm {
+    match term {
+        OwnedTerm::Pid(pid) => OwnedTerm::Pid(ExternalPid::new(
+            pid.node.clone(),
+            pid.id,
+            pid.serial,
+            pid.creation,
+        )),
+        other => other.clone(),
+    }
+}
+
+// ============================================================================
+// Deterministic Encoding Tests
+// ============================================================================
+
+// Expected bytes are what term_to_binary(T, [deterministic]) returns on OTP 26 through 28.
+
+fn encode_deterministic(term: &OwnedTerm) -> Vec<u8> {
+    encode_with_options(term, &EncodeOptions::deterministic()).unwrap()
+}
+
+#[test]
+fn test_deterministic_golden_vectors() {
+    let cases: Vec<(OwnedTerm, Vec<u8>)> = vec![
+        (erl_atom!("ok"), vec![131, 119, 2, 111, 107]),
+        (OwnedTerm::Nil, vec![131, 106]),
+        (OwnedTerm::List(vec![]), vec![131, 106]),
+        (erl_int!(42), vec![131, 97, 42]),
+        (erl_int!(-1), vec![131, 98, 255, 255, 255, 255]),
+        (erl_int!(256), vec![131, 98, 0, 0, 1, 0]),
+        (erl_int!(2147483648), vec![131, 110, 4, 0, 0, 0, 0, 128]),
+        (erl_int!(-2147483649), vec![131, 110, 4, 1, 1, 0, 0, 128]),
+        (
+            OwnedTerm::from(1u128 << 64),
+            vec![131, 110, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+        ),
+        (
+            OwnedTerm::Float(1.5),
+            vec![131, 70, 63, 248, 0, 0, 0, 0, 0, 0],
+        ),
+        (OwnedTerm::charlist("abc"), vec![131, 107, 0, 3, 97, 98, 99]),
+        (
+            erl_list![erl_int!(1), erl_int!(256)],
+            vec![131, 108, 0, 0, 0, 2, 97, 1, 98, 0, 0, 1, 0, 106],
+        ),
+        (
+            OwnedTerm::Binary(b"abc".to_vec()),
+            vec![131, 109, 0, 0, 0, 3, 97, 98, 99],
+        ),
+        (
+            OwnedTerm::BitBinary {
+                bytes: vec![1, 0x5f],
+                bits: 3,
+            },
+            vec![131, 77, 0, 0, 0, 2, 3, 1, 64],
+        ),
+        (
+            erl_tuple![erl_atom!("ok"), erl_int!(1)],
+            vec![131, 104, 2, 119, 2, 111, 107, 97, 1],
+        ),
+        (
+            erl_map! { erl_atom!("b") => erl_int!(2), erl_atom!("a") => erl_int!(1) },
+            vec![131, 116, 0, 0, 0, 2, 119, 1, 97, 97, 1, 119, 1, 98, 97, 2],
+        ),
+        (
+            OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("lists"), Atom::new("map"), 2)),
+            vec![
+                131, 113, 119, 5, 108, 105, 115, 116, 115, 119, 3, 109, 97, 112, 97, 2,
+            ],
+        ),
+    ];
+
+    for (term, expected) in cases {
+        assert_eq!(encode_deterministic(&term), expected, "{:?}", term);
+        assert_eq!(
+            encoded_size(&term, &EncodeOptions::deterministic()).unwrap(),
+            expected.len(),
+            "{:?}",
+            term
+        );
+        let decoded = decode(&expected).unwrap();
+        assert_eq!(encode_deterministic(&decoded), expected, "{:?}", decoded);
+    }
+}
+
+#[test]
+fn test_deterministic_map_key_order_puts_integers_before_floats() {
+    // #{1.0 => a, 2 => b}: 2 sorts before 1.0 in map key order
+    let map = erl_map! {
+        OwnedTerm::Float(1.0) => erl_atom!("a"),
+        erl_int!(2) => erl_atom!("b")
+    };
+    assert_eq!(
+        encode_deterministic(&map),
+        vec![
+            131, 116, 0, 0, 0, 2, 97, 2, 119, 1, 98, 70, 63, 240, 0, 0, 0, 0, 0, 0, 119, 1, 97
+        ]
+    );
+
+    let nested = erl_map! {
+        erl_tuple![OwnedTerm::Float(1.0)] => erl_int!(1),
+        erl_tuple![erl_int!(2)] => erl_int!(2)
+    };
+    let decoded = decode(&encode_deterministic(&nested)).unwrap();
+    assert_eq!(decoded, nested);
+    let encoded = encode_deterministic(&nested);
+    // The first key is {2}
+    assert_eq!(&encoded[6..10], &[104, 1, 97, 2]);
+}
+
+#[test]
+fn test_deterministic_large_map_is_sorted() {
+    let map = OwnedTerm::Map(
+        (0..33)
+            .rev()
+            .map(|i| (erl_int!(i), OwnedTerm::Nil))
+            .collect(),
+    );
+    let encoded = encode_deterministic(&map);
+    let mut expected = vec![131, 116, 0, 0, 0, 33];
+    for i in 0..33u8 {
+        expected.extend([97, i, 106]);
+    }
+    assert_eq!(encoded, expected);
+    assert_eq!(encoded, encode_deterministic(&decode(&encoded).unwrap()));
+}
+
+#[test]
+fn test_deterministic_normalizes_bigints() {
+    let padded = OwnedTerm::BigInt(BigInt::new(false, vec![42, 0]));
+    assert_eq!(encode_deterministic(&padded), vec![131, 97, 42]);
+
+    let padded_large = OwnedTerm::BigInt(BigInt::new(true, vec![0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0]));
+    assert_eq!(
+        encode_deterministic(&padded_large),
+        vec![131, 110, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+    );
+}
+
+#[test]
+fn test_deterministic_whole_byte_bitstring_is_a_binary() {
+    let term = OwnedTerm::BitBinary {
+        bytes: vec![1, 2],
+        bits: 8,
+    };
+    assert_eq!(
+        encode_deterministic(&term),
+        vec![131, 109, 0, 0, 0, 2, 1, 2]
+    );
+}
+
+#[test]
+fn test_bitstring_with_invalid_bit_count_is_an_error() {
+    for bits in [0, 9] {
+        let term = OwnedTerm::BitBinary {
+            bytes: vec![1, 2],
+            bits,
+        };
+        assert!(matches!(
+            encode(&term),
+            Err(EncodeError::InvalidBitCount { bits: b }) if b == bits
+        ));
+        assert!(matches!(
+            encode_with_options(&term, &EncodeOptions::deterministic()),
+            Err(EncodeError::InvalidBitCount { bits: b }) if b == bits
+        ));
+        assert!(matches!(
+            encoded_size(&term, &EncodeOptions::deterministic()),
+            Err(EncodeError::InvalidBitCount { bits: b }) if b == bits
+        ));
+    }
+}
+
+#[test]
+fn test_deterministic_pid_port_and_reference() {
+    let node = Atom::new("nonode@nohost");
+    let mut node_bytes = vec![119, 13];
+    node_bytes.extend_from_slice(b"nonode@nohost");
+
+    let pid = OwnedTerm::Pid(ExternalPid::new(node.clone(), 85, 0, 0));
+    let mut expected = vec![131, 88];
+    expected.extend(&node_bytes);
+    expected.extend([0, 0, 0, 85, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(encode_deterministic(&pid), expected);
+
+    let port = OwnedTerm::Port(ExternalPort::new(node.clone(), 7, 3));
+    let mut expected = vec![131, 89];
+    expected.extend(&node_bytes);
+    expected.extend([0, 0, 0, 7, 0, 0, 0, 3]);
+    assert_eq!(encode_deterministic(&port), expected);
+    assert_eq!(decode(&expected).unwrap(), port);
+
+    let wide_port = OwnedTerm::Port(ExternalPort::new(node.clone(), 1 << 40, 3));
+    assert_eq!(encode_deterministic(&wide_port)[1], 120);
+
+    let reference = OwnedTerm::Reference(ExternalReference::new(node, 1, vec![1, 2, 3]));
+    let mut expected = vec![131, 90, 0, 3];
+    expected.extend(&node_bytes);
+    expected.extend([0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3]);
+    assert_eq!(encode_deterministic(&reference), expected);
+}
+
+#[test]
+fn test_deterministic_ignores_local_ext() {
+    let pid = decode(&make_local_ext_pid_bytes()).unwrap();
+    let encoded = encode_deterministic(&pid);
+    assert_eq!(encoded[1], 88);
+    assert_eq!(decode(&encoded).unwrap(), pid);
+    assert_ne!(encode(&pid).unwrap(), encoded);
+
+    let port = decode(&make_local_ext_port_bytes()).unwrap();
+    assert_eq!(encode_deterministic(&port)[1], 89);
+}
+
+#[test]
+fn test_deterministic_is_off_by_default() {
+    assert!(!EncodeOptions::default().deterministic);
+    assert_eq!(encode(&OwnedTerm::charlist("abc")).unwrap()[1], 108);
+}
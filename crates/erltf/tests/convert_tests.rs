@@ -0,0 +1,138 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::TermConversionError;
+use erltf::types::BigInt;
+use erltf::{
+    FromTerm, IntoTerm, OwnedTerm, StringEncoding, erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+};
+use std::collections::{BTreeMap, HashMap};
+
+// ============================================================================
+// Scalars
+// ============================================================================
+
+#[test]
+fn test_integers() {
+    assert_eq!(42u8.into_term(), erl_int!(42));
+    assert_eq!(i8::from_term(erl_int!(-128)), Ok(-128));
+    assert_eq!(
+        i8::from_term(erl_int!(128)),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert_eq!(
+        u64::MAX.into_term(),
+        OwnedTerm::BigInt(BigInt::from(u64::MAX))
+    );
+    assert_eq!(u64::from_term(u64::MAX.into_term()), Ok(u64::MAX));
+    assert!(matches!(
+        i32::from_term(OwnedTerm::Float(1.0)),
+        Err(TermConversionError::WrongType { .. })
+    ));
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!("hello".into_term(), OwnedTerm::Binary(b"hello".to_vec()));
+    assert_eq!(
+        String::from_term(OwnedTerm::Binary(b"hello".to_vec())),
+        Ok("hello".to_string())
+    );
+    assert_eq!(
+        String::from_term(OwnedTerm::charlist("héllo")),
+        Ok("héllo".to_string())
+    );
+    assert_eq!(String::from_term(OwnedTerm::Nil), Ok(String::new()));
+    assert!(String::from_term(erl_atom!("hello")).is_err());
+}
+
+#[test]
+fn test_string_encodings() {
+    for encoding in [
+        StringEncoding::Binary,
+        StringEncoding::Charlist,
+        StringEncoding::Atom,
+    ] {
+        let term = encoding.encode("queue");
+        assert_eq!(encoding.decode(term), Ok("queue".to_string()));
+    }
+    assert_eq!(StringEncoding::Atom.encode("ok"), erl_atom!("ok"));
+    assert_eq!(
+        StringEncoding::Charlist.decode(erl_list![erl_int!(-1)]),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert!(matches!(
+        StringEncoding::Atom.decode(OwnedTerm::Binary(b"ok".to_vec())),
+        Err(TermConversionError::WrongType {
+            expected: "Atom",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_option_and_bool() {
+    assert_eq!(None::<i64>.into_term(), erl_atom!("undefined"));
+    assert_eq!(Option::<i64>::from_term(erl_atom!("undefined")), Ok(None));
+    assert_eq!(Option::<i64>::from_term(erl_int!(1)), Ok(Some(1)));
+    assert_eq!(Option::<i64>::from_missing(), Some(None));
+    assert_eq!(i64::from_missing(), None);
+
+    assert_eq!(true.into_term(), erl_atom!("true"));
+    assert_eq!(bool::from_term(erl_atom!("false")), Ok(false));
+    assert!(bool::from_term(erl_atom!("yes")).is_err());
+}
+
+// ============================================================================
+// Collections
+// ============================================================================
+
+#[test]
+fn test_lists_and_tuples() {
+    assert_eq!(
+        vec![1i64, 2].into_term(),
+        erl_list![erl_int!(1), erl_int!(2)]
+    );
+    assert_eq!(Vec::<i64>::from_term(OwnedTerm::Nil), Ok(vec![]));
+    assert_eq!(
+        (erl_atom!("ok"), 7i64).into_term(),
+        erl_tuple![erl_atom!("ok"), erl_int!(7)]
+    );
+    assert_eq!(
+        <(i64, String)>::from_term(erl_tuple![erl_int!(1), OwnedTerm::Binary(b"a".to_vec())]),
+        Ok((1, "a".to_string()))
+    );
+    assert_eq!(
+        <(i64, i64)>::from_term(erl_tuple![erl_int!(1)]),
+        Err(TermConversionError::WrongArity {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn test_maps() {
+    let map = BTreeMap::from([(1i64, "one".to_string())]);
+    let term = map.clone().into_term();
+    assert_eq!(
+        term,
+        erl_map! { erl_int!(1) => OwnedTerm::Binary(b"one".to_vec()) }
+    );
+    assert_eq!(BTreeMap::<i64, String>::from_term(term.clone()), Ok(map));
+    assert_eq!(
+        HashMap::<i64, String>::from_term(term),
+        Ok(HashMap::from([(1, "one".to_string())]))
+    );
+}
@@ -0,0 +1,301 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::PathSegment;
+use erltf::{
+    BorrowedTerm, DecodeError, EncodeOptions, OwnedTerm, TermCursor, encode, encode_with_options,
+    erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+};
+
+fn sample() -> OwnedTerm {
+    erl_tuple![
+        erl_atom!("ok"),
+        erl_map! {
+            erl_atom!("name") => OwnedTerm::Binary(b"orders".to_vec()),
+            erl_atom!("messages") => erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]
+        },
+        OwnedTerm::Binary(vec![0; 1000])
+    ]
+}
+
+// ============================================================================
+// Navigation
+// ============================================================================
+
+#[test]
+fn test_root_decodes_to_whole_term() {
+    let data = encode(&sample()).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(cursor.byte_offset(), 1);
+    assert!(cursor.path().is_empty());
+    assert_eq!(cursor.as_bytes().unwrap(), &data[1..]);
+    assert_eq!(cursor.decode().unwrap(), sample());
+}
+
+#[test]
+fn test_tuple_elements() {
+    let data = encode(&sample()).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(cursor.element_count().unwrap(), Some(3));
+
+    let first = cursor.tuple_element(0).unwrap().unwrap();
+    assert_eq!(first.decode().unwrap(), erl_atom!("ok"));
+    assert_eq!(first.path(), &[PathSegment::TupleElement(0)]);
+
+    let last = cursor.tuple_element(2).unwrap().unwrap();
+    assert_eq!(last.decode().unwrap(), OwnedTerm::Binary(vec![0; 1000]));
+
+    assert!(cursor.tuple_element(3).unwrap().is_none());
+    assert!(cursor.list_element(0).unwrap().is_none());
+    assert!(first.tuple_element(0).unwrap().is_none());
+    assert_eq!(first.element_count().unwrap(), None);
+}
+
+#[test]
+fn test_large_tuple_elements() {
+    let term = OwnedTerm::Tuple((0..300).map(|i| erl_int!(i)).collect());
+    let data = encode(&term).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(cursor.element_count().unwrap(), Some(300));
+    let element = cursor.tuple_element(299).unwrap().unwrap();
+    assert_eq!(element.decode().unwrap(), erl_int!(299));
+}
+
+#[test]
+fn test_map_get() {
+    let data = encode(&sample()).unwrap();
+    let map = TermCursor::new(&data)
+        .unwrap()
+        .tuple_element(1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(map.element_count().unwrap(), Some(2));
+
+    let messages = map.map_get(&erl_atom!("messages")).unwrap().unwrap();
+    assert_eq!(
+        messages.path(),
+        &[
+            PathSegment::TupleElement(1),
+            PathSegment::MapValue("messages".to_string())
+        ]
+    );
+    let second = messages.list_element(1).unwrap().unwrap();
+    assert_eq!(second.decode().unwrap(), erl_int!(2));
+    assert_eq!(second.path().last(), Some(&PathSegment::ListElement(1)));
+
+    assert!(map.map_get(&erl_atom!("missing")).unwrap().is_none());
+    assert!(messages.map_get(&erl_atom!("name")).unwrap().is_none());
+}
+
+#[test]
+fn test_map_get_with_compound_key() {
+    let key = erl_tuple![erl_atom!("vhost"), OwnedTerm::Binary(b"/".to_vec())];
+    let term = erl_map! { key.clone() => erl_int!(7), erl_int!(1) => erl_int!(8) };
+    let data = encode(&term).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    let value = cursor.map_get(&key).unwrap().unwrap();
+    assert_eq!(value.decode().unwrap(), erl_int!(7));
+}
+
+#[test]
+fn test_improper_list_tail() {
+    let term = OwnedTerm::ImproperList {
+        elements: vec![erl_int!(1), erl_int!(2)],
+        tail: Box::new(erl_atom!("tail")),
+    };
+    let data = encode(&term).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(cursor.element_count().unwrap(), Some(2));
+    assert!(cursor.list_element(2).unwrap().is_none());
+
+    let tail = cursor.list_tail().unwrap().unwrap();
+    assert_eq!(tail.decode().unwrap(), erl_atom!("tail"));
+    assert_eq!(tail.path(), &[PathSegment::ImproperListTail]);
+
+    let proper = encode(&erl_list![erl_int!(1)]).unwrap();
+    let cursor = TermCursor::new(&proper).unwrap();
+    assert!(cursor.list_tail().unwrap().is_none());
+}
+
+#[test]
+fn test_empty_list_and_strings() {
+    let data = encode(&OwnedTerm::Nil).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(cursor.element_count().unwrap(), Some(0));
+    assert!(cursor.list_element(0).unwrap().is_none());
+    assert_eq!(cursor.elements().unwrap().unwrap().count(), 0);
+
+    // STRING_EXT has no sub-terms to move into
+    let data =
+        encode_with_options(&OwnedTerm::charlist("abc"), &EncodeOptions::deterministic()).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert!(cursor.list_element(0).unwrap().is_none());
+    assert!(cursor.elements().unwrap().is_none());
+    assert_eq!(cursor.decode().unwrap(), OwnedTerm::charlist("abc"));
+}
+
+// ============================================================================
+// Iteration
+// ============================================================================
+
+#[test]
+fn test_elements() {
+    let data = encode(&sample()).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(
+        cursor.elements().unwrap().unwrap().size_hint(),
+        (3, Some(3))
+    );
+    let decoded: Vec<OwnedTerm> = cursor
+        .elements()
+        .unwrap()
+        .unwrap()
+        .map(|element| element.unwrap().decode().unwrap())
+        .collect();
+    assert_eq!(decoded, sample().try_into_tuple().unwrap());
+}
+
+#[test]
+fn test_entries() {
+    let term = erl_map! { erl_atom!("a") => erl_int!(1), erl_atom!("b") => erl_int!(2) };
+    let data = encode(&term).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    let mut entries = Vec::new();
+    for entry in cursor.entries().unwrap().unwrap() {
+        let (key, value) = entry.unwrap();
+        assert_eq!(key.path(), &[PathSegment::MapKey]);
+        entries.push((key.decode().unwrap(), value.decode().unwrap()));
+    }
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![(erl_atom!("a"), erl_int!(1)), (erl_atom!("b"), erl_int!(2))]
+    );
+    assert!(cursor.elements().unwrap().is_none());
+}
+
+#[test]
+fn test_decode_borrowed() {
+    let data = encode(&sample()).unwrap();
+    let cursor = TermCursor::new(&data).unwrap();
+    let binary = cursor.tuple_element(2).unwrap().unwrap();
+    match binary.decode_borrowed().unwrap() {
+        BorrowedTerm::Binary(bytes) => {
+            assert_eq!(bytes.len(), 1000);
+            assert!(data.as_ptr_range().contains(&bytes.as_ptr()));
+        }
+        other => panic!("expected a binary, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[test]
+fn test_invalid_roots() {
+    assert_eq!(
+        TermCursor::new(&[]).unwrap_err().error,
+        DecodeError::UnexpectedEof
+    );
+    assert!(matches!(
+        TermCursor::new(&[130, 106]).unwrap_err().error,
+        DecodeError::InvalidVersion { actual: 130, .. }
+    ));
+    assert_eq!(
+        TermCursor::new(&[131]).unwrap_err().error,
+        DecodeError::UnexpectedEof
+    );
+
+    let compressed = encode_with_options(
+        &OwnedTerm::Binary(vec![0; 1000]),
+        &EncodeOptions::compressed(),
+    )
+    .unwrap();
+    assert!(matches!(
+        TermCursor::new(&compressed).unwrap_err().error,
+        DecodeError::UnsupportedType(_)
+    ));
+}
+
+#[test]
+fn test_truncated_data_reports_path() {
+    let data = encode(&sample()).unwrap();
+    let truncated = &data[..data.len() - 10];
+    let cursor = TermCursor::new(truncated).unwrap();
+
+    // Elements before the damage are still reachable
+    let map = cursor.tuple_element(1).unwrap().unwrap();
+    assert_eq!(
+        map.map_get(&erl_atom!("name"))
+            .unwrap()
+            .unwrap()
+            .decode()
+            .unwrap(),
+        OwnedTerm::Binary(b"orders".to_vec())
+    );
+
+    let binary = cursor.tuple_element(2).unwrap().unwrap();
+    let error = binary.decode().unwrap_err();
+    assert_eq!(error.error, DecodeError::UnexpectedEof);
+    assert_eq!(error.context.path, vec![PathSegment::TupleElement(2)]);
+    assert_eq!(error.context.byte_offset, binary.byte_offset());
+
+    let results: Vec<_> = cursor.elements().unwrap().unwrap().collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[2].is_err());
+}
+
+#[test]
+fn test_deeply_nested_input_does_not_overflow() {
+    let depth = 1_000_000;
+    let mut data = vec![131];
+    for _ in 0..depth {
+        data.extend_from_slice(&[104, 1]);
+    }
+    data.extend_from_slice(&[119, 2, b'o', b'k']);
+
+    let cursor = TermCursor::new(&data).unwrap();
+    assert_eq!(cursor.as_bytes().unwrap().len(), data.len() - 1);
+
+    let truncated = &data[..data.len() - 4];
+    let cursor = TermCursor::new(truncated).unwrap();
+    assert_eq!(
+        cursor.as_bytes().unwrap_err().error,
+        DecodeError::UnexpectedEof
+    );
+}
+
+#[test]
+fn test_skipping_ignores_size_limits() {
+    // {#{0 => 0, ...} with more entries than decoding allows, ok}
+    let entries = 1_000_001u32;
+    let mut data = vec![131, 104, 2, 116];
+    data.extend_from_slice(&entries.to_be_bytes());
+    for _ in 0..entries {
+        data.extend_from_slice(&[97, 0, 97, 0]);
+    }
+    data.extend_from_slice(&[119, 2, b'o', b'k']);
+
+    let cursor = TermCursor::new(&data).unwrap();
+    let map = cursor.tuple_element(0).unwrap().unwrap();
+    assert!(matches!(
+        map.decode().unwrap_err().error,
+        DecodeError::MapTooLarge { .. }
+    ));
+    assert_eq!(
+        cursor.tuple_element(1).unwrap().unwrap().decode().unwrap(),
+        erl_atom!("ok")
+    );
+}
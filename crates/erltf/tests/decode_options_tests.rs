@@ -0,0 +1,251 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use erltf::types::{Atom, ExternalFun, ExternalPid};
+use erltf::{
+    DecodeError, DecodeOptions, EncodeOptions, OwnedTerm, decode, decode_shared_with_options,
+    decode_with_options, encode, encode_with_options, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
+
+fn nested_lists(depth: usize) -> OwnedTerm {
+    let mut term = OwnedTerm::Nil;
+    for _ in 0..depth {
+        term = OwnedTerm::List(vec![term]);
+    }
+    term
+}
+
+// ============================================================================
+// Element Counts
+// ============================================================================
+
+#[test]
+fn test_default_options_match_decode() {
+    let term = erl_tuple![
+        erl_atom!("ok"),
+        erl_list![erl_int!(1), erl_int!(2)],
+        erl_map! { erl_atom!("a") => OwnedTerm::Binary(vec![1; 100]) }
+    ];
+    let data = encode(&term).unwrap();
+    assert_eq!(
+        decode_with_options(&data, &DecodeOptions::default()).unwrap(),
+        decode(&data).unwrap()
+    );
+}
+
+#[test]
+fn test_element_count_limits() {
+    let list = encode(&OwnedTerm::List(vec![erl_int!(1000); 11])).unwrap();
+    let options = DecodeOptions::new().with_max_list_size(10);
+    assert_eq!(
+        decode_with_options(&list, &options),
+        Err(DecodeError::ListTooLarge { size: 11, max: 10 })
+    );
+
+    let string =
+        encode_with_options(&OwnedTerm::charlist("abc"), &EncodeOptions::deterministic()).unwrap();
+    let options = DecodeOptions::new().with_max_list_size(2);
+    assert_eq!(
+        decode_with_options(&string, &options),
+        Err(DecodeError::ListTooLarge { size: 3, max: 2 })
+    );
+
+    let tuple = encode(&OwnedTerm::Tuple(vec![OwnedTerm::Nil; 4])).unwrap();
+    let options = DecodeOptions::new().with_max_tuple_size(3);
+    assert_eq!(
+        decode_with_options(&tuple, &options),
+        Err(DecodeError::TupleTooLarge { size: 4, max: 3 })
+    );
+
+    let map = encode(&erl_map! { erl_int!(1) => erl_int!(1), erl_int!(2) => erl_int!(2) }).unwrap();
+    let options = DecodeOptions::new().with_max_map_size(1);
+    assert_eq!(
+        decode_with_options(&map, &options),
+        Err(DecodeError::MapTooLarge { size: 2, max: 1 })
+    );
+
+    let binary = encode(&OwnedTerm::Binary(vec![0; 20])).unwrap();
+    let options = DecodeOptions::new().with_max_binary_size(16);
+    assert_eq!(
+        decode_with_options(&binary, &options),
+        Err(DecodeError::BinaryTooLarge { size: 20, max: 16 })
+    );
+
+    let atom = encode(&erl_atom!("a_rather_long_atom")).unwrap();
+    let options = DecodeOptions::new().with_max_atom_size(8);
+    assert_eq!(
+        decode_with_options(&atom, &options),
+        Err(DecodeError::AtomTooLarge { size: 18, max: 8 })
+    );
+}
+
+#[test]
+fn test_limits_apply_to_nested_terms() {
+    let term = erl_tuple![erl_atom!("data"), erl_list![OwnedTerm::Binary(vec![0; 64])]];
+    let data = encode(&term).unwrap();
+    let options = DecodeOptions::new().with_max_binary_size(32);
+    assert!(matches!(
+        decode_with_options(&data, &options),
+        Err(DecodeError::BinaryTooLarge { size: 64, max: 32 })
+    ));
+}
+
+// ============================================================================
+// Depth, Memory and Compression
+// ============================================================================
+
+#[test]
+fn test_max_depth() {
+    let data = encode(&nested_lists(10)).unwrap();
+    // Ten lists and the innermost []
+    let options = DecodeOptions::new().with_max_depth(11);
+    assert_eq!(
+        decode_with_options(&data, &options).unwrap(),
+        nested_lists(10)
+    );
+
+    let options = DecodeOptions::new().with_max_depth(10);
+    assert_eq!(
+        decode_with_options(&data, &options),
+        Err(DecodeError::DepthLimitExceeded { max: 10 })
+    );
+}
+
+#[test]
+fn test_max_depth_prevents_deep_recursion() {
+    // 100k nested SMALL_TUPLE_EXT headers, never terminated
+    let mut data = vec![131];
+    for _ in 0..100_000 {
+        data.extend([104, 1]);
+    }
+    let options = DecodeOptions::new().with_max_depth(64);
+    assert_eq!(
+        decode_with_options(&data, &options),
+        Err(DecodeError::DepthLimitExceeded { max: 64 })
+    );
+}
+
+#[test]
+fn test_max_memory() {
+    let term = OwnedTerm::List(vec![OwnedTerm::Binary(vec![0; 1000]); 10]);
+    let data = encode(&term).unwrap();
+    assert_eq!(
+        decode_with_options(&data, &DecodeOptions::new().with_max_memory(1_000_000)).unwrap(),
+        term
+    );
+    assert_eq!(
+        decode_with_options(&data, &DecodeOptions::new().with_max_memory(5000)),
+        Err(DecodeError::MemoryLimitExceeded { max: 5000 })
+    );
+}
+
+#[test]
+fn test_max_memory_rejects_huge_declared_lengths() {
+    // A list header claiming 10 million elements, followed by nothing
+    let data = [131, 108, 0, 0x98, 0x96, 0x80];
+    let options = DecodeOptions::new().with_max_memory(1 << 20);
+    assert_eq!(
+        decode_with_options(&data, &options),
+        Err(DecodeError::MemoryLimitExceeded { max: 1 << 20 })
+    );
+}
+
+#[test]
+fn test_max_compression_ratio() {
+    let term = OwnedTerm::Binary(vec![0; 100_000]);
+    let data = encode_with_options(&term, &EncodeOptions::compressed()).unwrap();
+    assert_eq!(data[1], 80);
+
+    let options = DecodeOptions::new().with_max_compression_ratio(10);
+    assert_eq!(
+        decode_with_options(&data, &options),
+        Err(DecodeError::CompressionRatioExceeded { max: 10 })
+    );
+
+    let options = DecodeOptions::new().with_max_compression_ratio(10_000);
+    assert_eq!(decode_with_options(&data, &options).unwrap(), term);
+}
+
+#[test]
+fn test_compressed_size_mismatch_is_rejected() {
+    let term = OwnedTerm::Binary(vec![0; 1000]);
+    let mut data = encode_with_options(&term, &EncodeOptions::compressed()).unwrap();
+    // Declare one byte less than the compressed stream holds
+    data[2..6].copy_from_slice(&1004u32.to_be_bytes());
+    assert!(decode(&data).is_err());
+}
+
+#[test]
+fn test_shared_decoding_with_options() {
+    let term = OwnedTerm::Binary(vec![1; 500]);
+    let data = Bytes::from(encode(&term).unwrap());
+    assert_eq!(
+        decode_shared_with_options(&data, &DecodeOptions::default()).unwrap(),
+        term
+    );
+    assert!(matches!(
+        decode_shared_with_options(&data, &DecodeOptions::new().with_max_binary_size(100)),
+        Err(DecodeError::BinaryTooLarge { .. })
+    ));
+}
+
+// ============================================================================
+// Safe Mode
+// ============================================================================
+
+#[test]
+fn test_safe_mode_rejects_unknown_atoms() {
+    let term = erl_tuple![erl_atom!("ok"), erl_atom!("user_created")];
+    let data = encode(&term).unwrap();
+
+    assert_eq!(
+        decode_with_options(&data, &DecodeOptions::safe(Vec::<&str>::new())),
+        Err(DecodeError::UnsafeAtom("user_created".to_string()))
+    );
+    assert_eq!(
+        decode_with_options(&data, &DecodeOptions::safe(["user_created"])).unwrap(),
+        term
+    );
+}
+
+#[test]
+fn test_safe_mode_checks_node_names() {
+    let pid = OwnedTerm::Pid(ExternalPid::new(Atom::new("evil@host"), 1, 0, 1));
+    let data = encode(&pid).unwrap();
+    assert!(matches!(
+        decode_with_options(&data, &DecodeOptions::safe(["other@host"])),
+        Err(DecodeError::UnsafeAtom(_))
+    ));
+    assert_eq!(
+        decode_with_options(&data, &DecodeOptions::safe(["evil@host"])).unwrap(),
+        pid
+    );
+}
+
+#[test]
+fn test_safe_mode_rejects_funs() {
+    let fun = OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("os"), Atom::new("cmd"), 1));
+    let data = encode(&fun).unwrap();
+    let options = DecodeOptions::safe(["os", "cmd"]);
+    assert_eq!(
+        decode_with_options(&data, &options),
+        Err(DecodeError::UnsafeFun)
+    );
+    assert_eq!(
+        decode_with_options(&data, &options.with_safe(false)).unwrap(),
+        fun
+    );
+}
@@ -0,0 +1,181 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{ExternalPid, ExternalReference};
+use erltf::{Atom, OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_match, erl_tuple};
+
+fn pid() -> OwnedTerm {
+    OwnedTerm::Pid(ExternalPid::new(Atom::new("node@host"), 42, 0, 1))
+}
+
+fn reference() -> OwnedTerm {
+    OwnedTerm::Reference(ExternalReference::new(
+        Atom::new("node@host"),
+        1,
+        vec![1, 2, 3],
+    ))
+}
+
+// ============================================================================
+// Scalars
+// ============================================================================
+
+#[test]
+fn test_wildcard_and_variable() {
+    let term = erl_int!(1);
+    assert_eq!(erl_match!(term, _), Some(()));
+    assert_eq!(erl_match!(term, X), Some(&erl_int!(1)));
+    assert_eq!(erl_match!(term, _Ignored), Some(&erl_int!(1)));
+}
+
+#[test]
+fn test_atoms_are_not_variables() {
+    // Atoms need the `:` form, lowercase names do not compile, see the `erl_match!` docs
+    let error = erl_tuple![erl_atom!("error"), erl_int!(1)];
+    assert_eq!(erl_match!(error, {:ok, X}), None);
+    assert_eq!(erl_match!(error, {:error, X}), Some(&erl_int!(1)));
+}
+
+#[test]
+fn test_atoms() {
+    let term = erl_atom!("$gen_call");
+    assert_eq!(erl_match!(term, :"$gen_call"), Some(()));
+    assert_eq!(erl_match!(term, :gen_call), None);
+    assert_eq!(erl_match!(erl_atom!("ok"), :ok), Some(()));
+    assert_eq!(erl_match!(erl_int!(1), :ok), None);
+}
+
+#[test]
+fn test_literals() {
+    assert_eq!(erl_match!(erl_int!(42), 42), Some(()));
+    assert_eq!(erl_match!(erl_int!(-1), -1), Some(()));
+    assert_eq!(erl_match!(erl_int!(42), 43), None);
+    assert_eq!(erl_match!(OwnedTerm::Float(1.5), 1.5), Some(()));
+    assert_eq!(erl_match!(erl_atom!("true"), true), Some(()));
+    assert_eq!(
+        erl_match!(OwnedTerm::Binary(b"text".to_vec()), <<"text">>),
+        Some(())
+    );
+    assert_eq!(
+        erl_match!(OwnedTerm::Binary(b"text".to_vec()), <<"other">>),
+        None
+    );
+}
+
+// ============================================================================
+// Tuples and Lists
+// ============================================================================
+
+#[test]
+fn test_gen_call() {
+    let message = erl_tuple![
+        erl_atom!("$gen_call"),
+        erl_tuple![pid(), reference()],
+        erl_tuple![erl_atom!("lookup"), erl_int!(7)]
+    ];
+    let (from, tag, request) = erl_match!(message, {:"$gen_call", {From, Tag}, Request}).unwrap();
+    assert_eq!(from, &pid());
+    assert_eq!(tag, &reference());
+    assert_eq!(request, &erl_tuple![erl_atom!("lookup"), erl_int!(7)]);
+
+    assert_eq!(
+        erl_match!(message, {:"$gen_call", _, {:lookup, Key}}),
+        Some(&erl_int!(7))
+    );
+    assert_eq!(erl_match!(message, {:"$gen_cast", Request}), None);
+}
+
+#[test]
+fn test_tuple_arity_must_match() {
+    let term = erl_tuple![erl_atom!("ok"), erl_int!(1)];
+    assert_eq!(erl_match!(term, {:ok, Value}), Some(&erl_int!(1)));
+    assert_eq!(erl_match!(term, {:ok}), None);
+    assert_eq!(erl_match!(term, {:ok, _, _}), None);
+    assert_eq!(erl_match!(OwnedTerm::Tuple(vec![]), {}), Some(()));
+    assert_eq!(
+        erl_match!(erl_list![erl_atom!("ok"), erl_int!(1)], {:ok, _}),
+        None
+    );
+}
+
+#[test]
+fn test_lists() {
+    let term = erl_list![erl_int!(1), erl_int!(2), erl_int!(3)];
+    assert_eq!(
+        erl_match!(term, [A, _, C]),
+        Some((&erl_int!(1), &erl_int!(3)))
+    );
+    assert_eq!(erl_match!(term, [_, _]), None);
+    assert_eq!(erl_match!(term, []), None);
+    assert_eq!(erl_match!(OwnedTerm::Nil, []), Some(()));
+    assert_eq!(erl_match!(OwnedTerm::List(vec![]), []), Some(()));
+}
+
+#[test]
+fn test_list_head_and_tail() {
+    let term = erl_list![erl_int!(1), erl_int!(2), erl_int!(3)];
+    let (head, tail) = erl_match!(term, [Head | Tail]).unwrap();
+    assert_eq!(head, &erl_int!(1));
+    assert_eq!(tail, &[erl_int!(2), erl_int!(3)][..]);
+
+    let (second, rest) = erl_match!(term, [1, Second | Rest]).unwrap();
+    assert_eq!(second, &erl_int!(2));
+    assert_eq!(rest, &[erl_int!(3)][..]);
+
+    let rest = erl_match!(term, [1, 2, 3 | Rest]).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(erl_match!(term, [_, _, _, _ | _]), None);
+    assert_eq!(erl_match!(OwnedTerm::Nil, [_ | _]), None);
+}
+
+// ============================================================================
+// Maps
+// ============================================================================
+
+#[test]
+fn test_map_keys() {
+    let term = erl_map! {
+        erl_atom!("name") => OwnedTerm::Binary(b"q1".to_vec()),
+        erl_atom!("durable") => erl_atom!("true"),
+        OwnedTerm::Binary(b"vhost".to_vec()) => OwnedTerm::Binary(b"/".to_vec()),
+        erl_int!(1) => erl_int!(2)
+    };
+    assert_eq!(
+        erl_match!(term, #{:name => Name, :durable => true}),
+        Some(&OwnedTerm::Binary(b"q1".to_vec()))
+    );
+    assert_eq!(
+        erl_match!(term, #{<<"vhost">> => <<"/">>, 1 => Value}),
+        Some(&erl_int!(2))
+    );
+    assert_eq!(erl_match!(term, #{}), Some(()));
+    assert_eq!(erl_match!(term, #{:missing => _}), None);
+    assert_eq!(erl_match!(term, #{:durable => false}), None);
+    assert_eq!(erl_match!(erl_list![], #{}), None);
+}
+
+#[test]
+fn test_nested_patterns() {
+    let term = erl_tuple![
+        erl_atom!("reply"),
+        erl_list![
+            erl_map! { erl_atom!("port") => erl_int!(5672) },
+            erl_map! { erl_atom!("port") => erl_int!(15672) }
+        ]
+    ];
+    assert_eq!(
+        erl_match!(term, {:reply, [#{:port => First} | _]}),
+        Some(&erl_int!(5672))
+    );
+}
@@ -0,0 +1,205 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(any(feature = "proptest", feature = "arbitrary"))]
+
+use erltf::{GenerateOptions, OwnedTerm};
+
+fn depth(term: &OwnedTerm) -> u32 {
+    match term {
+        OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) => {
+            1 + elements.iter().map(depth).max().unwrap_or(0)
+        }
+        OwnedTerm::ImproperList { elements, tail } => {
+            1 + elements
+                .iter()
+                .map(depth)
+                .max()
+                .unwrap_or(0)
+                .max(depth(tail))
+        }
+        OwnedTerm::Map(map) => {
+            1 + map
+                .iter()
+                .map(|(k, v)| depth(k).max(depth(v)))
+                .max()
+                .unwrap_or(0)
+        }
+        _ => 0,
+    }
+}
+
+/// No high zero digits and no negative zero, as `term_to_binary` writes them.
+fn bigints_are_normalized(term: &OwnedTerm) -> bool {
+    term.subterms().all(|t| match t {
+        OwnedTerm::BigInt(big) => {
+            big.digits.last().is_some_and(|&d| d != 0)
+                || (big.digits.is_empty() && big.sign.is_positive())
+        }
+        _ => true,
+    })
+}
+
+#[test]
+fn test_generate_options_builders() {
+    let options = GenerateOptions::new()
+        .with_max_depth(5)
+        .with_max_len(2)
+        .with_max_binary_len(4);
+    assert_eq!(options.max_depth, 5);
+    assert_eq!(options.max_len, 2);
+    assert_eq!(options.max_binary_len, 4);
+    assert!(!options.round_trippable);
+    assert!(GenerateOptions::round_trippable().round_trippable);
+}
+
+// ============================================================================
+// proptest
+// ============================================================================
+
+#[cfg(feature = "proptest")]
+mod strategies {
+    use super::{bigints_are_normalized, depth};
+    use erltf::generate;
+    use erltf::types::{Atom, BigInt, ExternalPid, ExternalReference};
+    use erltf::{BorrowedTerm, GenerateOptions, OwnedTerm, decode, encode, encode_borrowed};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_round_trippable_terms_round_trip(
+            term in any_with::<OwnedTerm>(GenerateOptions::round_trippable())
+        ) {
+            prop_assert!(bigints_are_normalized(&term));
+            let encoded = encode(&term).unwrap();
+            prop_assert_eq!(decode(&encoded).unwrap(), term);
+        }
+
+        #[test]
+        fn test_round_trippable_borrowed_terms_encode(
+            term in any_with::<BorrowedTerm<'static>>(GenerateOptions::round_trippable())
+        ) {
+            let encoded = encode_borrowed(&term).unwrap();
+            prop_assert_eq!(decode(&encoded).unwrap(), term.to_owned());
+        }
+
+        #[test]
+        fn test_terms_respect_depth_and_len(
+            term in generate::term(GenerateOptions::new().with_max_depth(2).with_max_len(3))
+        ) {
+            prop_assert!(depth(&term) <= 2);
+            if let OwnedTerm::List(elements) | OwnedTerm::Tuple(elements) = &term {
+                prop_assert!(elements.len() <= 3);
+            }
+        }
+
+        #[test]
+        fn test_binaries_respect_max_binary_len(
+            term in generate::term(GenerateOptions::new().with_max_depth(0).with_max_binary_len(4))
+        ) {
+            if let OwnedTerm::Binary(bytes) | OwnedTerm::BitBinary { bytes, .. } = &term {
+                prop_assert!(bytes.len() <= 4);
+            }
+        }
+
+        #[test]
+        fn test_arbitrary_scalar_types_round_trip(
+            atom in any::<Atom>(),
+            pid in any::<ExternalPid>(),
+            reference in any::<ExternalReference>(),
+            bigint in any::<BigInt>(),
+        ) {
+            for term in [
+                OwnedTerm::Atom(atom),
+                OwnedTerm::Pid(pid),
+                OwnedTerm::Reference(reference),
+                OwnedTerm::BigInt(bigint),
+            ] {
+                let encoded = encode(&term).unwrap();
+                prop_assert_eq!(decode(&encoded).unwrap(), term);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// arbitrary
+// ============================================================================
+
+#[cfg(feature = "arbitrary")]
+mod unstructured {
+    use super::{bigints_are_normalized, depth};
+    use arbitrary::{Arbitrary, Unstructured};
+    use erltf::generate::arbitrary_term;
+    use erltf::types::{Atom, BigInt, ExternalPid, ExternalReference};
+    use erltf::{BorrowedTerm, GenerateOptions, OwnedTerm, decode, encode};
+
+    fn seeded_bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arbitrary_round_trippable_terms_round_trip() {
+        let options = GenerateOptions::round_trippable();
+        for seed in 0..500 {
+            let data = seeded_bytes(seed, 512);
+            let mut u = Unstructured::new(&data);
+            let term = arbitrary_term(&mut u, &options).unwrap();
+            assert!(bigints_are_normalized(&term), "seed {seed}");
+            let encoded = encode(&term).unwrap();
+            assert_eq!(decode(&encoded).unwrap(), term, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_terms_respect_max_depth() {
+        let options = GenerateOptions::new().with_max_depth(1);
+        for seed in 0..200 {
+            let data = seeded_bytes(seed, 256);
+            let term = arbitrary_term(&mut Unstructured::new(&data), &options).unwrap();
+            assert!(depth(&term) <= 1, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_on_empty_input() {
+        let mut u = Unstructured::new(&[]);
+        assert!(OwnedTerm::arbitrary(&mut u).is_ok());
+        assert!(BorrowedTerm::arbitrary(&mut u).is_ok());
+    }
+
+    #[test]
+    fn test_arbitrary_scalar_types() {
+        let data = seeded_bytes(7, 256);
+        let mut u = Unstructured::new(&data);
+        let atom = Atom::arbitrary(&mut u).unwrap();
+        assert!(atom.as_str().chars().count() <= 255);
+        let pid = ExternalPid::arbitrary(&mut u).unwrap();
+        let reference = ExternalReference::arbitrary(&mut u).unwrap();
+        assert!(!reference.ids.is_empty());
+        let bigint = BigInt::arbitrary(&mut u).unwrap();
+        assert!(!bigint.digits.is_empty());
+
+        let encoded = encode(&OwnedTerm::Pid(pid.clone())).unwrap();
+        assert_eq!(decode(&encoded).unwrap(), OwnedTerm::Pid(pid));
+    }
+}
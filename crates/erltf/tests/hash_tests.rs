@@ -0,0 +1,263 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use erltf::hash::{atom_hash, make_hash2, phash2, phash2_range};
+use erltf::types::{Atom, BigInt, ExternalPort, Sign};
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+const MASK_27: u32 = (1 << 27) - 1;
+
+/// Builds `{{{...{Leaf}...}}}` without recursion.
+fn nested_tuples(depth: usize, leaf: OwnedTerm) -> OwnedTerm {
+    let mut term = leaf;
+    for _ in 0..depth {
+        term = OwnedTerm::Tuple(vec![term]);
+    }
+    term
+}
+
+/// Takes deeply nested tuples apart without recursing in `Drop`.
+fn dismantle(mut term: OwnedTerm) -> OwnedTerm {
+    while let OwnedTerm::Tuple(mut elements) = term {
+        term = elements.pop().unwrap_or(OwnedTerm::Nil);
+    }
+    term
+}
+
+// ============================================================================
+// Values fixed by the runtime
+// ============================================================================
+
+#[test]
+fn test_nil_hashes_to_the_runtime_constant() {
+    assert_eq!(make_hash2(&OwnedTerm::Nil), 3_468_870_702);
+    assert_eq!(phash2(&OwnedTerm::Nil), 113_427_502);
+    assert_eq!(phash2(&OwnedTerm::List(vec![])), 113_427_502);
+}
+
+#[test]
+fn test_first_atom_hashes_to_its_atom_table_value() {
+    assert_eq!(phash2(&erl_atom!("a")), 97);
+    assert_eq!(phash2(&erl_atom!("ab")), 97 * 16 + 98);
+    assert_eq!(make_hash2(&erl_atom!("ok")), atom_hash(&Atom::new("ok")));
+}
+
+#[test]
+fn test_atom_hash_folds_latin1_characters() {
+    // 'é' is stored as UTF-8 but hashed as the Latin-1 byte 0xE9
+    assert_eq!(atom_hash(&Atom::new("é")), 0xe9);
+    assert_eq!(atom_hash(&Atom::new("")), 0);
+    // Long names wrap around through the high nibble
+    let long = atom_hash(&Atom::new("a_rather_long_atom_name"));
+    assert_eq!(long & 0xf000_0000, 0);
+}
+
+#[test]
+fn test_empty_binary_hashes_to_the_binary_constant() {
+    let expected = 0x9e37_79b9u32.wrapping_mul(13);
+    assert_eq!(make_hash2(&OwnedTerm::Binary(vec![])), expected);
+    assert_eq!(phash2(&OwnedTerm::Binary(vec![])), expected & MASK_27);
+}
+
+// ============================================================================
+// Representation independence
+// ============================================================================
+
+#[test]
+fn test_binary_representations_hash_alike() {
+    let data = b"the quick brown fox jumps over the lazy dog".to_vec();
+    let binary = OwnedTerm::Binary(data.clone());
+    let expected = phash2(&binary);
+
+    assert_eq!(
+        phash2(&OwnedTerm::SharedBinary(Bytes::from(data.clone()))),
+        expected
+    );
+    assert_eq!(
+        phash2(&OwnedTerm::String(String::from_utf8(data.clone()).unwrap())),
+        expected
+    );
+    assert_eq!(
+        phash2(&OwnedTerm::BitBinary {
+            bytes: data,
+            bits: 8
+        }),
+        expected
+    );
+}
+
+#[test]
+fn test_bit_binary_hashes_its_trailing_bits() {
+    let bits = |last: u8, bits: u8| OwnedTerm::BitBinary {
+        bytes: vec![1, 2, last],
+        bits,
+    };
+    // Only the used high bits of the last byte count
+    assert_eq!(phash2(&bits(0b1010_0000, 3)), phash2(&bits(0b1011_1111, 3)));
+    assert_ne!(phash2(&bits(0b1010_0000, 3)), phash2(&bits(0b1000_0000, 3)));
+    assert_ne!(phash2(&bits(0b1010_0000, 3)), phash2(&bits(0b1010_0000, 4)));
+    assert_ne!(
+        phash2(&bits(0b1010_0000, 3)),
+        phash2(&OwnedTerm::binary(vec![1, 2]))
+    );
+}
+
+#[test]
+fn test_integers_hash_alike_regardless_of_representation() {
+    let cases: [(i64, BigInt); 5] = [
+        (0, BigInt::new(Sign::Positive, vec![])),
+        (42, BigInt::new(Sign::Positive, vec![42, 0, 0])),
+        (-(1 << 27), BigInt::new(Sign::Negative, vec![0, 0, 0, 8])),
+        (1 << 40, BigInt::new(Sign::Positive, vec![0, 0, 0, 0, 0, 1])),
+        (
+            i64::MIN,
+            BigInt::new(Sign::Negative, vec![0, 0, 0, 0, 0, 0, 0, 0x80]),
+        ),
+    ];
+    for (small, big) in cases {
+        assert_eq!(
+            phash2(&OwnedTerm::Integer(small)),
+            phash2(&OwnedTerm::BigInt(big)),
+            "{small}"
+        );
+    }
+}
+
+#[test]
+fn test_integers_differ_by_sign_and_magnitude() {
+    let values = [
+        0i64,
+        1,
+        -1,
+        255,
+        256,
+        (1 << 27) - 1,
+        1 << 27,
+        -(1 << 27) - 1,
+    ];
+    for (i, a) in values.iter().enumerate() {
+        for b in &values[i + 1..] {
+            assert_ne!(make_hash2(&erl_int!(*a)), make_hash2(&erl_int!(*b)));
+        }
+    }
+
+    let huge = |sign| OwnedTerm::BigInt(BigInt::new(sign, vec![0xff; 20]));
+    assert_ne!(phash2(&huge(Sign::Positive)), phash2(&huge(Sign::Negative)));
+}
+
+#[test]
+fn test_negative_zero_hashes_like_zero() {
+    assert_eq!(
+        phash2(&OwnedTerm::Float(-0.0)),
+        phash2(&OwnedTerm::Float(0.0))
+    );
+    assert_ne!(phash2(&OwnedTerm::Float(1.0)), phash2(&erl_int!(1)));
+}
+
+#[test]
+fn test_lists_hash_alike_regardless_of_representation() {
+    let list = erl_list![erl_int!(1), erl_atom!("x"), erl_int!(300), erl_int!(2)];
+    let improper = OwnedTerm::ImproperList {
+        elements: vec![erl_int!(1), erl_atom!("x"), erl_int!(300), erl_int!(2)],
+        tail: Box::new(OwnedTerm::Nil),
+    };
+    assert_eq!(phash2(&list), phash2(&improper));
+
+    let with_tail = OwnedTerm::improper_list(vec![erl_int!(1)], erl_int!(2));
+    assert_ne!(
+        phash2(&with_tail),
+        phash2(&erl_list![erl_int!(1), erl_int!(2)])
+    );
+}
+
+#[test]
+fn test_charlists_hash_differently_from_binaries() {
+    let charlist = OwnedTerm::List(b"hello".iter().map(|&b| erl_int!(b)).collect());
+    assert_ne!(
+        phash2(&charlist),
+        phash2(&OwnedTerm::binary(b"hello".to_vec()))
+    );
+    assert_ne!(
+        phash2(&charlist),
+        phash2(&OwnedTerm::List(
+            b"hellp".iter().map(|&b| erl_int!(b)).collect()
+        ))
+    );
+}
+
+#[test]
+fn test_ports_hash_the_whole_id() {
+    let port = |id| OwnedTerm::Port(ExternalPort::new(Atom::new("node@host"), id, 1));
+    assert_ne!(phash2(&port(7)), phash2(&port(7 | (1 << 32))));
+    assert_eq!(phash2(&port(7)), phash2(&port(7)));
+}
+
+// ============================================================================
+// Compound terms
+// ============================================================================
+
+#[test]
+fn test_tuple_elements_are_order_sensitive() {
+    let a = erl_tuple![erl_atom!("a"), erl_int!(1)];
+    let b = erl_tuple![erl_int!(1), erl_atom!("a")];
+    assert_ne!(phash2(&a), phash2(&b));
+    assert_ne!(phash2(&OwnedTerm::Tuple(vec![])), phash2(&OwnedTerm::Nil));
+}
+
+#[test]
+fn test_maps_hash_key_value_pairs() {
+    let map = erl_map! {
+        erl_atom!("a") => erl_int!(1),
+        erl_atom!("b") => erl_int!(2)
+    };
+    let swapped = erl_map! {
+        erl_atom!("a") => erl_int!(2),
+        erl_atom!("b") => erl_int!(1)
+    };
+    assert_ne!(phash2(&map), phash2(&swapped));
+    assert_ne!(phash2(&map), phash2(&erl_map! {}));
+
+    // The hash preceding a map is restored before the pairs are folded in
+    let nested = erl_tuple![erl_atom!("wrap"), map.clone(), erl_atom!("after")];
+    let other = erl_tuple![erl_atom!("wrap"), swapped, erl_atom!("after")];
+    assert_ne!(phash2(&nested), phash2(&other));
+}
+
+#[test]
+fn test_deeply_nested_terms_do_not_overflow_the_stack() {
+    let term = nested_tuples(200_000, erl_atom!("leaf"));
+    assert!(phash2(&term) <= MASK_27);
+    dismantle(term);
+}
+
+// ============================================================================
+// Ranges
+// ============================================================================
+
+#[test]
+fn test_phash2_range() {
+    let term = erl_tuple![erl_atom!("user"), OwnedTerm::binary(b"joe".to_vec())];
+    let full = make_hash2(&term);
+    assert_eq!(phash2(&term), full & MASK_27);
+    assert_eq!(phash2_range(&term, 1), 0);
+    assert_eq!(phash2_range(&term, 64), full % 64);
+    assert_eq!(phash2_range(&term, u32::MAX), full % u32::MAX);
+}
+
+#[test]
+#[should_panic(expected = "phash2 range must be positive")]
+fn test_phash2_range_rejects_zero() {
+    phash2_range(&erl_atom!("a"), 0);
+}
@@ -0,0 +1,148 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::OwnedTerm;
+use erltf::types::{Atom, BigInt, ExternalFun, ExternalPid, ExternalReference};
+use erltf::{erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+fn inspect(term: &OwnedTerm) -> String {
+    term.inspect().to_string()
+}
+
+#[test]
+fn test_inspect_atoms() {
+    assert_eq!(inspect(&erl_atom!("ok")), ":ok");
+    assert_eq!(inspect(&erl_atom!("nil")), "nil");
+    assert_eq!(inspect(&erl_atom!("true")), "true");
+    assert_eq!(inspect(&erl_atom!("valid?")), ":valid?");
+    assert_eq!(inspect(&erl_atom!("Foo")), ":Foo");
+    assert_eq!(inspect(&erl_atom!("with space")), ":\"with space\"");
+    assert_eq!(inspect(&erl_atom!("Elixir.MyApp.Struct")), "MyApp.Struct");
+    assert_eq!(inspect(&erl_atom!("Elixir")), "Elixir");
+    assert_eq!(inspect(&erl_atom!("Elixir.lower")), ":\"Elixir.lower\"");
+}
+
+#[test]
+fn test_inspect_numbers() {
+    assert_eq!(inspect(&erl_int!(-7)), "-7");
+    assert_eq!(inspect(&OwnedTerm::Float(1.5)), "1.5");
+    assert_eq!(
+        inspect(&OwnedTerm::BigInt(BigInt::new(
+            true,
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 1]
+        ))),
+        "-18446744073709551616"
+    );
+}
+
+#[test]
+fn test_inspect_binaries_and_charlists() {
+    assert_eq!(inspect(&OwnedTerm::Binary(b"x".to_vec())), "\"x\"");
+    assert_eq!(inspect(&OwnedTerm::Binary(vec![])), "\"\"");
+    assert_eq!(
+        inspect(&OwnedTerm::Binary("héllo\n#{x}".as_bytes().to_vec())),
+        "\"héllo\\n\\#{x}\""
+    );
+    assert_eq!(inspect(&OwnedTerm::Binary(vec![0, 255])), "<<0, 255>>");
+    assert_eq!(inspect(&OwnedTerm::charlist("abc")), "~c\"abc\"");
+    assert_eq!(inspect(&OwnedTerm::charlist("é")), "[233]");
+    assert_eq!(
+        inspect(&OwnedTerm::BitBinary {
+            bytes: vec![1, 0b1010_0000],
+            bits: 3
+        }),
+        "<<1, 5::size(3)>>"
+    );
+}
+
+#[test]
+fn test_inspect_lists_and_keywords() {
+    assert_eq!(inspect(&OwnedTerm::Nil), "[]");
+    assert_eq!(inspect(&OwnedTerm::List(vec![])), "[]");
+    assert_eq!(inspect(&erl_list![erl_int!(1), erl_atom!("a")]), "[1, :a]");
+    let keywords = erl_list![
+        erl_tuple![erl_atom!("a"), erl_int!(1)],
+        erl_tuple![erl_atom!("b c"), OwnedTerm::Binary(b"x".to_vec())]
+    ];
+    assert_eq!(inspect(&keywords), "[a: 1, \"b c\": \"x\"]");
+    // Bare atoms make a valid proplist but not a keyword list
+    assert_eq!(inspect(&erl_list![erl_atom!("a")]), "[:a]");
+    assert_eq!(
+        inspect(&OwnedTerm::improper_list(vec![erl_int!(1)], erl_int!(2))),
+        "[1 | 2]"
+    );
+}
+
+#[test]
+fn test_inspect_tuples_and_maps() {
+    assert_eq!(
+        inspect(&erl_tuple![erl_atom!("ok"), erl_int!(1)]),
+        "{:ok, 1}"
+    );
+    assert_eq!(
+        inspect(&erl_map! { erl_atom!("name") => OwnedTerm::Binary(b"x".to_vec()) }),
+        "%{name: \"x\"}"
+    );
+    assert_eq!(
+        inspect(&erl_map! {
+            erl_atom!("a") => erl_int!(1),
+            OwnedTerm::Binary(b"b".to_vec()) => erl_int!(2)
+        }),
+        "%{:a => 1, \"b\" => 2}"
+    );
+    assert_eq!(inspect(&erl_map! {}), "%{}");
+}
+
+#[test]
+fn test_inspect_structs() {
+    let term = erl_map! {
+        erl_atom!("__struct__") => erl_atom!("Elixir.MyApp.User"),
+        erl_atom!("name") => OwnedTerm::Binary(b"x".to_vec()),
+        erl_atom!("age") => erl_int!(3)
+    };
+    assert_eq!(inspect(&term), "%MyApp.User{age: 3, name: \"x\"}");
+}
+
+#[test]
+fn test_inspect_identifiers_and_funs() {
+    let node = Atom::new("nonode@nohost");
+    assert_eq!(
+        inspect(&OwnedTerm::Pid(ExternalPid::new(node.clone(), 85, 0, 1))),
+        "#PID<0.85.0>"
+    );
+    assert_eq!(
+        inspect(&OwnedTerm::Reference(ExternalReference::new(
+            node,
+            0,
+            vec![1, 2, 3]
+        ))),
+        "#Reference<0.3.2.1>"
+    );
+    assert_eq!(
+        inspect(&OwnedTerm::ExternalFun(ExternalFun::new(
+            Atom::new("Elixir.Enum"),
+            Atom::new("map"),
+            2
+        ))),
+        "&Enum.map/2"
+    );
+    assert_eq!(
+        inspect(&OwnedTerm::ExternalFun(ExternalFun::new(
+            Atom::new("lists"),
+            Atom::new("reverse"),
+            1
+        ))),
+        "&:lists.reverse/1"
+    );
+}
@@ -16,6 +16,7 @@ use erltf::OwnedTerm;
 use erltf::types::BigInt;
 use erltf::{erl_atom, erl_int, erl_list, erl_map, erl_tuple};
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 #[test]
 fn test_erlang_term_ordering_types() {
@@ -330,3 +331,51 @@ fn test_negative_bigint_larger_than_8_bytes_vs_integer() {
 
     assert!(large_negative_bigint < min_int);
 }
+
+#[test]
+fn test_proper_vs_improper_list_ordering() {
+    // [0 | 0] < [0, 1] because the integer tail sorts before the cell [1]
+    let improper = OwnedTerm::ImproperList {
+        elements: vec![OwnedTerm::integer(0)],
+        tail: Box::new(OwnedTerm::integer(0)),
+    };
+    let proper = OwnedTerm::List(vec![OwnedTerm::integer(0), OwnedTerm::integer(1)]);
+    assert!(improper < proper);
+    assert!(proper > improper);
+
+    // [0 | <<>>] > [0, 1] because binaries sort after lists
+    let binary_tail = OwnedTerm::ImproperList {
+        elements: vec![OwnedTerm::integer(0)],
+        tail: Box::new(OwnedTerm::Binary(vec![])),
+    };
+    assert!(binary_tail > proper);
+    assert!(OwnedTerm::Nil < improper);
+
+    let map: BTreeMap<OwnedTerm, OwnedTerm> =
+        [(improper, OwnedTerm::Nil), (proper, OwnedTerm::Nil)]
+            .into_iter()
+            .collect();
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_binary_vs_bit_binary_ordering() {
+    let binary = OwnedTerm::Binary(vec![1, 2]);
+    let shorter = OwnedTerm::BitBinary {
+        bytes: vec![1, 2, 0b1000_0000],
+        bits: 1,
+    };
+    let smaller = OwnedTerm::BitBinary {
+        bytes: vec![1, 1, 0b1111_0000],
+        bits: 4,
+    };
+    assert!(binary < shorter);
+    assert!(smaller < binary);
+    assert_ne!(
+        OwnedTerm::Binary(vec![1]).cmp(&OwnedTerm::BitBinary {
+            bytes: vec![1],
+            bits: 8,
+        }),
+        Ordering::Equal
+    );
+}
@@ -0,0 +1,332 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference};
+use erltf::{
+    MAX_PARSE_DEPTH, OwnedTerm, ParseErrorKind, erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+    parse,
+};
+
+// ============================================================================
+// Atoms
+// ============================================================================
+
+#[test]
+fn test_parse_unquoted_atom() {
+    assert_eq!(parse("ok").unwrap(), erl_atom!("ok"));
+    assert_eq!(parse("rabbit@host_1").unwrap(), erl_atom!("rabbit@host_1"));
+}
+
+#[test]
+fn test_parse_quoted_atom_with_escapes() {
+    assert_eq!(parse("'Hello World'").unwrap(), erl_atom!("Hello World"));
+    assert_eq!(parse(r"'it\'s'").unwrap(), erl_atom!("it's"));
+    assert_eq!(parse(r"'a\nb\x41\101'").unwrap(), erl_atom!("a\nbAA"));
+    assert_eq!(parse("''").unwrap(), erl_atom!(""));
+}
+
+#[test]
+fn test_parse_atom_too_large() {
+    let input = format!("'{}'", "a".repeat(256));
+    let err = parse(&input).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::AtomTooLarge { size: 256 });
+}
+
+#[test]
+fn test_parse_depth_limit() {
+    // Tuples, maps and lists nested in each other `depth` levels deep
+    let nested = |depth: usize| {
+        let (mut opening, mut closing) = (String::new(), String::new());
+        for level in 0..depth {
+            let (open, close) = match level % 3 {
+                _ if level + 1 == depth => ("[", "]"),
+                0 => ("{", "}"),
+                1 => ("#{a => ", "}"),
+                _ => ("[", "]"),
+            };
+            opening.push_str(open);
+            closing.insert_str(0, close);
+        }
+        opening + &closing
+    };
+    assert!(parse(&nested(MAX_PARSE_DEPTH)).is_ok());
+
+    let err = parse(&nested(MAX_PARSE_DEPTH + 1)).unwrap_err();
+    assert_eq!(
+        err.kind,
+        ParseErrorKind::DepthLimitExceeded {
+            max: MAX_PARSE_DEPTH
+        }
+    );
+
+    // Far deeper input fails the same way instead of overflowing the stack
+    let err = parse(&nested(100_000)).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        ParseErrorKind::DepthLimitExceeded { .. }
+    ));
+}
+
+#[test]
+fn test_parse_variable_is_rejected() {
+    let err = parse("{ok, Pid}").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedVariable("Pid".into()));
+    assert_eq!((err.line, err.column), (1, 6));
+}
+
+// ============================================================================
+// Numbers
+// ============================================================================
+
+#[test]
+fn test_parse_integers() {
+    assert_eq!(parse("42").unwrap(), erl_int!(42));
+    assert_eq!(parse("-42").unwrap(), erl_int!(-42));
+    assert_eq!(parse("+7").unwrap(), erl_int!(7));
+    assert_eq!(parse("1_000_000").unwrap(), erl_int!(1_000_000));
+    assert_eq!(parse("16#FF").unwrap(), erl_int!(255));
+    assert_eq!(parse("-2#1010").unwrap(), erl_int!(-10));
+    assert_eq!(parse("36#zz").unwrap(), erl_int!(1295));
+}
+
+#[test]
+fn test_parse_integer_boundaries() {
+    assert_eq!(
+        parse("9223372036854775807").unwrap(),
+        OwnedTerm::Integer(i64::MAX)
+    );
+    assert_eq!(
+        parse("-9223372036854775808").unwrap(),
+        OwnedTerm::Integer(i64::MIN)
+    );
+    assert_eq!(
+        parse("9223372036854775808").unwrap(),
+        OwnedTerm::BigInt(BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0x80]))
+    );
+}
+
+#[test]
+fn test_parse_bignum() {
+    assert_eq!(
+        parse("-16#10000000000000000").unwrap(),
+        OwnedTerm::BigInt(BigInt::new(true, vec![0, 0, 0, 0, 0, 0, 0, 0, 1]))
+    );
+}
+
+#[test]
+fn test_parse_char_literals() {
+    assert_eq!(parse("$a").unwrap(), erl_int!(97));
+    assert_eq!(parse(r"$\n").unwrap(), erl_int!(10));
+    assert_eq!(parse(r"$\s").unwrap(), erl_int!(32));
+    assert_eq!(parse("$é").unwrap(), erl_int!(233));
+}
+
+#[test]
+fn test_parse_floats() {
+    assert_eq!(parse("3.5").unwrap(), OwnedTerm::Float(3.5));
+    assert_eq!(parse("-0.25").unwrap(), OwnedTerm::Float(-0.25));
+    assert_eq!(parse("1.0e10").unwrap(), OwnedTerm::Float(1.0e10));
+    assert_eq!(parse("2.5E-3").unwrap(), OwnedTerm::Float(2.5e-3));
+}
+
+#[test]
+fn test_parse_invalid_radix() {
+    let err = parse("37#1").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidInteger("37".into()));
+}
+
+// ============================================================================
+// Strings and binaries
+// ============================================================================
+
+#[test]
+fn test_parse_string_as_charlist() {
+    assert_eq!(parse("\"abc\"").unwrap(), OwnedTerm::charlist("abc"));
+    assert_eq!(parse("\"\"").unwrap(), OwnedTerm::Nil);
+    assert_eq!(parse("\"ab\" \"cd\"").unwrap(), OwnedTerm::charlist("abcd"));
+}
+
+#[test]
+fn test_parse_binaries() {
+    assert_eq!(parse("<<>>").unwrap(), OwnedTerm::Binary(vec![]));
+    assert_eq!(
+        parse("<<1,2,255>>").unwrap(),
+        OwnedTerm::Binary(vec![1, 2, 255])
+    );
+    assert_eq!(
+        parse("<<\"hello\">>").unwrap(),
+        OwnedTerm::Binary(b"hello".to_vec())
+    );
+    assert_eq!(
+        parse("<<\"é\"/utf8, 0>>").unwrap(),
+        OwnedTerm::Binary(vec![0xc3, 0xa9, 0])
+    );
+    assert_eq!(
+        parse("<<256, -1>>").unwrap(),
+        OwnedTerm::Binary(vec![0, 255])
+    );
+}
+
+#[test]
+fn test_parse_bitstring() {
+    assert_eq!(
+        parse("<<1, 5:3>>").unwrap(),
+        OwnedTerm::BitBinary {
+            bytes: vec![1, 0b1010_0000],
+            bits: 3
+        }
+    );
+    let err = parse("<<5:3, 1>>").unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::InvalidBinarySegment(_)));
+}
+
+#[test]
+fn test_parse_binary_non_latin1_without_utf8() {
+    let err = parse("<<\"λ\">>").unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::InvalidBinarySegment(_)));
+}
+
+// ============================================================================
+// Containers
+// ============================================================================
+
+#[test]
+fn test_parse_tuples_and_lists() {
+    assert_eq!(parse("{}").unwrap(), OwnedTerm::Tuple(vec![]));
+    assert_eq!(parse("[]").unwrap(), OwnedTerm::Nil);
+    assert_eq!(
+        parse("{ok, [1, 2, 3]}").unwrap(),
+        erl_tuple![
+            erl_atom!("ok"),
+            erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]
+        ]
+    );
+}
+
+#[test]
+fn test_parse_list_tails() {
+    assert_eq!(
+        parse("[1 | 2]").unwrap(),
+        OwnedTerm::improper_list(vec![erl_int!(1)], erl_int!(2))
+    );
+    assert_eq!(
+        parse("[1 | [2, 3]]").unwrap(),
+        erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]
+    );
+    assert_eq!(parse("[1 | []]").unwrap(), erl_list![erl_int!(1)]);
+}
+
+#[test]
+fn test_parse_maps() {
+    assert_eq!(parse("#{}").unwrap(), erl_map! {});
+    assert_eq!(
+        parse("#{a => 1, <<\"b\">> => [x]}").unwrap(),
+        erl_map! {
+            erl_atom!("a") => erl_int!(1),
+            OwnedTerm::Binary(b"b".to_vec()) => erl_list![erl_atom!("x")]
+        }
+    );
+}
+
+// ============================================================================
+// Pids, references, ports and funs
+// ============================================================================
+
+#[test]
+fn test_parse_pid_port_and_reference() {
+    let node = Atom::new("nonode@nohost");
+    assert_eq!(
+        parse("<0.123.0>").unwrap(),
+        OwnedTerm::Pid(ExternalPid::new(node.clone(), 123, 0, 0))
+    );
+    assert_eq!(
+        parse("#Port<0.7>").unwrap(),
+        OwnedTerm::Port(ExternalPort::new(node.clone(), 7, 0))
+    );
+    assert_eq!(
+        parse("#Ref<0.3.2.1>").unwrap(),
+        OwnedTerm::Reference(ExternalReference::new(node, 0, vec![1, 2, 3]))
+    );
+}
+
+#[test]
+fn test_parse_with_node() {
+    let node = Atom::new("rabbit@localhost");
+    let term = erltf::parse_with_node("<0.1.2>", node.clone()).unwrap();
+    assert_eq!(term, OwnedTerm::Pid(ExternalPid::new(node, 1, 2, 0)));
+}
+
+#[test]
+fn test_parse_remote_pid_is_rejected() {
+    let err = parse("<5.1.0>").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidLiteral("pid"));
+}
+
+#[test]
+fn test_parse_external_fun() {
+    assert_eq!(
+        parse("fun lists:map/2").unwrap(),
+        OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("lists"), Atom::new("map"), 2))
+    );
+}
+
+// ============================================================================
+// Whitespace, comments and errors
+// ============================================================================
+
+#[test]
+fn test_parse_whitespace_comments_and_trailing_dot() {
+    let input = "% a comment\n{ ok , % another\n  1 } .\n";
+    assert_eq!(
+        parse(input).unwrap(),
+        erl_tuple![erl_atom!("ok"), erl_int!(1)]
+    );
+}
+
+#[test]
+fn test_from_str() {
+    let term: OwnedTerm = "{error, badarg}".parse().unwrap();
+    assert_eq!(term, erl_tuple![erl_atom!("error"), erl_atom!("badarg")]);
+}
+
+#[test]
+fn test_parse_error_position() {
+    let err = parse("{ok,\n  [1, 2;]}").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar(';'));
+    assert_eq!((err.line, err.column), (2, 8));
+    assert_eq!(
+        err.to_string(),
+        "unexpected character ';' at line 2, column 8"
+    );
+}
+
+#[test]
+fn test_parse_unexpected_eof() {
+    let err = parse("[1, 2").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_parse_trailing_garbage() {
+    let err = parse("ok ok").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('o'));
+    assert_eq!((err.line, err.column), (1, 4));
+}
+
+#[test]
+fn test_parse_roundtrips_through_codec() {
+    let term = parse("{reply, #{count => 16#FFFFFFFFFFFFFFFFFF}, [<<1,2>> | tail]}").unwrap();
+    let decoded = erltf::decode(&erltf::encode(&term).unwrap()).unwrap();
+    assert_eq!(term, decoded);
+}
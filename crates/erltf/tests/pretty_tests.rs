@@ -0,0 +1,249 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference};
+use erltf::{
+    OwnedTerm, PrettyOptions, erl_atom, erl_int, erl_list, erl_map, erl_tuple, parse, pretty_print,
+};
+
+fn pretty(term: &OwnedTerm) -> String {
+    term.to_pretty_string()
+}
+
+fn assert_roundtrip(term: &OwnedTerm) {
+    let printed = pretty(term);
+    assert_eq!(&parse(&printed).unwrap(), term, "printed as {}", printed);
+}
+
+// ============================================================================
+// Atoms
+// ============================================================================
+
+#[test]
+fn test_pretty_atoms() {
+    assert_eq!(pretty(&erl_atom!("ok")), "ok");
+    assert_eq!(pretty(&erl_atom!("rabbit@host")), "rabbit@host");
+    assert_eq!(pretty(&erl_atom!("Hello")), "'Hello'");
+    assert_eq!(pretty(&erl_atom!("with space")), "'with space'");
+    assert_eq!(pretty(&erl_atom!("")), "''");
+    assert_eq!(pretty(&erl_atom!("it's")), r"'it\'s'");
+    assert_eq!(pretty(&erl_atom!("end")), "'end'");
+    assert_eq!(pretty(&erl_atom!("a-b")), "'a-b'");
+}
+
+// ============================================================================
+// Numbers
+// ============================================================================
+
+#[test]
+fn test_pretty_numbers() {
+    assert_eq!(pretty(&erl_int!(-42)), "-42");
+    assert_eq!(pretty(&OwnedTerm::Float(1.0)), "1.0");
+    assert_eq!(pretty(&OwnedTerm::Float(0.1)), "0.1");
+    assert_eq!(pretty(&OwnedTerm::Float(1.0e300)), "1.0e300");
+    assert_eq!(pretty(&OwnedTerm::Float(-2.5e-10)), "-2.5e-10");
+}
+
+#[test]
+fn test_pretty_bigint_in_decimal() {
+    let big = OwnedTerm::BigInt(BigInt::new(false, vec![0, 0, 0, 0, 0, 0, 0, 0, 1]));
+    assert_eq!(pretty(&big), "18446744073709551616");
+
+    let negative = OwnedTerm::BigInt(BigInt::new(true, vec![0xff; 16]));
+    assert_eq!(
+        pretty(&negative),
+        "-340282366920938463463374607431768211455"
+    );
+    assert_roundtrip(&negative);
+}
+
+// ============================================================================
+// Strings and binaries
+// ============================================================================
+
+#[test]
+fn test_pretty_charlists() {
+    assert_eq!(pretty(&OwnedTerm::charlist("hello")), "\"hello\"");
+    assert_eq!(pretty(&OwnedTerm::charlist("a\"b\n")), r#""a\"b\n""#);
+    assert_eq!(
+        pretty(&erl_list![erl_int!(1), erl_int!(2), erl_int!(3)]),
+        "[1,2,3]"
+    );
+    assert_eq!(pretty(&OwnedTerm::Nil), "[]");
+}
+
+#[test]
+fn test_pretty_binaries() {
+    assert_eq!(pretty(&OwnedTerm::Binary(vec![])), "<<>>");
+    assert_eq!(
+        pretty(&OwnedTerm::Binary(b"hello".to_vec())),
+        "<<\"hello\">>"
+    );
+    assert_eq!(
+        pretty(&OwnedTerm::Binary("héllo".as_bytes().to_vec())),
+        "<<\"héllo\"/utf8>>"
+    );
+    assert_eq!(pretty(&OwnedTerm::Binary(vec![0, 1, 255])), "<<0,1,255>>");
+    assert_eq!(
+        pretty(&OwnedTerm::BitBinary {
+            bytes: vec![1, 0b1010_0000],
+            bits: 3
+        }),
+        "<<1,5:3>>"
+    );
+}
+
+#[test]
+fn test_pretty_string_variant_prints_as_binary() {
+    assert_eq!(pretty(&OwnedTerm::String("abc".into())), "<<\"abc\">>");
+}
+
+// ============================================================================
+// Containers and identifiers
+// ============================================================================
+
+#[test]
+fn test_pretty_containers() {
+    let term = erl_tuple![
+        erl_atom!("ok"),
+        erl_map! { erl_atom!("a") => erl_int!(1), erl_atom!("b") => OwnedTerm::Nil },
+        OwnedTerm::improper_list(vec![erl_int!(1)], erl_atom!("t"))
+    ];
+    assert_eq!(pretty(&term), "{ok,#{a => 1,b => []},[1|t]}");
+    assert_eq!(pretty(&OwnedTerm::Tuple(vec![])), "{}");
+    assert_eq!(pretty(&erl_map! {}), "#{}");
+}
+
+#[test]
+fn test_pretty_identifiers() {
+    let node = Atom::new("nonode@nohost");
+    assert_eq!(
+        pretty(&OwnedTerm::Pid(ExternalPid::new(node.clone(), 85, 0, 3))),
+        "<0.85.0>"
+    );
+    assert_eq!(
+        pretty(&OwnedTerm::Port(ExternalPort::new(node.clone(), 7, 0))),
+        "#Port<0.7>"
+    );
+    assert_eq!(
+        pretty(&OwnedTerm::Reference(ExternalReference::new(
+            node,
+            0,
+            vec![1, 2, 3]
+        ))),
+        "#Ref<0.3.2.1>"
+    );
+    assert_eq!(
+        pretty(&OwnedTerm::ExternalFun(ExternalFun::new(
+            Atom::new("lists"),
+            Atom::new("map"),
+            2
+        ))),
+        "fun lists:map/2"
+    );
+}
+
+// ============================================================================
+// Line width and depth
+// ============================================================================
+
+#[test]
+fn test_pretty_breaks_long_lines() {
+    let items: Vec<OwnedTerm> = (0..4)
+        .map(|i| {
+            erl_tuple![
+                erl_atom!("key"),
+                OwnedTerm::charlist(format!("value_{}", i))
+            ]
+        })
+        .collect();
+    let term = erl_tuple![erl_atom!("ok"), OwnedTerm::List(items)];
+    let options = PrettyOptions::new().with_line_width(40);
+
+    let expected = "\
+{ok,[{key,\"value_0\"},
+     {key,\"value_1\"},
+     {key,\"value_2\"},
+     {key,\"value_3\"}]}";
+    assert_eq!(pretty_print(&term, &options), expected);
+    assert!(expected.lines().all(|line| line.len() <= 40));
+    assert_eq!(parse(expected).unwrap(), term);
+}
+
+#[test]
+fn test_pretty_breaks_maps() {
+    let term = erl_map! {
+        erl_atom!("first") => OwnedTerm::charlist("a fairly long string value"),
+        erl_atom!("second") => erl_list![erl_int!(1), erl_int!(2)]
+    };
+    let options = PrettyOptions::new().with_line_width(40);
+    assert_eq!(
+        pretty_print(&term, &options),
+        "#{first => \"a fairly long string value\",\n  second => [1,2]}"
+    );
+}
+
+#[test]
+fn test_pretty_depth_limit() {
+    let term = erl_tuple![
+        erl_atom!("a"),
+        erl_tuple![erl_atom!("b"), erl_tuple![erl_atom!("c"), erl_atom!("d")]],
+        erl_int!(3)
+    ];
+    let depth = |d| pretty_print(&term, &PrettyOptions::new().with_depth(d));
+    assert_eq!(depth(0), "...");
+    assert_eq!(depth(1), "{...}");
+    assert_eq!(depth(2), "{a,...}");
+    assert_eq!(depth(3), "{a,{...},...}");
+    assert_eq!(depth(4), "{a,{b,...},3}");
+    assert_eq!(depth(10), "{a,{b,{c,d}},3}");
+}
+
+#[test]
+fn test_pretty_depth_limit_lists_and_binaries() {
+    let list = erl_list![erl_int!(1), erl_int!(2), erl_int!(3), erl_int!(4)];
+    let options = PrettyOptions::new().with_depth(3);
+    assert_eq!(pretty_print(&list, &options), "[1,2|...]");
+
+    let binary = OwnedTerm::Binary(vec![1, 2, 3, 4, 5]);
+    assert_eq!(pretty_print(&binary, &options), "<<1,2,...>>");
+}
+
+// ============================================================================
+// Round trips
+// ============================================================================
+
+#[test]
+fn test_pretty_roundtrips_through_parser() {
+    let node = Atom::new("nonode@nohost");
+    let terms = [
+        erl_atom!("Quoted Atom\t"),
+        OwnedTerm::Float(-0.0001),
+        OwnedTerm::BigInt(BigInt::new(false, vec![1, 2, 3, 4, 5, 6, 7, 8, 9])),
+        OwnedTerm::charlist("tab\there"),
+        OwnedTerm::Binary(vec![7, 200]),
+        OwnedTerm::Binary("λ".as_bytes().to_vec()),
+        OwnedTerm::BitBinary {
+            bytes: vec![0xab, 0xc0],
+            bits: 2,
+        },
+        erl_map! { OwnedTerm::Binary(b"k".to_vec()) => erl_list![erl_atom!("v")] },
+        OwnedTerm::improper_list(vec![erl_int!(1), erl_int!(2)], erl_int!(3)),
+        OwnedTerm::Pid(ExternalPid::new(node.clone(), 1, 2, 0)),
+        OwnedTerm::Reference(ExternalReference::new(node, 0, vec![9, 8, 7])),
+    ];
+    for term in &terms {
+        assert_roundtrip(term);
+    }
+}
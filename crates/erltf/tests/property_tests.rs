@@ -15,7 +15,10 @@
 use erltf::types::{
     Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun, Sign,
 };
-use erltf::{OwnedTerm, decode, encode};
+use erltf::{
+    EncodeOptions, OwnedTerm, decode, encode, encode_with_dist_header_multi, encode_with_options,
+    encoded_size, encoded_size_with_dist_header,
+};
 use proptest::prelude::*;
 
 fn arb_atom() -> impl Strategy<Value = Atom> {
@@ -148,6 +151,122 @@ fn arb_term() -> impl Strategy<Value = OwnedTerm> {
     })
 }
 
+fn arb_any_atom() -> impl Strategy<Value = Atom> {
+    prop_oneof![
+        arb_atom(),
+        "[a-z\u{e0}-\u{ff}]{0,20}".prop_map(Atom::new),
+        "[a-z\u{3b1}-\u{3c9}\u{65e5}]{0,100}".prop_map(Atom::new),
+    ]
+}
+
+fn arb_scalar_term() -> impl Strategy<Value = OwnedTerm> {
+    prop_oneof![
+        any::<i64>().prop_map(OwnedTerm::Integer),
+        any::<u8>().prop_map(|v| OwnedTerm::Integer(v as i64)),
+        any::<f64>().prop_map(OwnedTerm::Float),
+        arb_any_atom().prop_map(OwnedTerm::Atom),
+        prop::collection::vec(any::<u8>(), 0..100).prop_map(OwnedTerm::Binary),
+        "[a-z\u{e9}]{0,20}".prop_map(OwnedTerm::String),
+        prop::collection::vec(0u8..=255, 1..20).prop_map(|bytes| OwnedTerm::List(
+            bytes
+                .into_iter()
+                .map(|b| OwnedTerm::Integer(b as i64))
+                .collect()
+        )),
+        arb_bit_binary(),
+        (prop::bool::ANY, prop::collection::vec(any::<u8>(), 0..300))
+            .prop_map(|(negative, digits)| OwnedTerm::BigInt(BigInt::new(negative, digits))),
+        arb_pid().prop_map(OwnedTerm::Pid),
+        arb_port().prop_map(OwnedTerm::Port),
+        arb_reference().prop_map(OwnedTerm::Reference),
+        arb_external_fun().prop_map(OwnedTerm::ExternalFun),
+        arb_internal_fun().prop_map(|fun| OwnedTerm::InternalFun(Box::new(fun))),
+        Just(OwnedTerm::Nil),
+        prop::collection::vec(any::<u8>(), 256..300).prop_map(|elements| OwnedTerm::Tuple(
+            elements
+                .into_iter()
+                .map(|e| OwnedTerm::Integer(e as i64))
+                .collect()
+        )),
+    ]
+}
+
+fn arb_any_term() -> impl Strategy<Value = OwnedTerm> {
+    arb_scalar_term().prop_recursive(3, 64, 10, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..10).prop_map(OwnedTerm::List),
+            prop::collection::vec(inner.clone(), 0..10).prop_map(OwnedTerm::Tuple),
+            (prop::collection::vec(inner.clone(), 1..5), inner.clone()).prop_map(
+                |(elements, tail)| OwnedTerm::ImproperList {
+                    elements,
+                    tail: Box::new(tail),
+                }
+            ),
+            prop::collection::vec((inner.clone(), inner), 0..10)
+                .prop_map(|entries| OwnedTerm::Map(entries.into_iter().collect())),
+        ]
+    })
+}
+
+fn arb_encode_options() -> impl Strategy<Value = EncodeOptions> {
+    (0u8..=2, prop::bool::ANY, prop::bool::ANY, prop::bool::ANY).prop_map(
+        |(minor_version, small_atoms, deterministic, preserve_local_ext)| {
+            EncodeOptions::new()
+                .with_minor_version(minor_version)
+                .with_small_atoms(small_atoms)
+                .with_deterministic(deterministic)
+                .with_preserve_local_ext(preserve_local_ext)
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn test_prop_encoded_size_matches_encode(term in arb_any_term()) {
+        let encoded = encode(&term).unwrap();
+        prop_assert_eq!(encoded_size(&term, &EncodeOptions::default()).unwrap(), encoded.len());
+    }
+
+    #[test]
+    fn test_prop_encoded_size_matches_encode_with_options(
+        term in arb_any_term(),
+        options in arb_encode_options(),
+    ) {
+        let encoded = encode_with_options(&term, &options).unwrap();
+        prop_assert_eq!(encoded_size(&term, &options).unwrap(), encoded.len());
+    }
+
+    #[test]
+    fn test_prop_encoded_size_with_dist_header(
+        control in arb_any_term(),
+        payload in arb_any_term(),
+    ) {
+        let terms = [&control, &payload];
+        match encode_with_dist_header_multi(&terms) {
+            Ok(encoded) => {
+                prop_assert_eq!(encoded_size_with_dist_header(&terms).unwrap(), encoded.len());
+            }
+            Err(_) => prop_assert!(encoded_size_with_dist_header(&terms).is_err()),
+        }
+    }
+
+    #[test]
+    fn test_prop_encoded_size_of_decoded_local_ext(id in any::<u32>(), hash in any::<u64>()) {
+        let mut data = vec![131, 121];
+        data.extend(hash.to_be_bytes());
+        data.extend([88, 119, 3, b'a', b'@', b'b']);
+        data.extend(id.to_be_bytes());
+        data.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+        let pid = decode(&data).unwrap();
+        let options = EncodeOptions::new().with_preserve_local_ext(false);
+        prop_assert_eq!(encoded_size(&pid, &EncodeOptions::default()).unwrap(), data.len());
+        prop_assert_eq!(
+            encoded_size(&pid, &options).unwrap(),
+            encode_with_options(&pid, &options).unwrap().len()
+        );
+    }
+}
+
 proptest! {
     #[test]
     fn test_prop_roundtrip_integer(value in any::<i32>()) {
@@ -0,0 +1,209 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::TermConversionError;
+use erltf::{
+    OwnedTerm, ParseErrorKind, RecordDef, RecordRegistry, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple, parse, parse_records,
+};
+
+const HEADER: &str = r#"
+%% Records shared by the queue processes.
+-include_lib("kernel/include/file.hrl").
+-define(DEFAULT_LIMIT, 100).
+
+-record(resource, {virtual_host :: binary(), kind = queue, name}).
+
+-record(q_state, {
+    name :: #resource{} | undefined,   % owning queue
+    limit = 100 :: non_neg_integer(),
+    consumers = [] :: [pid()],
+    opts = #{mode => "lazy, really"} :: map(),
+    started_at = erlang:monotonic_time(),
+    'weird-field' = {a, {b, c}},
+    tag = <<"a,b">>
+}).
+
+-type q_state() :: #q_state{}.
+-record(empty, {}).
+"#;
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_records_from_header() {
+    let records = parse_records(HEADER).unwrap();
+    let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["resource", "q_state", "empty"]);
+
+    let resource = &records[0];
+    let fields: Vec<&str> = resource.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(fields, ["virtual_host", "kind", "name"]);
+    assert_eq!(resource.fields[0].default, None);
+    assert_eq!(resource.fields[1].default, Some(erl_atom!("queue")));
+    assert_eq!(records[2].arity(), 1);
+}
+
+#[test]
+fn test_parse_records_defaults_and_types() {
+    let records = parse_records(HEADER).unwrap();
+    let state = &records[1];
+    let fields: Vec<&str> = state.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        fields,
+        [
+            "name",
+            "limit",
+            "consumers",
+            "opts",
+            "started_at",
+            "weird-field",
+            "tag"
+        ]
+    );
+
+    let defaults: Vec<Option<OwnedTerm>> = state.fields.iter().map(|f| f.default.clone()).collect();
+    assert_eq!(defaults[0], None);
+    assert_eq!(defaults[1], Some(erl_int!(100)));
+    assert_eq!(defaults[2], Some(OwnedTerm::Nil));
+    assert_eq!(
+        defaults[3],
+        Some(parse("#{mode => \"lazy, really\"}").unwrap())
+    );
+    assert_eq!(defaults[4], None);
+    assert_eq!(defaults[5], Some(parse("{a, {b, c}}").unwrap()));
+    assert_eq!(defaults[6], Some(OwnedTerm::Binary(b"a,b".to_vec())));
+}
+
+#[test]
+fn test_parse_records_reports_position() {
+    let err = parse_records("-record(broken, {a = 1,\n  B}).").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('B'));
+    assert_eq!((err.line, err.column), (2, 3));
+
+    let err = parse_records("-record(unterminated, {a, b").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedEof);
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+#[test]
+fn test_record_to_map_and_back() {
+    let registry = RecordRegistry::from_hrl(HEADER).unwrap();
+    let record = erl_tuple![
+        erl_atom!("resource"),
+        OwnedTerm::Binary(b"/".to_vec()),
+        erl_atom!("queue"),
+        OwnedTerm::Binary(b"orders".to_vec())
+    ];
+
+    let map = registry.to_map(&record).unwrap();
+    assert_eq!(
+        map,
+        erl_map! {
+            erl_atom!("virtual_host") => OwnedTerm::Binary(b"/".to_vec()),
+            erl_atom!("kind") => erl_atom!("queue"),
+            erl_atom!("name") => OwnedTerm::Binary(b"orders".to_vec())
+        }
+    );
+    assert_eq!(registry.from_map("resource", &map).unwrap(), record);
+}
+
+#[test]
+fn test_from_map_fills_defaults() {
+    let registry = RecordRegistry::from_hrl(HEADER).unwrap();
+    let map = erl_map! { erl_atom!("name") => OwnedTerm::Binary(b"orders".to_vec()) };
+
+    assert_eq!(
+        registry.from_map("resource", &map).unwrap(),
+        erl_tuple![
+            erl_atom!("resource"),
+            erl_atom!("undefined"),
+            erl_atom!("queue"),
+            OwnedTerm::Binary(b"orders".to_vec())
+        ]
+    );
+}
+
+#[test]
+fn test_conversion_errors() {
+    let registry = RecordRegistry::from_hrl(HEADER).unwrap();
+
+    assert_eq!(
+        registry.from_map("resource", &erl_map! { erl_atom!("owner") => erl_int!(1) }),
+        Err(TermConversionError::UnknownField("owner".to_string()))
+    );
+    assert_eq!(
+        registry.from_map("missing", &erl_map! {}),
+        Err(TermConversionError::UnknownTag("missing".to_string()))
+    );
+    assert_eq!(
+        registry.to_map(&erl_tuple![erl_atom!("resource"), erl_int!(1)]),
+        Err(TermConversionError::UnknownTag("resource".to_string()))
+    );
+
+    let def = registry.get("resource").unwrap();
+    assert_eq!(
+        def.to_map(&erl_tuple![erl_atom!("resource"), erl_int!(1)]),
+        Err(TermConversionError::WrongArity {
+            expected: 4,
+            actual: 2
+        })
+    );
+    assert!(def.to_map(&erl_int!(1)).is_err());
+}
+
+#[test]
+fn test_records_to_maps_nested() {
+    let registry = RecordRegistry::new().with_record(
+        RecordDef::new("point")
+            .with_field("x")
+            .with_default("y", erl_int!(0)),
+    );
+    let term = erl_list![
+        erl_tuple![
+            erl_atom!("ok"),
+            erl_tuple![erl_atom!("point"), erl_int!(1), erl_int!(2)]
+        ],
+        erl_tuple![erl_atom!("point"), erl_int!(3)],
+        erl_tuple![
+            erl_atom!("point"),
+            erl_tuple![erl_atom!("point"), erl_int!(4), erl_int!(5)],
+            erl_int!(6)
+        ]
+    ];
+
+    assert_eq!(
+        registry.records_to_maps(&term),
+        erl_list![
+            erl_tuple![
+                erl_atom!("ok"),
+                erl_map! { erl_atom!("x") => erl_int!(1), erl_atom!("y") => erl_int!(2) }
+            ],
+            erl_tuple![erl_atom!("point"), erl_int!(3)],
+            erl_map! {
+                erl_atom!("x") => erl_map! { erl_atom!("x") => erl_int!(4), erl_atom!("y") => erl_int!(5) },
+                erl_atom!("y") => erl_int!(6)
+            }
+        ]
+    );
+    assert_eq!(
+        registry.get("point").unwrap().default_record(),
+        erl_tuple![erl_atom!("point"), erl_atom!("undefined"), erl_int!(0)]
+    );
+}
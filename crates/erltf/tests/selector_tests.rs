@@ -0,0 +1,198 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::TermConversionError;
+use erltf::{
+    OwnedTerm, Selector, SelectorError, SelectorErrorKind, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
+
+fn status() -> OwnedTerm {
+    erl_list![
+        erl_tuple![
+            erl_atom!("cluster_name"),
+            OwnedTerm::Binary(b"rabbit@host".to_vec())
+        ],
+        erl_tuple![
+            erl_atom!("listeners"),
+            erl_list![
+                erl_map! {
+                    erl_atom!("protocol") => erl_atom!("amqp"),
+                    erl_atom!("port") => erl_int!(5672)
+                },
+                erl_map! {
+                    erl_atom!("protocol") => erl_atom!("http"),
+                    erl_atom!("port") => erl_int!(15672)
+                }
+            ]
+        ],
+        erl_tuple![
+            erl_atom!("node info"),
+            erl_map! {
+                OwnedTerm::Binary(b"name".to_vec()) => erl_atom!("rabbit"),
+                erl_int!(1) => erl_tuple![erl_atom!("ip"), erl_tuple![erl_int!(127), erl_int!(0), erl_int!(0), erl_int!(1)]]
+            }
+        ]
+    ]
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_equivalent_spellings() {
+    let a = Selector::parse("$.listeners[*].port").unwrap();
+    let b: Selector = "listeners.*.port".parse().unwrap();
+    let c = Selector::parse("$#{listeners}[*]#{port}").unwrap();
+    let d = Selector::parse("$.'listeners'[*].#{port}").unwrap();
+    assert_eq!(a, b);
+    assert_eq!(a, c);
+    assert_eq!(a, d);
+}
+
+#[test]
+fn test_root_selector() {
+    let term = status();
+    assert_eq!(Selector::parse("$").unwrap().first(&term), Some(&term));
+    assert_eq!(Selector::parse("").unwrap().first(&term), Some(&term));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Selector::parse("$.a[x]"),
+        Err(SelectorError::new(
+            SelectorErrorKind::InvalidIndex("x".to_string()),
+            4
+        ))
+    );
+    assert_eq!(
+        Selector::parse("$.a[1"),
+        Err(SelectorError::new(SelectorErrorKind::UnexpectedEof, 5))
+    );
+    assert_eq!(
+        Selector::parse("$.a!"),
+        Err(SelectorError::new(
+            SelectorErrorKind::UnexpectedChar('!'),
+            3
+        ))
+    );
+    assert_eq!(
+        Selector::parse("$."),
+        Err(SelectorError::new(SelectorErrorKind::UnexpectedEof, 2))
+    );
+    assert!(matches!(
+        Selector::parse("#{<<1,>>}").unwrap_err().kind,
+        SelectorErrorKind::InvalidKey(_)
+    ));
+    assert_eq!(
+        Selector::parse("$.'unterminated").unwrap_err().kind,
+        SelectorErrorKind::UnexpectedEof
+    );
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+#[test]
+fn test_wildcard_over_list_of_maps() {
+    let term = status();
+    let ports: Vec<&OwnedTerm> = term.select("$.listeners[*].port").unwrap().collect();
+    assert_eq!(ports, vec![&erl_int!(5672), &erl_int!(15672)]);
+
+    let protocols: Vec<&OwnedTerm> = term.select("listeners.*.protocol").unwrap().collect();
+    assert_eq!(protocols, vec![&erl_atom!("amqp"), &erl_atom!("http")]);
+}
+
+#[test]
+fn test_index_and_term_keys() {
+    let term = status();
+    assert_eq!(
+        term.select_first("[2][1].#{<<\"name\">>}").unwrap(),
+        Some(&erl_atom!("rabbit"))
+    );
+    assert_eq!(
+        term.select_first("$.'node info'#{1}[1][3]").unwrap(),
+        Some(&erl_int!(1))
+    );
+    assert_eq!(
+        term.select_first("$.cluster_name").unwrap(),
+        Some(&OwnedTerm::Binary(b"rabbit@host".to_vec()))
+    );
+}
+
+#[test]
+fn test_wildcard_over_map_values_and_tuples() {
+    let term = erl_map! {
+        erl_atom!("a") => erl_tuple![erl_int!(1), erl_int!(2)],
+        erl_atom!("b") => erl_tuple![erl_int!(3)]
+    };
+    let all: Vec<&OwnedTerm> = term.select("$.*[*]").unwrap().collect();
+    assert_eq!(all, vec![&erl_int!(1), &erl_int!(2), &erl_int!(3)]);
+}
+
+#[test]
+fn test_no_matches() {
+    let term = status();
+    assert_eq!(term.select("$.missing").unwrap().count(), 0);
+    assert_eq!(term.select("$.listeners[5]").unwrap().count(), 0);
+    assert_eq!(term.select("$.cluster_name[*]").unwrap().count(), 0);
+    assert_eq!(term.select("$.listeners[*].missing").unwrap().count(), 0);
+    assert_eq!(OwnedTerm::Nil.select("[*]").unwrap().count(), 0);
+}
+
+#[test]
+fn test_proplist_lookup_uses_first_pair() {
+    let term = erl_list![
+        erl_atom!("flag"),
+        erl_tuple![erl_atom!("k"), erl_int!(1)],
+        erl_tuple![erl_atom!("k"), erl_int!(2)]
+    ];
+    assert_eq!(
+        term.select("k").unwrap().collect::<Vec<_>>(),
+        vec![&erl_int!(1)]
+    );
+}
+
+// ============================================================================
+// Typed Extraction
+// ============================================================================
+
+#[test]
+fn test_extract() {
+    let term = status();
+    let selector = Selector::parse("$.listeners[*].port").unwrap();
+    let ports: Vec<i64> = selector.extract(&term).collect::<Result<_, _>>().unwrap();
+    assert_eq!(ports, vec![5672, 15672]);
+
+    let protocols: Result<Vec<i64>, _> = term
+        .select("$.listeners[*].protocol")
+        .unwrap()
+        .extract()
+        .collect();
+    assert!(matches!(
+        protocols,
+        Err(TermConversionError::WrongType { .. })
+    ));
+
+    let name: Vec<String> = term
+        .select("$.cluster_name")
+        .unwrap()
+        .extract()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(name, vec!["rabbit@host".to_string()]);
+}
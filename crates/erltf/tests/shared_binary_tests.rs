@@ -0,0 +1,174 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use erltf::{
+    AtomCache, DecodeError, EncodeOptions, OwnedTerm, SHARED_BINARY_THRESHOLD, decode,
+    decode_shared, decode_shared_with_atom_cache, decode_shared_with_trailing, encode,
+    encode_with_options, erl_atom, erl_int, erl_map, erl_tuple,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn is_within(slice: &[u8], buffer: &[u8]) -> bool {
+    let range = buffer.as_ptr_range();
+    range.contains(&slice.as_ptr()) && slice.as_ptr_range().end <= range.end
+}
+
+fn hash_of(term: &OwnedTerm) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    term.hash(&mut hasher);
+    hasher.finish()
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+#[test]
+fn test_decode_shared_slices_large_binaries() {
+    let payload = vec![0xab; 1024];
+    let term = erl_tuple![erl_atom!("data"), OwnedTerm::Binary(payload.clone())];
+    let data = Bytes::from(encode(&term).unwrap());
+
+    let decoded = decode_shared(&data).unwrap();
+    let elements = decoded.as_tuple().unwrap();
+    match &elements[1] {
+        OwnedTerm::SharedBinary(b) => {
+            assert_eq!(&b[..], &payload[..]);
+            assert!(is_within(b, &data));
+        }
+        other => panic!("expected a shared binary, got {:?}", other),
+    }
+    assert_eq!(decoded, term);
+}
+
+#[test]
+fn test_decode_shared_copies_small_binaries() {
+    let small = OwnedTerm::Binary(vec![1; SHARED_BINARY_THRESHOLD]);
+    let data = Bytes::from(encode(&small).unwrap());
+    assert!(matches!(
+        decode_shared(&data).unwrap(),
+        OwnedTerm::Binary(_)
+    ));
+
+    let large = OwnedTerm::Binary(vec![1; SHARED_BINARY_THRESHOLD + 1]);
+    let data = Bytes::from(encode(&large).unwrap());
+    assert!(matches!(
+        decode_shared(&data).unwrap(),
+        OwnedTerm::SharedBinary(_)
+    ));
+}
+
+#[test]
+fn test_decode_still_copies() {
+    let term = OwnedTerm::Binary(vec![7; 500]);
+    let data = encode(&term).unwrap();
+    assert!(matches!(decode(&data).unwrap(), OwnedTerm::Binary(_)));
+}
+
+#[test]
+fn test_decode_shared_outlives_original_handle() {
+    let data = Bytes::from(encode(&OwnedTerm::Binary(vec![3; 200])).unwrap());
+    let decoded = decode_shared(&data).unwrap();
+    drop(data);
+    assert_eq!(decoded.as_binary(), Some(&[3u8; 200][..]));
+}
+
+#[test]
+fn test_decode_shared_compressed() {
+    let term = erl_tuple![erl_atom!("blob"), OwnedTerm::Binary(b"abcd".repeat(1000))];
+    let data = Bytes::from(encode_with_options(&term, &EncodeOptions::compressed()).unwrap());
+    let decoded = decode_shared(&data).unwrap();
+    assert!(matches!(
+        decoded.as_tuple().unwrap()[1],
+        OwnedTerm::SharedBinary(_)
+    ));
+    assert_eq!(decoded, term);
+}
+
+#[test]
+fn test_decode_shared_with_trailing() {
+    let first = OwnedTerm::Binary(vec![9; 100]);
+    let mut encoded = encode(&first).unwrap();
+    encoded.extend(encode(&erl_int!(42)).unwrap());
+    let data = Bytes::from(encoded);
+
+    let (term, rest) = decode_shared_with_trailing(&data).unwrap();
+    assert_eq!(term, first);
+    assert!(is_within(&rest, &data));
+    assert_eq!(decode_shared(&rest).unwrap(), erl_int!(42));
+    assert!(matches!(
+        decode_shared(&data),
+        Err(DecodeError::TrailingData(_))
+    ));
+}
+
+#[test]
+fn test_decode_shared_with_atom_cache_control_and_payload() {
+    let control = erl_tuple![erl_int!(2), erl_atom!(""), erl_atom!("target")];
+    let payload = OwnedTerm::Binary(vec![5; 300]);
+    let mut encoded = encode(&control).unwrap();
+    encoded.extend(&encode(&payload).unwrap()[1..]);
+    let data = Bytes::from(encoded);
+
+    let mut cache = AtomCache::new();
+    let (decoded_control, decoded_payload) =
+        decode_shared_with_atom_cache(&data, &mut cache).unwrap();
+    assert_eq!(decoded_control, control);
+    match decoded_payload {
+        Some(OwnedTerm::SharedBinary(b)) => assert!(is_within(&b, &data)),
+        other => panic!("expected a shared binary payload, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Equivalence with Binary
+// ============================================================================
+
+#[test]
+fn test_shared_binary_equals_binary() {
+    let bytes = b"some binary".to_vec();
+    let owned = OwnedTerm::Binary(bytes.clone());
+    let shared = OwnedTerm::SharedBinary(Bytes::from(bytes.clone()));
+    assert_eq!(owned, shared);
+    assert_ne!(shared, OwnedTerm::String("some binary".into()));
+    assert_eq!(hash_of(&owned), hash_of(&shared));
+    assert_eq!(owned.cmp(&shared), std::cmp::Ordering::Equal);
+    assert!(shared < OwnedTerm::Binary(b"some binary!".to_vec()));
+    assert_eq!(shared.to_string(), owned.to_string());
+}
+
+#[test]
+fn test_shared_binary_as_map_key() {
+    let key = vec![b'k'; 100];
+    let map = erl_map! { OwnedTerm::Binary(key.clone()) => erl_int!(1) };
+    let data = Bytes::from(encode(&map).unwrap());
+
+    let decoded = decode_shared(&data).unwrap();
+    assert_eq!(decoded.map_get(&OwnedTerm::Binary(key)), Some(&erl_int!(1)));
+}
+
+#[test]
+fn test_shared_binary_conversions() {
+    let shared = OwnedTerm::from(Bytes::from_static(b"hello"));
+    assert_eq!(shared.as_binary(), Some(&b"hello"[..]));
+    assert_eq!(shared.len(), 5);
+    assert_eq!(shared.clone().try_into_binary().unwrap(), b"hello".to_vec());
+    assert_eq!(String::try_from(shared.clone()).unwrap(), "hello");
+    assert_eq!(
+        encode(&shared).unwrap(),
+        encode(&OwnedTerm::Binary(b"hello".to_vec())).unwrap()
+    );
+}
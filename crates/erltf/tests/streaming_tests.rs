@@ -0,0 +1,207 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{Atom, BigInt, ExternalFun, ExternalPid, ExternalReference, InternalFun};
+use erltf::{
+    DecodeError, DecodeOptions, EncodeOptions, OwnedTerm, StreamDecoder, StreamItem, encode,
+    encode_with_options, erl_atom, erl_int, erl_list, erl_map, erl_tuple,
+};
+
+fn sample_terms() -> Vec<OwnedTerm> {
+    let node = Atom::new("rabbit@localhost");
+    let pid = ExternalPid::new(node.clone(), 85, 2, 7);
+    vec![
+        erl_atom!("ok"),
+        erl_int!(-12345678),
+        OwnedTerm::Float(2.5),
+        OwnedTerm::BigInt(BigInt::new(true, vec![1; 300])),
+        OwnedTerm::Binary(vec![7; 1000]),
+        OwnedTerm::BitBinary {
+            bytes: vec![1, 0xe0],
+            bits: 3,
+        },
+        OwnedTerm::charlist("abc"),
+        erl_tuple![erl_atom!("reply"), erl_list![erl_int!(1), erl_int!(2)]],
+        OwnedTerm::improper_list(vec![erl_int!(1)], erl_atom!("tail")),
+        erl_map! { erl_atom!("a") => OwnedTerm::Nil, OwnedTerm::Binary(b"b".to_vec()) => erl_int!(2) },
+        OwnedTerm::Pid(pid.clone()),
+        OwnedTerm::Reference(ExternalReference::new(node, 3, vec![1, 2, 3])),
+        OwnedTerm::ExternalFun(ExternalFun::new(Atom::new("lists"), Atom::new("map"), 2)),
+        OwnedTerm::InternalFun(Box::new(InternalFun::new(
+            1,
+            [9; 16],
+            4,
+            1,
+            Atom::new("erl_eval"),
+            5,
+            6,
+            pid,
+            vec![erl_atom!("free")],
+        ))),
+    ]
+}
+
+fn drain(decoder: &mut StreamDecoder, out: &mut Vec<OwnedTerm>) -> usize {
+    loop {
+        match decoder.next_term().unwrap() {
+            StreamItem::Term(term) => out.push(term),
+            StreamItem::NeedMore(n) => return n,
+        }
+    }
+}
+
+#[test]
+fn test_stream_decode_byte_by_byte() {
+    for term in sample_terms() {
+        let data = encode(&term).unwrap();
+        let mut decoder = StreamDecoder::new();
+        let mut decoded = Vec::new();
+        for byte in &data {
+            assert!(decoded.is_empty());
+            decoder.push(std::slice::from_ref(byte));
+            drain(&mut decoder, &mut decoded);
+        }
+        assert_eq!(decoded, vec![term]);
+        assert_eq!(decoder.buffered_len(), 0);
+        decoder.finish().unwrap();
+    }
+}
+
+#[test]
+fn test_stream_decode_concatenated_terms_in_chunks() {
+    let terms = sample_terms();
+    let mut data = Vec::new();
+    for term in &terms {
+        data.extend(encode(term).unwrap());
+    }
+
+    for chunk_size in [1, 3, 64, 4096, data.len()] {
+        let mut decoder = StreamDecoder::new();
+        let mut decoded = Vec::new();
+        for chunk in data.chunks(chunk_size) {
+            decoder.push(chunk);
+            drain(&mut decoder, &mut decoded);
+        }
+        assert_eq!(decoded, terms, "chunk size {}", chunk_size);
+        decoder.finish().unwrap();
+    }
+}
+
+#[test]
+fn test_stream_decode_compressed_terms() {
+    let term = erl_tuple![
+        erl_atom!("data"),
+        OwnedTerm::Binary(b"abcdefgh".repeat(10_000))
+    ];
+    let options = EncodeOptions::compressed();
+    let mut data = encode_with_options(&term, &options).unwrap();
+    assert_eq!(data[1], 80);
+    data.extend(encode_with_options(&erl_int!(1), &options).unwrap());
+
+    let mut decoder = StreamDecoder::new();
+    let mut decoded = Vec::new();
+    for chunk in data.chunks(100) {
+        decoder.push(chunk);
+        drain(&mut decoder, &mut decoded);
+    }
+    assert_eq!(decoded, vec![term, erl_int!(1)]);
+}
+
+#[test]
+fn test_stream_decode_reports_missing_bytes() {
+    let data = encode(&OwnedTerm::Binary(vec![0; 100])).unwrap();
+    let mut decoder = StreamDecoder::new();
+    assert_eq!(decoder.next_term().unwrap(), StreamItem::NeedMore(1));
+
+    // Version, tag and two of the four length bytes
+    decoder.push(&data[..4]);
+    assert_eq!(decoder.next_term().unwrap(), StreamItem::NeedMore(2));
+
+    decoder.push(&data[4..6]);
+    assert_eq!(decoder.next_term().unwrap(), StreamItem::NeedMore(100));
+
+    decoder.push(&data[6..56]);
+    assert_eq!(decoder.next_term().unwrap(), StreamItem::NeedMore(50));
+
+    decoder.push(&data[56..]);
+    assert_eq!(
+        decoder.next_term().unwrap(),
+        StreamItem::Term(OwnedTerm::Binary(vec![0; 100]))
+    );
+}
+
+#[test]
+fn test_stream_decode_finish_with_partial_term() {
+    let data = encode(&erl_atom!("partial")).unwrap();
+    let mut decoder = StreamDecoder::new();
+    decoder.push(&data[..3]);
+    assert!(matches!(
+        decoder.next_term().unwrap(),
+        StreamItem::NeedMore(_)
+    ));
+    assert_eq!(decoder.finish(), Err(DecodeError::UnexpectedEof));
+}
+
+#[test]
+fn test_stream_decode_invalid_version() {
+    let mut decoder = StreamDecoder::new();
+    decoder.push(&[130, 106]);
+    assert_eq!(
+        decoder.next_term(),
+        Err(DecodeError::InvalidVersion {
+            expected: 131,
+            actual: 130
+        })
+    );
+}
+
+#[test]
+fn test_stream_decode_invalid_tag() {
+    let mut decoder = StreamDecoder::new();
+    decoder.push(&[131, 255]);
+    assert_eq!(decoder.next_term(), Err(DecodeError::InvalidTag(255)));
+}
+
+#[test]
+fn test_stream_decode_rejects_oversized_binary_before_buffering() {
+    let mut decoder = StreamDecoder::new();
+    decoder.push(&[131, 109, 0xff, 0xff, 0xff, 0xff]);
+    assert!(matches!(
+        decoder.next_term(),
+        Err(DecodeError::BinaryTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_stream_decode_with_options() {
+    let options = DecodeOptions::new().with_max_binary_size(16);
+    let mut decoder = StreamDecoder::with_options(options);
+    decoder.push(&[131, 109, 0, 0, 0, 17]);
+    assert_eq!(
+        decoder.next_term(),
+        Err(DecodeError::BinaryTooLarge { size: 17, max: 16 })
+    );
+
+    let mut decoder = StreamDecoder::with_options(DecodeOptions::safe(Vec::<String>::new()));
+    decoder.push(&encode(&erl_atom!("ok")).unwrap());
+    decoder.push(&encode(&erl_atom!("surely_unknown_atom")).unwrap());
+    assert_eq!(
+        decoder.next_term().unwrap(),
+        StreamItem::Term(erl_atom!("ok"))
+    );
+    assert_eq!(
+        decoder.next_term(),
+        Err(DecodeError::UnsafeAtom("surely_unknown_atom".to_string()))
+    );
+}
@@ -0,0 +1,142 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use erltf::types::{Atom, ExternalPid, InternalFun};
+use erltf::{
+    EncodeError, EncodeOptions, OwnedTerm, SHARED_BINARY_THRESHOLD, decode, decode_shared, encode,
+    encode_vectored, encode_vectored_with_options, encode_with_options, erl_atom, erl_int,
+    erl_list, erl_map, erl_tuple,
+};
+
+fn concat(chunks: &[Bytes]) -> Vec<u8> {
+    chunks
+        .iter()
+        .flat_map(|chunk| chunk.iter().copied())
+        .collect()
+}
+
+fn large_payload(len: usize) -> Bytes {
+    Bytes::from((0..len).map(|i| i as u8).collect::<Vec<u8>>())
+}
+
+#[test]
+fn test_encode_vectored_matches_encode() {
+    let payload = large_payload(4096);
+    let term = erl_tuple![
+        erl_atom!("upload"),
+        OwnedTerm::SharedBinary(payload.clone()),
+        erl_map! { erl_atom!("small") => OwnedTerm::binary(b"abc".to_vec()) },
+        erl_list![OwnedTerm::SharedBinary(payload), erl_int!(1)]
+    ];
+
+    let chunks = encode_vectored(&term).unwrap();
+    assert_eq!(concat(&chunks), encode(&term).unwrap());
+    assert_eq!(decode(&concat(&chunks)).unwrap(), term);
+}
+
+#[test]
+fn test_encode_vectored_references_shared_binaries() {
+    let payload = large_payload(1 << 20);
+    let term = erl_tuple![
+        erl_atom!("data"),
+        OwnedTerm::SharedBinary(payload.clone()),
+        erl_atom!("eof")
+    ];
+
+    let chunks = encode_vectored(&term).unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[1].as_ptr(), payload.as_ptr());
+    assert_eq!(chunks[1].len(), payload.len());
+}
+
+#[test]
+fn test_encode_vectored_copies_small_and_owned_binaries() {
+    let small = OwnedTerm::SharedBinary(large_payload(SHARED_BINARY_THRESHOLD));
+    let owned = OwnedTerm::binary(vec![7; 4096]);
+
+    for term in [small, owned] {
+        let chunks = encode_vectored(&term).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(concat(&chunks), encode(&term).unwrap());
+    }
+}
+
+#[test]
+fn test_encode_vectored_round_trips_decode_shared() {
+    let encoded = encode(&erl_list![
+        OwnedTerm::binary(vec![1; 1000]),
+        OwnedTerm::binary(vec![2; 1000])
+    ])
+    .unwrap();
+    let buffer = Bytes::from(encoded.clone());
+    let term = decode_shared(&buffer).unwrap();
+
+    let chunks = encode_vectored(&term).unwrap();
+    assert_eq!(concat(&chunks), encoded);
+    let shared = chunks
+        .iter()
+        .filter(|chunk| buffer.as_ptr_range().contains(&chunk.as_ptr()))
+        .count();
+    assert_eq!(shared, 2);
+}
+
+#[test]
+fn test_encode_vectored_keeps_fun_environments_contiguous() {
+    let pid = ExternalPid::new(Atom::new("node@host"), 1, 0, 1);
+    let fun = OwnedTerm::InternalFun(Box::new(InternalFun::new(
+        0,
+        [0; 16],
+        0,
+        1,
+        Atom::new("mod"),
+        0,
+        0,
+        pid,
+        vec![OwnedTerm::SharedBinary(large_payload(1000))],
+    )));
+
+    let chunks = encode_vectored(&fun).unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(concat(&chunks), encode(&fun).unwrap());
+}
+
+#[test]
+fn test_encode_vectored_with_options() {
+    let term = erl_tuple![
+        erl_map! { erl_atom!("b") => erl_int!(2), erl_atom!("a") => erl_int!(1) },
+        OwnedTerm::SharedBinary(Bytes::from(vec![0u8; 10_000]))
+    ];
+
+    let deterministic = EncodeOptions::deterministic();
+    let chunks = encode_vectored_with_options(&term, &deterministic).unwrap();
+    assert_eq!(
+        concat(&chunks),
+        encode_with_options(&term, &deterministic).unwrap()
+    );
+
+    let compressed = EncodeOptions::compressed();
+    let chunks = encode_vectored_with_options(&term, &compressed).unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(
+        concat(&chunks),
+        encode_with_options(&term, &compressed).unwrap()
+    );
+
+    let invalid = EncodeOptions::new().with_minor_version(3);
+    assert!(matches!(
+        encode_vectored_with_options(&term, &invalid),
+        Err(EncodeError::InvalidMinorVersion { version: 3 })
+    ));
+}
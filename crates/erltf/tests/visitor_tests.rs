@@ -0,0 +1,249 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::types::{Atom, ExternalPid};
+use erltf::visit::{TermVisitor, TermVisitorMut, walk_term, walk_term_mut};
+use erltf::{OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple};
+
+fn pid(id: u32) -> OwnedTerm {
+    OwnedTerm::Pid(ExternalPid::new(Atom::new("node@host"), id, 0, 1))
+}
+
+fn sample() -> OwnedTerm {
+    erl_map! {
+        erl_atom!("owner") => pid(1),
+        erl_atom!("items") => erl_list![
+            erl_tuple![erl_atom!("item"), OwnedTerm::binary(b"a".to_vec())],
+            erl_tuple![erl_atom!("item"), OwnedTerm::binary(b"b".to_vec())]
+        ],
+        erl_atom!("tail") => OwnedTerm::improper_list(vec![erl_int!(1)], erl_int!(2))
+    }
+}
+
+/// Builds `[[[...[Leaf]...]]]` without recursion.
+fn nested_lists(depth: usize, leaf: OwnedTerm) -> OwnedTerm {
+    let mut term = leaf;
+    for _ in 0..depth {
+        term = OwnedTerm::List(vec![term]);
+    }
+    term
+}
+
+/// Takes a deeply nested list apart without recursing in `Drop`.
+fn dismantle(mut term: OwnedTerm) -> OwnedTerm {
+    while let OwnedTerm::List(mut elements) = term {
+        term = elements.pop().unwrap_or(OwnedTerm::Nil);
+    }
+    term
+}
+
+// ============================================================================
+// TermVisitor
+// ============================================================================
+
+#[test]
+fn test_visitor_counts_atoms_including_pid_nodes() {
+    #[derive(Default)]
+    struct Atoms<'t>(Vec<&'t str>);
+
+    impl<'t> TermVisitor<'t> for Atoms<'t> {
+        fn visit_atom(&mut self, atom: &'t Atom) {
+            self.0.push(atom.as_str());
+        }
+    }
+
+    let term = sample();
+    let mut atoms = Atoms::default();
+    atoms.visit_term(&term);
+    assert_eq!(
+        atoms.0,
+        ["items", "item", "item", "owner", "node@host", "tail"]
+    );
+}
+
+#[test]
+fn test_visitor_can_skip_subtrees() {
+    #[derive(Default)]
+    struct Binaries<'t>(Vec<&'t [u8]>);
+
+    impl<'t> TermVisitor<'t> for Binaries<'t> {
+        fn visit_term(&mut self, term: &'t OwnedTerm) {
+            if term.tuple_get(1) == Some(&OwnedTerm::binary(b"a".to_vec())) {
+                return;
+            }
+            walk_term(self, term);
+        }
+
+        fn visit_binary(&mut self, bytes: &'t [u8]) {
+            self.0.push(bytes);
+        }
+    }
+
+    let term = sample();
+    let mut binaries = Binaries::default();
+    binaries.visit_term(&term);
+    assert_eq!(binaries.0, [b"b".as_slice()]);
+}
+
+#[test]
+fn test_visitor_sees_bitstring_bits() {
+    #[derive(Default)]
+    struct Bitstrings<'t> {
+        bitstrings: Vec<(&'t [u8], u8)>,
+        binaries: Vec<&'t [u8]>,
+    }
+
+    impl<'t> TermVisitor<'t> for Bitstrings<'t> {
+        fn visit_bitstring(&mut self, bytes: &'t [u8], bits: u8) {
+            self.bitstrings.push((bytes, bits));
+        }
+
+        fn visit_binary(&mut self, bytes: &'t [u8]) {
+            self.binaries.push(bytes);
+        }
+    }
+
+    let term = erl_list![
+        OwnedTerm::BitBinary {
+            bytes: vec![0xAB, 0x80],
+            bits: 1,
+        },
+        OwnedTerm::binary(b"a".to_vec())
+    ];
+    let mut visitor = Bitstrings::default();
+    visitor.visit_term(&term);
+    assert_eq!(visitor.bitstrings, [([0xAB, 0x80].as_slice(), 1)]);
+    assert_eq!(visitor.binaries, [b"a".as_slice()]);
+
+    #[derive(Default)]
+    struct Binaries<'t>(Vec<&'t [u8]>);
+
+    impl<'t> TermVisitor<'t> for Binaries<'t> {
+        fn visit_binary(&mut self, bytes: &'t [u8]) {
+            self.0.push(bytes);
+        }
+    }
+
+    let mut binaries = Binaries::default();
+    binaries.visit_term(&term);
+    assert_eq!(binaries.0, [[0xAB, 0x80].as_slice(), b"a".as_slice()]);
+}
+
+// ============================================================================
+// TermVisitorMut
+// ============================================================================
+
+#[test]
+fn test_visitor_mut_rewrites_in_place() {
+    struct Rewrite;
+
+    impl TermVisitorMut for Rewrite {
+        fn visit_term_mut(&mut self, term: &mut OwnedTerm) {
+            if term.as_pid().is_some() {
+                *term = erl_atom!("pid");
+            } else {
+                walk_term_mut(self, term);
+            }
+        }
+
+        fn visit_atom_mut(&mut self, atom: &mut Atom) {
+            if atom == "items" {
+                *atom = Atom::new("entries");
+            }
+        }
+    }
+
+    let mut term = sample();
+    Rewrite.visit_term_mut(&mut term);
+    assert_eq!(term.map_get_atom_key("owner"), Some(&erl_atom!("pid")));
+    assert!(term.map_get_atom_key("items").is_none());
+    assert_eq!(term.map_get_atom_key("entries").map(|l| l.len()), Some(2));
+}
+
+#[test]
+fn test_visitor_mut_renames_pid_nodes() {
+    struct Renode;
+
+    impl TermVisitorMut for Renode {
+        fn visit_pid_mut(&mut self, pid: &mut ExternalPid) {
+            pid.node = Atom::new("other@host");
+        }
+    }
+
+    let mut term = erl_list![pid(7)];
+    Renode.visit_term_mut(&mut term);
+    assert_eq!(
+        term,
+        erl_list![OwnedTerm::Pid(ExternalPid::new(
+            Atom::new("other@host"),
+            7,
+            0,
+            1
+        ))]
+    );
+}
+
+// ============================================================================
+// subterms, fold and transform
+// ============================================================================
+
+#[test]
+fn test_subterms_order() {
+    let term = erl_tuple![
+        erl_list![erl_int!(1), erl_int!(2)],
+        erl_map! { erl_atom!("k") => erl_int!(3) }
+    ];
+    let visited: Vec<String> = term.subterms().map(|t| t.to_string()).collect();
+    assert_eq!(visited[0], term.to_string());
+    assert_eq!(&visited[1..4], ["[1, 2]", "1", "2"]);
+    assert_eq!(&visited[5..], ["k", "3"]);
+}
+
+#[test]
+fn test_fold_counts_binaries() {
+    let count = sample().fold(0, |n, t| n + usize::from(t.as_binary().is_some()));
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_transform_is_bottom_up() {
+    let mut seen = Vec::new();
+    let result = erl_tuple![erl_int!(1), erl_list![erl_int!(2)]].transform(|t| {
+        seen.push(t.to_string());
+        match t {
+            OwnedTerm::Integer(i) => OwnedTerm::Integer(i * 10),
+            other => other,
+        }
+    });
+    assert_eq!(seen, ["1", "2", "[20]", "{10, [20]}"]);
+    assert_eq!(result, erl_tuple![erl_int!(10), erl_list![erl_int!(20)]]);
+
+    let identity = sample().transform(|t| t);
+    assert_eq!(identity, sample());
+}
+
+#[test]
+fn test_deeply_nested_terms_do_not_overflow() {
+    let depth = 1_000_000;
+    let term = nested_lists(depth, erl_atom!("leaf"));
+
+    assert_eq!(term.subterms().count(), depth + 1);
+    assert_eq!(term.fold(0, |n, t| n + usize::from(t.is_atom())), 1);
+
+    let transformed = term.transform(|t| match t {
+        OwnedTerm::Atom(_) => erl_int!(1),
+        other => other,
+    });
+    assert_eq!(dismantle(transformed), erl_int!(1));
+}
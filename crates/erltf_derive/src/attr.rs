@@ -0,0 +1,237 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Fields, GenericParam, Generics, Ident, LitStr, Member,
+    Result, Variant,
+};
+
+/// How a struct, or the fields of an enum variant, map to a term.
+pub enum Layout {
+    /// `#{name => Name, ...}`
+    Map(Vec<Field>),
+    /// `[{name, Name}, ...]`
+    Proplist(Vec<Field>),
+    /// `{tag, Field1, Field2, ...}`
+    Record(String, Vec<Field>),
+    /// `{Field1, Field2, ...}`
+    Tuple(Vec<Field>),
+    /// The only field itself
+    Newtype(Field),
+    /// An atom
+    Unit(String),
+}
+
+pub struct Field {
+    pub member: Member,
+    /// A local variable the field is bound to when destructuring
+    pub binding: Ident,
+    pub key: String,
+    pub encoding: Option<Encoding>,
+}
+
+#[derive(Clone, Copy)]
+pub enum Encoding {
+    Binary,
+    Charlist,
+    Atom,
+}
+
+impl Encoding {
+    pub fn tokens(self) -> TokenStream {
+        match self {
+            Encoding::Binary => quote!(::erltf::StringEncoding::Binary),
+            Encoding::Charlist => quote!(::erltf::StringEncoding::Charlist),
+            Encoding::Atom => quote!(::erltf::StringEncoding::Atom),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ContainerAttrs {
+    record: Option<String>,
+    proplist: bool,
+    rename: Option<String>,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    encoding: Option<Encoding>,
+}
+
+fn parse_container_attrs(attrs: &[Attribute]) -> Result<ContainerAttrs> {
+    let mut parsed = ContainerAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("erltf")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("record") {
+                parsed.record = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("proplist") {
+                parsed.proplist = true;
+            } else if meta.path.is_ident("map") {
+                parsed.proplist = false;
+            } else if meta.path.is_ident("rename") {
+                parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `record`, `map`, `proplist` or `rename`"));
+            }
+            Ok(())
+        })?;
+    }
+    if parsed.record.is_some() && parsed.proplist {
+        return Err(Error::new(
+            Span::call_site(),
+            "`record` and `proplist` cannot be combined",
+        ));
+    }
+    Ok(parsed)
+}
+
+fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut parsed = FieldAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("erltf")) {
+        attr.parse_nested_meta(|meta| {
+            let encoding = if meta.path.is_ident("rename") {
+                parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                return Ok(());
+            } else if meta.path.is_ident("binary") {
+                Encoding::Binary
+            } else if meta.path.is_ident("charlist") {
+                Encoding::Charlist
+            } else if meta.path.is_ident("atom") {
+                Encoding::Atom
+            } else {
+                return Err(meta.error("expected `rename`, `binary`, `charlist` or `atom`"));
+            };
+            if parsed.encoding.replace(encoding).is_some() {
+                return Err(meta.error("only one string encoding can be set"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(parsed)
+}
+
+fn parse_fields(fields: &Fields) -> Result<Vec<Field>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let attrs = parse_field_attrs(&field.attrs)?;
+            let (member, binding, name) = match &field.ident {
+                Some(ident) => {
+                    let name = ident.to_string().trim_start_matches("r#").to_string();
+                    (
+                        Member::Named(ident.clone()),
+                        format_ident!("__{}", name),
+                        name,
+                    )
+                }
+                None => (
+                    Member::Unnamed(index.into()),
+                    format_ident!("__field{}", index),
+                    index.to_string(),
+                ),
+            };
+            Ok(Field {
+                member,
+                binding,
+                key: attrs.rename.unwrap_or(name),
+                encoding: attrs.encoding,
+            })
+        })
+        .collect()
+}
+
+/// The layout of a struct.
+pub fn struct_layout(input: &DeriveInput) -> Result<Layout> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(&input.ident, "expected a struct"));
+    };
+    let attrs = parse_container_attrs(&input.attrs)?;
+    let fields = parse_fields(&data.fields)?;
+    let layout = match (&data.fields, attrs.record) {
+        (_, Some(tag)) => Layout::Record(tag, fields),
+        (Fields::Named(_), None) if attrs.proplist => Layout::Proplist(fields),
+        (Fields::Named(_), None) => Layout::Map(fields),
+        (_, None) if attrs.proplist => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`proplist` requires named fields",
+            ));
+        }
+        (Fields::Unnamed(_), None) if fields.len() == 1 => {
+            Layout::Newtype(fields.into_iter().next().expect("one field"))
+        }
+        (Fields::Unnamed(_), None) => Layout::Tuple(fields),
+        (Fields::Unit, None) => Layout::Unit(
+            attrs
+                .rename
+                .unwrap_or_else(|| snake_case(&input.ident.to_string())),
+        ),
+    };
+    Ok(layout)
+}
+
+/// The layout of an enum variant: an atom or a tagged tuple.
+pub fn variant_layout(variant: &Variant) -> Result<Layout> {
+    let attrs = parse_container_attrs(&variant.attrs)?;
+    if attrs.record.is_some() || attrs.proplist {
+        return Err(Error::new_spanned(
+            &variant.ident,
+            "enum variants only support `rename`",
+        ));
+    }
+    let tag = attrs
+        .rename
+        .unwrap_or_else(|| snake_case(&variant.ident.to_string()));
+    match &variant.fields {
+        Fields::Unit => Ok(Layout::Unit(tag)),
+        fields => Ok(Layout::Record(tag, parse_fields(fields)?)),
+    }
+}
+
+/// Adds `bound` to every type parameter.
+pub fn add_bounds(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(syn::parse_quote!(#bound));
+        }
+    }
+    generics
+}
+
+/// `HttpListener` becomes `http_listener`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let acronym_end = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if after_lower || acronym_end {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
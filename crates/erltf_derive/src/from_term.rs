@@ -0,0 +1,161 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::attr::{Field, Layout, add_bounds, struct_layout, variant_layout};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Member, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let body = match &input.data {
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let arm = match variant_layout(variant)? {
+                        Layout::Unit(tag) => quote! {
+                            #tag if term.is_atom() => ::core::result::Result::Ok(Self::#ident)
+                        },
+                        Layout::Record(tag, fields) => {
+                            let len = fields.len();
+                            let construct = construct_from_elements(quote!(Self::#ident), &fields);
+                            quote! {
+                                #tag if term.is_tuple() => {
+                                    let mut elements = ::erltf::convert::__private::record_elements(term, #tag, #len)?;
+                                    #construct
+                                }
+                            }
+                        }
+                        _ => unreachable!("variants are atoms or tagged tuples"),
+                    };
+                    Ok(arm)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                let tag = ::erltf::convert::__private::variant_tag(&term)?;
+                match tag.as_str() {
+                    #(#arms,)*
+                    _ => ::core::result::Result::Err(::erltf::errors::TermConversionError::UnknownTag(tag)),
+                }
+            }
+        }
+        Data::Struct(_) => match struct_layout(input)? {
+            Layout::Map(fields) => from_entries(
+                quote!(::erltf::convert::__private::map_entries(term)?),
+                &fields,
+            ),
+            Layout::Proplist(fields) => from_entries(
+                quote!(::erltf::convert::__private::proplist_entries(term)?),
+                &fields,
+            ),
+            Layout::Record(tag, fields) => {
+                let len = fields.len();
+                let construct = construct_from_elements(quote!(Self), &fields);
+                quote! {
+                    let mut elements = ::erltf::convert::__private::record_elements(term, #tag, #len)?;
+                    #construct
+                }
+            }
+            Layout::Tuple(fields) => {
+                let len = fields.len();
+                let construct = construct_from_elements(quote!(Self), &fields);
+                quote! {
+                    let mut elements = ::erltf::convert::__private::tuple_elements(term, #len)?;
+                    #construct
+                }
+            }
+            Layout::Newtype(field) => {
+                let value = match field.encoding {
+                    Some(encoding) => {
+                        let encoding = encoding.tokens();
+                        quote!(::erltf::convert::__private::EncodedString::decode(term, #encoding)?)
+                    }
+                    None => quote!(::erltf::FromTerm::from_term(term)?),
+                };
+                quote!(::core::result::Result::Ok(Self(#value)))
+            }
+            Layout::Unit(name) => quote! {
+                match term {
+                    ::erltf::OwnedTerm::Atom(ref atom) if atom == #name => ::core::result::Result::Ok(Self),
+                    ::erltf::OwnedTerm::Atom(atom) => ::core::result::Result::Err(
+                        ::erltf::errors::TermConversionError::UnknownTag(atom.name.to_string()),
+                    ),
+                    other => ::core::result::Result::Err(::erltf::errors::TermConversionError::WrongType {
+                        expected: "Atom",
+                        actual: other.type_name(),
+                    }),
+                }
+            },
+        },
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "unions are not supported",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(::erltf::FromTerm));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::erltf::FromTerm for #ident #ty_generics #where_clause {
+            fn from_term(
+                term: ::erltf::OwnedTerm,
+            ) -> ::core::result::Result<Self, ::erltf::errors::TermConversionError> {
+                #body
+            }
+        }
+    })
+}
+
+/// Builds `Self` from the fields of a map or proplist, looked up by key.
+fn from_entries(entries: TokenStream, fields: &[Field]) -> TokenStream {
+    let values = fields.iter().map(|f| {
+        let key = &f.key;
+        match f.encoding {
+            Some(encoding) => {
+                let encoding = encoding.tokens();
+                quote!(::erltf::convert::__private::string_field(&mut entries, #key, #encoding)?)
+            }
+            None => quote!(::erltf::convert::__private::field(&mut entries, #key)?),
+        }
+    });
+    let members = fields.iter().map(|f| &f.member);
+    quote! {
+        let mut entries = #entries;
+        ::core::result::Result::Ok(Self { #(#members: #values),* })
+    }
+}
+
+/// Builds `path` from the positional `elements` iterator.
+fn construct_from_elements(path: TokenStream, fields: &[Field]) -> TokenStream {
+    let values = fields.iter().map(|f| match f.encoding {
+        Some(encoding) => {
+            let encoding = encoding.tokens();
+            quote!(::erltf::convert::__private::next_string(&mut elements, #encoding)?)
+        }
+        None => quote!(::erltf::convert::__private::next(&mut elements)?),
+    });
+    match fields.first().map(|f| &f.member) {
+        Some(Member::Named(_)) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote!(::core::result::Result::Ok(#path { #(#members: #values),* }))
+        }
+        Some(Member::Unnamed(_)) => quote!(::core::result::Result::Ok(#path(#(#values),*))),
+        None => quote!(::core::result::Result::Ok(#path {})),
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::attr::{Field, Layout, add_bounds, struct_layout, variant_layout};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Member, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let body = match &input.data {
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let layout = variant_layout(variant)?;
+                    let arm = match &layout {
+                        Layout::Record(_, fields) => {
+                            let pattern = destructure(fields);
+                            let term = build(&layout, |f| encode(f, &f.binding));
+                            quote!(Self::#ident #pattern => #term)
+                        }
+                        _ => {
+                            let term = build(&layout, |_| TokenStream::new());
+                            quote!(Self::#ident => #term)
+                        }
+                    };
+                    Ok(arm)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Struct(_) => {
+            let layout = struct_layout(input)?;
+            build(&layout, |f| {
+                let member = &f.member;
+                encode(f, &quote!(self.#member))
+            })
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "unions are not supported",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let generics = add_bounds(&input.generics, quote!(::erltf::IntoTerm));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::erltf::IntoTerm for #ident #ty_generics #where_clause {
+            fn into_term(self) -> ::erltf::OwnedTerm {
+                #body
+            }
+        }
+    })
+}
+
+/// `{a: __a, b: __b}` or `(__field0, __field1)` for a variant's fields.
+fn destructure(fields: &[Field]) -> TokenStream {
+    let bindings = fields.iter().map(|f| &f.binding);
+    match fields.first().map(|f| &f.member) {
+        Some(Member::Named(_)) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote!({ #(#members: #bindings),* })
+        }
+        Some(Member::Unnamed(_)) => quote!((#(#bindings),*)),
+        None => quote!({}),
+    }
+}
+
+fn encode(field: &Field, value: &impl quote::ToTokens) -> TokenStream {
+    match field.encoding {
+        Some(encoding) => {
+            let encoding = encoding.tokens();
+            quote!(::erltf::convert::__private::EncodedString::encode(&#value, #encoding))
+        }
+        None => quote!(::erltf::IntoTerm::into_term(#value)),
+    }
+}
+
+fn build(layout: &Layout, value: impl Fn(&Field) -> TokenStream) -> TokenStream {
+    match layout {
+        Layout::Map(fields) => {
+            let keys = fields.iter().map(|f| &f.key);
+            let values = fields.iter().map(value);
+            quote! {{
+                let mut map = ::erltf::convert::__private::Map::new();
+                #(map.insert(::erltf::OwnedTerm::atom(#keys), #values);)*
+                ::erltf::OwnedTerm::Map(map)
+            }}
+        }
+        Layout::Proplist(fields) => {
+            let keys = fields.iter().map(|f| &f.key);
+            let values = fields.iter().map(value);
+            quote! {
+                ::erltf::OwnedTerm::List(::std::vec![
+                    #(::erltf::OwnedTerm::Tuple(::std::vec![::erltf::OwnedTerm::atom(#keys), #values])),*
+                ])
+            }
+        }
+        Layout::Record(tag, fields) => {
+            let values = fields.iter().map(value);
+            quote! {
+                ::erltf::OwnedTerm::Tuple(::std::vec![::erltf::OwnedTerm::atom(#tag), #(#values),*])
+            }
+        }
+        Layout::Tuple(fields) => {
+            let values = fields.iter().map(value);
+            quote!(::erltf::OwnedTerm::Tuple(::std::vec![#(#values),*]))
+        }
+        Layout::Newtype(field) => value(field),
+        Layout::Unit(name) => quote!(::erltf::OwnedTerm::atom(#name)),
+    }
+}
@@ -0,0 +1,365 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use erltf::errors::TermConversionError;
+use erltf::types::ExternalPid;
+use erltf::{
+    Atom, FromTerm, IntoTerm, OwnedTerm, decode, encode, erl_atom, erl_int, erl_list, erl_map,
+    erl_tuple,
+};
+use std::collections::BTreeMap;
+
+fn binary(s: &str) -> OwnedTerm {
+    OwnedTerm::Binary(s.as_bytes().to_vec())
+}
+
+fn roundtrip<T: IntoTerm + FromTerm + Clone + PartialEq + std::fmt::Debug>(value: T) {
+    let term = value.clone().into_term();
+    let decoded = decode(&encode(&term).unwrap()).unwrap();
+    assert_eq!(T::from_term(decoded).unwrap(), value);
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+#[erltf(record = "user")]
+struct User {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+struct Queue {
+    name: String,
+    #[erltf(rename = "messages_ready")]
+    ready: u64,
+    durable: bool,
+    #[erltf(atom)]
+    kind: String,
+    arguments: BTreeMap<String, i64>,
+    owner: Option<ExternalPid>,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+#[erltf(proplist)]
+struct Listener {
+    protocol: Atom,
+    port: u16,
+    #[erltf(charlist)]
+    interface: String,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+struct Connection {
+    #[erltf(charlist)]
+    user: Option<String>,
+    #[erltf(atom)]
+    state: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+struct Point(i64, i64);
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+struct VHost(#[erltf(binary)] String);
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+struct Pong;
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect(f64, f64),
+    #[erltf(rename = "poly")]
+    Polygon {
+        points: Vec<Point>,
+        #[erltf(charlist)]
+        label: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+enum HttpStatus {
+    NotFound,
+    HTTPError,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoTerm, FromTerm)]
+#[erltf(record = "wrapper")]
+struct Wrapper<T> {
+    inner: T,
+}
+
+// ============================================================================
+// Records and Tuples
+// ============================================================================
+
+#[test]
+fn test_record() {
+    let user = User {
+        name: "joe".to_string(),
+        age: 42,
+    };
+    assert_eq!(
+        user.clone().into_term(),
+        erl_tuple![erl_atom!("user"), binary("joe"), erl_int!(42)]
+    );
+    roundtrip(user);
+}
+
+#[test]
+fn test_record_errors() {
+    assert_eq!(
+        User::from_term(erl_tuple![erl_atom!("group"), binary("joe"), erl_int!(42)]),
+        Err(TermConversionError::UnknownTag("group".to_string()))
+    );
+    assert_eq!(
+        User::from_term(erl_tuple![erl_atom!("user"), binary("joe")]),
+        Err(TermConversionError::WrongArity {
+            expected: 3,
+            actual: 2
+        })
+    );
+    assert_eq!(
+        User::from_term(erl_tuple![erl_atom!("user"), binary("joe"), erl_int!(-1)]),
+        Err(TermConversionError::OutOfRange)
+    );
+    assert!(matches!(
+        User::from_term(erl_atom!("user")),
+        Err(TermConversionError::WrongType { .. })
+    ));
+}
+
+#[test]
+fn test_tuple_newtype_and_unit_structs() {
+    assert_eq!(
+        Point(1, 2).into_term(),
+        erl_tuple![erl_int!(1), erl_int!(2)]
+    );
+    roundtrip(Point(-5, 7));
+
+    assert_eq!(VHost("/".to_string()).into_term(), binary("/"));
+    roundtrip(VHost("/".to_string()));
+
+    assert_eq!(Pong.into_term(), erl_atom!("pong"));
+    assert_eq!(Pong::from_term(erl_atom!("pong")), Ok(Pong));
+    assert_eq!(
+        Pong::from_term(erl_atom!("pang")),
+        Err(TermConversionError::UnknownTag("pang".to_string()))
+    );
+}
+
+// ============================================================================
+// Maps and Proplists
+// ============================================================================
+
+fn queue() -> Queue {
+    Queue {
+        name: "orders".to_string(),
+        ready: 10,
+        durable: true,
+        kind: "classic".to_string(),
+        arguments: BTreeMap::from([("x-max-length".to_string(), 1000)]),
+        owner: None,
+    }
+}
+
+#[test]
+fn test_map() {
+    assert_eq!(
+        queue().into_term(),
+        erl_map! {
+            erl_atom!("name") => binary("orders"),
+            erl_atom!("messages_ready") => erl_int!(10),
+            erl_atom!("durable") => erl_atom!("true"),
+            erl_atom!("kind") => erl_atom!("classic"),
+            erl_atom!("arguments") => erl_map! { binary("x-max-length") => erl_int!(1000) },
+            erl_atom!("owner") => erl_atom!("undefined")
+        }
+    );
+    roundtrip(queue());
+}
+
+#[test]
+fn test_map_missing_fields() {
+    let mut term = queue().into_term();
+    let map = term.as_map_mut().unwrap();
+    map.remove(&erl_atom!("owner"));
+    assert_eq!(Queue::from_term(term.clone()), Ok(queue()));
+
+    term.as_map_mut().unwrap().remove(&erl_atom!("durable"));
+    assert_eq!(
+        Queue::from_term(term),
+        Err(TermConversionError::MissingField("durable"))
+    );
+}
+
+#[test]
+fn test_map_string_encoding_is_enforced() {
+    let mut term = queue().into_term();
+    term.as_map_mut()
+        .unwrap()
+        .insert(erl_atom!("kind"), binary("classic"));
+    assert!(matches!(
+        Queue::from_term(term),
+        Err(TermConversionError::WrongType {
+            expected: "Atom",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_optional_encoded_fields() {
+    let connection = Connection {
+        user: Some("guest".to_string()),
+        state: Some("running".to_string()),
+    };
+    assert_eq!(
+        connection.clone().into_term(),
+        erl_map! {
+            erl_atom!("user") => OwnedTerm::charlist("guest"),
+            erl_atom!("state") => erl_atom!("running")
+        }
+    );
+    roundtrip(connection);
+
+    let closed = Connection {
+        user: None,
+        state: None,
+    };
+    assert_eq!(
+        closed.clone().into_term(),
+        erl_map! {
+            erl_atom!("user") => erl_atom!("undefined"),
+            erl_atom!("state") => erl_atom!("undefined")
+        }
+    );
+    roundtrip(closed.clone());
+    assert_eq!(Connection::from_term(erl_map! {}), Ok(closed));
+}
+
+#[test]
+fn test_proplist() {
+    let listener = Listener {
+        protocol: Atom::new("amqp"),
+        port: 5672,
+        interface: "::".to_string(),
+    };
+    assert_eq!(
+        listener.clone().into_term(),
+        erl_list![
+            erl_tuple![erl_atom!("protocol"), erl_atom!("amqp")],
+            erl_tuple![erl_atom!("port"), erl_int!(5672)],
+            erl_tuple![erl_atom!("interface"), OwnedTerm::charlist("::")]
+        ]
+    );
+    roundtrip(listener.clone());
+
+    // Order does not matter, the first entry for a key wins and extra entries are ignored
+    let term = erl_list![
+        erl_tuple![erl_atom!("port"), erl_int!(5672)],
+        erl_tuple![erl_atom!("ip"), binary("127.0.0.1")],
+        erl_tuple![erl_atom!("interface"), OwnedTerm::charlist("::")],
+        erl_tuple![erl_atom!("protocol"), erl_atom!("amqp")],
+        erl_tuple![erl_atom!("port"), erl_int!(1)]
+    ];
+    assert_eq!(Listener::from_term(term), Ok(listener));
+}
+
+// ============================================================================
+// Enums
+// ============================================================================
+
+#[test]
+fn test_enum_variants() {
+    assert_eq!(Shape::Empty.into_term(), erl_atom!("empty"));
+    assert_eq!(
+        Shape::Circle(1.5).into_term(),
+        erl_tuple![erl_atom!("circle"), OwnedTerm::Float(1.5)]
+    );
+    assert_eq!(
+        Shape::Rect(1.0, 2.0).into_term(),
+        erl_tuple![
+            erl_atom!("rect"),
+            OwnedTerm::Float(1.0),
+            OwnedTerm::Float(2.0)
+        ]
+    );
+    let polygon = Shape::Polygon {
+        points: vec![Point(0, 0), Point(1, 1)],
+        label: "tri".to_string(),
+    };
+    assert_eq!(
+        polygon.clone().into_term(),
+        erl_tuple![
+            erl_atom!("poly"),
+            erl_list![
+                erl_tuple![erl_int!(0), erl_int!(0)],
+                erl_tuple![erl_int!(1), erl_int!(1)]
+            ],
+            OwnedTerm::charlist("tri")
+        ]
+    );
+    for shape in [
+        Shape::Empty,
+        Shape::Circle(2.0),
+        Shape::Rect(3.0, 4.0),
+        polygon,
+    ] {
+        roundtrip(shape);
+    }
+}
+
+#[test]
+fn test_enum_errors() {
+    assert_eq!(
+        Shape::from_term(erl_atom!("triangle")),
+        Err(TermConversionError::UnknownTag("triangle".to_string()))
+    );
+    // A unit variant is an atom, not a tuple
+    assert_eq!(
+        Shape::from_term(erl_tuple![erl_atom!("empty")]),
+        Err(TermConversionError::UnknownTag("empty".to_string()))
+    );
+    assert!(matches!(
+        Shape::from_term(erl_int!(1)),
+        Err(TermConversionError::WrongType { .. })
+    ));
+}
+
+#[test]
+fn test_variant_names_are_snake_case() {
+    assert_eq!(HttpStatus::NotFound.into_term(), erl_atom!("not_found"));
+    assert_eq!(HttpStatus::HTTPError.into_term(), erl_atom!("http_error"));
+    roundtrip(HttpStatus::HTTPError);
+}
+
+// ============================================================================
+// Generics
+// ============================================================================
+
+#[test]
+fn test_generic_struct() {
+    let wrapper = Wrapper {
+        inner: vec![Some(1i32), None],
+    };
+    assert_eq!(
+        wrapper.clone().into_term(),
+        erl_tuple![
+            erl_atom!("wrapper"),
+            erl_list![erl_int!(1), erl_atom!("undefined")]
+        ]
+    );
+    roundtrip(wrapper);
+}
@@ -94,6 +94,13 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
                     visitor.visit_bytes(b)
                 }
             }
+            OwnedTerm::SharedBinary(b) => {
+                if let Ok(s) = std::str::from_utf8(b) {
+                    visitor.visit_str(s)
+                } else {
+                    visitor.visit_bytes(b)
+                }
+            }
             OwnedTerm::String(s) => visitor.visit_str(s),
             OwnedTerm::List(l) => visitor.visit_seq(SeqDeserializer::new(l)),
             OwnedTerm::Tuple(t) => visitor.visit_seq(SeqDeserializer::new(t)),
@@ -264,6 +271,10 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
                 let s = std::str::from_utf8(b).map_err(|e| Error::InvalidValue(e.to_string()))?;
                 visitor.visit_borrowed_str(s)
             }
+            OwnedTerm::SharedBinary(b) => {
+                let s = std::str::from_utf8(b).map_err(|e| Error::InvalidValue(e.to_string()))?;
+                visitor.visit_borrowed_str(s)
+            }
             OwnedTerm::String(s) => visitor.visit_borrowed_str(s),
             OwnedTerm::Atom(a) => visitor.visit_borrowed_str(a.as_str()),
             _ => Err(Error::TypeMismatch {
@@ -280,6 +291,7 @@ impl<'de> SerdeDeserializer<'de> for &mut Deserializer<'de> {
     fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
         match self.term {
             OwnedTerm::Binary(b) => visitor.visit_borrowed_bytes(b),
+            OwnedTerm::SharedBinary(b) => visitor.visit_borrowed_bytes(b),
             _ => Err(Error::TypeMismatch {
                 expected: "binary".into(),
                 found: format!("{:?}", self.term),
@@ -0,0 +1,592 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between `serde_json::Value` and `OwnedTerm`.
+//!
+//! Erlang and Elixir JSON libraries disagree on how terms map to JSON, so every
+//! conversion takes a [`JsonConvention`]. Only [`JsonConvention::Tagged`] can
+//! represent every term; the others return an error for terms such as pids or
+//! (non-object) tuples that the library they mimic would reject.
+
+use crate::error::{Error, Result};
+use erltf::term::OwnedTerm;
+use erltf::types::{
+    Atom, BigInt, ExternalFun, ExternalPid, ExternalPort, ExternalReference, InternalFun,
+};
+use serde_json::{Map, Number, Value};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonConvention {
+    /// jsx and thoas: binaries are strings, maps and proplists are objects,
+    /// `null` is the atom `null`. Objects decode as maps with binary keys.
+    #[default]
+    Jsx,
+    /// jiffy: objects are `{[{Key, Value}]}` tuples, `null` is the atom `null`.
+    Jiffy,
+    /// Elixir's Jason: maps are objects, `null` is `nil`, and the `__struct__`
+    /// key of structs is left out.
+    Jason,
+    /// Lossless: terms without a JSON counterpart become single-key objects
+    /// such as `{"atom": "ok"}` or `{"tuple": [1, 2]}`, and `from_json`
+    /// restores the exact term.
+    Tagged,
+}
+
+impl JsonConvention {
+    fn null_atom(self) -> &'static str {
+        match self {
+            JsonConvention::Jason => "nil",
+            _ => "null",
+        }
+    }
+}
+
+/// Converts a term to JSON following `convention`.
+///
+/// ```
+/// use erltf::{erl_atom, erl_int, erl_map};
+/// use erltf_serde::{JsonConvention, to_json};
+///
+/// let term = erl_map! { erl_atom!("count") => erl_int!(2) };
+/// let json = to_json(&term, JsonConvention::Jsx).unwrap();
+/// assert_eq!(json, serde_json::json!({"count": 2}));
+/// ```
+pub fn to_json(term: &OwnedTerm, convention: JsonConvention) -> Result<Value> {
+    match convention {
+        JsonConvention::Tagged => tagged_to_json(term),
+        _ => plain_to_json(term, convention),
+    }
+}
+
+/// Converts JSON to a term following `convention`.
+pub fn from_json(value: &Value, convention: JsonConvention) -> Result<OwnedTerm> {
+    match convention {
+        JsonConvention::Tagged => tagged_from_json(value),
+        _ => plain_from_json(value, convention),
+    }
+}
+
+// ============================================================================
+// jsx, jiffy and Jason
+// ============================================================================
+
+fn plain_to_json(term: &OwnedTerm, convention: JsonConvention) -> Result<Value> {
+    match term {
+        OwnedTerm::Integer(i) => Ok(Value::from(*i)),
+        OwnedTerm::BigInt(big) => bigint_to_number(big),
+        OwnedTerm::Float(f) => float_to_number(*f),
+        OwnedTerm::Atom(atom) => Ok(match atom.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            name if name == convention.null_atom() => Value::Null,
+            name => Value::String(name.to_string()),
+        }),
+        OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) | OwnedTerm::String(_) => {
+            binary_to_string(term).map(Value::String)
+        }
+        OwnedTerm::Nil => Ok(Value::Array(Vec::new())),
+        OwnedTerm::List(elements)
+            if convention == JsonConvention::Jsx && is_object_proplist(elements) =>
+        {
+            pairs_to_object(elements, convention)
+        }
+        OwnedTerm::List(elements) => elements
+            .iter()
+            .map(|element| plain_to_json(element, convention))
+            .collect::<Result<_>>()
+            .map(Value::Array),
+        OwnedTerm::Map(map) => {
+            let mut object = Map::with_capacity(map.len());
+            for (key, value) in map {
+                if convention == JsonConvention::Jason && is_struct_key(key) {
+                    continue;
+                }
+                object.insert(key_to_string(key)?, plain_to_json(value, convention)?);
+            }
+            Ok(Value::Object(object))
+        }
+        OwnedTerm::Tuple(elements)
+            if convention == JsonConvention::Jiffy && elements.len() == 1 =>
+        {
+            match &elements[0] {
+                OwnedTerm::Nil => Ok(Value::Object(Map::new())),
+                OwnedTerm::List(pairs) if pairs.iter().all(is_pair) => {
+                    pairs_to_object(pairs, convention)
+                }
+                _ => Err(unsupported(term)),
+            }
+        }
+        _ => Err(unsupported(term)),
+    }
+}
+
+fn plain_from_json(value: &Value, convention: JsonConvention) -> Result<OwnedTerm> {
+    match value {
+        Value::Null => Ok(OwnedTerm::Atom(Atom::new(convention.null_atom()))),
+        Value::Bool(b) => Ok(OwnedTerm::boolean(*b)),
+        Value::Number(n) => number_to_term(n),
+        Value::String(s) => Ok(OwnedTerm::Binary(s.as_bytes().to_vec())),
+        Value::Array(values) => elements_from_json(values, |v| plain_from_json(v, convention)),
+        Value::Object(object) if convention == JsonConvention::Jiffy => {
+            let pairs = object
+                .iter()
+                .map(|(k, v)| {
+                    Ok(OwnedTerm::Tuple(vec![
+                        OwnedTerm::Binary(k.as_bytes().to_vec()),
+                        plain_from_json(v, convention)?,
+                    ]))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(OwnedTerm::Tuple(vec![list_or_nil(pairs)]))
+        }
+        Value::Object(object) => {
+            let mut map = BTreeMap::new();
+            for (k, v) in object {
+                map.insert(
+                    OwnedTerm::Binary(k.as_bytes().to_vec()),
+                    plain_from_json(v, convention)?,
+                );
+            }
+            Ok(OwnedTerm::Map(map))
+        }
+    }
+}
+
+fn pairs_to_object(pairs: &[OwnedTerm], convention: JsonConvention) -> Result<Value> {
+    let mut object = Map::with_capacity(pairs.len());
+    for pair in pairs {
+        if let OwnedTerm::Tuple(kv) = pair
+            && let [key, value] = kv.as_slice()
+        {
+            object.insert(key_to_string(key)?, plain_to_json(value, convention)?);
+        }
+    }
+    Ok(Value::Object(object))
+}
+
+/// A non-empty list of `{Key, Value}` pairs, which jsx encodes as an object.
+fn is_object_proplist(elements: &[OwnedTerm]) -> bool {
+    !elements.is_empty() && elements.iter().all(is_pair)
+}
+
+fn is_pair(element: &OwnedTerm) -> bool {
+    matches!(
+        element,
+        OwnedTerm::Tuple(kv) if kv.len() == 2 && matches!(
+            kv[0],
+            OwnedTerm::Atom(_)
+                | OwnedTerm::Binary(_)
+                | OwnedTerm::SharedBinary(_)
+                | OwnedTerm::String(_)
+                | OwnedTerm::Integer(_)
+        )
+    )
+}
+
+fn is_struct_key(key: &OwnedTerm) -> bool {
+    matches!(key, OwnedTerm::Atom(atom) if atom.as_str() == "__struct__")
+}
+
+fn key_to_string(key: &OwnedTerm) -> Result<String> {
+    match key {
+        OwnedTerm::Atom(atom) => Ok(atom.as_str().to_string()),
+        OwnedTerm::Integer(i) => Ok(i.to_string()),
+        OwnedTerm::BigInt(big) => Ok(big.to_string()),
+        OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) | OwnedTerm::String(_) => {
+            binary_to_string(key)
+        }
+        _ => Err(Error::UnsupportedType(format!(
+            "{} as a JSON object key",
+            key.type_name()
+        ))),
+    }
+}
+
+fn binary_to_string(term: &OwnedTerm) -> Result<String> {
+    match term {
+        OwnedTerm::String(s) => Ok(s.clone()),
+        _ => {
+            let bytes = term.as_binary().unwrap_or_default();
+            String::from_utf8(bytes.to_vec())
+                .map_err(|_| Error::InvalidValue("binary is not valid UTF-8".to_string()))
+        }
+    }
+}
+
+fn bigint_to_number(big: &BigInt) -> Result<Value> {
+    if let Ok(value) = i128::try_from(big) {
+        if let Ok(i) = i64::try_from(value) {
+            return Ok(Value::from(i));
+        }
+        if let Ok(u) = u64::try_from(value) {
+            return Ok(Value::from(u));
+        }
+    }
+    Err(Error::InvalidValue(format!(
+        "{big} is out of range for a JSON number"
+    )))
+}
+
+fn float_to_number(f: f64) -> Result<Value> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| Error::InvalidValue(format!("{f} cannot be represented in JSON")))
+}
+
+fn number_to_term(n: &Number) -> Result<OwnedTerm> {
+    if let Some(i) = n.as_i64() {
+        Ok(OwnedTerm::Integer(i))
+    } else if let Some(u) = n.as_u64() {
+        Ok(OwnedTerm::from(u))
+    } else if let Some(f) = n.as_f64() {
+        Ok(OwnedTerm::Float(f))
+    } else {
+        Err(Error::InvalidValue(format!("unsupported JSON number {n}")))
+    }
+}
+
+fn elements_from_json<F>(values: &[Value], f: F) -> Result<OwnedTerm>
+where
+    F: FnMut(&Value) -> Result<OwnedTerm>,
+{
+    values
+        .iter()
+        .map(f)
+        .collect::<Result<Vec<_>>>()
+        .map(list_or_nil)
+}
+
+fn list_or_nil(elements: Vec<OwnedTerm>) -> OwnedTerm {
+    if elements.is_empty() {
+        OwnedTerm::Nil
+    } else {
+        OwnedTerm::List(elements)
+    }
+}
+
+fn unsupported(term: &OwnedTerm) -> Error {
+    Error::UnsupportedType(format!(
+        "{} cannot be represented in JSON",
+        term.type_name()
+    ))
+}
+
+// ============================================================================
+// Tagged
+// ============================================================================
+
+fn tagged_to_json(term: &OwnedTerm) -> Result<Value> {
+    Ok(match term {
+        OwnedTerm::Integer(i) => Value::from(*i),
+        OwnedTerm::Float(f) => match Number::from_f64(*f) {
+            Some(n) => Value::Number(n),
+            None if f.is_nan() => tag("float", "nan"),
+            None if *f > 0.0 => tag("float", "inf"),
+            None => tag("float", "-inf"),
+        },
+        OwnedTerm::Atom(atom) => match atom.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            name => tag("atom", name),
+        },
+        OwnedTerm::Binary(_) | OwnedTerm::SharedBinary(_) => {
+            let bytes = term.as_binary().unwrap_or_default();
+            match std::str::from_utf8(bytes) {
+                Ok(s) => Value::String(s.to_string()),
+                Err(_) => tag("binary", bytes),
+            }
+        }
+        OwnedTerm::BitBinary { bytes, bits } => tag(
+            "bitstring",
+            fields([("bytes", bytes.as_slice().into()), ("bits", (*bits).into())]),
+        ),
+        OwnedTerm::String(s) => tag("string", s.as_str()),
+        OwnedTerm::Nil => Value::Array(Vec::new()),
+        OwnedTerm::List(elements) if elements.is_empty() => tag("list", Value::Array(Vec::new())),
+        OwnedTerm::List(elements) => match charlist_to_string(elements) {
+            Some(s) => tag("charlist", s),
+            None => Value::Array(tagged_elements(elements)?),
+        },
+        OwnedTerm::ImproperList { elements, tail } => tag(
+            "improper_list",
+            fields([
+                ("elements", Value::Array(tagged_elements(elements)?)),
+                ("tail", tagged_to_json(tail)?),
+            ]),
+        ),
+        OwnedTerm::Tuple(elements) => tag("tuple", Value::Array(tagged_elements(elements)?)),
+        OwnedTerm::Map(map) => {
+            let entries = map
+                .iter()
+                .map(|(k, v)| Ok(Value::Array(vec![tagged_to_json(k)?, tagged_to_json(v)?])))
+                .collect::<Result<Vec<_>>>()?;
+            tag("map", entries)
+        }
+        OwnedTerm::BigInt(big) => tag(
+            "bigint",
+            fields([
+                ("negative", big.sign.is_negative().into()),
+                ("digits", big.digits.as_slice().into()),
+            ]),
+        ),
+        OwnedTerm::Pid(pid) => tag("pid", pid_to_json(pid)),
+        OwnedTerm::Port(port) => tag(
+            "port",
+            fields([
+                ("node", port.node.as_str().into()),
+                ("id", port.id.into()),
+                ("creation", port.creation.into()),
+            ]),
+        ),
+        OwnedTerm::Reference(reference) => tag(
+            "reference",
+            fields([
+                ("node", reference.node.as_str().into()),
+                ("creation", reference.creation.into()),
+                ("ids", reference.ids.as_slice().into()),
+            ]),
+        ),
+        OwnedTerm::ExternalFun(fun) => tag(
+            "fun",
+            fields([
+                ("module", fun.module.as_str().into()),
+                ("function", fun.function.as_str().into()),
+                ("arity", fun.arity.into()),
+            ]),
+        ),
+        OwnedTerm::InternalFun(fun) => tag(
+            "internal_fun",
+            fields([
+                ("module", fun.module.as_str().into()),
+                ("arity", fun.arity.into()),
+                ("uniq", fun.uniq.as_slice().into()),
+                ("index", fun.index.into()),
+                ("num_free", fun.num_free.into()),
+                ("old_index", fun.old_index.into()),
+                ("old_uniq", fun.old_uniq.into()),
+                ("pid", pid_to_json(&fun.pid)),
+                ("free_vars", Value::Array(tagged_elements(&fun.free_vars)?)),
+            ]),
+        ),
+    })
+}
+
+fn tagged_from_json(value: &Value) -> Result<OwnedTerm> {
+    match value {
+        Value::Null => Err(Error::InvalidValue(
+            "null has no tagged term representation".to_string(),
+        )),
+        Value::Bool(b) => Ok(OwnedTerm::boolean(*b)),
+        Value::Number(n) => number_to_term(n),
+        Value::String(s) => Ok(OwnedTerm::Binary(s.as_bytes().to_vec())),
+        Value::Array(values) => elements_from_json(values, tagged_from_json),
+        Value::Object(object) => {
+            let mut entries = object.iter();
+            match (entries.next(), entries.next()) {
+                (Some((tag, payload)), None) => untag(tag, payload),
+                _ => Err(Error::InvalidValue(
+                    "tagged terms are objects with a single key".to_string(),
+                )),
+            }
+        }
+    }
+}
+
+fn untag(tag: &str, payload: &Value) -> Result<OwnedTerm> {
+    match tag {
+        "atom" => Ok(OwnedTerm::Atom(Atom::new(str_payload(payload)?))),
+        "float" => match str_payload(payload)? {
+            "nan" => Ok(OwnedTerm::Float(f64::NAN)),
+            "inf" => Ok(OwnedTerm::Float(f64::INFINITY)),
+            "-inf" => Ok(OwnedTerm::Float(f64::NEG_INFINITY)),
+            other => Err(Error::InvalidValue(format!("unknown float {other:?}"))),
+        },
+        "binary" => Ok(OwnedTerm::Binary(bytes_payload(payload)?)),
+        "bitstring" => Ok(OwnedTerm::BitBinary {
+            bytes: bytes_payload(field(payload, "bytes")?)?,
+            bits: int_field(payload, "bits")?,
+        }),
+        "string" => Ok(OwnedTerm::String(str_payload(payload)?.to_string())),
+        "list" => Ok(OwnedTerm::List(tagged_elements_from(payload)?)),
+        "charlist" => Ok(OwnedTerm::List(
+            str_payload(payload)?
+                .chars()
+                .map(|c| OwnedTerm::Integer(i64::from(u32::from(c))))
+                .collect(),
+        )),
+        "improper_list" => Ok(OwnedTerm::ImproperList {
+            elements: tagged_elements_from(field(payload, "elements")?)?,
+            tail: Box::new(tagged_from_json(field(payload, "tail")?)?),
+        }),
+        "tuple" => Ok(OwnedTerm::Tuple(tagged_elements_from(payload)?)),
+        "map" => {
+            let mut map = BTreeMap::new();
+            for entry in array_payload(payload)? {
+                match array_payload(entry)?.as_slice() {
+                    [k, v] => {
+                        map.insert(tagged_from_json(k)?, tagged_from_json(v)?);
+                    }
+                    _ => {
+                        return Err(Error::InvalidValue(
+                            "map entries are [key, value] arrays".to_string(),
+                        ));
+                    }
+                }
+            }
+            Ok(OwnedTerm::Map(map))
+        }
+        "bigint" => Ok(OwnedTerm::BigInt(BigInt::new(
+            bool_field(payload, "negative")?,
+            bytes_payload(field(payload, "digits")?)?,
+        ))),
+        "pid" => Ok(OwnedTerm::Pid(pid_from_json(payload)?)),
+        "port" => Ok(OwnedTerm::Port(ExternalPort::new(
+            atom_field(payload, "node")?,
+            int_field(payload, "id")?,
+            int_field(payload, "creation")?,
+        ))),
+        "reference" => Ok(OwnedTerm::Reference(ExternalReference::new(
+            atom_field(payload, "node")?,
+            int_field(payload, "creation")?,
+            array_payload(field(payload, "ids")?)?
+                .iter()
+                .map(int_payload)
+                .collect::<Result<_>>()?,
+        ))),
+        "fun" => Ok(OwnedTerm::ExternalFun(ExternalFun::new(
+            atom_field(payload, "module")?,
+            atom_field(payload, "function")?,
+            int_field(payload, "arity")?,
+        ))),
+        "internal_fun" => {
+            let uniq = bytes_payload(field(payload, "uniq")?)?
+                .try_into()
+                .map_err(|_| Error::InvalidValue("uniq must have 16 bytes".to_string()))?;
+            Ok(OwnedTerm::InternalFun(Box::new(InternalFun::new(
+                int_field(payload, "arity")?,
+                uniq,
+                int_field(payload, "index")?,
+                int_field(payload, "num_free")?,
+                atom_field(payload, "module")?,
+                int_field(payload, "old_index")?,
+                int_field(payload, "old_uniq")?,
+                pid_from_json(field(payload, "pid")?)?,
+                tagged_elements_from(field(payload, "free_vars")?)?,
+            ))))
+        }
+        other => Err(Error::InvalidValue(format!("unknown term tag {other:?}"))),
+    }
+}
+
+fn tag(name: &str, payload: impl Into<Value>) -> Value {
+    let mut object = Map::with_capacity(1);
+    object.insert(name.to_string(), payload.into());
+    Value::Object(object)
+}
+
+fn fields<const N: usize>(fields: [(&str, Value); N]) -> Value {
+    Value::Object(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+fn tagged_elements(elements: &[OwnedTerm]) -> Result<Vec<Value>> {
+    elements.iter().map(tagged_to_json).collect()
+}
+
+fn tagged_elements_from(payload: &Value) -> Result<Vec<OwnedTerm>> {
+    array_payload(payload)?
+        .iter()
+        .map(tagged_from_json)
+        .collect()
+}
+
+/// Lists of printable code points are written as strings, like `~c"..."` in Elixir.
+fn charlist_to_string(elements: &[OwnedTerm]) -> Option<String> {
+    elements
+        .iter()
+        .map(|element| match element {
+            OwnedTerm::Integer(i) => u32::try_from(*i)
+                .ok()
+                .and_then(char::from_u32)
+                .filter(|c| !c.is_control() || c.is_ascii_whitespace()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn pid_to_json(pid: &ExternalPid) -> Value {
+    fields([
+        ("node", pid.node.as_str().into()),
+        ("id", pid.id.into()),
+        ("serial", pid.serial.into()),
+        ("creation", pid.creation.into()),
+    ])
+}
+
+fn pid_from_json(payload: &Value) -> Result<ExternalPid> {
+    Ok(ExternalPid::new(
+        atom_field(payload, "node")?,
+        int_field(payload, "id")?,
+        int_field(payload, "serial")?,
+        int_field(payload, "creation")?,
+    ))
+}
+
+fn field<'v>(payload: &'v Value, name: &str) -> Result<&'v Value> {
+    payload
+        .get(name)
+        .ok_or_else(|| Error::InvalidValue(format!("missing field {name:?}")))
+}
+
+fn atom_field(payload: &Value, name: &str) -> Result<Atom> {
+    str_payload(field(payload, name)?).map(Atom::new)
+}
+
+fn bool_field(payload: &Value, name: &str) -> Result<bool> {
+    field(payload, name)?
+        .as_bool()
+        .ok_or_else(|| Error::InvalidValue(format!("{name} must be a boolean")))
+}
+
+fn int_field<T: TryFrom<u64>>(payload: &Value, name: &str) -> Result<T> {
+    int_payload(field(payload, name)?)
+}
+
+fn int_payload<T: TryFrom<u64>>(payload: &Value) -> Result<T> {
+    payload
+        .as_u64()
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| Error::InvalidValue(format!("{payload} is out of range")))
+}
+
+fn str_payload(payload: &Value) -> Result<&str> {
+    payload
+        .as_str()
+        .ok_or_else(|| Error::InvalidValue(format!("expected a string, found {payload}")))
+}
+
+fn array_payload(payload: &Value) -> Result<&Vec<Value>> {
+    payload
+        .as_array()
+        .ok_or_else(|| Error::InvalidValue(format!("expected an array, found {payload}")))
+}
+
+fn bytes_payload(payload: &Value) -> Result<Vec<u8>> {
+    array_payload(payload)?.iter().map(int_payload).collect()
+}
@@ -14,10 +14,14 @@
 
 mod de;
 mod error;
+#[cfg(feature = "json")]
+mod json;
 mod ser;
 
 pub use de::{Deserializer, ProplistDeserializer, from_bytes, from_proplist, from_term};
 pub use error::{Error, Result};
+#[cfg(feature = "json")]
+pub use json::{JsonConvention, from_json, to_json};
 pub use ser::{Serializer, to_bytes, to_term};
 
 use erltf::OwnedTerm;
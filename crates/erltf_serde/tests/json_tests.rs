@@ -0,0 +1,233 @@
+// Copyright (C) 2025-2026 Michael S. Klishin and Contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "json")]
+
+use erltf::types::{Atom, BigInt, ExternalPid, ExternalReference};
+use erltf::{
+    GenerateOptions, OwnedTerm, erl_atom, erl_int, erl_list, erl_map, erl_tuple, generate,
+};
+use erltf_serde::{JsonConvention, from_json, to_json};
+use proptest::prelude::*;
+use serde_json::json;
+
+fn bin(s: &str) -> OwnedTerm {
+    OwnedTerm::Binary(s.as_bytes().to_vec())
+}
+
+fn round_trip_text(term: &OwnedTerm) -> OwnedTerm {
+    let json = to_json(term, JsonConvention::Tagged).unwrap();
+    let text = serde_json::to_string(&json).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    from_json(&parsed, JsonConvention::Tagged).unwrap()
+}
+
+//
+// jsx and thoas
+//
+
+#[test]
+fn test_jsx_scalars() {
+    assert_eq!(
+        to_json(&erl_int!(42), JsonConvention::Jsx).unwrap(),
+        json!(42)
+    );
+    assert_eq!(
+        to_json(&OwnedTerm::Float(1.5), JsonConvention::Jsx).unwrap(),
+        json!(1.5)
+    );
+    assert_eq!(
+        to_json(&bin("héllo"), JsonConvention::Jsx).unwrap(),
+        json!("héllo")
+    );
+    assert_eq!(
+        to_json(&erl_atom!("true"), JsonConvention::Jsx).unwrap(),
+        json!(true)
+    );
+    assert_eq!(
+        to_json(&erl_atom!("null"), JsonConvention::Jsx).unwrap(),
+        json!(null)
+    );
+    assert_eq!(
+        to_json(&erl_atom!("ok"), JsonConvention::Jsx).unwrap(),
+        json!("ok")
+    );
+    assert_eq!(
+        to_json(&OwnedTerm::Nil, JsonConvention::Jsx).unwrap(),
+        json!([])
+    );
+}
+
+#[test]
+fn test_jsx_maps_and_proplists_are_objects() {
+    let map = erl_map! {
+        bin("name") => bin("rabbit"),
+        erl_atom!("tags") => erl_list![bin("a"), bin("b")],
+        erl_int!(1) => erl_atom!("false"),
+    };
+    let expected = json!({"name": "rabbit", "tags": ["a", "b"], "1": false});
+    assert_eq!(to_json(&map, JsonConvention::Jsx).unwrap(), expected);
+
+    let proplist = erl_list![
+        erl_tuple![erl_atom!("vhost"), bin("/")],
+        erl_tuple![bin("durable"), erl_atom!("true")],
+    ];
+    assert_eq!(
+        to_json(&proplist, JsonConvention::Jsx).unwrap(),
+        json!({"vhost": "/", "durable": true})
+    );
+}
+
+#[test]
+fn test_jsx_decode() {
+    let value = json!({"a": [1, 2.5, null, "x"], "b": {}, "c": []});
+    let term = from_json(&value, JsonConvention::Jsx).unwrap();
+    let expected = erl_map! {
+        bin("a") => erl_list![erl_int!(1), OwnedTerm::Float(2.5), erl_atom!("null"), bin("x")],
+        bin("b") => OwnedTerm::Map(Default::default()),
+        bin("c") => OwnedTerm::Nil,
+    };
+    assert_eq!(term, expected);
+}
+
+#[test]
+fn test_jsx_rejects_unrepresentable_terms() {
+    assert!(to_json(&erl_tuple![erl_int!(1), erl_int!(2)], JsonConvention::Jsx).is_err());
+    assert!(to_json(&OwnedTerm::Binary(vec![0xff]), JsonConvention::Jsx).is_err());
+    assert!(to_json(&OwnedTerm::Float(f64::NAN), JsonConvention::Jsx).is_err());
+    let pid = OwnedTerm::Pid(ExternalPid::new(Atom::new("n@h"), 1, 0, 1));
+    assert!(to_json(&pid, JsonConvention::Jsx).is_err());
+}
+
+#[test]
+fn test_big_integers() {
+    let big = OwnedTerm::BigInt(BigInt::from(u64::MAX));
+    assert_eq!(to_json(&big, JsonConvention::Jsx).unwrap(), json!(u64::MAX));
+    assert_eq!(
+        from_json(&json!(u64::MAX), JsonConvention::Jsx).unwrap(),
+        big
+    );
+
+    let huge = OwnedTerm::BigInt(BigInt::from(u128::MAX));
+    assert!(to_json(&huge, JsonConvention::Jsx).is_err());
+}
+
+//
+// jiffy
+//
+
+#[test]
+fn test_jiffy_objects() {
+    let term = erl_tuple![erl_list![
+        erl_tuple![bin("a"), erl_int!(1)],
+        erl_tuple![erl_atom!("b"), erl_tuple![OwnedTerm::Nil]],
+    ]];
+    let value = json!({"a": 1, "b": {}});
+    assert_eq!(to_json(&term, JsonConvention::Jiffy).unwrap(), value);
+
+    let decoded = from_json(&value, JsonConvention::Jiffy).unwrap();
+    let expected = erl_tuple![erl_list![
+        erl_tuple![bin("a"), erl_int!(1)],
+        erl_tuple![bin("b"), erl_tuple![OwnedTerm::Nil]],
+    ]];
+    assert_eq!(decoded, expected);
+}
+
+#[test]
+fn test_jiffy_proplists_are_arrays() {
+    let proplist = erl_list![erl_tuple![erl_atom!("a"), erl_int!(1)]];
+    assert!(to_json(&proplist, JsonConvention::Jiffy).is_err());
+}
+
+//
+// Jason
+//
+
+#[test]
+fn test_jason_nil_and_structs() {
+    let user = erl_map! {
+        erl_atom!("__struct__") => erl_atom!("Elixir.MyApp.User"),
+        erl_atom!("name") => bin("Joe"),
+        erl_atom!("email") => erl_atom!("nil"),
+    };
+    assert_eq!(
+        to_json(&user, JsonConvention::Jason).unwrap(),
+        json!({"name": "Joe", "email": null})
+    );
+    assert_eq!(
+        from_json(&json!(null), JsonConvention::Jason).unwrap(),
+        erl_atom!("nil")
+    );
+    assert_eq!(
+        to_json(&erl_atom!("null"), JsonConvention::Jason).unwrap(),
+        json!("null")
+    );
+}
+
+//
+// Tagged
+//
+
+#[test]
+fn test_tagged_representation() {
+    let term = erl_tuple![
+        erl_atom!("ok"),
+        bin("text"),
+        erl_list![erl_int!(104), erl_int!(105)],
+    ];
+    assert_eq!(
+        to_json(&term, JsonConvention::Tagged).unwrap(),
+        json!({"tuple": [{"atom": "ok"}, "text", {"charlist": "hi"}]})
+    );
+}
+
+#[test]
+fn test_tagged_round_trips_edge_cases() {
+    let terms = vec![
+        OwnedTerm::Nil,
+        OwnedTerm::List(vec![]),
+        OwnedTerm::String("charlist or string".to_string()),
+        OwnedTerm::Binary(vec![0xff, 0x00]),
+        OwnedTerm::Float(f64::INFINITY),
+        OwnedTerm::Float(-0.0),
+        OwnedTerm::Float(3.0),
+        erl_list![erl_int!(0), erl_int!(-1)],
+        OwnedTerm::BigInt(BigInt::new(true, vec![0, 0, 1, 0])),
+        OwnedTerm::BigInt(BigInt::new(true, vec![0])),
+        OwnedTerm::Reference(ExternalReference::new(Atom::new("n@h"), 3, vec![1, 2, 3])),
+        erl_map! { erl_tuple![erl_int!(1)] => erl_atom!("null") },
+    ];
+    for term in terms {
+        assert_eq!(round_trip_text(&term), term);
+    }
+
+    let nan = round_trip_text(&OwnedTerm::Float(f64::NAN));
+    assert!(matches!(nan, OwnedTerm::Float(f) if f.is_nan()));
+}
+
+#[test]
+fn test_tagged_rejects_unknown_tags() {
+    assert!(from_json(&json!({"atom": "a", "extra": 1}), JsonConvention::Tagged).is_err());
+    assert!(from_json(&json!({"nope": 1}), JsonConvention::Tagged).is_err());
+    assert!(from_json(&json!(null), JsonConvention::Tagged).is_err());
+}
+
+proptest! {
+    #[test]
+    fn test_tagged_round_trips_generated_terms(
+        term in generate::term(GenerateOptions::round_trippable())
+    ) {
+        prop_assert_eq!(round_trip_text(&term), term);
+    }
+}